@@ -1,8 +1,5 @@
 use clap::Parser;
-use g_code::{
-    emit::{format_gcode_io, FormatOptions},
-    parse::snippet_parser,
-};
+use g_code::parse::snippet_parser;
 use log::{error, info};
 use roxmltree::ParsingOptions;
 use std::{
@@ -14,7 +11,10 @@ use std::{
 use svgtypes::LengthListParser;
 
 use svg2gcode::{
-    svg2program, ConversionOptions, Machine, Settings, SupportedFunctionality, Version,
+    tokens_to_gcode, try_svg2program, ArcDistanceMode, ArcFeedConfig, ArcFormat, CommandFilter,
+    CommentStyle, ConversionOptions, EndCommand, FeedrateUnits, Machine, MultiPassConfig,
+    PaintServerPolicy, PathMode, PocketConfig, PostprocessConfig, PrimingConfig, Settings,
+    SupportedFunctionality, ToleranceMode, UnsupportedPolicy, Version, ZAxisConfig,
 };
 
 #[derive(Debug, Parser)]
@@ -23,13 +23,76 @@ struct Opt {
     /// Curve interpolation tolerance (mm)
     #[arg(long)]
     tolerance: Option<f64>,
+    /// Whether --tolerance is in mm or a fraction of the diagonal of the drawing
+    ///
+    /// relative-to-diagonal flattens a drawing into the same number of segments at any size.
+    #[arg(long, value_parser = ["absolute", "relative-to-diagonal"].into_iter().collect::<Vec<_>>())]
+    tolerance_mode: Option<String>,
     /// Machine feed rate (mm/min)
     #[arg(long)]
     feedrate: Option<f64>,
+    /// Units of the emitted F words, converted from --feedrate which stays in mm/min
+    #[arg(long, value_parser = ["per-minute", "per-second"].into_iter().collect::<Vec<_>>())]
+    feedrate_units: Option<String>,
     /// Dots per Inch (DPI)
     /// Used for scaling visual units (pixels, points, picas, etc.)
     #[arg(long)]
     dpi: Option<f64>,
+    /// Number of user units (and pixels) in a millimeter, overriding --dpi
+    ///
+    /// Use 1 for CAD exports where one user unit is one millimeter.
+    #[arg(long)]
+    user_units_per_mm: Option<f64>,
+    /// Report an error for any coordinate farther than this from the origin (mm)
+    ///
+    /// Catches corrupt transforms that would produce coordinates a controller can't handle.
+    #[arg(long)]
+    max_abs_coordinate: Option<f64>,
+    /// Name of an attribute (i.e. data-tolerance) overriding the tolerance for an element and its
+    /// descendants
+    ///
+    /// Useful for flattening some elements more finely than the rest.
+    #[arg(long)]
+    tolerance_attribute: Option<String>,
+    /// Whether --feedrate is in mm/min (true), or in user units/min scaled by --dpi (false)
+    #[arg(long)]
+    feedrate_is_physical: Option<bool>,
+    /// id of an element (i.e. a registration mark) whose center becomes the origin, overriding
+    /// --origin
+    #[arg(long)]
+    origin_element: Option<String>,
+    /// JSON file mapping group ids to the feedrate, power, and cut_z to cut their contents with
+    ///
+    /// i.e. {"layer1": {"feedrate": 500}, "layer2": {"feedrate": 1500, "power": 800}}
+    #[arg(long)]
+    layer_params: Option<PathBuf>,
+    /// Cut closed subpaths after the subpaths inside of them (i.e. holes before the outline of a
+    /// part)
+    #[arg(long)]
+    order_by_containment: Option<bool>,
+    /// Number of times each subpath is cut, alternating direction (i.e. to darken marker strokes)
+    #[arg(long)]
+    stroke_repeats: Option<usize>,
+    /// Trace the outline of shapes that are filled but not stroked (default: true)
+    ///
+    /// When false, only stroked shapes are traced.
+    #[arg(long)]
+    trace_fill_as_outline: Option<bool>,
+    /// Leave out subpaths tracing the same outline as one already cut
+    ///
+    /// Useful for exports that draw a shape's fill and stroke as two elements
+    #[arg(long)]
+    dedupe_paths: Option<bool>,
+    /// Keep gentle curves within the tolerance of a line curved, cutting them as arcs or finer
+    /// lines
+    ///
+    /// Useful for large parts with --circular-interpolation
+    #[arg(long)]
+    preserve_curvature: Option<bool>,
+    /// Fill closed shapes with concentric inward offsets this far apart (mm), e.g. for pocket
+    /// engraving
+    #[arg(long)]
+    pocket_stepover: Option<f64>,
     #[arg(alias = "tool_on_sequence", long = "on")]
     /// G-Code for turning on the tool
     tool_on_sequence: Option<String>,
@@ -45,6 +108,94 @@ struct Opt {
     /// G-Code sequence inserted between sibling SVG groups/layers
     #[arg(alias = "between_layers_sequence", long = "between-layers")]
     between_layers_sequence: Option<String>,
+    /// G-Code sequence turning on an accessory like air assist (e.g. M7), emitted once when cutting first begins
+    #[arg(alias = "accessory_on_sequence", long = "accessory-on")]
+    accessory_on_sequence: Option<String>,
+    /// G-Code sequence turning the accessory off (e.g. M9), emitted once at the end of the program
+    #[arg(alias = "accessory_off_sequence", long = "accessory-off")]
+    accessory_off_sequence: Option<String>,
+    /// Height to retract to before traveling (mm)
+    ///
+    /// Together with --cut-z, makes svg2gcode drive the Z axis instead of relying on the tool on/off sequences.
+    #[arg(long, allow_hyphen_values = true)]
+    safe_z: Option<f64>,
+    /// Height to plunge to before cutting (mm)
+    #[arg(long, allow_hyphen_values = true)]
+    cut_z: Option<f64>,
+    /// Retract and plunge with Z-only moves rather than combining the retract into the travel move
+    #[arg(long)]
+    separate_z_moves: Option<bool>,
+    /// Number of times to cut the program, each pass deeper than the last
+    ///
+    /// Requires --safe-z and --cut-z. The first pass cuts at --cut-z.
+    #[arg(long)]
+    passes: Option<usize>,
+    /// Additional depth of each pass after the first (mm)
+    #[arg(long)]
+    depth_per_pass: Option<f64>,
+    /// Command emitted at the very end of the program, after the end sequence
+    ///
+    /// m30 also rewinds the program on most controllers.
+    #[arg(long, value_parser = ["m2", "m30"].into_iter().collect::<Vec<_>>())]
+    program_end_command: Option<String>,
+    /// Whether the X/Y endpoints of G2/G3 arcs are absolute or relative to the start of the arc
+    ///
+    /// I/J are always relative to the start of the arc. relative wraps each arc in G91/G90.
+    #[arg(long, value_parser = ["absolute", "relative"].into_iter().collect::<Vec<_>>())]
+    arc_distance_mode: Option<String>,
+    /// Whether G2/G3 arcs are given by their center (I/J) or their radius (R)
+    #[arg(long, value_parser = ["ij", "r"].into_iter().collect::<Vec<_>>())]
+    arc_format: Option<String>,
+    /// Maximum X and Y axis speeds in mm/min (i.e. 3000,1500)
+    ///
+    /// Cutting moves are slowed down so that neither axis exceeds its limit.
+    #[arg(long)]
+    axis_max_feedrate: Option<String>,
+    /// Slow down arcs tighter than this radius (mm), proportionally to their radius
+    #[arg(long)]
+    arc_feedrate_reference_radius: Option<f64>,
+    /// Lowest feedrate (mm/min) that axis limits and arc scaling may slow a move down to
+    #[arg(long)]
+    min_feedrate: Option<f64>,
+    /// Change tools (M6 T<n>) before cutting each top-level group (layer)
+    ///
+    /// The tool number is the group's data-tool attribute, else its position among the groups.
+    #[arg(long)]
+    tool_change: Option<bool>,
+    /// Turn the tool off and retract before every subpath, even when --join-gap would join it
+    #[arg(long)]
+    always_retract_between_subpaths: Option<bool>,
+    /// Height in mm to retract to between subpaths, instead of --safe-z
+    #[arg(long, allow_hyphen_values = true)]
+    travel_retract: Option<f64>,
+    /// Height in mm to retract to before the between layers sequence or a tool change, instead
+    /// of --safe-z
+    #[arg(long, allow_hyphen_values = true)]
+    tool_change_retract: Option<f64>,
+    /// Path control mode selected at the start of the program
+    ///
+    /// blend (G64 P) trades accuracy at corners for continuous motion, exact-stop (G61) stops at
+    /// the end of every move.
+    #[arg(long, value_parser = ["blend", "exact-stop"].into_iter().collect::<Vec<_>>())]
+    path_mode: Option<String>,
+    /// Distance in mm that --path-mode blend may deviate from the path, else the tolerance
+    #[arg(long)]
+    blend_tolerance: Option<f64>,
+    /// Cut a short priming stroke starting at these X,Y coordinates (mm) before the drawing
+    #[arg(long, allow_hyphen_values = true)]
+    priming_position: Option<String>,
+    /// Length in mm of the priming stroke, which goes along +X and back (default 10)
+    #[arg(long)]
+    priming_length: Option<f64>,
+    /// Select millimeters (G21) at the start of the program, defaults to true
+    ///
+    /// Turn off when appending to a job that already sets the units.
+    #[arg(long)]
+    emit_units: Option<bool>,
+    /// Most arcs (G2/G3) to emit, flattening the rest into lines for controllers with a small
+    /// arc buffer
+    #[arg(long)]
+    max_arcs: Option<usize>,
     /// A file path to an SVG, else reads from stdin
     file: Option<PathBuf>,
     /// Output file path (overwrites old files), else writes to stdout
@@ -77,6 +228,31 @@ struct Opt {
     /// Treat --dimensions as target paper size and scale drawing's tight bounding box to fit
     #[arg(long)]
     trim: Option<bool>,
+    /// Comma-separated language tags (e.g. "en-US,fr") that systemLanguage attributes are matched against
+    ///
+    /// Elements whose systemLanguage does not match are skipped. If unset, systemLanguage is ignored.
+    #[arg(long)]
+    system_language: Option<String>,
+    /// Trace the bounding rectangle of the drawing with rapid moves before cutting
+    #[arg(long)]
+    emit_bounding_frame: Option<bool>,
+    /// Scale the drawing uniformly so that the total length of its outlines is this many millimeters
+    #[arg(long)]
+    scale_to_cut_length: Option<f64>,
+    /// Comma-separated kinds of path commands to draw (lines, curves, arcs), for debugging
+    ///
+    /// The tool only moves to the end of the other commands.
+    #[arg(long)]
+    only_commands: Option<String>,
+    /// Semicolon-separated X,Y origins in mm of the top-level groups, in order (i.e. "0,0;150,0")
+    ///
+    /// Each group is moved so the bottom-left corner of its bounding box is at its origin.
+    #[arg(long, allow_hyphen_values = true)]
+    group_origins: Option<String>,
+    /// Comma-separated affine matrix "a,b,c,d,e,f" applied to the whole drawing before it is
+    /// placed, like an SVG matrix() transform but in mm with the Y axis pointing up
+    #[arg(long, allow_hyphen_values = true)]
+    global_transform: Option<String>,
     /// Whether to use circular arcs when generating g-code
     ///
     /// Please check if your machine supports G2/G3 commands before enabling this.
@@ -96,6 +272,44 @@ struct Opt {
     /// If not specified, uses the same tolerance as curve fitting.
     #[arg(long)]
     polygon_arc_tolerance: Option<f64>,
+    /// Relative tolerance within which an arc counts as a semicircle and is split in two
+    #[arg(long)]
+    semicircle_epsilon: Option<f64>,
+    /// Sweep angle (radians) below which an arc is emitted as a line
+    #[arg(long)]
+    min_sweep: Option<f64>,
+    /// How to handle elements filled or stroked with a gradient or pattern
+    ///
+    /// solid-color traces them like any other element, skip leaves them out.
+    #[arg(long, value_parser = ["solid-color", "skip"].into_iter().collect::<Vec<_>>())]
+    paint_server_policy: Option<String>,
+    /// Trace the contents of SVG markers at the vertices of paths, lines, polylines and polygons
+    ///
+    /// Useful for turning markers into drill points or dots.
+    #[arg(long)]
+    honor_markers: Option<bool>,
+    /// Skip shapes whose effective opacity is below this threshold (0 to 1)
+    ///
+    /// Useful for leaving out faint guide or template elements.
+    #[arg(long)]
+    min_opacity: Option<f64>,
+    /// How to handle unsupported or degenerate elements, like a rect with zero width
+    ///
+    /// Either way they are skipped, warn also logs a warning. error fails the conversion
+    /// instead, also for invalid attributes like a non-numeric data-feedrate.
+    #[arg(long, value_parser = ["warn", "ignore", "error"].into_iter().collect::<Vec<_>>())]
+    on_unsupported: Option<String>,
+    /// End the program with a comment counting the unsupported elements skipped by tag name
+    #[arg(long)]
+    summarize_skipped: Option<bool>,
+    /// Comment each element with the text of its <title> and <desc>
+    #[arg(long)]
+    emit_titles: Option<bool>,
+    /// Join subpaths starting within this distance (mm) of the end of the last one with a cutting move
+    ///
+    /// Avoids lifting the tool for nearly touching strokes, e.g. when pen plotting.
+    #[arg(long)]
+    join_gap: Option<f64>,
 
     #[arg(long)]
     /// Include line numbers at the beginning of each line
@@ -113,10 +327,69 @@ struct Opt {
     /// Workaround for parsers that don't accept comments on the same line
     newline_before_comment: Option<bool>,
     #[arg(long)]
-    /// When printing a node name , print a extra attribute 
+    /// Round feedrate (F) words to this many decimal places
+    ///
+    /// Useful for controllers that only accept whole-number feedrates
+    feedrate_decimals: Option<u8>,
+    #[arg(long, allow_hyphen_values = true)]
+    /// Shift the whole program by this X,Y offset in mm (i.e. 10,20), after everything else
+    ///
+    /// Useful for moving the job to a fixture datum
+    datum_offset: Option<String>,
+    #[arg(long)]
+    /// Round X/Y coordinates to the nearest multiple of this step in mm (i.e. 0.05)
+    ///
+    /// Useful for machines with coarse step resolution
+    grid_snap: Option<f64>,
+    #[arg(long)]
+    /// Bracket the moves of each element with begin/end comments, including its bounding box
+    ///
+    /// Useful for reviewing the program in CAM software
+    annotate_subpaths: Option<bool>,
+    #[arg(long)]
+    /// Leave out motion words (G0/G1/G2/G3) that repeat the motion mode already in effect
+    ///
+    /// Useful for controllers that expect modal g-code
+    modal_gcodes: Option<bool>,
+    #[arg(long)]
+    /// Leave out feedrate (F) words equal to the feedrate already in effect
+    ///
+    /// Useful for shrinking programs with a constant feedrate
+    modal_feedrate: Option<bool>,
+    #[arg(long)]
+    /// End each move with a "; t=<seconds>" comment estimating the time elapsed so far
+    ///
+    /// Only feed moves and dwells are counted
+    annotate_time: Option<bool>,
+    #[arg(long)]
+    /// Parse the generated g-code and format it again, failing if it is malformed
+    validate_roundtrip: Option<bool>,
+    #[arg(long, value_parser = ["mixed", "semicolon", "parentheses"].into_iter().collect::<Vec<_>>())]
+    /// Kind of comments to write, for controllers that only accept one
+    ///
+    /// mixed writes ; comments on their own line and (parenthesized) ones within a line.
+    comment_style: Option<String>,
+    #[arg(long)]
+    /// Number of blank lines separating layers in the output
+    blank_lines_between_layers: Option<usize>,
+    #[arg(long)]
+    /// Number of the first line when including line numbers
+    ///
+    /// Useful for appending to an existing program
+    line_number_start: Option<u32>,
+    #[arg(long)]
+    /// Step between consecutive line numbers when including them
+    line_number_increment: Option<u32>,
+    #[arg(long)]
+    /// When printing a node name , print a extra attribute
     ///
     /// Useful to print the label of layer on SVG generated by Inkscape
     extra_attribute_name: Option<String>,
+    #[arg(long)]
+    /// Annotate each cut move with the SVG user space coordinate it ends at
+    ///
+    /// Useful for debugging transform issues
+    annotate_source_coords: Option<bool>,
 }
 
 fn main() -> io::Result<()> {
@@ -137,13 +410,87 @@ fn main() -> io::Result<()> {
         {
             let conversion = &mut settings.conversion;
             conversion.dpi = opt.dpi.unwrap_or(conversion.dpi);
+            if let Some(user_units_per_mm) = opt.user_units_per_mm {
+                conversion.user_units_per_mm = Some(user_units_per_mm);
+            }
+            if let Some(max_abs_coordinate) = opt.max_abs_coordinate {
+                conversion.max_abs_coordinate = Some(max_abs_coordinate);
+            }
+            if let tolerance_attribute @ Some(_) = opt.tolerance_attribute {
+                conversion.tolerance_attribute = tolerance_attribute;
+            }
+            conversion.feedrate_is_physical = opt
+                .feedrate_is_physical
+                .unwrap_or(conversion.feedrate_is_physical);
+            if let origin_element @ Some(_) = opt.origin_element {
+                conversion.origin_element = origin_element;
+            }
+            if let Some(path) = opt.layer_params {
+                conversion.layer_params = serde_json::from_reader(File::open(path)?)?;
+            }
+            conversion.order_by_containment = opt
+                .order_by_containment
+                .unwrap_or(conversion.order_by_containment);
+            conversion.stroke_repeats = opt.stroke_repeats.unwrap_or(conversion.stroke_repeats);
+            conversion.trace_fill_as_outline = opt
+                .trace_fill_as_outline
+                .unwrap_or(conversion.trace_fill_as_outline);
+            conversion.dedupe_paths = opt.dedupe_paths.unwrap_or(conversion.dedupe_paths);
+            conversion.preserve_curvature = opt
+                .preserve_curvature
+                .unwrap_or(conversion.preserve_curvature);
+            if let Some(stepover) = opt.pocket_stepover {
+                conversion.pocket = Some(PocketConfig { stepover });
+            }
             conversion.feedrate = opt.feedrate.unwrap_or(conversion.feedrate);
+            match opt.feedrate_units.as_deref() {
+                Some("per-minute") => conversion.feedrate_units = FeedrateUnits::PerMinute,
+                Some("per-second") => conversion.feedrate_units = FeedrateUnits::PerSecond,
+                _ => {}
+            }
             conversion.tolerance = opt.tolerance.unwrap_or(conversion.tolerance);
+            match opt.tolerance_mode.as_deref() {
+                Some("absolute") => conversion.tolerance_mode = ToleranceMode::Absolute,
+                Some("relative-to-diagonal") => {
+                    conversion.tolerance_mode = ToleranceMode::RelativeToDiagonal
+                }
+                _ => {}
+            }
             conversion.detect_polygon_arcs = opt.detect_polygon_arcs;
-            conversion.min_polygon_arc_points = opt.min_polygon_arc_points.unwrap_or(conversion.min_polygon_arc_points);
+            conversion.min_polygon_arc_points = opt
+                .min_polygon_arc_points
+                .unwrap_or(conversion.min_polygon_arc_points);
             if let Some(tolerance) = opt.polygon_arc_tolerance {
                 conversion.polygon_arc_tolerance = Some(tolerance);
             }
+            conversion.semicircle_epsilon = opt
+                .semicircle_epsilon
+                .unwrap_or(conversion.semicircle_epsilon);
+            conversion.min_sweep = opt.min_sweep.unwrap_or(conversion.min_sweep);
+            match opt.paint_server_policy.as_deref() {
+                Some("solid-color") => {
+                    conversion.paint_server_policy = PaintServerPolicy::SolidColor
+                }
+                Some("skip") => conversion.paint_server_policy = PaintServerPolicy::Skip,
+                _ => {}
+            }
+            conversion.honor_markers = opt.honor_markers.unwrap_or(conversion.honor_markers);
+            if let Some(min_opacity) = opt.min_opacity {
+                conversion.min_opacity = Some(min_opacity);
+            }
+            match opt.on_unsupported.as_deref() {
+                Some("warn") => conversion.on_unsupported = UnsupportedPolicy::Warn,
+                Some("ignore") => conversion.on_unsupported = UnsupportedPolicy::Ignore,
+                Some("error") => conversion.on_unsupported = UnsupportedPolicy::Error,
+                _ => {}
+            }
+            conversion.summarize_skipped = opt
+                .summarize_skipped
+                .unwrap_or(conversion.summarize_skipped);
+            conversion.emit_titles = opt.emit_titles.unwrap_or(conversion.emit_titles);
+            if let Some(join_gap) = opt.join_gap {
+                conversion.join_gap = Some(join_gap);
+            }
         }
         {
             let machine = &mut settings.machine;
@@ -167,6 +514,108 @@ fn main() -> io::Result<()> {
             if let seq @ Some(_) = opt.between_layers_sequence {
                 machine.between_layers_sequence = seq;
             }
+            if let seq @ Some(_) = opt.accessory_on_sequence {
+                machine.accessory_on_sequence = seq;
+            }
+            if let seq @ Some(_) = opt.accessory_off_sequence {
+                machine.accessory_off_sequence = seq;
+            }
+            if opt.safe_z.is_some() || opt.cut_z.is_some() {
+                let z_axis = machine.z_axis.get_or_insert_with(ZAxisConfig::default);
+                z_axis.safe_z = opt.safe_z.unwrap_or(z_axis.safe_z);
+                z_axis.cut_z = opt.cut_z.unwrap_or(z_axis.cut_z);
+            }
+            machine.separate_z_moves = opt.separate_z_moves.unwrap_or(machine.separate_z_moves);
+            if opt.passes.is_some() || opt.depth_per_pass.is_some() {
+                let passes = machine.passes.get_or_insert(MultiPassConfig {
+                    count: 1,
+                    depth_per_pass: 0.,
+                });
+                passes.count = opt.passes.unwrap_or(passes.count);
+                passes.depth_per_pass = opt.depth_per_pass.unwrap_or(passes.depth_per_pass);
+            }
+            match opt.program_end_command.as_deref() {
+                Some("m2") => machine.program_end_command = Some(EndCommand::M2),
+                Some("m30") => machine.program_end_command = Some(EndCommand::M30),
+                _ => {}
+            }
+            match opt.arc_distance_mode.as_deref() {
+                Some("absolute") => machine.arc_distance_mode = ArcDistanceMode::AbsoluteEndpoints,
+                Some("relative") => machine.arc_distance_mode = ArcDistanceMode::RelativeEndpoints,
+                _ => {}
+            }
+            match opt.arc_format.as_deref() {
+                Some("ij") => machine.arc_format = ArcFormat::IJ,
+                Some("r") => machine.arc_format = ArcFormat::R,
+                _ => {}
+            }
+            if let Some(axis_max_feedrate) = opt.axis_max_feedrate {
+                let limits = axis_max_feedrate
+                    .split(',')
+                    .map(|limit| limit.parse::<f64>().expect("could not parse feedrate"))
+                    .collect::<Vec<_>>();
+                match limits[..] {
+                    [x, y] => machine.axis_max_feedrate = Some([x, y]),
+                    _ => panic!("expected X and Y feedrates separated by a comma"),
+                }
+            }
+            if let Some(reference_radius) = opt.arc_feedrate_reference_radius {
+                machine.arc_feedrate_scaling = Some(ArcFeedConfig { reference_radius });
+            }
+            if let Some(min_feedrate) = opt.min_feedrate {
+                machine.min_feedrate = Some(min_feedrate);
+            }
+            if let Some(tool_change) = opt.tool_change {
+                machine.tool_change = tool_change;
+            }
+            if let Some(always_retract) = opt.always_retract_between_subpaths {
+                machine.always_retract_between_subpaths = always_retract;
+            }
+            if let Some(travel_retract) = opt.travel_retract {
+                machine.travel_retract = Some(travel_retract);
+            }
+            if let Some(tool_change_retract) = opt.tool_change_retract {
+                machine.tool_change_retract = Some(tool_change_retract);
+            }
+            match opt.path_mode.as_deref() {
+                Some("blend") => machine.path_mode = Some(PathMode::Blend { tolerance: None }),
+                Some("exact-stop") => machine.path_mode = Some(PathMode::ExactStop),
+                _ => {}
+            }
+            if let (Some(blend_tolerance), Some(PathMode::Blend { tolerance })) =
+                (opt.blend_tolerance, machine.path_mode.as_mut())
+            {
+                *tolerance = Some(blend_tolerance);
+            }
+            if let Some(priming_position) = opt.priming_position {
+                let coordinates = priming_position
+                    .split(',')
+                    .map(|coordinate| {
+                        coordinate
+                            .parse::<f64>()
+                            .expect("could not parse priming position")
+                    })
+                    .collect::<Vec<_>>();
+                match coordinates[..] {
+                    [x, y] => {
+                        machine.priming_stroke = Some(PrimingConfig {
+                            position: [x, y],
+                            length: opt.priming_length.unwrap_or(10.),
+                        })
+                    }
+                    _ => panic!("expected X and Y coordinates separated by a comma"),
+                }
+            } else if let (Some(length), Some(priming)) =
+                (opt.priming_length, machine.priming_stroke.as_mut())
+            {
+                priming.length = length;
+            }
+            if let Some(emit_units) = opt.emit_units {
+                machine.emit_units = emit_units;
+            }
+            if let Some(max_arcs) = opt.max_arcs {
+                machine.max_arcs = Some(max_arcs);
+            }
         }
         {
             if let Some(origin) = opt.origin {
@@ -199,7 +648,63 @@ fn main() -> io::Result<()> {
             settings.postprocess.newline_before_comment = newline_before_comment;
         }
 
-	settings.conversion.extra_attribute_name = opt.extra_attribute_name ;
+        if let Some(feedrate_decimals) = opt.feedrate_decimals {
+            settings.postprocess.feedrate_decimals = Some(feedrate_decimals);
+        }
+        if let Some(datum_offset) = opt.datum_offset {
+            for (i, offset) in datum_offset
+                .split(',')
+                .map(|offset| {
+                    if offset.is_empty() {
+                        Default::default()
+                    } else {
+                        offset.parse::<f64>().expect("could not parse offset")
+                    }
+                })
+                .take(2)
+                .enumerate()
+            {
+                settings.postprocess.datum_offset[i] = offset;
+            }
+        }
+        if let Some(grid_snap) = opt.grid_snap {
+            settings.postprocess.grid_snap = Some(grid_snap);
+        }
+        if let Some(annotate_subpaths) = opt.annotate_subpaths {
+            settings.postprocess.annotate_subpaths = annotate_subpaths;
+        }
+        if let Some(modal_gcodes) = opt.modal_gcodes {
+            settings.postprocess.modal_gcodes = modal_gcodes;
+        }
+        if let Some(modal_feedrate) = opt.modal_feedrate {
+            settings.postprocess.modal_feedrate = modal_feedrate;
+        }
+        if let Some(annotate_time) = opt.annotate_time {
+            settings.postprocess.annotate_time = annotate_time;
+        }
+        if let Some(validate_roundtrip) = opt.validate_roundtrip {
+            settings.postprocess.validate_roundtrip = validate_roundtrip;
+        }
+        match opt.comment_style.as_deref() {
+            Some("mixed") => settings.postprocess.comment_style = CommentStyle::Mixed,
+            Some("semicolon") => settings.postprocess.comment_style = CommentStyle::Semicolon,
+            Some("parentheses") => settings.postprocess.comment_style = CommentStyle::Parentheses,
+            _ => {}
+        }
+        if let Some(blank_lines_between_layers) = opt.blank_lines_between_layers {
+            settings.postprocess.blank_lines_between_layers = blank_lines_between_layers;
+        }
+        if let Some(line_number_start) = opt.line_number_start {
+            settings.postprocess.line_number_start = line_number_start;
+        }
+        if let Some(line_number_increment) = opt.line_number_increment {
+            settings.postprocess.line_number_increment = line_number_increment;
+        }
+
+        settings.conversion.extra_attribute_name = opt.extra_attribute_name;
+        if let Some(annotate_source_coords) = opt.annotate_source_coords {
+            settings.conversion.annotate_source_coords = annotate_source_coords;
+        }
 
         if let Version::Unknown(ref unknown) = settings.version {
             error!(
@@ -264,7 +769,63 @@ fn main() -> io::Result<()> {
             Some("bottom") => svg2gcode::VerticalAlign::Bottom,
             _ => svg2gcode::VerticalAlign::Top,
         };
-        ConversionOptions { dimensions, h_align, v_align, trim: opt.trim.unwrap_or(false) }
+        let command_filter = opt.only_commands.as_deref().map(|kinds| {
+            let kinds = kinds.split(',').map(str::trim).collect::<Vec<_>>();
+            for kind in &kinds {
+                if !["lines", "curves", "arcs"].contains(kind) {
+                    panic!("unknown kind of path command: {kind}");
+                }
+            }
+            CommandFilter {
+                lines: kinds.contains(&"lines"),
+                curves: kinds.contains(&"curves"),
+                arcs: kinds.contains(&"arcs"),
+            }
+        });
+        let per_group_origins = opt.group_origins.as_deref().map(|origins| {
+            origins
+                .split(';')
+                .map(|origin| {
+                    let coordinates = origin
+                        .split(',')
+                        .map(|coordinate| {
+                            coordinate
+                                .trim()
+                                .parse::<f64>()
+                                .expect("could not parse group origin")
+                        })
+                        .collect::<Vec<_>>();
+                    match coordinates[..] {
+                        [x, y] => [x, y],
+                        _ => panic!("expected X and Y coordinates separated by a comma"),
+                    }
+                })
+                .collect()
+        });
+        let global_transform = opt.global_transform.as_deref().map(|matrix| {
+            let values = matrix
+                .split(',')
+                .map(|value| {
+                    value
+                        .trim()
+                        .parse::<f64>()
+                        .expect("could not parse global transform")
+                })
+                .collect::<Vec<_>>();
+            <[f64; 6]>::try_from(values).expect("expected 6 comma-separated matrix values")
+        });
+        ConversionOptions {
+            dimensions,
+            h_align,
+            v_align,
+            trim: opt.trim.unwrap_or(false),
+            system_language: opt.system_language.clone(),
+            emit_bounding_frame: opt.emit_bounding_frame.unwrap_or(false),
+            scale_to_cut_length: opt.scale_to_cut_length,
+            command_filter,
+            per_group_origins,
+            global_transform,
+        }
     };
 
     let input = match opt.file {
@@ -314,19 +875,33 @@ fn main() -> io::Result<()> {
             .as_deref()
             .map(snippet_parser)
             .transpose(),
+        settings
+            .machine
+            .accessory_on_sequence
+            .as_deref()
+            .map(snippet_parser)
+            .transpose(),
+        settings
+            .machine
+            .accessory_off_sequence
+            .as_deref()
+            .map(snippet_parser)
+            .transpose(),
     ];
 
-    let machine = if let [Ok(tool_on_action), Ok(tool_off_action), Ok(program_begin_sequence), Ok(program_end_sequence), Ok(between_layers_sequence)] =
+    let machine = if let [Ok(tool_on_action), Ok(tool_off_action), Ok(program_begin_sequence), Ok(program_end_sequence), Ok(between_layers_sequence), Ok(accessory_on_sequence), Ok(accessory_off_sequence)] =
         snippets
     {
         Machine::new(
-            settings.machine.supported_functionality,
+            settings.machine.supported_functionality.clone(),
             tool_on_action,
             tool_off_action,
             program_begin_sequence,
             program_end_sequence,
             between_layers_sequence,
         )
+        .with_config(&settings.machine)
+        .with_accessory_sequences(accessory_on_sequence, accessory_off_sequence)
     } else {
         use codespan_reporting::term::{
             emit,
@@ -340,7 +915,18 @@ fn main() -> io::Result<()> {
             ("tool_off_sequence", &settings.machine.tool_off_sequence),
             ("begin_sequence", &settings.machine.begin_sequence),
             ("end_sequence", &settings.machine.end_sequence),
-            ("between_layers_sequence", &settings.machine.between_layers_sequence),
+            (
+                "between_layers_sequence",
+                &settings.machine.between_layers_sequence,
+            ),
+            (
+                "accessory_on_sequence",
+                &settings.machine.accessory_on_sequence,
+            ),
+            (
+                "accessory_off_sequence",
+                &settings.machine.accessory_off_sequence,
+            ),
         ]
         .iter()
         .enumerate()
@@ -367,28 +953,21 @@ fn main() -> io::Result<()> {
     )
     .unwrap();
 
-    let program = svg2program(&document, &settings.conversion, options, machine);
+    let mut program = try_svg2program(&document, &settings.conversion, options, machine)
+        .map_err(io::Error::other)?;
+    settings
+        .postprocess
+        .apply(&mut program, settings.conversion.feedrate_units);
+
+    let postprocess = PostprocessConfig {
+        newline_before_comment: opt.out.is_none() && settings.postprocess.newline_before_comment,
+        ..settings.postprocess.clone()
+    };
+    let gcode = tokens_to_gcode(&program, &postprocess).map_err(io::Error::other)?;
 
     if let Some(out_path) = opt.out {
-        format_gcode_io(
-            &program,
-            FormatOptions {
-                line_numbers: settings.postprocess.line_numbers,
-                checksums: settings.postprocess.checksums,
-                ..Default::default()
-            },
-            File::create(out_path)?,
-        )
+        File::create(out_path)?.write_all(gcode.as_bytes())
     } else {
-        format_gcode_io(
-            &program,
-            FormatOptions {
-                line_numbers: settings.postprocess.line_numbers,
-                checksums: settings.postprocess.checksums,
-                newline_before_comment: settings.postprocess.newline_before_comment,
-                ..Default::default()
-            },
-            std::io::stdout(),
-        )
+        std::io::stdout().write_all(gcode.as_bytes())
     }
 }
@@ -118,15 +118,39 @@ fn test_conversion(svg_content: &str, tolerance: f64, min_arc_radius: Option<f64
     let config = GCodeConversionOptions {
         conversion: svg2gcode_wasm::ConversionConfig {
             tolerance,
+            tolerance_mode: None,
             feedrate: 3000.0,
+            feedrate_units: None,
             dpi: 96.0,
             origin_x: None,
             origin_y: None,
             min_arc_radius,
             extra_attribute_name: None,
+            annotate_source_coords: false,
             detect_polygon_arcs: false,
             min_polygon_arc_points: 5,
             polygon_arc_tolerance: None,
+            semicircle_epsilon: 1e-5,
+            min_sweep: 1e-6,
+            paint_server_policy: None,
+            honor_markers: false,
+            min_opacity: None,
+            on_unsupported: None,
+            summarize_skipped: false,
+            emit_titles: false,
+            join_gap: None,
+            user_units_per_mm: None,
+            max_abs_coordinate: None,
+            tolerance_attribute: None,
+            feedrate_is_physical: true,
+            origin_element: None,
+            layer_params: Default::default(),
+            order_by_containment: false,
+            stroke_repeats: 1,
+            trace_fill_as_outline: true,
+            dedupe_paths: false,
+            preserve_curvature: false,
+            pocket_stepover: None,
         },
         machine: svg2gcode_wasm::MachineConfig {
             circular_interpolation: true,
@@ -135,17 +159,57 @@ fn test_conversion(svg_content: &str, tolerance: f64, min_arc_radius: Option<f64
             begin_sequence: Some("; Document Start\nG21\nG17\nG90\nF10000\nG0 Z0\nG4 P0.2\nG0 X0 Y0".to_string()),
             end_sequence: Some("; END\nG0 Z0\nG4 P0.2\nG0 X0 Y0 F10000".to_string()),
             between_layers_sequence: Some("M0".to_string()),
+            accessory_on_sequence: None,
+            accessory_off_sequence: None,
+            safe_z: None,
+            cut_z: None,
+            separate_z_moves: true,
+            passes: None,
+            depth_per_pass: None,
+            program_end_command: None,
+            arc_distance_mode: None,
+            arc_format: None,
+            axis_max_feedrate: None,
+            arc_feedrate_reference_radius: None,
+            min_feedrate: None,
+            tool_change: false,
+            always_retract_between_subpaths: false,
+            travel_retract: None,
+            tool_change_retract: None,
+            path_mode: None,
+            blend_tolerance: None,
+            priming_position: None,
+            priming_length: None,
+            emit_units: true,
+            max_arcs: None,
         },
         postprocess: svg2gcode_wasm::PostprocessConfig {
             checksums: false,
             line_numbers: false,
+            line_number_start: 0,
+            line_number_increment: 1,
             newline_before_comment: true,
+            feedrate_decimals: None,
+            datum_offset: [0., 0.],
+            grid_snap: None,
+            annotate_subpaths: false,
+            modal_gcodes: false,
+            modal_feedrate: false,
+            annotate_time: false,
+            validate_roundtrip: false,
+            comment_style: None,
+            blank_lines_between_layers: 0,
         },
         override_width: None,
         override_height: None,
         h_align: None,
         v_align: None,
         trim: false,
+        system_language: None,
+        emit_bounding_frame: false,
+        scale_to_cut_length: None,
+        per_group_origins: None,
+        global_transform: None,
     };
     
     // Convert to JavaScript value for the WASM function
@@ -1,10 +1,14 @@
+use std::collections::HashMap;
+
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 use serde_json;
 use svg2gcode::{
-    svg2program, ConversionConfig as CoreConversionConfig, Machine,
+    parse_dimension, svg2paths_json, svg_physical_size, tokens_to_gcode, try_svg2program, ConversionConfig as CoreConversionConfig, Machine,
     MachineConfig as CoreMachineConfig, PostprocessConfig as CorePostprocessConfig, Settings,
     SupportedFunctionality as CoreSupportedFunctionality, ConversionOptions, HorizontalAlign, VerticalAlign,
+    FeedrateUnits, PaintServerPolicy, ToleranceMode, UnsupportedPolicy, ZAxisConfig as CoreZAxisConfig, MultiPassConfig as CoreMultiPassConfig, EndCommand, ArcDistanceMode, ArcFeedConfig, ArcFormat, PathMode, PocketConfig, PrimingConfig,
+    LayerParams as CoreLayerParams, CommentStyle,
 };
 use wasm_bindgen::prelude::*;
 
@@ -12,12 +16,52 @@ fn default_min_polygon_arc_points() -> usize {
     5
 }
 
+fn default_semicircle_epsilon() -> f64 {
+    1e-5
+}
+
+fn default_min_sweep() -> f64 {
+    1e-6
+}
+
+fn default_separate_z_moves() -> bool {
+    true
+}
+
+fn default_emit_units() -> bool {
+    true
+}
+
+fn default_feedrate_is_physical() -> bool {
+    true
+}
+
+fn default_stroke_repeats() -> usize {
+    1
+}
+
+fn default_trace_fill_as_outline() -> bool {
+    true
+}
+
+fn default_line_number_increment() -> u32 {
+    1
+}
+
 #[derive(Deserialize, Serialize, JsonSchema)]
 pub struct ConversionConfig {
     /// Curve interpolation tolerance in millimeters. Default: 0.002
     pub tolerance: f64,
+    /// Whether tolerance is in millimeters or a fraction of the diagonal of the drawing.
+    /// absolute|relative-to-diagonal. Default: absolute
+    #[serde(default)]
+    pub tolerance_mode: Option<String>,
     /// Machine feed rate in millimeters/minute. Default: 300.0
     pub feedrate: f64,
+    /// Units of the emitted F words, converted from feedrate. per-minute|per-second.
+    /// Default: per-minute
+    #[serde(default)]
+    pub feedrate_units: Option<String>,
     /// Dots per inch for pixels, picas, points, etc. Default: 96.0
     pub dpi: f64,
     /// The X coordinate of the origin in millimeters. Default: 0.0
@@ -30,6 +74,9 @@ pub struct ConversionConfig {
     pub min_arc_radius: Option<f64>,
     /// An extra attribute to include in comments, for debugging. Default: None
     pub extra_attribute_name: Option<String>,
+    /// Annotate each cut move with the SVG user space coordinate it ends at. Default: false
+    #[serde(default)]
+    pub annotate_source_coords: bool,
     /// Enable arc detection for polygons and polylines. Default: false
     #[serde(default)]
     pub detect_polygon_arcs: bool,
@@ -39,20 +86,154 @@ pub struct ConversionConfig {
     /// Maximum deviation tolerance for polygon arc detection (in mm). If omitted, uses the same tolerance as curve fitting.
     #[serde(default)]
     pub polygon_arc_tolerance: Option<f64>,
+    /// Relative tolerance within which an arc counts as a semicircle and is split in two. Default: 1e-5
+    #[serde(default = "default_semicircle_epsilon")]
+    pub semicircle_epsilon: f64,
+    /// Sweep angle (radians) below which an arc is emitted as a line. Default: 1e-6
+    #[serde(default = "default_min_sweep")]
+    pub min_sweep: f64,
+    /// How to handle elements filled or stroked with a gradient or pattern. solid-color|skip. Default: solid-color
+    #[serde(default)]
+    pub paint_server_policy: Option<String>,
+    /// Trace marker contents at the vertices of paths, lines, polylines and polygons. Default: false
+    #[serde(default)]
+    pub honor_markers: bool,
+    /// Skip shapes whose effective opacity is below this threshold (0 to 1). Default: None
+    #[serde(default)]
+    pub min_opacity: Option<f64>,
+    /// How to handle unsupported or degenerate elements. warn|ignore|error. Default: warn
+    #[serde(default)]
+    pub on_unsupported: Option<String>,
+    /// End the program with a comment counting the unsupported elements skipped by tag name.
+    /// Default: false
+    #[serde(default)]
+    pub summarize_skipped: bool,
+    /// Comment each element with the text of its <title> and <desc>. Default: false
+    #[serde(default)]
+    pub emit_titles: bool,
+    /// Join subpaths starting within this distance (mm) of the end of the last one with a cutting
+    /// move instead of lifting the tool. Default: None
+    #[serde(default)]
+    pub join_gap: Option<f64>,
+    /// Number of user units (and px) in a millimeter, overriding dpi. Default: None
+    #[serde(default)]
+    pub user_units_per_mm: Option<f64>,
+    /// Report an error for any X/Y farther than this from the origin (mm). Default: None
+    #[serde(default)]
+    pub max_abs_coordinate: Option<f64>,
+    /// Name of an attribute (e.g. data-tolerance) overriding the tolerance (mm) for an element and
+    /// its descendants. Default: None
+    #[serde(default)]
+    pub tolerance_attribute: Option<String>,
+    /// Whether feedrate is in mm/min, else user units/min scaled by dpi. Default: true
+    #[serde(default = "default_feedrate_is_physical")]
+    pub feedrate_is_physical: bool,
+    /// id of an element (e.g. a registration mark) whose center becomes the origin, overriding
+    /// origin. Default: None
+    #[serde(default)]
+    pub origin_element: Option<String>,
+    /// Feedrate, power, and cut_z for the contents of groups, by group id. Default: {}
+    #[serde(default)]
+    pub layer_params: HashMap<String, LayerParams>,
+    /// Cut closed subpaths after the subpaths inside of them. Default: false
+    #[serde(default)]
+    pub order_by_containment: bool,
+    /// Number of times each subpath is cut, alternating direction. Default: 1
+    #[serde(default = "default_stroke_repeats")]
+    pub stroke_repeats: usize,
+    /// Trace the outline of shapes that are filled but not stroked, else only stroked shapes are
+    /// traced. Default: true
+    #[serde(default = "default_trace_fill_as_outline")]
+    pub trace_fill_as_outline: bool,
+    /// Leave out subpaths tracing the same outline as one already cut. Default: false
+    #[serde(default)]
+    pub dedupe_paths: bool,
+    /// Keep gentle curves within the tolerance of a line curved, cutting them as arcs or finer
+    /// lines. Default: false
+    #[serde(default)]
+    pub preserve_curvature: bool,
+    /// Distance (mm) between concentric inward offsets filling closed shapes, for pocketing.
+    /// Default: None
+    #[serde(default)]
+    pub pocket_stepover: Option<f64>,
+}
+
+#[derive(Deserialize, Serialize, JsonSchema)]
+pub struct LayerParams {
+    /// Feedrate in millimeters/minute. Default: None
+    #[serde(default)]
+    pub feedrate: Option<f64>,
+    /// Tool power, emitted as an S word when the tool is turned on. Default: None
+    #[serde(default)]
+    pub power: Option<f64>,
+    /// Height the tool cuts at (mm), overriding cut_z. Default: None
+    #[serde(default)]
+    pub cut_z: Option<f64>,
+}
+
+impl From<LayerParams> for CoreLayerParams {
+    fn from(params: LayerParams) -> Self {
+        Self {
+            feedrate: params.feedrate,
+            power: params.power,
+            cut_z: params.cut_z,
+        }
+    }
 }
 
 impl From<ConversionConfig> for CoreConversionConfig {
     fn from(config: ConversionConfig) -> Self {
         Self {
             tolerance: config.tolerance,
+            tolerance_mode: match config.tolerance_mode.as_deref() {
+                Some("relative-to-diagonal") => ToleranceMode::RelativeToDiagonal,
+                _ => ToleranceMode::Absolute,
+            },
             feedrate: config.feedrate,
+            feedrate_units: match config.feedrate_units.as_deref() {
+                Some("per-second") => FeedrateUnits::PerSecond,
+                _ => FeedrateUnits::PerMinute,
+            },
             dpi: config.dpi,
             origin: [config.origin_x, config.origin_y],
             min_arc_radius: config.min_arc_radius,
             extra_attribute_name: config.extra_attribute_name,
+            annotate_source_coords: config.annotate_source_coords,
             detect_polygon_arcs: config.detect_polygon_arcs,
             min_polygon_arc_points: config.min_polygon_arc_points,
             polygon_arc_tolerance: config.polygon_arc_tolerance,
+            semicircle_epsilon: config.semicircle_epsilon,
+            min_sweep: config.min_sweep,
+            paint_server_policy: match config.paint_server_policy.as_deref() {
+                Some("skip") => PaintServerPolicy::Skip,
+                _ => PaintServerPolicy::SolidColor,
+            },
+            honor_markers: config.honor_markers,
+            min_opacity: config.min_opacity,
+            on_unsupported: match config.on_unsupported.as_deref() {
+                Some("ignore") => UnsupportedPolicy::Ignore,
+                Some("error") => UnsupportedPolicy::Error,
+                _ => UnsupportedPolicy::Warn,
+            },
+            summarize_skipped: config.summarize_skipped,
+            emit_titles: config.emit_titles,
+            join_gap: config.join_gap,
+            user_units_per_mm: config.user_units_per_mm,
+            max_abs_coordinate: config.max_abs_coordinate,
+            tolerance_attribute: config.tolerance_attribute,
+            feedrate_is_physical: config.feedrate_is_physical,
+            origin_element: config.origin_element,
+            layer_params: config
+                .layer_params
+                .into_iter()
+                .map(|(id, params)| (id, params.into()))
+                .collect(),
+            order_by_containment: config.order_by_containment,
+            stroke_repeats: config.stroke_repeats,
+            trace_fill_as_outline: config.trace_fill_as_outline,
+            dedupe_paths: config.dedupe_paths,
+            preserve_curvature: config.preserve_curvature,
+            pocket: config.pocket_stepover.map(|stepover| PocketConfig { stepover }),
         }
     }
 }
@@ -71,6 +252,86 @@ pub struct MachineConfig {
     pub end_sequence: Option<String>,
     /// G-Code sequence to run between sibling SVG groups/layers. Default: None
     pub between_layers_sequence: Option<String>,
+    /// G-Code sequence turning on an accessory like air assist (e.g. M7), emitted once when
+    /// cutting first begins. Default: None
+    #[serde(default)]
+    pub accessory_on_sequence: Option<String>,
+    /// G-Code sequence turning the accessory off (e.g. M9), emitted once at the end of the
+    /// program. Default: None
+    #[serde(default)]
+    pub accessory_off_sequence: Option<String>,
+    /// Height to retract to before traveling (mm). Z moves are only emitted when both this and
+    /// cut_z are set. Default: None
+    #[serde(default)]
+    pub safe_z: Option<f64>,
+    /// Height to plunge to before cutting (mm). Default: None
+    #[serde(default)]
+    pub cut_z: Option<f64>,
+    /// Retract and plunge with Z-only moves rather than combining the retract into the travel move. Default: true
+    #[serde(default = "default_separate_z_moves")]
+    pub separate_z_moves: bool,
+    /// Number of times to cut the program, each pass deeper than the last. Requires safe_z and
+    /// cut_z. Default: None
+    #[serde(default)]
+    pub passes: Option<usize>,
+    /// Additional depth of each pass after the first (mm). Default: 0
+    #[serde(default)]
+    pub depth_per_pass: Option<f64>,
+    /// Command emitted at the very end of the program, after the end sequence. m2|m30. Default: None
+    #[serde(default)]
+    pub program_end_command: Option<String>,
+    /// Whether G2/G3 X/Y endpoints are absolute or relative to the arc start (I/J are always
+    /// relative). absolute|relative. Default: absolute
+    #[serde(default)]
+    pub arc_distance_mode: Option<String>,
+    /// Whether G2/G3 arcs are given by their center offset or their radius. ij|r. Default: ij
+    #[serde(default)]
+    pub arc_format: Option<String>,
+    /// Maximum X and Y axis speeds (mm/min). Cutting moves are slowed down so that neither axis
+    /// exceeds its limit. Default: None
+    #[serde(default)]
+    pub axis_max_feedrate: Option<[f64; 2]>,
+    /// Slow down arcs tighter than this radius (mm), proportionally to their radius. Default: None
+    #[serde(default)]
+    pub arc_feedrate_reference_radius: Option<f64>,
+    /// Lowest feedrate (mm/min) that axis limits and arc scaling may slow a move down to.
+    /// Default: None
+    #[serde(default)]
+    pub min_feedrate: Option<f64>,
+    /// Change tools (`M6 T<n>`) before cutting each top-level group, numbered by its `data-tool`
+    /// attribute or else its position. Default: false
+    #[serde(default)]
+    pub tool_change: bool,
+    /// Turn the tool off and retract before every subpath, even when join_gap would join it.
+    /// Default: false
+    #[serde(default)]
+    pub always_retract_between_subpaths: bool,
+    /// Height (mm) to retract to between subpaths, instead of safe_z. Default: None
+    #[serde(default)]
+    pub travel_retract: Option<f64>,
+    /// Height (mm) to retract to before the between layers sequence or a tool change, instead of
+    /// safe_z. Default: None
+    #[serde(default)]
+    pub tool_change_retract: Option<f64>,
+    /// Path control mode selected at the start of the program. blend (G64 P)|exact-stop (G61).
+    /// Default: None
+    #[serde(default)]
+    pub path_mode: Option<String>,
+    /// Distance (mm) that the blend path mode may deviate from the path. Default: the tolerance
+    #[serde(default)]
+    pub blend_tolerance: Option<f64>,
+    /// Point (mm) where a short priming stroke starts before the drawing. Default: None
+    #[serde(default)]
+    pub priming_position: Option<[f64; 2]>,
+    /// Length (mm) of the priming stroke, which goes along +X and back. Default: 10
+    #[serde(default)]
+    pub priming_length: Option<f64>,
+    /// Select millimeters (G21) at the start of the program. Default: true
+    #[serde(default = "default_emit_units")]
+    pub emit_units: bool,
+    /// Most arcs (G2/G3) to emit, the rest are flattened into lines. Default: None
+    #[serde(default)]
+    pub max_arcs: Option<usize>,
 }
 
 impl From<MachineConfig> for CoreMachineConfig {
@@ -84,6 +345,52 @@ impl From<MachineConfig> for CoreMachineConfig {
             begin_sequence: config.begin_sequence,
             end_sequence: config.end_sequence,
             between_layers_sequence: config.between_layers_sequence,
+            accessory_on_sequence: config.accessory_on_sequence,
+            accessory_off_sequence: config.accessory_off_sequence,
+            z_axis: match (config.safe_z, config.cut_z) {
+                (Some(safe_z), Some(cut_z)) => Some(CoreZAxisConfig { safe_z, cut_z }),
+                _ => None,
+            },
+            separate_z_moves: config.separate_z_moves,
+            passes: config.passes.map(|count| CoreMultiPassConfig {
+                count,
+                depth_per_pass: config.depth_per_pass.unwrap_or_default(),
+            }),
+            program_end_command: match config.program_end_command.as_deref() {
+                Some("m2") => Some(EndCommand::M2),
+                Some("m30") => Some(EndCommand::M30),
+                _ => None,
+            },
+            arc_distance_mode: match config.arc_distance_mode.as_deref() {
+                Some("relative") => ArcDistanceMode::RelativeEndpoints,
+                _ => ArcDistanceMode::AbsoluteEndpoints,
+            },
+            arc_format: match config.arc_format.as_deref() {
+                Some("r") => ArcFormat::R,
+                _ => ArcFormat::IJ,
+            },
+            axis_max_feedrate: config.axis_max_feedrate,
+            arc_feedrate_scaling: config
+                .arc_feedrate_reference_radius
+                .map(|reference_radius| ArcFeedConfig { reference_radius }),
+            min_feedrate: config.min_feedrate,
+            tool_change: config.tool_change,
+            always_retract_between_subpaths: config.always_retract_between_subpaths,
+            travel_retract: config.travel_retract,
+            tool_change_retract: config.tool_change_retract,
+            path_mode: match config.path_mode.as_deref() {
+                Some("blend") => Some(PathMode::Blend {
+                    tolerance: config.blend_tolerance,
+                }),
+                Some("exact-stop") => Some(PathMode::ExactStop),
+                _ => None,
+            },
+            priming_stroke: config.priming_position.map(|position| PrimingConfig {
+                position,
+                length: config.priming_length.unwrap_or(10.),
+            }),
+            emit_units: config.emit_units,
+            max_arcs: config.max_arcs,
         }
     }
 }
@@ -94,8 +401,47 @@ pub struct PostprocessConfig {
     pub checksums: bool,
     /// Whether to include line numbers in the G-Code output. Default: false
     pub line_numbers: bool,
+    /// Number of the first line when including line numbers. Default: 0
+    #[serde(default)]
+    pub line_number_start: u32,
+    /// Step between consecutive line numbers. Default: 1
+    #[serde(default = "default_line_number_increment")]
+    pub line_number_increment: u32,
     /// Whether to include a newline before comments in the G-Code output. Default: false
     pub newline_before_comment: bool,
+    /// Round feedrate (F) words to this many decimal places. Default: None
+    #[serde(default)]
+    pub feedrate_decimals: Option<u8>,
+    /// Shift every absolute X/Y word by this offset (mm), after everything else. Default: [0, 0]
+    #[serde(default)]
+    pub datum_offset: [f64; 2],
+    /// Round every X/Y word to the nearest multiple of this step (mm). Default: None
+    #[serde(default)]
+    pub grid_snap: Option<f64>,
+    /// Bracket the moves of each element with begin/end comments, including its bounding box.
+    /// Default: false
+    #[serde(default)]
+    pub annotate_subpaths: bool,
+    /// Leave out motion words (G0/G1/G2/G3) repeating the motion mode already in effect.
+    /// Default: false
+    #[serde(default)]
+    pub modal_gcodes: bool,
+    /// Leave out feedrate (F) words equal to the feedrate already in effect. Default: false
+    #[serde(default)]
+    pub modal_feedrate: bool,
+    /// End each move with a "; t=<seconds>" comment estimating the time elapsed so far.
+    /// Default: false
+    #[serde(default)]
+    pub annotate_time: bool,
+    /// Parse the generated g-code and format it again, failing if it is malformed. Default: false
+    #[serde(default)]
+    pub validate_roundtrip: bool,
+    /// Kind of comments to write. mixed|semicolon|parentheses. Default: mixed
+    #[serde(default)]
+    pub comment_style: Option<String>,
+    /// Number of blank lines separating layers. Default: 0
+    #[serde(default)]
+    pub blank_lines_between_layers: usize,
 }
 
 impl From<PostprocessConfig> for CorePostprocessConfig {
@@ -103,7 +449,23 @@ impl From<PostprocessConfig> for CorePostprocessConfig {
         Self {
             checksums: config.checksums,
             line_numbers: config.line_numbers,
+            line_number_start: config.line_number_start,
+            line_number_increment: config.line_number_increment,
             newline_before_comment: config.newline_before_comment,
+            feedrate_decimals: config.feedrate_decimals,
+            datum_offset: config.datum_offset,
+            grid_snap: config.grid_snap,
+            annotate_subpaths: config.annotate_subpaths,
+            modal_gcodes: config.modal_gcodes,
+            modal_feedrate: config.modal_feedrate,
+            annotate_time: config.annotate_time,
+            validate_roundtrip: config.validate_roundtrip,
+            comment_style: match config.comment_style.as_deref() {
+                Some("semicolon") => CommentStyle::Semicolon,
+                Some("parentheses") => CommentStyle::Parentheses,
+                _ => CommentStyle::Mixed,
+            },
+            blank_lines_between_layers: config.blank_lines_between_layers,
         }
     }
 }
@@ -129,6 +491,23 @@ pub struct GCodeConversionOptions {
     /// If true, scales tight drawing bbox into the override dimensions (paper style fit).
     #[serde(default)]
     pub trim: bool,
+    /// Comma-separated language tags that systemLanguage attributes are matched against. Ignored if unset.
+    #[serde(default)]
+    pub system_language: Option<String>,
+    /// If true, traces the drawing's bounding rectangle with rapid moves before cutting.
+    #[serde(default)]
+    pub emit_bounding_frame: bool,
+    /// Optional total length in millimeters that the drawing's outlines are scaled uniformly to.
+    #[serde(default)]
+    pub scale_to_cut_length: Option<f64>,
+    /// Optional [x, y] origins in millimeters that the bottom-left corners of the top-level
+    /// groups are moved to, in order.
+    #[serde(default)]
+    pub per_group_origins: Option<Vec<[f64; 2]>>,
+    /// Optional affine matrix [a, b, c, d, e, f] applied to the whole drawing before it is placed,
+    /// in millimeters with the Y axis pointing up.
+    #[serde(default)]
+    pub global_transform: Option<[f64; 6]>,
 }
 
 impl GCodeConversionOptions {
@@ -136,6 +515,21 @@ impl GCodeConversionOptions {
         let schema = schemars::schema_for!(GCodeConversionOptions);
         serde_json::to_string_pretty(&schema).unwrap()
     }
+
+    /// Build [ConversionOptions] from the overrides
+    fn conversion_options(&self) -> Result<ConversionOptions, String> {
+        let mut dimensions: [Option<svgtypes::Length>; 2] = [None, None];
+        for (i, src) in [self.override_width.as_ref(), self.override_height.as_ref()].into_iter().enumerate() {
+            if let Some(s) = src {
+                if !s.is_empty() {
+                    dimensions[i] = Some(parse_dimension(s).map_err(|e| e.to_string())?);
+                }
+            }
+        }
+        let h_align = match self.h_align.as_deref() { Some("center") => HorizontalAlign::Center, Some("right") => HorizontalAlign::Right, _ => HorizontalAlign::Left };
+        let v_align = match self.v_align.as_deref() { Some("center") => VerticalAlign::Center, Some("bottom") => VerticalAlign::Bottom, _ => VerticalAlign::Top };
+        Ok(ConversionOptions { dimensions, h_align, v_align, trim: self.trim, system_language: self.system_language.clone(), emit_bounding_frame: self.emit_bounding_frame, scale_to_cut_length: self.scale_to_cut_length, command_filter: None, per_group_origins: self.per_group_origins.clone(), global_transform: self.global_transform })
+    }
 }
 
 #[wasm_bindgen]
@@ -147,6 +541,7 @@ pub fn param_schema_json() -> String {
 pub fn convert_svg(svg_str: &str, options: &JsValue) -> Result<String, String> {
     let options: GCodeConversionOptions =
         serde_wasm_bindgen::from_value(options.clone()).map_err(|e| e.to_string())?;
+    let conv_options = options.conversion_options()?;
 
     let settings = Settings {
         conversion: options.conversion.into(),
@@ -163,35 +558,39 @@ pub fn convert_svg(svg_str: &str, options: &JsValue) -> Result<String, String> {
         settings.machine.begin_sequence.as_deref().map(g_code::parse::snippet_parser).transpose().unwrap(),
         settings.machine.end_sequence.as_deref().map(g_code::parse::snippet_parser).transpose().unwrap(),
     settings.machine.between_layers_sequence.as_deref().map(g_code::parse::snippet_parser).transpose().unwrap(),
+    )
+    .with_config(&settings.machine)
+    .with_accessory_sequences(
+        settings.machine.accessory_on_sequence.as_deref().map(g_code::parse::snippet_parser).transpose().unwrap(),
+        settings.machine.accessory_off_sequence.as_deref().map(g_code::parse::snippet_parser).transpose().unwrap(),
     );
 
-    // Build ConversionOptions from overrides
-    let mut dimensions: [Option<svgtypes::Length>; 2] = [None, None];
-    for (i, src) in [options.override_width.as_ref(), options.override_height.as_ref()].into_iter().enumerate() {
-        if let Some(s) = src {
-            if !s.is_empty() {
-                if let Some(first) = svgtypes::LengthListParser::from(s.as_str()).next() { dimensions[i] = Some(first.map_err(|e| e.to_string())?); }
-            }
-        }
-    }
-    let h_align = match options.h_align.as_deref() { Some("center") => HorizontalAlign::Center, Some("right") => HorizontalAlign::Right, _ => HorizontalAlign::Left };
-    let v_align = match options.v_align.as_deref() { Some("center") => VerticalAlign::Center, Some("bottom") => VerticalAlign::Bottom, _ => VerticalAlign::Top };
-    let conv_options = ConversionOptions { dimensions, h_align, v_align, trim: options.trim };
-
-    let gcode_tokens = svg2program(&doc, &settings.conversion, conv_options, machine);
-
-    let mut gcode_out = String::new();
-    g_code::emit::format_gcode_fmt(
-        gcode_tokens.iter(),
-        g_code::emit::FormatOptions {
-            checksums: settings.postprocess.checksums,
-            line_numbers: settings.postprocess.line_numbers,
-            newline_before_comment: settings.postprocess.newline_before_comment,
-            ..Default::default()
-        },
-        &mut gcode_out,
-    )
-    .map_err(|e| e.to_string())?;
+    let mut gcode_tokens = try_svg2program(&doc, &settings.conversion, conv_options, machine)
+        .map_err(|e| e.to_string())?;
+    settings
+        .postprocess
+        .apply(&mut gcode_tokens, settings.conversion.feedrate_units);
 
-    Ok(gcode_out)
-}
\ No newline at end of file
+    tokens_to_gcode(&gcode_tokens, &settings.postprocess).map_err(|e| e.to_string())
+}
+/// Flatten an SVG into a JSON array of subpaths (points, id, color, length) instead of g-code
+#[wasm_bindgen]
+pub fn convert_svg_to_paths_json(svg_str: &str, options: &JsValue) -> Result<String, String> {
+    let options: GCodeConversionOptions =
+        serde_wasm_bindgen::from_value(options.clone()).map_err(|e| e.to_string())?;
+    let conv_options = options.conversion_options()?;
+
+    let doc = roxmltree::Document::parse(svg_str).map_err(|e| e.to_string())?;
+    Ok(svg2paths_json(&doc, &options.conversion.into(), conv_options))
+}
+
+/// Width and height in millimeters that an SVG declares for itself, or null if it can't be told
+#[wasm_bindgen]
+pub fn svg_physical_size_mm(svg_str: &str, options: &JsValue) -> Result<JsValue, String> {
+    let options: GCodeConversionOptions =
+        serde_wasm_bindgen::from_value(options.clone()).map_err(|e| e.to_string())?;
+
+    let doc = roxmltree::Document::parse(svg_str).map_err(|e| e.to_string())?;
+    let size = svg_physical_size(&doc, &options.conversion.into());
+    serde_wasm_bindgen::to_value(&size).map_err(|e| e.to_string())
+}
@@ -27,7 +27,7 @@ fn run(opts: ConversionOptions) -> (f64,f64,f64,f64) {
 
 #[test]
 fn trim_center_top() {
-    let opts = ConversionOptions { dimensions:[Some(svgtypes::Length{number:100.0, unit:svgtypes::LengthUnit::Mm}), Some(svgtypes::Length{number:50.0, unit:svgtypes::LengthUnit::Mm})], h_align:HorizontalAlign::Center, v_align:VerticalAlign::Top, trim:true };
+    let opts = ConversionOptions { dimensions:[Some(svgtypes::Length{number:100.0, unit:svgtypes::LengthUnit::Mm}), Some(svgtypes::Length{number:50.0, unit:svgtypes::LengthUnit::Mm})], h_align:HorizontalAlign::Center, v_align:VerticalAlign::Top, trim:true, ..Default::default() };
     let (min_x,max_x,min_y,max_y)=run(opts);
     // width scaled to 50 (uniform) and centered in 100 -> 25..75
     assert!((min_x-25.0).abs()<0.2, "min_x={min_x}");
@@ -38,7 +38,7 @@ fn trim_center_top() {
 
 #[test]
 fn trim_right_bottom() {
-    let opts = ConversionOptions { dimensions:[Some(svgtypes::Length{number:100.0, unit:svgtypes::LengthUnit::Mm}), Some(svgtypes::Length{number:50.0, unit:svgtypes::LengthUnit::Mm})], h_align:HorizontalAlign::Right, v_align:VerticalAlign::Bottom, trim:true };
+    let opts = ConversionOptions { dimensions:[Some(svgtypes::Length{number:100.0, unit:svgtypes::LengthUnit::Mm}), Some(svgtypes::Length{number:50.0, unit:svgtypes::LengthUnit::Mm})], h_align:HorizontalAlign::Right, v_align:VerticalAlign::Bottom, trim:true, ..Default::default() };
     let (min_x,max_x,min_y,max_y)=run(opts);
     assert!((min_x-50.0).abs()<0.2, "min_x={min_x}");
     assert!((max_x-100.0).abs()<0.2, "max_x={max_x}");
@@ -48,7 +48,7 @@ fn trim_right_bottom() {
 
 #[test]
 fn trim_width_only() {
-    let opts = ConversionOptions { dimensions:[Some(svgtypes::Length{number:80.0, unit:svgtypes::LengthUnit::Mm}), None], h_align:HorizontalAlign::Left, v_align:VerticalAlign::Top, trim:true };
+    let opts = ConversionOptions { dimensions:[Some(svgtypes::Length{number:80.0, unit:svgtypes::LengthUnit::Mm}), None], h_align:HorizontalAlign::Left, v_align:VerticalAlign::Top, trim:true, ..Default::default() };
     let (min_x,max_x,min_y,max_y)=run(opts);
     assert!((min_x-0.0).abs()<0.05);
     assert!((max_x-80.0).abs()<0.05);
@@ -59,7 +59,7 @@ fn trim_width_only() {
 #[test]
 fn dimensions_no_trim_center_alignment_should_not_scale_bbox() {
     // No trim: override viewport to 100x50, but drawing (10x10 user units) becomes scaled non-uniform? We just verify alignment translation roughly.
-    let opts = ConversionOptions { dimensions:[Some(svgtypes::Length{number:100.0, unit:svgtypes::LengthUnit::Mm}), Some(svgtypes::Length{number:50.0, unit:svgtypes::LengthUnit::Mm})], h_align:HorizontalAlign::Center, v_align:VerticalAlign::Center, trim:false };
+    let opts = ConversionOptions { dimensions:[Some(svgtypes::Length{number:100.0, unit:svgtypes::LengthUnit::Mm}), Some(svgtypes::Length{number:50.0, unit:svgtypes::LengthUnit::Mm})], h_align:HorizontalAlign::Center, v_align:VerticalAlign::Center, trim:false, ..Default::default() };
     let (min_x,max_x,min_y,max_y)=run(opts);
     let width = max_x - min_x; let height = max_y - min_y;
     // Expect full width equals 100 or close (since viewport width override). Just ensure within bounds.
@@ -69,7 +69,7 @@ fn dimensions_no_trim_center_alignment_should_not_scale_bbox() {
 
 #[test]
 fn trim_left_top() {
-    let opts = ConversionOptions { dimensions:[Some(svgtypes::Length{number:120.0, unit:svgtypes::LengthUnit::Mm}), Some(svgtypes::Length{number:60.0, unit:svgtypes::LengthUnit::Mm})], h_align:HorizontalAlign::Left, v_align:VerticalAlign::Top, trim:true };
+    let opts = ConversionOptions { dimensions:[Some(svgtypes::Length{number:120.0, unit:svgtypes::LengthUnit::Mm}), Some(svgtypes::Length{number:60.0, unit:svgtypes::LengthUnit::Mm})], h_align:HorizontalAlign::Left, v_align:VerticalAlign::Top, trim:true, ..Default::default() };
     let (min_x,max_x,min_y,max_y)=run(opts);
     // After trim scaling uniform factor = min(120/10,60/10)=6 -> bbox 60x60 placed top-left inside 120x60
     assert!((min_x-0.0).abs()<0.2);
@@ -7,9 +7,9 @@ fn main() {
     let machine = Machine::new(SupportedFunctionality { circular_interpolation: false }, None, None, None, None, None);
 
     let configs = [
-        ("center-top trim to 100x50", ConversionOptions { dimensions: [Some(svgtypes::Length { number: 100.0, unit: svgtypes::LengthUnit::Mm }), Some(svgtypes::Length { number: 50.0, unit: svgtypes::LengthUnit::Mm })], h_align: HorizontalAlign::Center, v_align: VerticalAlign::Top, trim: true }),
-        ("right-bottom trim to 100x50", ConversionOptions { dimensions: [Some(svgtypes::Length { number: 100.0, unit: svgtypes::LengthUnit::Mm }), Some(svgtypes::Length { number: 50.0, unit: svgtypes::LengthUnit::Mm })], h_align: HorizontalAlign::Right, v_align: VerticalAlign::Bottom, trim: true }),
-        ("left-top trim width 80", ConversionOptions { dimensions: [Some(svgtypes::Length { number: 80.0, unit: svgtypes::LengthUnit::Mm }), None], h_align: HorizontalAlign::Left, v_align: VerticalAlign::Top, trim: true }),
+        ("center-top trim to 100x50", ConversionOptions { dimensions: [Some(svgtypes::Length { number: 100.0, unit: svgtypes::LengthUnit::Mm }), Some(svgtypes::Length { number: 50.0, unit: svgtypes::LengthUnit::Mm })], h_align: HorizontalAlign::Center, v_align: VerticalAlign::Top, trim: true, ..Default::default() }),
+        ("right-bottom trim to 100x50", ConversionOptions { dimensions: [Some(svgtypes::Length { number: 100.0, unit: svgtypes::LengthUnit::Mm }), Some(svgtypes::Length { number: 50.0, unit: svgtypes::LengthUnit::Mm })], h_align: HorizontalAlign::Right, v_align: VerticalAlign::Bottom, trim: true, ..Default::default() }),
+        ("left-top trim width 80", ConversionOptions { dimensions: [Some(svgtypes::Length { number: 80.0, unit: svgtypes::LengthUnit::Mm }), None], h_align: HorizontalAlign::Left, v_align: VerticalAlign::Top, trim: true, ..Default::default() }),
     ];
 
     for (label, opts) in configs { 
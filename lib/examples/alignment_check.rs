@@ -40,7 +40,7 @@ fn main() {
     // Scenarios derived from previous integration tests
     run_case(
         "trim center-top 100x50",
-        ConversionOptions { dimensions:[Some(Length{number:100.0,unit:mm}), Some(Length{number:50.0,unit:mm})], h_align:HorizontalAlign::Center, v_align:VerticalAlign::Top, trim:true },
+        ConversionOptions { dimensions:[Some(Length{number:100.0,unit:mm}), Some(Length{number:50.0,unit:mm})], h_align:HorizontalAlign::Center, v_align:VerticalAlign::Top, trim:true, ..Default::default() },
         |min_x,max_x,min_y,max_y| {
             if !approx(min_x,25.0,0.3) { return Err(format!("min_x expected ~25 got {min_x}")); }
             if !approx(max_x,75.0,0.3) { return Err(format!("max_x expected ~75 got {max_x}")); }
@@ -52,7 +52,7 @@ fn main() {
 
     run_case(
         "trim right-bottom 100x50",
-        ConversionOptions { dimensions:[Some(Length{number:100.0,unit:mm}), Some(Length{number:50.0,unit:mm})], h_align:HorizontalAlign::Right, v_align:VerticalAlign::Bottom, trim:true },
+        ConversionOptions { dimensions:[Some(Length{number:100.0,unit:mm}), Some(Length{number:50.0,unit:mm})], h_align:HorizontalAlign::Right, v_align:VerticalAlign::Bottom, trim:true, ..Default::default() },
         |min_x,max_x,min_y,max_y| {
             if !approx(min_x,50.0,0.3) { return Err(format!("min_x expected ~50 got {min_x}")); }
             if !approx(max_x,100.0,0.3) { return Err(format!("max_x expected ~100 got {max_x}")); }
@@ -64,7 +64,7 @@ fn main() {
 
     run_case(
         "trim left-top width=80",
-        ConversionOptions { dimensions:[Some(Length{number:80.0,unit:mm}), None], h_align:HorizontalAlign::Left, v_align:VerticalAlign::Top, trim:true },
+        ConversionOptions { dimensions:[Some(Length{number:80.0,unit:mm}), None], h_align:HorizontalAlign::Left, v_align:VerticalAlign::Top, trim:true, ..Default::default() },
         |min_x,max_x,min_y,max_y| {
             if !approx(min_x,0.0,0.1) { return Err(format!("min_x expected 0 got {min_x}")); }
             if !approx(max_x,80.0,0.2) { return Err(format!("max_x expected 80 got {max_x}")); }
@@ -76,7 +76,7 @@ fn main() {
 
     run_case(
         "dimensions no-trim center-center 100x50",
-        ConversionOptions { dimensions:[Some(Length{number:100.0,unit:mm}), Some(Length{number:50.0,unit:mm})], h_align:HorizontalAlign::Center, v_align:VerticalAlign::Center, trim:false },
+        ConversionOptions { dimensions:[Some(Length{number:100.0,unit:mm}), Some(Length{number:50.0,unit:mm})], h_align:HorizontalAlign::Center, v_align:VerticalAlign::Center, trim:false, ..Default::default() },
         |min_x,max_x,min_y,max_y| {
             let width = max_x - min_x; let height = max_y - min_y;
             if !(width <= 100.5 && width > 40.0) { return Err(format!("unexpected width {width}")); }
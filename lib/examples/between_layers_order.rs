@@ -10,7 +10,7 @@ fn main() {
     let doc = roxmltree::Document::parse(svg).unwrap();
 
     let mut settings = Settings::default();
-    settings.conversion = ConversionConfig { tolerance: 0.002, feedrate: 300.0, dpi: 96.0, origin: [None,None], extra_attribute_name: None };
+    settings.conversion = ConversionConfig { tolerance: 0.002, feedrate: 300.0, dpi: 96.0, origin: [None,None], extra_attribute_name: None, ..Default::default() };
     settings.machine = MachineConfig {
         supported_functionality: SupportedFunctionality { circular_interpolation: false },
         tool_on_sequence: Some("M3".into()),
@@ -18,8 +18,9 @@ fn main() {
         begin_sequence: None,
         end_sequence: None,
         between_layers_sequence: Some("(BL)".into()),
+        ..Default::default()
     };
-    settings.postprocess = PostprocessConfig { checksums: false, line_numbers: false, newline_before_comment: false };
+    settings.postprocess = PostprocessConfig { checksums: false, line_numbers: false, newline_before_comment: false, ..Default::default() };
 
     let machine = Machine::new(
         settings.machine.supported_functionality.clone(),
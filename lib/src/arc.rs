@@ -79,8 +79,25 @@ fn arc_from_endpoints_and_tangents<S: Scalar>(
 
 pub trait FlattenWithArcs<S> {
     fn flattened(&self, tolerance: S) -> Vec<ArcOrLineSegment<S>>;
+
+    /// Same as [FlattenWithArcs::flattened], but only curves within a fraction of `tolerance`
+    /// of a line are taken as straight, so that gentle curves on large parts stay curved
+    fn flattened_preserving_curvature(&self, tolerance: S) -> Vec<ArcOrLineSegment<S>> {
+        self.flattened(tolerance)
+    }
 }
 
+/// Fraction of the tolerance a curve may deviate from a line by to be taken as straight, see
+/// [FlattenWithArcs::flattened_preserving_curvature]
+const PRESERVED_CURVATURE_FRACTION: f64 = 0.01;
+
+/// How many times a curve may be split in half while flattening it, so that a tolerance too
+/// small to ever be met (e.g. below floating point precision) can't recurse without bound
+///
+/// Pieces at this depth are drawn as their best fitting arc, or a line if there is none,
+/// regardless of the tolerance.
+const MAX_SPLIT_DEPTH: u32 = 16;
+
 impl<S> FlattenWithArcs<S> for CubicBezierSegment<S>
 where
     S: Scalar + Copy,
@@ -92,97 +109,132 @@ where
     /// Kaewsaiha, P., & Dejdumrong, N. (2012). Modeling of Bézier Curves Using a Combination of Linear and Circular Arc Approximations. 2012 Ninth International Conference on Computer Graphics, Imaging and Visualization. doi:10.1109/cgiv.2012.20
     ///
     fn flattened(&self, tolerance: S) -> Vec<ArcOrLineSegment<S>> {
-        if (self.to - self.from).square_length() < S::EPSILON {
-            return vec![];
-        } else if self.is_linear(tolerance) {
-            return vec![ArcOrLineSegment::Line(self.baseline())];
-        }
-        let mut acc = vec![];
-
-        self.for_each_monotonic_range(&mut |range| {
-            let inner_bezier = self.split_range(range);
-
-            if (inner_bezier.to - inner_bezier.from).square_length() < S::EPSILON {
-                return;
-            } else if inner_bezier.is_linear(tolerance) {
-                acc.push(ArcOrLineSegment::Line(inner_bezier.baseline()));
-                return;
-            }
+        flatten_cubic_bezier(self, tolerance, tolerance, 0)
+    }
 
-            if let Some(svg_arc) = arc_from_endpoints_and_tangents(
-                inner_bezier.from,
-                inner_bezier.derivative(S::ZERO),
-                inner_bezier.to,
-                inner_bezier.derivative(S::ONE),
-            )
-            .filter(|svg_arc| {
-                let arc = svg_arc.to_arc();
-                let mut max_deviation = S::ZERO;
-                // TODO: find a better way to check tolerance
-                // Ideally: derivative of |f(x) - g(x)| and look at 0 crossings
-                for i in 1..20 {
-                    let t = S::from(i).unwrap() / S::from(20).unwrap();
-                    max_deviation =
-                        max_deviation.max((arc.sample(t) - inner_bezier.sample(t)).length());
-                }
-                max_deviation < tolerance
-            }) {
-                acc.push(ArcOrLineSegment::Arc(svg_arc));
-            } else {
-                let (left, right) = inner_bezier.split(S::HALF);
-                acc.append(&mut FlattenWithArcs::flattened(&left, tolerance));
-                acc.append(&mut FlattenWithArcs::flattened(&right, tolerance));
-            }
-        });
-        acc
+    fn flattened_preserving_curvature(&self, tolerance: S) -> Vec<ArcOrLineSegment<S>> {
+        let straightness = tolerance * S::from(PRESERVED_CURVATURE_FRACTION).unwrap();
+        flatten_cubic_bezier(self, tolerance, straightness, 0)
     }
 }
 
-impl<S> FlattenWithArcs<S> for SvgArc<S>
-where
-    S: Scalar,
-{
-    fn flattened(&self, tolerance: S) -> Vec<ArcOrLineSegment<S>> {
-        if (self.to - self.from).square_length() < S::EPSILON {
-            return vec![];
-        } else if self.is_straight_line() {
-            return vec![ArcOrLineSegment::Line(LineSegment {
-                from: self.from,
-                to: self.to,
-            })];
-        } else if (self.radii.x.abs() - self.radii.y.abs()).abs() < S::EPSILON {
-            return vec![ArcOrLineSegment::Arc(*self)];
+/// [FlattenWithArcs::flattened] for a piece of a cubic bezier that was split `depth` times,
+/// taking pieces within `straightness` of a line as straight
+fn flatten_cubic_bezier<S: Scalar>(
+    cbs: &CubicBezierSegment<S>,
+    tolerance: S,
+    straightness: S,
+    depth: u32,
+) -> Vec<ArcOrLineSegment<S>> {
+    if (cbs.to - cbs.from).square_length() < S::EPSILON {
+        return vec![];
+    } else if cbs.is_linear(straightness) {
+        return vec![ArcOrLineSegment::Line(cbs.baseline())];
+    }
+    let mut acc = vec![];
+
+    cbs.for_each_monotonic_range(&mut |range| {
+        let inner_bezier = cbs.split_range(range);
+
+        if (inner_bezier.to - inner_bezier.from).square_length() < S::EPSILON {
+            return;
+        } else if inner_bezier.is_linear(straightness) {
+            acc.push(ArcOrLineSegment::Line(inner_bezier.baseline()));
+            return;
         }
 
-        let self_arc = self.to_arc();
-        if let Some(svg_arc) = arc_from_endpoints_and_tangents(
-            self.from,
-            self_arc.sample_tangent(S::ZERO),
-            self.to,
-            self_arc.sample_tangent(S::ONE),
-        )
-        .filter(|approx_svg_arc| {
-            let approx_arc = approx_svg_arc.to_arc();
+        let svg_arc = arc_from_endpoints_and_tangents(
+            inner_bezier.from,
+            inner_bezier.derivative(S::ZERO),
+            inner_bezier.to,
+            inner_bezier.derivative(S::ONE),
+        );
+        if depth >= MAX_SPLIT_DEPTH {
+            acc.push(match svg_arc {
+                Some(svg_arc) => ArcOrLineSegment::Arc(svg_arc),
+                None => ArcOrLineSegment::Line(inner_bezier.baseline()),
+            });
+        } else if let Some(svg_arc) = svg_arc.filter(|svg_arc| {
+            let arc = svg_arc.to_arc();
             let mut max_deviation = S::ZERO;
             // TODO: find a better way to check tolerance
             // Ideally: derivative of |f(x) - g(x)| and look at 0 crossings
             for i in 1..20 {
                 let t = S::from(i).unwrap() / S::from(20).unwrap();
                 max_deviation =
-                    max_deviation.max((approx_arc.sample(t) - self_arc.sample(t)).length());
+                    max_deviation.max((arc.sample(t) - inner_bezier.sample(t)).length());
             }
             max_deviation < tolerance
         }) {
-            vec![ArcOrLineSegment::Arc(svg_arc)]
+            acc.push(ArcOrLineSegment::Arc(svg_arc));
         } else {
-            let (left, right) = self_arc.split(S::HALF);
-            let mut acc = FlattenWithArcs::flattened(&left.to_svg_arc(), tolerance);
-            acc.append(&mut FlattenWithArcs::flattened(
-                &right.to_svg_arc(),
-                tolerance,
-            ));
-            acc
+            let (left, right) = inner_bezier.split(S::HALF);
+            acc.append(&mut flatten_cubic_bezier(&left, tolerance, straightness, depth + 1));
+            acc.append(&mut flatten_cubic_bezier(&right, tolerance, straightness, depth + 1));
+        }
+    });
+    acc
+}
+
+impl<S> FlattenWithArcs<S> for SvgArc<S>
+where
+    S: Scalar,
+{
+    fn flattened(&self, tolerance: S) -> Vec<ArcOrLineSegment<S>> {
+        flatten_svg_arc(self, tolerance, 0)
+    }
+}
+
+/// [FlattenWithArcs::flattened] for a piece of an elliptical arc that was split `depth` times
+fn flatten_svg_arc<S: Scalar>(
+    svg_arc: &SvgArc<S>,
+    tolerance: S,
+    depth: u32,
+) -> Vec<ArcOrLineSegment<S>> {
+    if (svg_arc.to - svg_arc.from).square_length() < S::EPSILON {
+        return vec![];
+    } else if svg_arc.is_straight_line() {
+        return vec![ArcOrLineSegment::Line(LineSegment {
+            from: svg_arc.from,
+            to: svg_arc.to,
+        })];
+    } else if (svg_arc.radii.x.abs() - svg_arc.radii.y.abs()).abs() < S::EPSILON {
+        return vec![ArcOrLineSegment::Arc(*svg_arc)];
+    }
+
+    let self_arc = svg_arc.to_arc();
+    let approx_svg_arc = arc_from_endpoints_and_tangents(
+        svg_arc.from,
+        self_arc.sample_tangent(S::ZERO),
+        svg_arc.to,
+        self_arc.sample_tangent(S::ONE),
+    );
+    if depth >= MAX_SPLIT_DEPTH {
+        vec![match approx_svg_arc {
+            Some(approx_svg_arc) => ArcOrLineSegment::Arc(approx_svg_arc),
+            None => ArcOrLineSegment::Line(LineSegment {
+                from: svg_arc.from,
+                to: svg_arc.to,
+            }),
+        }]
+    } else if let Some(approx_svg_arc) = approx_svg_arc.filter(|approx_svg_arc| {
+        let approx_arc = approx_svg_arc.to_arc();
+        let mut max_deviation = S::ZERO;
+        // TODO: find a better way to check tolerance
+        // Ideally: derivative of |f(x) - g(x)| and look at 0 crossings
+        for i in 1..20 {
+            let t = S::from(i).unwrap() / S::from(20).unwrap();
+            max_deviation =
+                max_deviation.max((approx_arc.sample(t) - self_arc.sample(t)).length());
         }
+        max_deviation < tolerance
+    }) {
+        vec![ArcOrLineSegment::Arc(approx_svg_arc)]
+    } else {
+        let (left, right) = self_arc.split(S::HALF);
+        let mut acc = flatten_svg_arc(&left.to_svg_arc(), tolerance, depth + 1);
+        acc.append(&mut flatten_svg_arc(&right.to_svg_arc(), tolerance, depth + 1));
+        acc
     }
 }
 
@@ -277,11 +329,11 @@ impl<S: Scalar> Transformed<S> for SvgArc<S> {
 #[cfg(test)]
 mod tests {
     use cairo::{Context, SvgSurface};
-    use lyon_geom::{point, vector, CubicBezierSegment, Point, Vector};
+    use lyon_geom::{point, vector, Angle, ArcFlags, CubicBezierSegment, Point, SvgArc, Vector};
     use std::path::PathBuf;
     use svgtypes::PathParser;
 
-    use crate::arc::{ArcOrLineSegment, FlattenWithArcs};
+    use crate::arc::{ArcOrLineSegment, FlattenWithArcs, MAX_SPLIT_DEPTH};
 
     #[test]
     #[ignore = "Creates an image file, will revise later"]
@@ -409,6 +461,40 @@ mod tests {
             }
         }
     }
+
+    #[test]
+    fn tiny_tolerance_stops_splitting() {
+        let tolerance = 1e-12;
+        // Sharp turn at the tip of a narrow loop
+        let cbs = CubicBezierSegment {
+            from: point(0., 0.),
+            ctrl1: point(100., 0.001),
+            ctrl2: point(100., -0.001),
+            to: point(0., 0.002),
+        };
+        let segments = FlattenWithArcs::flattened(&cbs, tolerance);
+        assert!(!segments.is_empty());
+        assert!(segments.len() <= 4 << MAX_SPLIT_DEPTH, "{}", segments.len());
+        let end = match segments.last().unwrap() {
+            ArcOrLineSegment::Arc(arc) => arc.to,
+            ArcOrLineSegment::Line(line) => line.to,
+        };
+        assert!((end - cbs.to).length() < 1e-9, "{end:?}");
+
+        let svg_arc = SvgArc {
+            from: point(0., 0.),
+            to: point(0., 1e-3),
+            radii: vector(100., 5e-4),
+            x_rotation: Angle::zero(),
+            flags: ArcFlags {
+                large_arc: false,
+                sweep: true,
+            },
+        };
+        let segments = FlattenWithArcs::flattened(&svg_arc, tolerance);
+        assert!(!segments.is_empty());
+        assert!(segments.len() <= 1 << MAX_SPLIT_DEPTH, "{}", segments.len());
+    }
 }
 
 /// Detects circular arcs in sequences of line segments (for polygon/polyline arc detection)
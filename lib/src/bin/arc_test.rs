@@ -134,6 +134,7 @@ fn test_conversion(doc: &Document, tolerance: f64, min_arc_radius: Option<f64>,
         detect_polygon_arcs: false,
         min_polygon_arc_points: 5,
         polygon_arc_tolerance: None,
+        ..Default::default()
     };
     
     let machine = Machine::new(
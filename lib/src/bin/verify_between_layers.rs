@@ -17,6 +17,7 @@ fn main() {
         detect_polygon_arcs: false,
         min_polygon_arc_points: 5,
         polygon_arc_tolerance: None,
+        ..Default::default()
     };
     settings.machine = MachineConfig {
         supported_functionality: SupportedFunctionality { circular_interpolation: false },
@@ -25,8 +26,9 @@ fn main() {
         begin_sequence: None,
         end_sequence: None,
         between_layers_sequence: Some("(BL)".into()),
+        ..Default::default()
     };
-    settings.postprocess = PostprocessConfig { checksums: false, line_numbers: false, newline_before_comment: false };
+    settings.postprocess = PostprocessConfig { checksums: false, line_numbers: false, newline_before_comment: false, ..Default::default() };
 
     let machine = Machine::new(
         settings.machine.supported_functionality.clone(),
@@ -0,0 +1,243 @@
+use std::{
+    collections::{hash_map::DefaultHasher, VecDeque},
+    hash::{Hash, Hasher},
+};
+
+use g_code::parse::snippet_parser;
+use roxmltree::{Document, ParsingOptions};
+
+use crate::{tokens_to_gcode, try_svg2program, ConversionOptions, Machine, Settings};
+
+/// In-memory memoization of whole conversions, bounded by least-recently-used eviction
+///
+/// Entries are keyed by a hash of the SVG text, and compared in full along with their
+/// [Settings] and [ConversionOptions] on lookup.
+#[derive(Debug)]
+pub struct ConversionCache {
+    capacity: usize,
+    /// Most recently used first
+    entries: VecDeque<CacheEntry>,
+    conversions: usize,
+}
+
+#[derive(Debug)]
+struct CacheEntry {
+    hash: u64,
+    svg: String,
+    settings: Settings,
+    options: ConversionOptions,
+    gcode: String,
+}
+
+impl ConversionCache {
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            capacity,
+            entries: VecDeque::with_capacity(capacity),
+            conversions: 0,
+        }
+    }
+
+    /// Number of conversions actually run, i.e. cache misses
+    pub fn conversions(&self) -> usize {
+        self.conversions
+    }
+
+    /// Convert an SVG to formatted g-code, reusing the result of an identical earlier call
+    pub fn convert_cached(
+        &mut self,
+        svg: &str,
+        settings: &Settings,
+        options: &ConversionOptions,
+    ) -> Result<String, String> {
+        // Settings and options contain floats and maps, so they can't derive Hash and their
+        // debug output depends on the order of map entries; they are only compared
+        let hash = {
+            let mut hasher = DefaultHasher::new();
+            svg.hash(&mut hasher);
+            hasher.finish()
+        };
+
+        if let Some(i) = self.entries.iter().position(|entry| {
+            entry.hash == hash
+                && entry.svg == svg
+                && &entry.settings == settings
+                && &entry.options == options
+        }) {
+            let entry = self.entries.remove(i).expect("index is in bounds");
+            let gcode = entry.gcode.clone();
+            self.entries.push_front(entry);
+            return Ok(gcode);
+        }
+
+        let gcode = convert(svg, settings, options)?;
+        self.conversions += 1;
+        if self.capacity > 0 {
+            if self.entries.len() == self.capacity {
+                self.entries.pop_back();
+            }
+            self.entries.push_front(CacheEntry {
+                hash,
+                svg: svg.to_string(),
+                settings: settings.clone(),
+                options: options.clone(),
+                gcode: gcode.clone(),
+            });
+        }
+        Ok(gcode)
+    }
+}
+
+fn convert(svg: &str, settings: &Settings, options: &ConversionOptions) -> Result<String, String> {
+    let document = Document::parse_with_options(
+        svg,
+        ParsingOptions {
+            allow_dtd: true,
+            ..Default::default()
+        },
+    )
+    .map_err(|err| err.to_string())?;
+
+    let [tool_on, tool_off, begin, end, between_layers, accessory_on, accessory_off] = [
+        &settings.machine.tool_on_sequence,
+        &settings.machine.tool_off_sequence,
+        &settings.machine.begin_sequence,
+        &settings.machine.end_sequence,
+        &settings.machine.between_layers_sequence,
+        &settings.machine.accessory_on_sequence,
+        &settings.machine.accessory_off_sequence,
+    ]
+    .map(|sequence| {
+        sequence
+            .as_deref()
+            .map(snippet_parser)
+            .transpose()
+            .map_err(|err| format!("could not parse gcode: {err:?}"))
+    });
+    let machine = Machine::new(
+        settings.machine.supported_functionality.clone(),
+        tool_on?,
+        tool_off?,
+        begin?,
+        end?,
+        between_layers?,
+    )
+    .with_config(&settings.machine)
+    .with_accessory_sequences(accessory_on?, accessory_off?);
+
+    let mut program = try_svg2program(&document, &settings.conversion, options.clone(), machine)
+        .map_err(|err| err.to_string())?;
+    settings
+        .postprocess
+        .apply(&mut program, settings.conversion.feedrate_units);
+
+    tokens_to_gcode(&program, &settings.postprocess).map_err(|err| err.to_string())
+}
+
+#[cfg(test)]
+mod test {
+    use svgtypes::{Length, LengthUnit};
+
+    use super::*;
+
+    #[test]
+    fn identical_call_is_served_from_cache() {
+        let svg = include_str!("../tests/square.svg");
+        let settings = Settings::default();
+        let options = ConversionOptions::default();
+        let mut cache = ConversionCache::new(2);
+
+        let first = cache.convert_cached(svg, &settings, &options).unwrap();
+        let second = cache.convert_cached(svg, &settings, &options).unwrap();
+        assert_eq!(first, second);
+        assert_eq!(cache.conversions(), 1);
+
+        let mut other_settings = settings.clone();
+        other_settings.conversion.feedrate = 1000.;
+        cache
+            .convert_cached(svg, &other_settings, &options)
+            .unwrap();
+        assert_eq!(cache.conversions(), 2);
+    }
+
+    #[test]
+    fn equal_settings_with_layer_params_are_served_from_cache() {
+        let svg = include_str!("../tests/square.svg");
+        // Separately built maps iterate in different orders
+        let settings = || {
+            let mut settings = Settings::default();
+            for i in 0..16 {
+                settings.conversion.layer_params.insert(
+                    format!("layer{i}"),
+                    crate::LayerParams {
+                        feedrate: Some(100. * f64::from(i + 1)),
+                        ..Default::default()
+                    },
+                );
+            }
+            settings
+        };
+        let options = ConversionOptions::default();
+        let mut cache = ConversionCache::new(2);
+
+        cache.convert_cached(svg, &settings(), &options).unwrap();
+        cache.convert_cached(svg, &settings(), &options).unwrap();
+        assert_eq!(cache.conversions(), 1);
+    }
+
+    #[test]
+    fn least_recently_used_entry_is_evicted() {
+        let svg = include_str!("../tests/square.svg");
+        let mut settings = [Settings::default(), Settings::default(), Settings::default()];
+        for (i, settings) in settings.iter_mut().enumerate() {
+            settings.conversion.feedrate = 100. * (i + 1) as f64;
+        }
+        let options = ConversionOptions::default();
+        let mut cache = ConversionCache::new(2);
+
+        cache.convert_cached(svg, &settings[0], &options).unwrap();
+        cache.convert_cached(svg, &settings[1], &options).unwrap();
+        // Touch the first entry so the second is evicted instead
+        cache.convert_cached(svg, &settings[0], &options).unwrap();
+        cache.convert_cached(svg, &settings[2], &options).unwrap();
+        assert_eq!(cache.conversions(), 3);
+
+        cache.convert_cached(svg, &settings[0], &options).unwrap();
+        assert_eq!(cache.conversions(), 3);
+        cache.convert_cached(svg, &settings[1], &options).unwrap();
+        assert_eq!(cache.conversions(), 4);
+    }
+
+    #[test]
+    fn calls_differing_only_in_options_are_converted_separately() {
+        let svg = include_str!("../tests/square.svg");
+        let settings = Settings::default();
+        let options = ConversionOptions::default();
+        let resized = ConversionOptions {
+            dimensions: [Some(Length::new(20., LengthUnit::Mm)), None],
+            ..Default::default()
+        };
+        let mut cache = ConversionCache::new(2);
+
+        let first = cache.convert_cached(svg, &settings, &options).unwrap();
+        let second = cache.convert_cached(svg, &settings, &resized).unwrap();
+        assert_ne!(first, second);
+        assert_eq!(cache.conversions(), 2);
+
+        cache.convert_cached(svg, &settings, &options).unwrap();
+        cache.convert_cached(svg, &settings, &resized).unwrap();
+        assert_eq!(cache.conversions(), 2);
+    }
+
+    #[test]
+    fn conversion_error_is_returned() {
+        let svg = r#"<svg xmlns="http://www.w3.org/2000/svg" width="20mm" height="20mm" viewBox="0 0 20 20">
+            <path id="broken" d="M 1 1 L 5"/>
+        </svg>"#;
+        let mut cache = ConversionCache::new(2);
+        let err = cache
+            .convert_cached(svg, &Settings::default(), &ConversionOptions::default())
+            .unwrap_err();
+        assert!(err.contains("#broken"), "{err}");
+    }
+}
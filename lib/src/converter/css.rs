@@ -0,0 +1,171 @@
+use crate::diagnostics::debug;
+use roxmltree::{Document, Node};
+
+const STYLE_TAG_NAME: &str = "style";
+
+/// Rules from the `<style>` elements of a document
+///
+/// Only type, class, and id selectors (and compounds of them like `path.cut`) are supported.
+/// Rules with any other kind of selector are ignored.
+#[derive(Debug, Clone, Default)]
+pub struct Stylesheet {
+    rules: Vec<Rule>,
+}
+
+#[derive(Debug, Clone)]
+struct Rule {
+    selector: Selector,
+    declarations: Vec<(String, String)>,
+}
+
+/// A compound selector like `rect.cut#outline`
+#[derive(Debug, Clone, Default)]
+struct Selector {
+    element: Option<String>,
+    id: Option<String>,
+    classes: Vec<String>,
+}
+
+impl Selector {
+    fn parse(selector: &str) -> Option<Self> {
+        if selector.is_empty()
+            || selector.contains(|c: char| {
+                c.is_whitespace() || matches!(c, '>' | '+' | '~' | '[' | ':' | '*')
+            })
+        {
+            return None;
+        }
+
+        let mut parsed = Self::default();
+        let mut start = 0;
+        for (i, c) in selector
+            .char_indices()
+            .filter(|(_, c)| matches!(c, '.' | '#'))
+            .chain(std::iter::once((selector.len(), '.')))
+        {
+            let name = &selector[start..i];
+            match selector[..start].chars().last() {
+                None if !name.is_empty() => parsed.element = Some(name.to_string()),
+                None => {}
+                Some(_) if name.is_empty() => return None,
+                Some('.') => parsed.classes.push(name.to_string()),
+                Some(_) => parsed.id = Some(name.to_string()),
+            }
+            start = i + c.len_utf8();
+        }
+        Some(parsed)
+    }
+
+    fn matches(&self, node: &Node) -> bool {
+        self.element
+            .as_deref()
+            .is_none_or(|element| node.has_tag_name(element))
+            && self
+                .id
+                .as_deref()
+                .is_none_or(|id| node.attribute("id") == Some(id))
+            && self.classes.iter().all(|class| {
+                node.attribute("class")
+                    .is_some_and(|classes| classes.split_whitespace().any(|c| c == class))
+            })
+    }
+
+    /// <https://www.w3.org/TR/selectors-3/#specificity>
+    fn specificity(&self) -> (usize, usize, usize) {
+        (
+            self.id.iter().count(),
+            self.classes.len(),
+            self.element.iter().count(),
+        )
+    }
+}
+
+impl Stylesheet {
+    pub fn from_document(doc: &Document) -> Self {
+        let mut stylesheet = Self::default();
+        doc.descendants()
+            .filter(|node| node.has_tag_name(STYLE_TAG_NAME))
+            .filter_map(|style| style.text())
+            .for_each(|css| stylesheet.parse(css));
+        stylesheet
+    }
+
+    fn parse(&mut self, css: &str) {
+        let mut css = css.to_string();
+        while let Some(start) = css.find("/*") {
+            let end = css[start..]
+                .find("*/")
+                .map_or(css.len(), |end| start + end + 2);
+            css.replace_range(start..end, "");
+        }
+
+        for block in css.split('}') {
+            let Some((selectors, declarations)) = block.split_once('{') else {
+                continue;
+            };
+            let declarations = declarations
+                .split(';')
+                .filter_map(|declaration| declaration.split_once(':'))
+                .map(|(property, value)| {
+                    let value = value.trim();
+                    let value = value.strip_suffix("!important").unwrap_or(value).trim();
+                    (property.trim().to_string(), value.to_string())
+                })
+                .collect::<Vec<_>>();
+            for selector in selectors.split(',').map(str::trim) {
+                match Selector::parse(selector) {
+                    Some(selector) => self.rules.push(Rule {
+                        selector,
+                        declarations: declarations.clone(),
+                    }),
+                    None => debug!("Unsupported CSS selector: {selector}"),
+                }
+            }
+        }
+    }
+
+    /// Value of a property for a node from the most specific matching rule, later rules winning ties
+    pub fn property(&self, node: &Node, name: &str) -> Option<&str> {
+        self.rules
+            .iter()
+            .enumerate()
+            .filter(|(_, rule)| rule.selector.matches(node))
+            .filter_map(|(order, rule)| {
+                rule.declarations
+                    .iter()
+                    .rev()
+                    .find(|(property, _)| property == name)
+                    .map(|(_, value)| (rule.selector.specificity(), order, value.as_str()))
+            })
+            .max_by_key(|(specificity, order, _)| (*specificity, *order))
+            .map(|(_, _, value)| value)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn most_specific_rule_wins() {
+        let svg = r##"<svg xmlns="http://www.w3.org/2000/svg">
+            <style>
+                /* id beats class beats element */
+                #outline { stroke: blue }
+                .cut { stroke: red; fill: none }
+                path { stroke: green }
+            </style>
+            <path id="outline" class="cut" d="M 0 0 L 1 1"/>
+            <path class="engrave cut" d="M 0 0 L 1 1"/>
+            <path d="M 0 0 L 1 1"/>
+        </svg>"##;
+        let doc = Document::parse(svg).unwrap();
+        let stylesheet = Stylesheet::from_document(&doc);
+        let strokes = doc
+            .descendants()
+            .filter(|node| node.has_tag_name("path"))
+            .map(|node| stylesheet.property(&node, "stroke"))
+            .collect::<Vec<_>>();
+        assert_eq!(strokes, [Some("blue"), Some("red"), Some("green")]);
+    }
+}
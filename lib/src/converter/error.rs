@@ -0,0 +1,84 @@
+use std::fmt;
+
+use roxmltree::Node;
+
+/// Reasons an SVG can't be converted
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ConversionError {
+    /// The `d` attribute of a path is malformed
+    PathParse {
+        /// `id` of the path, if it has one
+        node_id: Option<String>,
+        /// Position in the `d` attribute at which parsing failed
+        byte_offset: usize,
+        message: String,
+    },
+    /// A dimension override isn't a single length
+    InvalidDimension { value: String, message: String },
+    /// An element can't be converted and [crate::ConversionConfig::on_unsupported] is
+    /// [crate::UnsupportedPolicy::Error]
+    Unsupported { element: String, reason: String },
+    /// An attribute can't be used and [crate::ConversionConfig::on_unsupported] is
+    /// [crate::UnsupportedPolicy::Error]
+    InvalidAttribute {
+        element: String,
+        attribute: String,
+        value: String,
+        message: String,
+    },
+}
+
+impl ConversionError {
+    pub(super) fn path_parse(node: &Node, d: &str, err: svgtypes::Error) -> Self {
+        use svgtypes::Error::*;
+        let byte_offset = match err {
+            UnexpectedData(pos)
+            | InvalidChar(_, pos)
+            | InvalidString(_, pos)
+            | InvalidNumber(pos) => {
+                // svgtypes counts characters starting from one
+                d.char_indices()
+                    .nth(pos.saturating_sub(1))
+                    .map_or(d.len(), |(offset, _)| offset)
+            }
+            UnexpectedEndOfStream | InvalidValue => d.len(),
+        };
+        Self::PathParse {
+            node_id: node.attribute("id").map(str::to_string),
+            byte_offset,
+            message: err.to_string(),
+        }
+    }
+}
+
+impl fmt::Display for ConversionError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::PathParse {
+                node_id,
+                byte_offset,
+                message,
+            } => {
+                write!(f, "could not parse path")?;
+                if let Some(id) = node_id {
+                    write!(f, " #{id}")?;
+                }
+                write!(f, " at byte {byte_offset}: {message}")
+            }
+            Self::InvalidDimension { value, message } => {
+                write!(f, "could not parse dimension \"{value}\": {message}")
+            }
+            Self::Unsupported { element, reason } => {
+                write!(f, "could not convert {element}: {reason}")
+            }
+            Self::InvalidAttribute {
+                element,
+                attribute,
+                value,
+                message,
+            } => write!(f, "invalid {attribute} \"{value}\" on {element}: {message}"),
+        }
+    }
+}
+
+impl std::error::Error for ConversionError {}
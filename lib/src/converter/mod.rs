@@ -1,7 +1,9 @@
+use std::collections::HashMap;
 use std::fmt::Debug;
 
 use g_code::emit::Token;
-use lyon_geom::euclid::default::Transform2D;
+use crate::diagnostics::warn;
+use lyon_geom::{euclid::default::Transform2D, Box2D, Point};
 use roxmltree::{Document, Node};
 #[cfg(feature = "serde")]
 use serde::{Deserialize, Serialize};
@@ -9,11 +11,20 @@ use svgtypes::Length;
 use uom::si::f64::Length as UomLength;
 use uom::si::length::{inch, millimeter, centimeter, pica_computer};
 
-use crate::{turtle::*, Machine};
+use crate::{turtle::*, Machine, TokenPostProcessor};
+use css::Stylesheet;
+pub use error::ConversionError;
+pub use path::CommandFilter;
+pub use units::parse_dimension;
 
+mod css;
+mod error;
 #[cfg(feature = "serde")]
 mod length_serde;
+mod paint;
 mod path;
+#[cfg(feature = "text")]
+mod text;
 mod transform;
 mod units;
 mod visit;
@@ -22,21 +33,49 @@ mod visit;
 #[derive(Debug, Clone, PartialEq)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct ConversionConfig {
-    /// Curve interpolation tolerance in millimeters
+    /// Curve interpolation tolerance in millimeters, unless [ConversionConfig::tolerance_mode]
+    /// makes it relative
+    ///
+    /// This is the physical deviation allowed regardless of [ConversionConfig::dpi]: curves are
+    /// flattened after conversion to millimeters, or with the tolerance scaled into user units
+    /// where they must be flattened before (e.g. when clipping).
     pub tolerance: f64,
+    /// Whether [ConversionConfig::tolerance] is in millimeters or relative to the drawing's size
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub tolerance_mode: ToleranceMode,
     /// Feedrate in millimeters / minute
     pub feedrate: f64,
+    /// Units of the `F` words in the output, converted from [ConversionConfig::feedrate] and
+    /// [LayerParams::feedrate] which stay in millimeters / minute
+    ///
+    /// Feedrates in user sequences are emitted as given, and [crate::PostprocessConfig::annotate_time]
+    /// still reads `F` words as millimeters / minute.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub feedrate_units: FeedrateUnits,
     /// Dots per inch for pixels, picas, points, etc.
     pub dpi: f64,
     /// Set the origin point in millimeters for this conversion
+    ///
+    /// On each axis given an origin, the bottom left corner of the SVG is moved to it, or the
+    /// drawing's bounding box if it reaches past that corner. With [ConversionOptions::trim] or
+    /// [ConversionOptions::dimensions], the drawing is first aligned and the origin is where its
+    /// container starts instead. Axes without an origin are left as the SVG or the alignment
+    /// placed them.
     #[cfg_attr(feature = "serde", serde(default = "zero_origin"))]
     pub origin: [Option<f64>; 2],
-    /// Minimum arc radius (in mm) below which arcs are converted to lines.
+    /// Minimum arc radius (in mm) below which arcs are flattened into line segments.
     /// If `None`, a conservative default derived from tolerance (tolerance * 0.05) is used.
     #[cfg_attr(feature = "serde", serde(default))]
     pub min_arc_radius: Option<f64>,
     /// Set extra attribute to add when printing node name
     pub extra_attribute_name: Option<String>,
+    /// Annotate each cut move with the untransformed SVG user space coordinate it ends at, as an
+    /// inline comment. Useful for debugging transforms.
+    ///
+    /// An arc found by [ConversionConfig::detect_polygon_arcs] is annotated with the coordinate of
+    /// the last point it replaces.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub annotate_source_coords: bool,
     /// Enable arc detection for polygons and polylines
     #[cfg_attr(feature = "serde", serde(default))]
     pub detect_polygon_arcs: bool,
@@ -47,6 +86,172 @@ pub struct ConversionConfig {
     /// If `None`, uses the same tolerance as curve fitting
     #[cfg_attr(feature = "serde", serde(default))]
     pub polygon_arc_tolerance: Option<f64>,
+    /// Relative tolerance within which an arc counts as a semicircle and is split in two
+    /// before emitting circular interpolation
+    #[cfg_attr(feature = "serde", serde(default = "default_semicircle_epsilon"))]
+    pub semicircle_epsilon: f64,
+    /// Sweep angle in radians below which an arc is emitted as a line
+    #[cfg_attr(feature = "serde", serde(default = "default_min_sweep"))]
+    pub min_sweep: f64,
+    /// How to handle elements filled or stroked with a gradient or pattern (`url(#...)`)
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub paint_server_policy: PaintServerPolicy,
+    /// Trace the contents of `marker-start`, `marker-mid`, and `marker-end` at the vertices of
+    /// paths, lines, polylines, and polygons (e.g. as drill points)
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub honor_markers: bool,
+    /// Skip shapes whose effective opacity (`opacity` of the shape and its ancestors, times
+    /// `stroke-opacity`, or `fill-opacity` for shapes traced by their fill) is below this
+    /// threshold, e.g. faint guide or template elements
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub min_opacity: Option<f64>,
+    /// How to handle unsupported or degenerate elements (e.g. a `rect` with zero width)
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub on_unsupported: UnsupportedPolicy,
+    /// End the program with a comment counting the unsupported elements that were skipped by
+    /// tag name (e.g. `; Skipped: text(2), image(1)`), for auditing a file
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub summarize_skipped: bool,
+    /// Comment each element with the text of its `<title>` and `<desc>` children, as
+    /// `title: <text>` and `desc: <text>` before the element's own comment
+    ///
+    /// Coming first, they aren't taken for elements by
+    /// [crate::PostprocessConfig::annotate_subpaths].
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub emit_titles: bool,
+    /// Join subpaths that start within this distance (mm) of where the last one ended with a
+    /// cutting move, instead of lifting the tool and traveling
+    ///
+    /// Useful for pen plotting. Unlike travel optimization, this changes the drawn geometry.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub join_gap: Option<f64>,
+    /// Number of user units (and `px`) in a millimeter, overriding [ConversionConfig::dpi]
+    ///
+    /// Clearer than a DPI for CAD exports, where one user unit is usually one millimeter.
+    /// Physical units like `mm` or `in` keep their size.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub user_units_per_mm: Option<f64>,
+    /// Report an error for any X/Y farther than this from the origin (mm), which usually means a
+    /// corrupt transform produced coordinates a controller can't handle
+    ///
+    /// Reported once for each subpath that goes beyond it.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub max_abs_coordinate: Option<f64>,
+    /// Name of an attribute (e.g. `data-tolerance`) that overrides [ConversionConfig::tolerance]
+    /// for an element and its descendants, to locally tighten or loosen flattening
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub tolerance_attribute: Option<String>,
+    /// Whether [ConversionConfig::feedrate] is in millimeters / minute, as opposed to user units /
+    /// minute which are converted with [ConversionConfig::dpi]
+    ///
+    /// Either way, the feedrate doesn't change with the size of the drawing.
+    #[cfg_attr(feature = "serde", serde(default = "default_feedrate_is_physical"))]
+    pub feedrate_is_physical: bool,
+    /// `id` of an element (e.g. a registration mark) whose center becomes the origin of the
+    /// output, overriding [ConversionConfig::origin]
+    ///
+    /// Only shapes are supported, not groups.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub origin_element: Option<String>,
+    /// Machine parameters for the contents of groups, by group `id`
+    ///
+    /// Parameters of nested groups take precedence, and those they don't set are inherited.
+    /// Any element can also override the feedrate of its contents with a `data-feedrate`
+    /// attribute (millimeters / minute), which takes precedence over its group's.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub layer_params: HashMap<String, LayerParams>,
+    /// Cut closed subpaths after the subpaths inside of them (e.g. holes before the outline of a
+    /// part), which is safer for cutouts since the part stays in place while its holes are cut
+    ///
+    /// Other subpaths keep the order they are drawn in.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub order_by_containment: bool,
+    /// Number of times each subpath is cut, e.g. to darken marker strokes
+    ///
+    /// Repeats alternate direction, so the tool keeps cutting instead of traveling back to the
+    /// start of the subpath.
+    #[cfg_attr(feature = "serde", serde(default = "default_stroke_repeats"))]
+    pub stroke_repeats: usize,
+    /// Trace the outline of shapes that are filled but not stroked, treating the fill as a cut
+    ///
+    /// When disabled, only shapes with a stroke are traced.
+    #[cfg_attr(feature = "serde", serde(default = "default_trace_fill_as_outline"))]
+    pub trace_fill_as_outline: bool,
+    /// Leave out subpaths that trace the same outline (within [ConversionConfig::tolerance]) as
+    /// one already cut, e.g. when an export draws a shape's fill and stroke as two elements
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub dedupe_paths: bool,
+    /// Only take curves as straight if they are much closer than [ConversionConfig::tolerance]
+    /// to a line, so that gentle curves on large parts are cut as arcs or finer lines rather than
+    /// one line
+    ///
+    /// Only affects curves converted to arcs with
+    /// [crate::SupportedFunctionality::circular_interpolation], others are flattened into lines
+    /// within the tolerance either way.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub preserve_curvature: bool,
+    /// Fill closed subpaths with concentric inward offsets of their outline, cut after it, e.g.
+    /// for pocket engraving a region
+    ///
+    /// Each closed subpath is pocketed on its own, so holes in a shape are filled too. Offsets
+    /// are exact for convex outlines and stop at the first one that turns inside out.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub pocket: Option<PocketConfig>,
+}
+
+/// Concentric offsets filling closed subpaths, see [ConversionConfig::pocket]
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct PocketConfig {
+    /// Distance (mm) between successive offsets, usually a little less than the tool's width
+    pub stepover: f64,
+}
+
+/// Machine parameters overridden for the contents of a group, see
+/// [ConversionConfig::layer_params]
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct LayerParams {
+    /// Feedrate in millimeters / minute
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub feedrate: Option<f64>,
+    /// Tool power (e.g. laser power or spindle speed), emitted as an `S` word when the tool is
+    /// turned on
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub power: Option<f64>,
+    /// Height the tool cuts at (mm), overriding [crate::ZAxisConfig::cut_z]
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub cut_z: Option<f64>,
+}
+
+impl LayerParams {
+    /// These parameters, falling back to those of `outer` that aren't set
+    pub fn or(self, outer: Self) -> Self {
+        Self {
+            feedrate: self.feedrate.or(outer.feedrate),
+            power: self.power.or(outer.power),
+            cut_z: self.cut_z.or(outer.cut_z),
+        }
+    }
+}
+
+impl ConversionConfig {
+    /// DPI that user units are converted to millimeters with, accounting for
+    /// [ConversionConfig::user_units_per_mm]
+    pub fn effective_dpi(&self) -> f64 {
+        self.user_units_per_mm.map_or(self.dpi, |user_units_per_mm| {
+            user_units_per_mm * UomLength::new::<inch>(1.).get::<millimeter>()
+        })
+    }
+
+    /// Feedrate in millimeters / minute, accounting for [ConversionConfig::feedrate_is_physical]
+    pub fn feedrate_mm_per_min(&self) -> f64 {
+        if self.feedrate_is_physical {
+            self.feedrate
+        } else {
+            UomLength::new::<inch>(self.feedrate / self.effective_dpi()).get::<millimeter>()
+        }
+    }
 }
 
 const fn zero_origin() -> [Option<f64>; 2] {
@@ -57,18 +262,62 @@ const fn default_min_polygon_arc_points() -> usize {
     5
 }
 
+const fn default_semicircle_epsilon() -> f64 {
+    1e-5
+}
+
+const fn default_min_sweep() -> f64 {
+    1e-6
+}
+
+const fn default_feedrate_is_physical() -> bool {
+    true
+}
+
+const fn default_stroke_repeats() -> usize {
+    1
+}
+
+const fn default_trace_fill_as_outline() -> bool {
+    true
+}
+
 impl Default for ConversionConfig {
     fn default() -> Self {
         Self {
             tolerance: 0.002,
+            tolerance_mode: ToleranceMode::default(),
             feedrate: 300.0,
+            feedrate_units: FeedrateUnits::default(),
             dpi: 96.0,
             origin: zero_origin(),
             min_arc_radius: None,
             extra_attribute_name: None,
+            annotate_source_coords: false,
             detect_polygon_arcs: false,
             min_polygon_arc_points: default_min_polygon_arc_points(),
             polygon_arc_tolerance: None,
+            semicircle_epsilon: default_semicircle_epsilon(),
+            min_sweep: default_min_sweep(),
+            paint_server_policy: PaintServerPolicy::default(),
+            honor_markers: false,
+            min_opacity: None,
+            on_unsupported: UnsupportedPolicy::default(),
+            summarize_skipped: false,
+            emit_titles: false,
+            join_gap: None,
+            user_units_per_mm: None,
+            max_abs_coordinate: None,
+            tolerance_attribute: None,
+            feedrate_is_physical: default_feedrate_is_physical(),
+            origin_element: None,
+            layer_params: HashMap::new(),
+            order_by_containment: false,
+            stroke_repeats: default_stroke_repeats(),
+            trace_fill_as_outline: default_trace_fill_as_outline(),
+            dedupe_paths: false,
+            preserve_curvature: false,
+            pocket: None,
         }
     }
 }
@@ -99,6 +348,42 @@ pub struct ConversionOptions {
     /// - With neither dimension: no effect
     #[cfg_attr(feature = "serde", serde(default))]
     pub trim: bool,
+    /// Language tags (e.g. `en-US`) that `systemLanguage` attributes are matched against
+    ///
+    /// If `None`, `systemLanguage` is not evaluated and such elements are always drawn.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub system_language: Option<String>,
+    /// If true, trace the bounding rectangle of the drawing with rapid moves before cutting
+    ///
+    /// Useful for checking placement on the material.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub emit_bounding_frame: bool,
+    /// Scale the placed drawing uniformly about the origin so that the total length of its
+    /// flattened outlines is this many millimeters, e.g. for a consistent engraving dose
+    ///
+    /// Applied after `dimensions` and `trim`. [crate::ConversionConfig::stroke_repeats] and
+    /// passes are not counted, and each document of [svg2program_multi] is scaled on its own.
+    /// Curves are measured as flattened before scaling, so the result is only as exact as
+    /// [crate::ConversionConfig::tolerance].
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub scale_to_cut_length: Option<f64>,
+    /// Only draw these kinds of path commands, e.g. to debug arcs in isolation
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub command_filter: Option<CommandFilter>,
+    /// Origins (mm) of the top-level groups (layers) in order, e.g. for fixtured multi-part jobs
+    ///
+    /// Each group is moved so the bottom-left corner of its bounding box is at its origin. This
+    /// happens after [ConversionConfig::origin] and alignment, which don't take these into
+    /// account. Groups past the end of the list keep the document's placement.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub per_group_origins: Option<Vec<[f64; 2]>>,
+    /// Affine matrix `[a, b, c, d, e, f]` applied to the whole drawing, like an SVG
+    /// `matrix(a b c d e f)` transform but in millimeters with the Y axis pointing up
+    ///
+    /// Applied before everything else, so the drawing is placed according to its transformed
+    /// bounding box.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub global_transform: Option<[f64; 6]>,
 }
 
 #[derive(Debug, Clone, Copy, PartialEq)]
@@ -115,6 +400,88 @@ pub enum VerticalAlign { Bottom, Center, Top }
 
 impl Default for VerticalAlign { fn default() -> Self { Self::Top } }
 
+/// Handling of gradient and pattern paint servers, which have no single color
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "kebab-case"))]
+pub enum PaintServerPolicy {
+    /// Treat as a solid color: the first stop of a gradient, else the fallback color
+    #[default]
+    SolidColor,
+    /// Do not trace elements painted by a paint server
+    Skip,
+}
+
+/// How [ConversionConfig::tolerance] is given
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "kebab-case"))]
+pub enum ToleranceMode {
+    /// In millimeters
+    #[default]
+    Absolute,
+    /// As a fraction of the diagonal of the drawing's bounding box, so that a drawing is
+    /// flattened into the same number of segments at any size
+    RelativeToDiagonal,
+}
+
+/// Units of the `F` words in the output, see [ConversionConfig::feedrate_units]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "kebab-case"))]
+pub enum FeedrateUnits {
+    /// Millimeters / minute, as most controllers expect in G21 mode
+    #[default]
+    PerMinute,
+    /// Millimeters / second
+    PerSecond,
+}
+
+impl FeedrateUnits {
+    /// `feedrate` in millimeters / minute, converted to these units
+    pub fn from_mm_per_min(self, feedrate: f64) -> f64 {
+        match self {
+            Self::PerMinute => feedrate,
+            Self::PerSecond => feedrate / 60.,
+        }
+    }
+
+    /// `feedrate` in these units, converted to millimeters / minute
+    pub fn to_mm_per_min(self, feedrate: f64) -> f64 {
+        match self {
+            Self::PerMinute => feedrate,
+            Self::PerSecond => feedrate * 60.,
+        }
+    }
+}
+
+/// How the programs of [svg2program_per_layer] share setup and teardown
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "kebab-case"))]
+pub enum PerLayerMode {
+    /// Every program has the begin and end sequences, so each can be run on its own
+    #[default]
+    Independent,
+    /// The programs are run one after another: only the first has the begin sequence and only
+    /// the last has the end sequence and program end command
+    Shared,
+}
+
+/// Handling of SVG content that can't be traced meaningfully, like degenerate shapes
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "kebab-case"))]
+pub enum UnsupportedPolicy {
+    /// Skip it and log a warning
+    #[default]
+    Warn,
+    /// Skip it silently
+    Ignore,
+    /// Fail the conversion with a [ConversionError] naming the element
+    Error,
+}
+
 /// Maps SVG [`Node`]s and their attributes into operations on a [`Terrarium`]
 #[derive(Debug)]
 struct ConversionVisitor<'a, T: Turtle> {
@@ -124,9 +491,80 @@ struct ConversionVisitor<'a, T: Turtle> {
     viewport_dim_stack: Vec<[f64; 2]>,
     _config: &'a ConversionConfig,
     options: ConversionOptions,
+    /// Whether `clip-path` and `transform-box: fill-box` are resolved, off while finding the outline
+    /// of a clip path or an element
+    resolve_bounding_boxes: bool,
+    /// Rules from the `<style>` elements of the document being visited
+    stylesheet: Stylesheet,
+    /// Tolerances (user units) of the elements being visited that set
+    /// [ConversionConfig::tolerance_attribute]
+    tolerance_stack: Vec<f64>,
+    /// First error encountered, after which the output is incomplete
+    error: Option<ConversionError>,
+    /// Elements referenced by the `<use>` elements being visited, to break reference cycles
+    use_stack: Vec<roxmltree::NodeId>,
+    /// Markers being traced, to break reference cycles through the `marker-*` properties of
+    /// their contents
+    marker_stack: Vec<roxmltree::NodeId>,
+    /// Parameters of the groups being visited that are in [ConversionConfig::layer_params],
+    /// combined with those of the groups they are in
+    layer_params_stack: Vec<LayerParams>,
+    /// Tag names of the unsupported elements skipped so far and how many of each, in the order
+    /// first encountered
+    skipped: Vec<(String, usize)>,
+    /// Index of the only top-level group (layer) that is drawn, see [svg2program_per_layer]
+    layer: Option<usize>,
+    /// `id` of an element to find the transform of, see [resolve_element_transform]
+    tracked_id: Option<String>,
+    /// Transform of the first element visited with [ConversionVisitor::tracked_id]
+    tracked_transform: Option<Transform2D<f64>>,
+    /// Whether unsupported elements and invalid attributes are reported and counted in
+    /// [ConversionVisitor::skipped], only on the visit that converts the document so the
+    /// preprocessing visits and later passes don't repeat them
+    report_problems: bool,
 }
 
 impl<'a, T: Turtle> ConversionVisitor<'a, T> {
+    /// Visitor that draws onto `terrarium` with nothing visited yet and no document stylesheet
+    fn new(
+        terrarium: Terrarium<T>,
+        config: &'a ConversionConfig,
+        options: ConversionOptions,
+    ) -> Self {
+        Self {
+            terrarium,
+            name_stack: vec![],
+            viewport_dim_stack: vec![],
+            _config: config,
+            options,
+            resolve_bounding_boxes: true,
+            stylesheet: Stylesheet::default(),
+            tolerance_stack: vec![],
+            error: None,
+            use_stack: vec![],
+            marker_stack: vec![],
+            layer_params_stack: vec![],
+            skipped: vec![],
+            layer: None,
+            tracked_id: None,
+            tracked_transform: None,
+            report_problems: false,
+        }
+    }
+
+    /// Part 1 of converting from SVG to GCode coordinates: flip the Y axis, then apply
+    /// [ConversionOptions::global_transform]
+    fn initial_transform(&self) -> Transform2D<f64> {
+        let flip = Transform2D::scale(1., -1.);
+        let Some([a, b, c, d, e, f]) = self.options.global_transform else {
+            return flip;
+        };
+        // The translation is in millimeters, but the transforms are in user units
+        let mm_per_user_unit =
+            UomLength::new::<inch>(1.0 / self._config.effective_dpi()).get::<millimeter>();
+        flip.then(&Transform2D::new(a, b, c, d, e / mm_per_user_unit, f / mm_per_user_unit))
+    }
+
     fn comment(&mut self, node: &Node) {
         let mut comment = String::new();
         self.name_stack.iter().for_each(|name| {
@@ -135,12 +573,34 @@ impl<'a, T: Turtle> ConversionVisitor<'a, T> {
         });
         comment += &node_name(node,&self._config.extra_attribute_name);
 
+        if self._config.emit_titles {
+            for title in node_titles(node) {
+                self.terrarium.turtle.comment(title);
+            }
+        }
         self.terrarium.turtle.comment(comment);
+        let (line_cap, line_join) = paint::stroke_line_style(*node, &self.stylesheet);
+        self.terrarium.turtle.element(&ElementInfo {
+            id: node.attribute("id").map(str::to_string),
+            color: ["stroke", "fill"].into_iter().find_map(|property| {
+                match paint::resolve_paint(
+                    *node,
+                    property,
+                    self._config.paint_server_policy,
+                    &self.stylesheet,
+                ) {
+                    paint::ResolvedPaint::Color(color) => Some(color),
+                    paint::ResolvedPaint::None | paint::ResolvedPaint::Skip => None,
+                }
+            }),
+            line_cap,
+            line_join,
+            miter_limit: paint::stroke_miterlimit(*node, &self.stylesheet),
+        });
     }
 
     fn begin(&mut self) {
-        // Part 1 of converting from SVG to GCode coordinates
-        self.terrarium.push_transform(Transform2D::scale(1., -1.));
+        self.terrarium.push_transform(self.initial_transform());
         self.terrarium.turtle.begin();
     }
 
@@ -150,23 +610,66 @@ impl<'a, T: Turtle> ConversionVisitor<'a, T> {
     }
 }
 
-/// Top-level function for converting an SVG [`Document`] into g-code
-pub fn svg2program<'a, 'input: 'a>(
-    doc: &'a Document,
+/// Turtles that g-code is generated through, outermost first
+type GCodeTurtleStack<'input> = DpiConvertingTurtle<
+    ContainmentOrderingTurtle<DedupingTurtle<PocketingTurtle<GCodeTurtle<'input>>>>,
+>;
+
+impl<'a, 'input> ConversionVisitor<'a, GCodeTurtleStack<'input>> {
+    /// The [GCodeTurtle] at the bottom of the stack
+    fn gcode_turtle(&mut self) -> &mut GCodeTurtle<'input> {
+        &mut self.terrarium.turtle.inner.inner.inner.inner
+    }
+}
+
+/// Transform applied on top of the SVG coordinate system: trim/alignment, then the origin, then
+/// [ConversionOptions::scale_to_cut_length]
+///
+/// Requires preprocessing passes over the document to find its bounding box and cut length.
+fn conversion_transform(
+    doc: &Document,
     config: &ConversionConfig,
-    options: ConversionOptions,
-    machine: Machine<'input>,
-) -> Vec<Token<'input>> {
+    options: &ConversionOptions,
+) -> Transform2D<f64> {
+    let transform = placement_transform(doc, config, options);
+    let Some(target) = options.scale_to_cut_length else {
+        return transform;
+    };
+    let cut_length = drawing_subpaths(&[(doc, transform)], config, options)
+        .iter()
+        .map(|subpath| subpath.length)
+        .sum::<f64>();
+    if cut_length > 0. {
+        transform.then_scale(target / cut_length, target / cut_length)
+    } else {
+        warn!("Nothing is drawn, so it can't be scaled to a cut length");
+        transform
+    }
+}
+
+/// Transform applied on top of the SVG coordinate system: trim/alignment, then the origin
+///
+/// Requires a preprocessing pass over the document to find its bounding box.
+fn placement_transform(
+    doc: &Document,
+    config: &ConversionConfig,
+    options: &ConversionOptions,
+) -> Transform2D<f64> {
     let bounding_box_and_viewport_generator = || {
         let mut visitor = ConversionVisitor {
-            terrarium: Terrarium::new(DpiConvertingTurtle {
-                inner: PreprocessTurtle::default(),
-                dpi: config.dpi,
-            }),
-            _config: config,
-            options: options.clone(),
-            name_stack: vec![],
-            viewport_dim_stack: vec![],
+            stylesheet: Stylesheet::from_document(doc),
+            ..ConversionVisitor::new(
+                Terrarium::new(DpiConvertingTurtle {
+                    inner: PreprocessTurtle::new(config.origin_element.clone()),
+                    dpi: config.effective_dpi(),
+                }),
+                config,
+                // The document is placed as a whole, before the groups are moved to their origins
+                ConversionOptions {
+                    per_group_origins: None,
+                    ..options.clone()
+                },
+            )
         };
 
         visitor.begin();
@@ -174,47 +677,31 @@ pub fn svg2program<'a, 'input: 'a>(
         visitor.end();
 
         (
-            visitor.terrarium.turtle.inner.bounding_box,
+            visitor.terrarium.turtle.inner.bounding_box.unwrap_or_default(),
             // Last pushed viewport dims (user units) if any
             visitor.viewport_dim_stack.last().copied().unwrap_or([1.0, 1.0]),
+            visitor.terrarium.turtle.inner.tracked_bounding_box,
         )
     };
 
-    // Convert from millimeters to user units
-    // Convert configured origin (in mm) into user units using the *configured* dpi (previously CSS_DEFAULT_DPI caused scaling drift when dpi overridden)
-    let origin = config
-        .origin
-        .map(|dim| dim.map(|d| UomLength::new::<millimeter>(d).get::<inch>() * config.dpi));
-
     // Precompute bounding box (mm) & viewport size (user units) when needed for alignment/trim/origin
-    let (pre_bbox_mm, viewport_user_units) = bounding_box_and_viewport_generator();
+    let (pre_bbox_mm, viewport_user_units, origin_element_bbox_mm) =
+        bounding_box_and_viewport_generator();
 
     // Convert viewport size to mm (DPI based) for alignment math
     let viewport_mm = viewport_user_units.map(|v| {
         // user units -> inches -> mm (mirrors DpiConvertingTurtle logic for coordinates)
-        UomLength::new::<inch>(v / config.dpi).get::<millimeter>()
+        UomLength::new::<inch>(v / config.effective_dpi()).get::<millimeter>()
     });
 
-    let origin_transform = match origin {
-        [None, Some(origin_y)] => {
-            Transform2D::translation(0., origin_y - pre_bbox_mm.min.y)
-        }
-        [Some(origin_x), None] => {
-            Transform2D::translation(origin_x - pre_bbox_mm.min.x, 0.)
-        }
-        [Some(origin_x), Some(origin_y)] => {
-            Transform2D::translation(
-                origin_x - pre_bbox_mm.min.x,
-                origin_y - pre_bbox_mm.min.y,
-            )
-        }
-        [None, None] => Transform2D::identity(),
-    };
+    // The transforms are in user units, but the placement math is done in mm
+    let mm_per_user_unit = UomLength::new::<inch>(1.0 / config.effective_dpi()).get::<millimeter>();
 
     // Alignment & optional trim scaling
+    let alignment_requested = options.trim || options.dimensions.iter().any(|d| d.is_some());
     let mut post_transform = Transform2D::identity();
 
-    if options.trim || options.dimensions.iter().any(|d| d.is_some()) {
+    if alignment_requested {
         // Target sizes in mm if provided
         let target_mm: [Option<f64>; 2] = options.dimensions.map(|opt_l| opt_l.map(|l| {
             // length in user units (already converted earlier when applying overrides) -> user units numeric
@@ -223,10 +710,10 @@ pub fn svg2program<'a, 'input: 'a>(
             match l.unit { svgtypes::LengthUnit::Mm => l.number,
                 svgtypes::LengthUnit::Cm => UomLength::new::<centimeter>(l.number).get::<millimeter>(),
                 svgtypes::LengthUnit::In => UomLength::new::<inch>(l.number).get::<millimeter>(),
-                svgtypes::LengthUnit::Px => UomLength::new::<inch>(l.number / config.dpi).get::<millimeter>(),
+                svgtypes::LengthUnit::Px => UomLength::new::<inch>(l.number / config.effective_dpi()).get::<millimeter>(),
                 svgtypes::LengthUnit::Pt => UomLength::new::<inch>(l.number / 72.0).get::<millimeter>(),
                 svgtypes::LengthUnit::Pc => UomLength::new::<pica_computer>(l.number).get::<millimeter>(),
-                _ => UomLength::new::<inch>(l.number / config.dpi).get::<millimeter>() }
+                _ => UomLength::new::<inch>(l.number / config.effective_dpi()).get::<millimeter>() }
         }));
 
     let mut bbox = pre_bbox_mm;
@@ -274,16 +761,155 @@ pub fn svg2program<'a, 'input: 'a>(
             VerticalAlign::Center => (container_h - bbox.height()) / 2. - bbox.min.y,
             VerticalAlign::Top => (container_h - bbox.height()) - bbox.min.y,
         };
-    // Current transform stack is in user units; our math was done in mm (pre_bbox_mm).
-    let mm_per_user_unit = UomLength::new::<inch>(1.0 / config.dpi).get::<millimeter>();
     let dx = dx_mm / mm_per_user_unit;
     let dy = dy_mm / mm_per_user_unit;
     post_transform = Transform2D::translation(dx, dy).then(&post_transform);
     }
 
-    let options_clone_for_transform = options.clone();
-    let options_for_visitor = options_clone_for_transform.clone();
-    
+    // An origin element overrides the numeric origin, its center becomes (0, 0) wherever
+    // trim/alignment moved it
+    if let Some(name) = &config.origin_element {
+        match origin_element_bbox_mm {
+            Some(bbox) => {
+                let center = post_transform.transform_point(bbox.center() / mm_per_user_unit);
+                return post_transform.then_translate(-center.to_vector());
+            }
+            None => warn!("Could not find origin element {name}, using the numeric origin"),
+        }
+    }
+
+    // Each axis given an origin is moved so that the origin is where the drawing starts on it.
+    // With trim/dimensions that's the bottom left corner of the container it was aligned in.
+    // Otherwise it's the bottom left corner of the SVG, unless the drawing reaches past it, so
+    // that the default origin only moves drawings that would have negative coordinates. Axes
+    // without an origin are left where the SVG or the alignment put them.
+    let start = if alignment_requested {
+        lyon_geom::point(0., 0.)
+    } else {
+        pre_bbox_mm.min.min(lyon_geom::point(0., 0.))
+    };
+    let [dx, dy] = [(config.origin[0], start.x), (config.origin[1], start.y)]
+        .map(|(origin, start)| origin.map_or(0., |origin| origin - start) / mm_per_user_unit);
+    post_transform.then_translate(lyon_geom::vector(dx, dy))
+}
+
+/// Top-level function for converting an SVG [`Document`] into g-code
+///
+/// Panics if the document can't be converted, see [try_svg2program].
+pub fn svg2program<'a, 'input: 'a>(
+    doc: &'a Document,
+    config: &ConversionConfig,
+    options: ConversionOptions,
+    machine: Machine<'input>,
+) -> Vec<Token<'input>> {
+    try_svg2program(doc, config, options, machine).unwrap_or_else(|err| panic!("{err}"))
+}
+
+/// Same as [svg2program], calling `progress` with the fraction (0 to 1) of the conversion done
+/// so far, e.g. to show a progress bar for large documents
+pub fn svg2program_with_progress<'a, 'input: 'a>(
+    doc: &'a Document,
+    config: &ConversionConfig,
+    options: ConversionOptions,
+    machine: Machine<'input>,
+    progress: &mut dyn FnMut(f32),
+) -> Vec<Token<'input>> {
+    documents2program([(doc, [0.; 2])], config, options, machine, None, progress)
+        .unwrap_or_else(|err| panic!("{err}"))
+}
+
+/// Same as [svg2program], but returns an error rather than panicking if the document can't be
+/// converted
+pub fn try_svg2program<'a, 'input: 'a>(
+    doc: &'a Document,
+    config: &ConversionConfig,
+    options: ConversionOptions,
+    machine: Machine<'input>,
+) -> Result<Vec<Token<'input>>, ConversionError> {
+    documents2program([(doc, [0.; 2])], config, options, machine, None, &mut |_| {})
+}
+
+/// Converts each top-level SVG group (layer) of a [`Document`] into a program of its own, e.g.
+/// to load one file per tool
+///
+/// Elements outside of any layer are drawn with the first one. A document without layers is
+/// converted into a single program.
+pub fn svg2program_per_layer<'a, 'input: 'a>(
+    doc: &'a Document,
+    config: &ConversionConfig,
+    options: ConversionOptions,
+    machine: Machine<'input>,
+    mode: PerLayerMode,
+) -> Vec<Vec<Token<'input>>> {
+    let layer_count = visit::layer_count(doc);
+    if layer_count == 0 {
+        return vec![svg2program(doc, config, options, machine)];
+    }
+    (0..layer_count)
+        .map(|layer| {
+            let mut machine = machine.clone();
+            if mode == PerLayerMode::Shared {
+                if layer > 0 {
+                    machine = machine.without_program_begin();
+                }
+                if layer + 1 < layer_count {
+                    machine = machine.without_program_end();
+                }
+            }
+            documents2program(
+                [(doc, [0.; 2])],
+                config,
+                options.clone(),
+                machine,
+                Some(layer),
+                &mut |_| {},
+            )
+            .unwrap_or_else(|err| panic!("{err}"))
+        })
+        .collect()
+}
+
+/// Converts several SVG [`Document`]s into a single g-code program, e.g. to panelize a sheet
+///
+/// Each document is converted as it would be by [svg2program] and then translated by its offset
+/// in millimeters. The begin and end sequences are only emitted once.
+pub fn svg2program_multi<'a, 'input: 'a>(
+    inputs: &'a [(Document<'a>, [f64; 2])],
+    config: &ConversionConfig,
+    options: ConversionOptions,
+    machine: Machine<'input>,
+) -> Vec<Token<'input>> {
+    documents2program(
+        inputs.iter().map(|(doc, offset)| (doc, *offset)),
+        config,
+        options,
+        machine,
+        None,
+        &mut |_| {},
+    )
+    .unwrap_or_else(|err| panic!("{err}"))
+}
+
+fn documents2program<'a, 'input: 'a>(
+    inputs: impl IntoIterator<Item = (&'a Document<'a>, [f64; 2])>,
+    config: &ConversionConfig,
+    options: ConversionOptions,
+    machine: Machine<'input>,
+    layer: Option<usize>,
+    progress: &mut dyn FnMut(f32),
+) -> Result<Vec<Token<'input>>, ConversionError> {
+    let mm_per_user_unit = UomLength::new::<inch>(1.0 / config.effective_dpi()).get::<millimeter>();
+    let inputs = inputs
+        .into_iter()
+        .map(|(doc, [offset_x, offset_y])| {
+            let transform = conversion_transform(doc, config, &options).then(
+                &Transform2D::translation(offset_x / mm_per_user_unit, offset_y / mm_per_user_unit),
+            );
+            (doc, transform)
+        })
+        .collect::<Vec<_>>();
+    let config = &absolute_tolerance_config(&inputs, config, &options);
+
     // Create polygon arc configuration
     let polygon_arc_config = PolygonArcConfig {
         enabled: config.detect_polygon_arcs,
@@ -291,41 +917,328 @@ pub fn svg2program<'a, 'input: 'a>(
         tolerance: config.polygon_arc_tolerance.unwrap_or(config.tolerance),
     };
     
+    let frame = if options.emit_bounding_frame {
+        drawing_bounding_box(&inputs, config, &options)
+    } else {
+        None
+    };
+
     let mut conversion_visitor = ConversionVisitor {
-        terrarium: Terrarium::new(DpiConvertingTurtle {
-            inner: GCodeTurtle::new(
-                machine,
-                config.tolerance,
-                config.feedrate,
-                config.min_arc_radius.unwrap_or(config.tolerance * 0.05),
-                polygon_arc_config,
-            ),
-            dpi: config.dpi,
+        layer,
+        ..ConversionVisitor::new(
+            Terrarium::new(DpiConvertingTurtle {
+                inner: ContainmentOrderingTurtle::new(
+                    DedupingTurtle::new(
+                        PocketingTurtle::new(
+                            GCodeTurtle::new(
+                                machine,
+                                config.tolerance,
+                                config.feedrate_mm_per_min(),
+                                config.min_arc_radius.unwrap_or(config.tolerance * 0.05),
+                                polygon_arc_config,
+                                ArcSplitConfig {
+                                    semicircle_epsilon: config.semicircle_epsilon,
+                                    min_sweep: config.min_sweep,
+                                },
+                            )
+                            .with_annotate_source_coords(config.annotate_source_coords)
+                            .with_join_gap(config.join_gap)
+                            .with_max_abs_coordinate(config.max_abs_coordinate)
+                            .with_stroke_repeats(config.stroke_repeats)
+                            .with_feedrate_units(config.feedrate_units)
+                            .with_preserve_curvature(config.preserve_curvature),
+                            config.pocket,
+                            config.tolerance,
+                        ),
+                        config.dedupe_paths,
+                        config.tolerance,
+                    ),
+                    config.order_by_containment,
+                    config.tolerance,
+                ),
+                dpi: config.effective_dpi(),
+            }),
+            config,
+            options,
+        )
+    };
+
+    conversion_visitor.terrarium.track_source = config.annotate_source_coords;
+
+    // Each document needs its own transform underneath the Y axis flip, so this does the work
+    // of begin/end itself
+    conversion_visitor.terrarium.turtle.begin();
+    if let Some(frame) = frame {
+        conversion_visitor.gcode_turtle().bounding_frame(frame);
+    }
+    conversion_visitor.gcode_turtle().priming_stroke();
+    let pass_count = conversion_visitor.gcode_turtle().machine.pass_count();
+    // Each document in each pass is an equal share of the work
+    let share_count = (pass_count * inputs.len()) as f32;
+    for pass in 0..pass_count {
+        // Every pass skips the same elements, so only the first reports them
+        conversion_visitor.report_problems = pass == 0;
+        // Buffered subpaths belong to the previous pass
+        conversion_visitor.terrarium.turtle.flush();
+        conversion_visitor.gcode_turtle().start_pass(pass);
+        for (i, (doc, transform)) in inputs.iter().enumerate() {
+            let done = (pass * inputs.len() + i) as f32;
+            conversion_visitor.stylesheet = Stylesheet::from_document(doc);
+            conversion_visitor.terrarium.push_transform(*transform);
+            let initial_transform = conversion_visitor.initial_transform();
+            conversion_visitor.terrarium.push_transform(initial_transform);
+            visit::depth_first_visit_with_progress(doc, &mut conversion_visitor, &mut |fraction| {
+                progress((done + fraction) / share_count)
+            });
+            conversion_visitor.terrarium.pop_transform();
+            conversion_visitor.terrarium.pop_transform();
+        }
+    }
+    if config.summarize_skipped && !conversion_visitor.skipped.is_empty() {
+        let counts = conversion_visitor
+            .skipped
+            .iter()
+            .map(|(name, count)| format!("{name}({count})"))
+            .collect::<Vec<_>>();
+        conversion_visitor
+            .terrarium
+            .turtle
+            .comment(format!(" Skipped: {}", counts.join(", ")));
+    }
+    conversion_visitor.terrarium.turtle.end();
+
+    match conversion_visitor.error {
+        Some(err) => Err(err),
+        None => Ok(std::mem::take(
+            &mut conversion_visitor.gcode_turtle().program,
+        )),
+    }
+}
+
+/// `config` with its tolerance in millimeters, found from the bounding box of the transformed
+/// documents under [ToleranceMode::RelativeToDiagonal]
+fn absolute_tolerance_config(
+    inputs: &[(&Document, Transform2D<f64>)],
+    config: &ConversionConfig,
+    options: &ConversionOptions,
+) -> ConversionConfig {
+    let mut config = config.clone();
+    if config.tolerance_mode == ToleranceMode::RelativeToDiagonal {
+        let diagonal = drawing_bounding_box(inputs, &config, options)
+            .map_or(0., |bounding_box| bounding_box.size().to_vector().length());
+        if diagonal > 0. {
+            config.tolerance *= diagonal;
+        } else {
+            warn!("Nothing is drawn, so the tolerance is taken to be in millimeters");
+        }
+        config.tolerance_mode = ToleranceMode::Absolute;
+    }
+    config
+}
+
+/// Tight bounding box (in millimeters) of everything drawn from the transformed documents
+fn drawing_bounding_box(
+    inputs: &[(&Document, Transform2D<f64>)],
+    config: &ConversionConfig,
+    options: &ConversionOptions,
+) -> Option<Box2D<f64>> {
+    let points = drawing_subpaths(inputs, config, options)
+        .into_iter()
+        .flat_map(|subpath| subpath.points.into_iter().map(Point::from))
+        .collect::<Vec<_>>();
+    (!points.is_empty()).then(|| Box2D::from_points(points))
+}
+
+/// Flattened subpaths (in millimeters) of everything drawn from the transformed documents
+fn drawing_subpaths(
+    inputs: &[(&Document, Transform2D<f64>)],
+    config: &ConversionConfig,
+    options: &ConversionOptions,
+) -> Vec<SubpathInfo> {
+    let mut visitor = ConversionVisitor::new(
+        Terrarium::new(DpiConvertingTurtle {
+            inner: SubpathCollectingTurtle::new(config.tolerance),
+            dpi: config.effective_dpi(),
         }),
-        _config: config,
-        options: options_for_visitor,
-        name_stack: vec![],
-        viewport_dim_stack: vec![],
+        config,
+        options.clone(),
+    );
+    for (doc, transform) in inputs {
+        visitor.stylesheet = Stylesheet::from_document(doc);
+        visitor.terrarium.push_transform(*transform);
+        visitor.begin();
+        visit::depth_first_visit(doc, &mut visitor);
+        visitor.end();
+        visitor.terrarium.pop_transform();
+    }
+    visitor.terrarium.turtle.inner.subpaths
+}
+
+/// Flattens an SVG [`Document`] into subpaths without generating any g-code
+///
+/// Useful for consumers that only need the geometry, such as a preview.
+pub fn svg2paths(
+    doc: &Document,
+    config: &ConversionConfig,
+    options: ConversionOptions,
+) -> Vec<SubpathInfo> {
+    let combined_transform = conversion_transform(doc, config, &options);
+    let config = &absolute_tolerance_config(&[(doc, combined_transform)], config, &options);
+
+    let mut visitor = ConversionVisitor {
+        stylesheet: Stylesheet::from_document(doc),
+        report_problems: true,
+        ..ConversionVisitor::new(
+            Terrarium::new(DpiConvertingTurtle {
+                inner: SubpathCollectingTurtle::new(config.tolerance),
+                dpi: config.effective_dpi(),
+            }),
+            config,
+            options,
+        )
     };
 
-    // Compose transforms: apply trim/alignment first, then optional user-specified origin translation.
-    let alignment_requested = options_clone_for_transform.trim || options_clone_for_transform.dimensions.iter().any(|d| d.is_some());
-    let default_origin_requested = config.origin == [Some(0.0), Some(0.0)];
-    let apply_origin = !default_origin_requested && alignment_requested || !alignment_requested; // keep legacy behavior when no alignment/trim, otherwise skip default normalization
-    let combined_transform = if apply_origin {
-        post_transform.then(&origin_transform)
-    } else {
-        post_transform
+    visitor.terrarium.push_transform(combined_transform);
+    visitor.begin();
+    visit::depth_first_visit(doc, &mut visitor);
+    visitor.end();
+    visitor.terrarium.pop_transform();
+
+    visitor.terrarium.turtle.inner.subpaths
+}
+
+/// Tight bounding box (in millimeters) of an SVG [`Document`] as it would be placed by
+/// [svg2program], without generating any g-code
+///
+/// Curves are flattened with [ConversionConfig::tolerance]. The box is empty and at the origin if
+/// nothing is drawn.
+pub fn svg_bounding_box(
+    doc: &Document,
+    config: &ConversionConfig,
+    options: ConversionOptions,
+) -> Box2D<f64> {
+    let transform = conversion_transform(doc, config, &options);
+    drawing_bounding_box(&[(doc, transform)], config, &options).unwrap_or_default()
+}
+
+/// Physical size (width and height in millimeters) the SVG declares for itself, e.g. to show it
+/// before converting
+///
+/// Read from the root element's `width` and `height`. A missing one follows from the `viewBox`
+/// aspect ratio, and without either the `viewBox` is taken to be in pixels. Returns [None] if the
+/// size can't be told, including when it is given as a percentage.
+pub fn svg_physical_size(doc: &Document, config: &ConversionConfig) -> Option<[f64; 2]> {
+    use svgtypes::{LengthListParser, LengthUnit, ViewBox};
+    use units::DimensionHint;
+
+    // Only used to resolve units
+    let visitor = ConversionVisitor {
+        resolve_bounding_boxes: false,
+        ..ConversionVisitor::new(
+            Terrarium::new(PreprocessTurtle::default()),
+            config,
+            ConversionOptions::default(),
+        )
+    };
+    let root = doc.root_element();
+    let [width, height] = [
+        ("width", DimensionHint::Horizontal),
+        ("height", DimensionHint::Vertical),
+    ]
+    .map(|(attr, hint)| {
+        root.attribute(attr)
+            .and_then(|value| LengthListParser::from(value).next())
+            .and_then(Result::ok)
+            .filter(|length| length.unit != LengthUnit::Percent)
+            .map(|length| visitor.length_to_user_units(length, hint))
+    });
+    let view_box = root
+        .attribute("viewBox")
+        .and_then(|view_box| view_box.parse::<ViewBox>().ok());
+
+    let size = match (width, height, view_box) {
+        (Some(width), Some(height), _) => [width, height],
+        (Some(width), None, Some(view_box)) => [width, width * view_box.h / view_box.w],
+        (None, Some(height), Some(view_box)) => [height * view_box.w / view_box.h, height],
+        (None, None, Some(view_box)) => [view_box.w, view_box.h],
+        _ => return None,
     };
-    conversion_visitor
-        .terrarium
-        .push_transform(combined_transform);
-    conversion_visitor.begin();
-    visit::depth_first_visit(doc, &mut conversion_visitor);
-    conversion_visitor.end();
-    conversion_visitor.terrarium.pop_transform();
+    Some(size.map(|user_units| {
+        UomLength::new::<inch>(user_units / config.effective_dpi()).get::<millimeter>()
+    }))
+}
 
-    conversion_visitor.terrarium.turtle.inner.program
+/// Composed transform from the user units of the element with the given `id` to the
+/// millimeters of [svg2program]'s output, e.g. to debug why it is mis-positioned
+///
+/// Includes the element's own `transform` and the placement of the drawing. Returns [None] if no
+/// element has the `id`, or it is in a part of the document that isn't drawn.
+pub fn resolve_element_transform(
+    doc: &Document,
+    config: &ConversionConfig,
+    options: ConversionOptions,
+    id: &str,
+) -> Option<Transform2D<f64>> {
+    let combined_transform = conversion_transform(doc, config, &options);
+    let mut visitor = ConversionVisitor {
+        stylesheet: Stylesheet::from_document(doc),
+        tracked_id: Some(id.to_string()),
+        ..ConversionVisitor::new(Terrarium::new(PreprocessTurtle::default()), config, options)
+    };
+
+    visitor.terrarium.push_transform(combined_transform);
+    visitor.begin();
+    visit::depth_first_visit(doc, &mut visitor);
+    visitor.end();
+    visitor.terrarium.pop_transform();
+
+    let mm_per_user_unit = UomLength::new::<inch>(1.0 / config.effective_dpi()).get::<millimeter>();
+    visitor
+        .tracked_transform
+        .map(|transform| transform.then_scale(mm_per_user_unit, mm_per_user_unit))
+}
+
+/// Same as [svg2paths], serialized as a JSON array
+#[cfg(feature = "serde")]
+pub fn svg2paths_json(
+    doc: &Document,
+    config: &ConversionConfig,
+    options: ConversionOptions,
+) -> String {
+    serde_json::to_string(&svg2paths(doc, config, options)).expect("subpaths are serializable")
+}
+
+/// Same as [svg2program], but runs `post_processor` over the program before returning it
+pub fn svg2program_with_post_processor<'a, 'input: 'a>(
+    doc: &'a Document,
+    config: &ConversionConfig,
+    options: ConversionOptions,
+    machine: Machine<'input>,
+    post_processor: &mut dyn TokenPostProcessor<'input>,
+) -> Vec<Token<'input>> {
+    let mut program = svg2program(doc, config, options, machine);
+    post_processor.process(&mut program);
+    program
+}
+
+/// Children of an element whose text is commented, see [ConversionConfig::emit_titles]
+const TITLE_TAG_NAMES: [&str; 2] = ["title", "desc"];
+
+/// `<tag>: <text>` comments for the [TITLE_TAG_NAMES] children of a node, with whitespace
+/// collapsed so that each fits on a line
+fn node_titles<'a>(node: &Node<'a, '_>) -> impl Iterator<Item = String> + 'a {
+    node.children()
+        .filter(|child| TITLE_TAG_NAMES.contains(&child.tag_name().name()))
+        .filter_map(|child| {
+            let text = child
+                .descendants()
+                .filter(|descendant| descendant.is_text())
+                .filter_map(|descendant| descendant.text())
+                .flat_map(str::split_whitespace)
+                .collect::<Vec<_>>()
+                .join(" ");
+            (!text.is_empty()).then(|| format!("{}: {text}", child.tag_name().name()))
+        })
 }
 
 fn node_name(node: &Node , attr_to_print :  &Option<String> ) -> String {
@@ -351,6 +1264,28 @@ mod test {
     use super::*;
     use svgtypes::LengthUnit;
 
+    #[test]
+    fn svg2paths_json_has_expected_structure() {
+        let svg = r#"<svg xmlns="http://www.w3.org/2000/svg" width="20mm" height="20mm" viewBox="0 0 20 20">
+            <rect id="square" x="0" y="0" width="10" height="10" style="fill:none;stroke:#ff0000"/>
+            <line id="stroke" x1="0" y1="15" x2="20" y2="15" stroke="blue"/>
+        </svg>"#;
+        let doc = Document::parse(svg).unwrap();
+        let json = svg2paths_json(&doc, &ConversionConfig::default(), ConversionOptions::default());
+        let subpaths: serde_json::Value = serde_json::from_str(&json).unwrap();
+        let subpaths = subpaths.as_array().unwrap();
+
+        assert_eq!(subpaths.len(), 2);
+        assert_eq!(subpaths[0]["id"], "square");
+        assert_eq!(subpaths[0]["color"], "#ff0000");
+        assert!((subpaths[0]["length"].as_f64().unwrap() - 40.).abs() < 1e-9);
+        assert_eq!(subpaths[0]["points"].as_array().unwrap().len(), 5);
+        assert_eq!(subpaths[1]["id"], "stroke");
+        assert_eq!(subpaths[1]["color"], "#0000ff");
+        assert!((subpaths[1]["length"].as_f64().unwrap() - 20.).abs() < 1e-9);
+        assert_eq!(subpaths[1]["points"][0].as_array().unwrap().len(), 2);
+    }
+
     #[test]
     fn serde_conversion_options_is_correct() {
         let default_struct = ConversionOptions::default();
@@ -0,0 +1,329 @@
+use std::str::FromStr;
+
+use crate::diagnostics::warn;
+use roxmltree::Node;
+use svgtypes::{Color, Paint, PaintFallback, IRI};
+
+use super::{css::Stylesheet, visit::XLINK_NAMESPACE, PaintServerPolicy};
+use crate::turtle::{LineCap, LineJoin};
+
+const LINEAR_GRADIENT_TAG_NAME: &str = "linearGradient";
+const RADIAL_GRADIENT_TAG_NAME: &str = "radialGradient";
+const STOP_TAG_NAME: &str = "stop";
+
+/// A `fill` or `stroke` reduced to something usable for engraving
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ResolvedPaint {
+    None,
+    Color(Color),
+    /// Painted by a paint server and [PaintServerPolicy::Skip] is in effect
+    Skip,
+}
+
+/// Looks up a presentation property, preferring an inline `style` declaration, then a `<style>`
+/// rule, then the attribute
+pub fn presentation_property<'a>(
+    node: &Node<'a, '_>,
+    name: &str,
+    stylesheet: &'a Stylesheet,
+) -> Option<&'a str> {
+    node.attribute("style")
+        .and_then(|style| {
+            style
+                .split(';')
+                .filter_map(|declaration| declaration.split_once(':'))
+                .find(|(property, _)| property.trim() == name)
+                .map(|(_, value)| value.trim())
+        })
+        .or_else(|| stylesheet.property(node, name))
+        .or_else(|| node.attribute(name))
+}
+
+/// Looks up a presentation property on the node or, failing that, the closest ancestor specifying it
+pub fn inherited_property<'a>(
+    node: &Node<'a, '_>,
+    name: &str,
+    stylesheet: &'a Stylesheet,
+) -> Option<&'a str> {
+    node.ancestors()
+        .filter(Node::is_element)
+        .filter_map(|ancestor| presentation_property(&ancestor, name, stylesheet))
+        .find(|value| *value != "inherit")
+}
+
+/// Inherited `stroke-linecap` and `stroke-linejoin` of a node
+pub fn stroke_line_style(node: Node, stylesheet: &Stylesheet) -> (LineCap, LineJoin) {
+    fn resolve<T: Default>(
+        node: Node,
+        property: &str,
+        stylesheet: &Stylesheet,
+        parse: fn(&str) -> Option<T>,
+    ) -> T {
+        inherited_property(&node, property, stylesheet)
+            .and_then(|value| {
+                let parsed = parse(value);
+                if parsed.is_none() {
+                    warn!("Could not parse {property}: {value}");
+                }
+                parsed
+            })
+            .unwrap_or_default()
+    }
+
+    (
+        resolve(node, "stroke-linecap", stylesheet, LineCap::parse),
+        resolve(node, "stroke-linejoin", stylesheet, LineJoin::parse),
+    )
+}
+
+/// Inherited `stroke-miterlimit` of a node, the longest a miter join may be relative to the
+/// stroke width before it is drawn as a bevel
+///
+/// An invalid declaration is discarded, so the value is inherited as if it weren't there.
+pub fn stroke_miterlimit(node: Node, stylesheet: &Stylesheet) -> f64 {
+    const DEFAULT_MITERLIMIT: f64 = 4.;
+    node.ancestors()
+        .filter(Node::is_element)
+        .filter_map(|ancestor| presentation_property(&ancestor, "stroke-miterlimit", stylesheet))
+        .filter(|value| *value != "inherit")
+        .find_map(|value| match value.parse::<f64>() {
+            Ok(miterlimit) if miterlimit >= 1. => Some(miterlimit),
+            _ => {
+                warn!("Could not parse stroke-miterlimit: {value}");
+                None
+            }
+        })
+        .unwrap_or(DEFAULT_MITERLIMIT)
+}
+
+/// Opacity a node is rendered with: the product of its own and its ancestors' `opacity`,
+/// multiplied by the inherited `stroke-opacity` or `fill-opacity`, whichever is given as
+/// `paint_opacity` for the paint it is traced by
+pub fn effective_opacity(node: Node, paint_opacity: &str, stylesheet: &Stylesheet) -> f64 {
+    let group_opacity: f64 = node
+        .ancestors()
+        .filter(Node::is_element)
+        .filter_map(|ancestor| presentation_property(&ancestor, "opacity", stylesheet))
+        .map(parse_opacity)
+        .product();
+    group_opacity * inherited_property(&node, paint_opacity, stylesheet).map_or(1., parse_opacity)
+}
+
+/// Parse an `<alpha-value>`, clamped to `[0, 1]`
+fn parse_opacity(value: &str) -> f64 {
+    let parsed = match value.strip_suffix('%') {
+        Some(percentage) => percentage.trim().parse::<f64>().map(|p| p / 100.),
+        None => value.parse::<f64>(),
+    };
+    match parsed {
+        Ok(opacity) => opacity.clamp(0., 1.),
+        Err(err) => {
+            warn!("Could not parse opacity {value}: {err}");
+            1.
+        }
+    }
+}
+
+/// Resolve the inherited `fill` or `stroke` of a node.
+///
+/// Paint servers (`url(#...)`) are replaced by a representative color according to `policy`:
+/// the first stop of a gradient, or the fallback color of anything else.
+pub fn resolve_paint(
+    node: Node,
+    property: &str,
+    policy: PaintServerPolicy,
+    stylesheet: &Stylesheet,
+) -> ResolvedPaint {
+    let paint = match inherited_property(&node, property, stylesheet).map(Paint::from_str) {
+        Some(Ok(paint)) => paint,
+        Some(Err(err)) => {
+            warn!("Could not parse {property}: {err}");
+            return ResolvedPaint::None;
+        }
+        // Initial values: https://www.w3.org/TR/SVG2/painting.html#SpecifyingPaint
+        None if property == "fill" => return ResolvedPaint::Color(Color::black()),
+        None => return ResolvedPaint::None,
+    };
+
+    match paint {
+        Paint::Color(color) => ResolvedPaint::Color(color),
+        Paint::FuncIRI(_, _) if policy == PaintServerPolicy::Skip => ResolvedPaint::Skip,
+        Paint::FuncIRI(id, fallback) => {
+            let server = node
+                .document()
+                .descendants()
+                .find(|candidate| candidate.attribute("id") == Some(id));
+            match (
+                server.and_then(|server| first_stop_color(server, stylesheet)),
+                fallback,
+            ) {
+                (Some(color), _) | (None, Some(PaintFallback::Color(color))) => {
+                    ResolvedPaint::Color(color)
+                }
+                (None, Some(PaintFallback::None)) => ResolvedPaint::None,
+                (None, Some(PaintFallback::CurrentColor)) => {
+                    ResolvedPaint::Color(current_color(node, stylesheet))
+                }
+                (None, None) => ResolvedPaint::Color(Color::black()),
+            }
+        }
+        // `currentColor` is inherited as a keyword, so it resolves against the painted node
+        Paint::CurrentColor => ResolvedPaint::Color(current_color(node, stylesheet)),
+        // Not tracked yet, treated like the initial fill value
+        Paint::ContextFill | Paint::ContextStroke => ResolvedPaint::Color(Color::black()),
+        Paint::None | Paint::Inherit => ResolvedPaint::None,
+    }
+}
+
+/// Inherited value of the `color` property, which `currentColor` refers to
+///
+/// <https://www.w3.org/TR/css-color-4/#resolving-other-colors>
+fn current_color(node: Node, stylesheet: &Stylesheet) -> Color {
+    let color = node
+        .ancestors()
+        .filter(Node::is_element)
+        .filter_map(|ancestor| presentation_property(&ancestor, "color", stylesheet))
+        // `color: currentColor` is the same as inheriting
+        .find(|value| !matches!(*value, "inherit" | "currentColor"));
+    match color.map(Color::from_str) {
+        Some(Ok(color)) => color,
+        Some(Err(err)) => {
+            warn!("Could not parse color: {err}");
+            Color::black()
+        }
+        // Initial value depends on the user agent, black is the usual one
+        None => Color::black(),
+    }
+}
+
+/// Color of the first `<stop>` in a gradient
+fn first_stop_color(gradient: Node, stylesheet: &Stylesheet) -> Option<Color> {
+    let is_gradient = |node: &Node| {
+        matches!(
+            node.tag_name().name(),
+            LINEAR_GRADIENT_TAG_NAME | RADIAL_GRADIENT_TAG_NAME
+        )
+    };
+    if !is_gradient(&gradient) {
+        return None;
+    }
+    // A gradient without stops of its own uses those of the gradient it references
+    // https://www.w3.org/TR/SVG2/pservers.html#PaintServerTemplates
+    let mut visited = vec![];
+    let mut current = gradient;
+    let stop = loop {
+        if let Some(stop) = current
+            .children()
+            .find(|child| child.has_tag_name(STOP_TAG_NAME))
+        {
+            break stop;
+        }
+        visited.push(current);
+        current = current
+            .attribute("href")
+            .or_else(|| current.attribute((XLINK_NAMESPACE, "href")))
+            .and_then(|href| IRI::from_str(href).ok())
+            .and_then(|IRI(id)| {
+                current
+                    .document()
+                    .descendants()
+                    .find(|n| n.attribute("id") == Some(id))
+            })
+            .filter(|referenced| is_gradient(referenced) && !visited.contains(referenced))?;
+    };
+    Some(match presentation_property(&stop, "stop-color", stylesheet) {
+        Some("currentColor") => current_color(stop, stylesheet),
+        Some(color) => Color::from_str(color).unwrap_or_else(|_| Color::black()),
+        None => Color::black(),
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn linear_gradient_resolves_to_first_stop() {
+        let svg = r##"<svg xmlns="http://www.w3.org/2000/svg">
+            <defs>
+                <linearGradient id="grad">
+                    <stop offset="0" stop-color="#ff0000"/>
+                    <stop offset="1" style="stop-color:#0000ff"/>
+                </linearGradient>
+            </defs>
+            <g style="fill:url(#grad)">
+                <rect id="target" width="10" height="10"/>
+            </g>
+        </svg>"##;
+        let doc = roxmltree::Document::parse(svg).unwrap();
+        let rect = doc
+            .descendants()
+            .find(|n| n.attribute("id") == Some("target"))
+            .unwrap();
+
+        let stylesheet = Stylesheet::default();
+
+        assert_eq!(
+            resolve_paint(rect, "fill", PaintServerPolicy::SolidColor, &stylesheet),
+            ResolvedPaint::Color(Color::new_rgb(255, 0, 0))
+        );
+        assert_eq!(
+            resolve_paint(rect, "fill", PaintServerPolicy::Skip, &stylesheet),
+            ResolvedPaint::Skip
+        );
+        assert_eq!(
+            resolve_paint(rect, "stroke", PaintServerPolicy::Skip, &stylesheet),
+            ResolvedPaint::None
+        );
+    }
+
+    #[test]
+    fn gradient_inherits_stops_through_href() {
+        let svg = r##"<svg xmlns="http://www.w3.org/2000/svg" xmlns:xlink="http://www.w3.org/1999/xlink">
+            <defs>
+                <linearGradient id="base">
+                    <stop offset="0" stop-color="#00ff00"/>
+                </linearGradient>
+                <linearGradient id="middle" xlink:href="#base" gradientTransform="rotate(45)"/>
+                <radialGradient id="grad" href="#middle"/>
+                <linearGradient id="loop" href="#loop"/>
+            </defs>
+            <rect id="target" fill="url(#grad)" stroke="url(#loop)" width="10" height="10"/>
+        </svg>"##;
+        let doc = roxmltree::Document::parse(svg).unwrap();
+        let rect = doc
+            .descendants()
+            .find(|n| n.attribute("id") == Some("target"))
+            .unwrap();
+        let stylesheet = Stylesheet::default();
+
+        assert_eq!(
+            resolve_paint(rect, "fill", PaintServerPolicy::SolidColor, &stylesheet),
+            ResolvedPaint::Color(Color::new_rgb(0, 255, 0))
+        );
+        // A reference cycle has no stops, like a missing paint server
+        assert_eq!(
+            resolve_paint(rect, "stroke", PaintServerPolicy::SolidColor, &stylesheet),
+            ResolvedPaint::Color(Color::black())
+        );
+    }
+
+    #[test]
+    fn current_color_resolves_to_inherited_color() {
+        let svg = r##"<svg xmlns="http://www.w3.org/2000/svg" color="blue">
+            <g color="red">
+                <path id="target" stroke="currentColor" d="M 0 0 L 10 10"/>
+            </g>
+        </svg>"##;
+        let doc = roxmltree::Document::parse(svg).unwrap();
+        let path = doc
+            .descendants()
+            .find(|n| n.attribute("id") == Some("target"))
+            .unwrap();
+
+        assert_eq!(
+            resolve_paint(path, "stroke", PaintServerPolicy::SolidColor, &Stylesheet::default()),
+            ResolvedPaint::Color(Color::new_rgb(255, 0, 0))
+        );
+    }
+}
@@ -1,24 +1,59 @@
 use euclid::Angle;
-use log::debug;
-use lyon_geom::{point, vector, ArcFlags};
+use crate::diagnostics::debug;
+use lyon_geom::{point, vector, ArcFlags, Point};
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
 use svgtypes::PathSegment;
 
 use crate::Turtle;
 
 use super::Terrarium;
 
+/// Kinds of path commands that are drawn, for debugging one kind in isolation
+///
+/// The tool only moves to the end of the other commands.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct CommandFilter {
+    /// Lines (L, H, V) and closepaths (Z)
+    pub lines: bool,
+    /// Cubic and quadratic Bézier curves (C, S, Q, T)
+    pub curves: bool,
+    /// Elliptical arcs (A)
+    pub arcs: bool,
+}
+
+impl CommandFilter {
+    /// Whether the segment is drawn, moves always are
+    pub fn draws(&self, segment: &PathSegment) -> bool {
+        use PathSegment::*;
+        match segment {
+            MoveTo { .. } => true,
+            ClosePath { .. } | LineTo { .. } | HorizontalLineTo { .. } | VerticalLineTo { .. } => {
+                self.lines
+            }
+            CurveTo { .. } | SmoothCurveTo { .. } | Quadratic { .. } | SmoothQuadratic { .. } => {
+                self.curves
+            }
+            EllipticalArc { .. } => self.arcs,
+        }
+    }
+}
+
 /// Maps [`PathSegment`]s into concrete operations on the [`Terrarium`]
 ///
-/// Performs a [`Terrarium::reset`] on each call
+/// Performs a [`Terrarium::reset`] on each call. Segments left out by `filter` are not drawn.
 pub fn apply_path<T: Turtle>(
     terrarium: &mut Terrarium<T>,
     path: impl IntoIterator<Item = PathSegment>,
+    filter: Option<CommandFilter>,
 ) {
     use PathSegment::*;
 
     terrarium.reset();
     path.into_iter().for_each(|segment| {
         debug!("Drawing {:?}", &segment);
+        terrarium.skip_drawing = filter.is_some_and(|filter| !filter.draws(&segment));
         match segment {
             MoveTo { abs, x, y } => terrarium.move_to(abs, x, y),
             ClosePath { abs: _ } => {
@@ -62,4 +97,45 @@ pub fn apply_path<T: Turtle>(
             ),
         }
     });
+    terrarium.skip_drawing = false;
+}
+
+/// Vertices of a path in its own user space, as used for placing markers
+///
+/// <https://www.w3.org/TR/SVG2/painting.html#VertexMarkerProperties>
+pub fn path_vertices(path: impl IntoIterator<Item = PathSegment>) -> Vec<Point<f64>> {
+    use PathSegment::*;
+
+    let mut current = Point::zero();
+    let mut subpath_start = Point::zero();
+    let mut vertices = vec![];
+    for segment in path {
+        let (abs, x, y) = match segment {
+            MoveTo { abs, x, y }
+            | LineTo { abs, x, y }
+            | CurveTo { abs, x, y, .. }
+            | SmoothCurveTo { abs, x, y, .. }
+            | Quadratic { abs, x, y, .. }
+            | SmoothQuadratic { abs, x, y }
+            | EllipticalArc { abs, x, y, .. } => (abs, Some(x), Some(y)),
+            HorizontalLineTo { abs, x } => (abs, Some(x), None),
+            VerticalLineTo { abs, y } => (abs, None, Some(y)),
+            ClosePath { .. } => {
+                current = subpath_start;
+                vertices.push(current);
+                continue;
+            }
+        };
+        let resolve = |value: Option<f64>, current: f64| match value {
+            Some(value) if abs => value,
+            Some(value) => current + value,
+            None => current,
+        };
+        current = point(resolve(x, current.x), resolve(y, current.y));
+        if matches!(segment, MoveTo { .. }) {
+            subpath_start = current;
+        }
+        vertices.push(current);
+    }
+    vertices
 }
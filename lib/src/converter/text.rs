@@ -0,0 +1,255 @@
+use crate::diagnostics::{debug, warn};
+use roxmltree::Node;
+use svgtypes::{Length, LengthListParser, PathSegment};
+
+use crate::Turtle;
+
+use super::{
+    paint::{inherited_property, presentation_property},
+    units::DimensionHint,
+    ConversionVisitor,
+};
+
+const TSPAN_TAG_NAME: &str = "tspan";
+
+/// Initial value of `font-size` (`medium`)
+const DEFAULT_FONT_SIZE: f64 = 16.;
+/// Height of capital letters relative to the font size
+const CAP_HEIGHT: f64 = 0.7;
+/// Glyphs are drawn on a grid 4 units wide, with the baseline at 0 and capitals reaching up to
+/// this height
+const GLYPH_HEIGHT: f64 = 6.;
+/// Distance from one glyph to the next, in grid units
+const GLYPH_ADVANCE: f64 = 6.;
+/// How far `baseline-shift: super` raises the baseline, relative to the font size
+const SUPERSCRIPT_SHIFT: f64 = 0.4;
+/// How far `baseline-shift: sub` lowers the baseline, relative to the font size
+const SUBSCRIPT_SHIFT: f64 = 0.2;
+
+type Glyph = &'static [&'static [(i8, i8)]];
+
+/// Strokes of a character in the built-in single-stroke font
+///
+/// Only digits, capital letters, and some punctuation are available. Lowercase letters are drawn as
+/// capitals.
+fn glyph(c: char) -> Option<Glyph> {
+    Some(match c.to_ascii_uppercase() {
+        ' ' => &[],
+        'A' => &[&[(0, 0), (0, 4), (2, 6), (4, 4), (4, 0)], &[(0, 3), (4, 3)]],
+        'B' => &[
+            &[(0, 0), (0, 6), (3, 6), (4, 5), (4, 4), (3, 3), (0, 3)],
+            &[(3, 3), (4, 2), (4, 1), (3, 0), (0, 0)],
+        ],
+        'C' => &[&[(4, 6), (0, 6), (0, 0), (4, 0)]],
+        'D' => &[&[(0, 0), (0, 6), (3, 6), (4, 5), (4, 1), (3, 0), (0, 0)]],
+        'E' => &[&[(4, 6), (0, 6), (0, 0), (4, 0)], &[(0, 3), (3, 3)]],
+        'F' => &[&[(4, 6), (0, 6), (0, 0)], &[(0, 3), (3, 3)]],
+        'G' => &[&[(4, 6), (0, 6), (0, 0), (4, 0), (4, 3), (2, 3)]],
+        'H' => &[&[(0, 0), (0, 6)], &[(4, 0), (4, 6)], &[(0, 3), (4, 3)]],
+        'I' => &[&[(1, 6), (3, 6)], &[(2, 6), (2, 0)], &[(1, 0), (3, 0)]],
+        'J' => &[&[(4, 6), (4, 0), (0, 0), (0, 2)]],
+        'K' => &[&[(0, 0), (0, 6)], &[(4, 6), (0, 3), (4, 0)]],
+        'L' => &[&[(0, 6), (0, 0), (4, 0)]],
+        'M' => &[&[(0, 0), (0, 6), (2, 3), (4, 6), (4, 0)]],
+        'N' => &[&[(0, 0), (0, 6), (4, 0), (4, 6)]],
+        'O' => &[&[(0, 0), (0, 6), (4, 6), (4, 0), (0, 0)]],
+        'P' => &[&[(0, 0), (0, 6), (4, 6), (4, 3), (0, 3)]],
+        'Q' => &[&[(0, 0), (0, 6), (4, 6), (4, 0), (0, 0)], &[(2, 2), (4, 0)]],
+        'R' => &[&[(0, 0), (0, 6), (4, 6), (4, 3), (0, 3), (4, 0)]],
+        'S' | '5' => &[&[(4, 6), (0, 6), (0, 3), (4, 3), (4, 0), (0, 0)]],
+        'T' => &[&[(0, 6), (4, 6)], &[(2, 6), (2, 0)]],
+        'U' => &[&[(0, 6), (0, 0), (4, 0), (4, 6)]],
+        'V' => &[&[(0, 6), (2, 0), (4, 6)]],
+        'W' => &[&[(0, 6), (1, 0), (2, 3), (3, 0), (4, 6)]],
+        'X' => &[&[(0, 0), (4, 6)], &[(0, 6), (4, 0)]],
+        'Y' => &[&[(0, 6), (2, 3), (4, 6)], &[(2, 3), (2, 0)]],
+        'Z' => &[&[(0, 6), (4, 6), (0, 0), (4, 0)]],
+        '0' => &[&[(0, 0), (0, 6), (4, 6), (4, 0), (0, 0), (4, 6)]],
+        '1' => &[&[(1, 5), (2, 6), (2, 0)], &[(1, 0), (3, 0)]],
+        '2' => &[&[(0, 6), (4, 6), (4, 3), (0, 3), (0, 0), (4, 0)]],
+        '3' => &[&[(0, 6), (4, 6), (4, 0), (0, 0)], &[(1, 3), (4, 3)]],
+        '4' => &[&[(0, 6), (0, 3), (4, 3)], &[(4, 6), (4, 0)]],
+        '6' => &[&[(4, 6), (0, 6), (0, 0), (4, 0), (4, 3), (0, 3)]],
+        '7' => &[&[(0, 6), (4, 6), (2, 0)]],
+        '8' => &[&[(0, 0), (0, 6), (4, 6), (4, 0), (0, 0)], &[(0, 3), (4, 3)]],
+        '9' => &[&[(4, 3), (0, 3), (0, 6), (4, 6), (4, 0), (0, 0)]],
+        '-' => &[&[(1, 3), (3, 3)]],
+        '+' => &[&[(0, 3), (4, 3)], &[(2, 1), (2, 5)]],
+        '.' => &[&[(2, 0), (2, 1)]],
+        ',' => &[&[(2, 1), (1, -1)]],
+        ':' => &[&[(2, 1), (2, 2)], &[(2, 4), (2, 5)]],
+        '/' => &[&[(0, 0), (4, 6)]],
+        '_' => &[&[(0, 0), (4, 0)]],
+        _ => return None,
+    })
+}
+
+/// A character to be drawn and the positioning attributes that apply to it
+#[derive(Debug)]
+struct TextChar {
+    c: char,
+    font_size: f64,
+    /// Absolute `x` and `y`
+    position: [Option<f64>; 2],
+    /// Relative `dx` and `dy`
+    shift: [Option<f64>; 2],
+    /// How far the baseline is raised by the `baseline-shift` of the enclosing `<tspan>`s
+    baseline_shift: f64,
+}
+
+/// `x`, `y`, `dx`, and `dy` of a `<text>` or `<tspan>`, which apply to its characters in order
+struct Positioning {
+    /// Index of the first character of the element
+    start: usize,
+    lists: [Vec<f64>; 4],
+}
+
+impl<'a, T: Turtle> ConversionVisitor<'a, T> {
+    /// Lay out the characters of a `<text>` and its `<tspan>`s as strokes of the built-in font
+    ///
+    /// <https://www.w3.org/TR/SVG2/text.html#TextLayoutAlgorithm>
+    pub fn text_path(&self, text: &Node) -> Vec<PathSegment> {
+        let mut chars = vec![];
+        self.collect_chars(text, 0., &mut vec![], &mut chars);
+        // Trailing whitespace is collapsed away
+        while chars.last().is_some_and(|c| c.c == ' ') {
+            chars.pop();
+        }
+
+        let mut path = vec![];
+        let [mut x, mut y] = [0., 0.];
+        for c in chars {
+            x = c.position[0].unwrap_or(x) + c.shift[0].unwrap_or(0.);
+            y = c.position[1].unwrap_or(y) + c.shift[1].unwrap_or(0.);
+            let unit = c.font_size * CAP_HEIGHT / GLYPH_HEIGHT;
+            match glyph(c.c) {
+                Some(strokes) => {
+                    for stroke in strokes {
+                        path.extend(stroke.iter().enumerate().map(|(i, (gx, gy))| {
+                            let (px, py) =
+                                (x + *gx as f64 * unit, y - c.baseline_shift - *gy as f64 * unit);
+                            if i == 0 {
+                                PathSegment::MoveTo { abs: true, x: px, y: py }
+                            } else {
+                                PathSegment::LineTo { abs: true, x: px, y: py }
+                            }
+                        }));
+                    }
+                }
+                None => debug!("No glyph for {:?}, leaving a gap", c.c),
+            }
+            x += unit * GLYPH_ADVANCE;
+        }
+        path
+    }
+
+    /// Gather the characters of an element with whitespace collapsed, along with their positioning
+    fn collect_chars(
+        &self,
+        element: &Node,
+        baseline_shift: f64,
+        positioning: &mut Vec<Positioning>,
+        chars: &mut Vec<TextChar>,
+    ) {
+        positioning.push(Positioning {
+            start: chars.len(),
+            lists: [
+                ("x", DimensionHint::Horizontal),
+                ("y", DimensionHint::Vertical),
+                ("dx", DimensionHint::Horizontal),
+                ("dy", DimensionHint::Vertical),
+            ]
+            .map(|(attr, hint)| self.length_list(element, attr, hint)),
+        });
+        let font_size = self.font_size(element);
+
+        for child in element.children() {
+            if child.is_element() {
+                if child.has_tag_name(TSPAN_TAG_NAME) {
+                    // Shifts add up, relative to the baseline of the parent
+                    let baseline_shift = baseline_shift + self.baseline_shift(&child);
+                    self.collect_chars(&child, baseline_shift, positioning, chars);
+                } else {
+                    debug!("Ignoring {} in text", child.tag_name().name());
+                }
+                continue;
+            }
+            let Some(text) = child.text() else {
+                continue;
+            };
+            for c in text.chars() {
+                let c = if c.is_whitespace() { ' ' } else { c };
+                // Leading and repeated whitespace is collapsed away
+                if c == ' ' && chars.last().is_none_or(|last| last.c == ' ') {
+                    continue;
+                }
+                // The innermost element with a value for this character wins
+                let value = |list: usize| {
+                    positioning
+                        .iter()
+                        .rev()
+                        .find_map(|p| p.lists[list].get(chars.len() - p.start).copied())
+                };
+                chars.push(TextChar {
+                    c,
+                    font_size,
+                    position: [value(0), value(1)],
+                    shift: [value(2), value(3)],
+                    baseline_shift,
+                });
+            }
+        }
+        positioning.pop();
+    }
+
+    fn length_list(&self, node: &Node, attr: &str, hint: DimensionHint) -> Vec<f64> {
+        node.attribute(attr)
+            .map(|list| {
+                LengthListParser::from(list)
+                    .filter_map(|length| match length {
+                        Ok(length) => Some(self.length_to_user_units(length, hint)),
+                        Err(err) => {
+                            warn!("Could not parse {attr} of text: {err}");
+                            None
+                        }
+                    })
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
+
+    /// `baseline-shift` of a `<tspan>` in user units, positive upwards
+    ///
+    /// Percentages are of the font size, which is the line height of the built-in font.
+    fn baseline_shift(&self, tspan: &Node) -> f64 {
+        let Some(value) = presentation_property(tspan, "baseline-shift", &self.stylesheet) else {
+            return 0.;
+        };
+        let font_size = self.font_size(tspan);
+        match value {
+            "baseline" => 0.,
+            "super" => font_size * SUPERSCRIPT_SHIFT,
+            "sub" => -font_size * SUBSCRIPT_SHIFT,
+            length => match length.parse::<Length>() {
+                Ok(Length {
+                    number,
+                    unit: svgtypes::LengthUnit::Percent,
+                }) => font_size * number / 100.,
+                Ok(length) => self.length_to_user_units(length, DimensionHint::Vertical),
+                Err(err) => {
+                    warn!("Could not parse baseline-shift {value}: {err}");
+                    0.
+                }
+            },
+        }
+    }
+
+    /// Inherited `font-size` in user units
+    fn font_size(&self, node: &Node) -> f64 {
+        inherited_property(node, "font-size", &self.stylesheet)
+            .and_then(|size| size.parse::<Length>().ok())
+            .map_or(DEFAULT_FONT_SIZE, |size| {
+                self.length_to_user_units(size, DimensionHint::Vertical)
+            })
+    }
+}
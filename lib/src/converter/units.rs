@@ -1,17 +1,53 @@
-use log::warn;
+use crate::diagnostics::warn;
 use roxmltree::Node;
 use svgtypes::{Length, LengthListParser};
 
 use crate::Turtle;
 
-use super::ConversionVisitor;
+use super::{ConversionError, ConversionVisitor};
 
 /// Historically a fixed 96 CSS px per inch was used here, but we now honor the
-/// user-configured DPI (`ConversionConfig.dpi`) so physical units (mm, cm, in,
+/// user-configured DPI (`ConversionConfig::effective_dpi`) so physical units (mm, cm, in,
 /// pt, pc) remain invariant when the caller changes DPI. The old constant is
 /// retained only for reference in documentation; do NOT use it for new code.
 pub const _CSS_REFERENCE_DPI: f64 = 96.;
 
+/// Parses a single length for [super::ConversionOptions::dimensions], e.g. `"210mm"`
+///
+/// A comma decimal separator (`"12,5mm"`) is accepted too, since it would otherwise be read as
+/// a list of two lengths.
+pub fn parse_dimension(dimension: &str) -> Result<Length, ConversionError> {
+    let trimmed = dimension.trim();
+    let is_comma_decimal = {
+        let mut parts = trimmed.split(',');
+        matches!(
+            (parts.next(), parts.next(), parts.next()),
+            (Some(integer), Some(fraction), None)
+                if integer.ends_with(|c: char| c.is_ascii_digit())
+                    && fraction.starts_with(|c: char| c.is_ascii_digit())
+        )
+    };
+    let normalized = if is_comma_decimal {
+        trimmed.replacen(',', ".", 1)
+    } else {
+        trimmed.to_string()
+    };
+
+    let invalid = |message: String| ConversionError::InvalidDimension {
+        value: dimension.to_string(),
+        message,
+    };
+    let mut parser = LengthListParser::from(normalized.as_str());
+    let length = parser
+        .next()
+        .ok_or_else(|| invalid("no length given".to_string()))?
+        .map_err(|err| invalid(err.to_string()))?;
+    if parser.next().is_some() {
+        return Err(invalid("expected a single length".to_string()));
+    }
+    Ok(length)
+}
+
 /// Used to compute percentages correctly
 ///
 /// <https://www.w3.org/TR/SVG/coords.html#Units>
@@ -54,11 +90,11 @@ impl<'a, T: Turtle> ConversionVisitor<'a, T> {
         use uom::si::length::*;
 
         match l.unit {
-            Cm => Length::new::<centimeter>(l.number).get::<inch>() * self._config.dpi,
-            Mm => Length::new::<millimeter>(l.number).get::<inch>() * self._config.dpi,
-            In => Length::new::<inch>(l.number).get::<inch>() * self._config.dpi,
-            Pc => Length::new::<pica_computer>(l.number).get::<inch>() * self._config.dpi,
-            Pt => Length::new::<point_computer>(l.number).get::<inch>() * self._config.dpi,
+            Cm => Length::new::<centimeter>(l.number).get::<inch>() * self._config.effective_dpi(),
+            Mm => Length::new::<millimeter>(l.number).get::<inch>() * self._config.effective_dpi(),
+            In => Length::new::<inch>(l.number).get::<inch>() * self._config.effective_dpi(),
+            Pc => Length::new::<pica_computer>(l.number).get::<inch>() * self._config.effective_dpi(),
+            Pt => Length::new::<point_computer>(l.number).get::<inch>() * self._config.effective_dpi(),
             // https://www.w3.org/TR/SVG/coords.html#ViewportSpace says None should be treated as Px
             Px | None => l.number,
             Em | Ex => {
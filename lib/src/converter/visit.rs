@@ -1,20 +1,38 @@
 use std::str::FromStr;
 
 use euclid::default::Transform2D;
-use log::{debug, warn};
+use crate::diagnostics::{debug, warn};
 use roxmltree::{Document, Node};
-use svgtypes::{AspectRatio, PathParser, PathSegment, PointsParser, TransformListParser, ViewBox};
+use lyon_geom::{point, vector, Box2D, Point};
+use svgtypes::{
+    AspectRatio, FuncIRI, Length, IRI, LengthUnit, PathParser, PathSegment, PointsParser, TransformListParser, ViewBox,
+};
 
 use super::{
-    path::apply_path,
+    css::Stylesheet,
+    paint::{
+        effective_opacity, inherited_property, presentation_property, resolve_paint, ResolvedPaint,
+    },
+    path::{apply_path, path_vertices},
     transform::{get_viewport_transform, svg_transform_into_euclid_transform},
     units::DimensionHint,
-    ConversionVisitor,
+    ConversionError, ConversionOptions, ConversionVisitor, LayerParams, UnsupportedPolicy,
+};
+use crate::{
+    converter::node_name,
+    turtle::{DpiConvertingTurtle, SubpathCollectingTurtle, Terrarium},
+    Turtle,
 };
-use crate::{converter::node_name, Turtle};
+
+const MM_PER_INCH: f64 = 25.4;
+
+/// Attribute overriding the feedrate of an element's contents, see
+/// [crate::ConversionConfig::layer_params]
+const FEEDRATE_ATTRIBUTE: &str = "data-feedrate";
 
 const SVG_TAG_NAME: &str = "svg";
 const CLIP_PATH_TAG_NAME: &str = "clipPath";
+const SWITCH_TAG_NAME: &str = "switch";
 const PATH_TAG_NAME: &str = "path";
 const POLYLINE_TAG_NAME: &str = "polyline";
 const POLYGON_TAG_NAME: &str = "polygon";
@@ -23,12 +41,20 @@ const CIRCLE_TAG_NAME: &str = "circle";
 const ELLIPSE_TAG_NAME: &str = "ellipse";
 const LINE_TAG_NAME: &str = "line";
 const GROUP_TAG_NAME: &str = "g";
+const ANCHOR_TAG_NAME: &str = "a";
 const DEFS_TAG_NAME: &str = "defs";
 const USE_TAG_NAME: &str = "use";
 const MARKER_TAG_NAME: &str = "marker";
 const SYMBOL_TAG_NAME: &str = "symbol";
+pub(super) const XLINK_NAMESPACE: &str = "http://www.w3.org/1999/xlink";
+const TEXT_TAG_NAME: &str = "text";
+/// Graphics elements that can't be traced
+const UNSUPPORTED_TAG_NAMES: [&str; 6] =
+    ["image", "foreignObject", "video", "audio", "canvas", "iframe"];
 
 pub trait XmlVisitor {
+    /// Whether an element passes its conditional processing attributes
+    fn is_enabled(&self, node: Node) -> bool;
     fn visit_enter(&mut self, node: Node);
     fn visit_exit(&mut self, node: Node);
 }
@@ -42,47 +68,663 @@ fn should_render_node(node: Node) -> bool {
         // - Defs are not rendered
         // - Markers are not directly rendered
         // - Symbols are not directly rendered
-        && !matches!(node.tag_name().name(), DEFS_TAG_NAME | MARKER_TAG_NAME | SYMBOL_TAG_NAME)
+        // - Clip paths are only used through `clip-path`
+        && !matches!(
+            node.tag_name().name(),
+            DEFS_TAG_NAME | MARKER_TAG_NAME | SYMBOL_TAG_NAME | CLIP_PATH_TAG_NAME
+        )
 }
 
-pub fn depth_first_visit(doc: &Document, visitor: &mut impl XmlVisitor) {
-    fn visit_node(node: Node, visitor: &mut impl XmlVisitor) {
-        if !should_render_node(node) {
-            return;
+/// Span that diagnostics about a node are reported in, see [crate::diagnostics]
+#[cfg(feature = "tracing")]
+fn element_span(node: Node) -> tracing::Span {
+    tracing::debug_span!(
+        "element",
+        tag = node.tag_name().name(),
+        id = node.attribute("id")
+    )
+}
+
+fn visit_node(node: Node, visitor: &mut impl XmlVisitor) {
+    if !should_render_node(node) || !visitor.is_enabled(node) {
+        return;
+    }
+    #[cfg(feature = "tracing")]
+    let _span = element_span(node).entered();
+    visitor.visit_enter(node);
+    if node.has_tag_name(SWITCH_TAG_NAME) {
+        // Only the first enabled child is rendered
+        // https://www.w3.org/TR/SVG2/struct.html#SwitchElement
+        if let Some(child) = node
+            .children()
+            .find(|child| should_render_node(*child) && visitor.is_enabled(*child))
+        {
+            visit_node(child, visitor);
         }
-        visitor.visit_enter(node);
+    } else {
         node.children().for_each(|child| visit_node(child, visitor));
-        visitor.visit_exit(node);
     }
+    visitor.visit_exit(node);
+}
+
+/// The `transform` attribute of a node, the identity if it has none
+///
+/// It can fail to parse, e.g. because it uses an unsupported function like `ref()`. Callers
+/// then use the identity, and the visitor reports it according to
+/// [crate::ConversionConfig::on_unsupported].
+fn transform_attribute(node: &Node) -> Result<Transform2D<f64>, svgtypes::Error> {
+    let Some(transform) = node.attribute("transform") else {
+        return Ok(Transform2D::identity());
+    };
+    // https://stackoverflow.com/questions/18582935/the-applying-order-of-svg-transforms
+    TransformListParser::from(transform).try_fold(
+        Transform2D::identity(),
+        |acc: Transform2D<f64>, token| {
+            token.map(|token| svg_transform_into_euclid_transform(token).then(&acc))
+        },
+    )
+}
+
+/// The feedrate set on a node with [FEEDRATE_ATTRIBUTE], if it has one, or why it can't be used
+fn feedrate_attribute(node: &Node) -> Option<Result<f64, String>> {
+    let value = node.attribute(FEEDRATE_ATTRIBUTE)?;
+    Some(match value.trim().parse::<f64>() {
+        Ok(feedrate) if feedrate.is_finite() && feedrate > 0. => Ok(feedrate),
+        Ok(_) => Err("feedrate must be positive".to_string()),
+        Err(err) => Err(err.to_string()),
+    })
+}
+
+/// The `<clipPath>` referenced by the `clip-path` of a node, if any
+fn referenced_clip_path<'a, 'input>(
+    node: &Node<'a, 'input>,
+    stylesheet: &Stylesheet,
+) -> Option<Node<'a, 'input>> {
+    let FuncIRI(id) = presentation_property(node, "clip-path", stylesheet)
+        .and_then(|value| FuncIRI::from_str(value).ok())?;
+    node.document()
+        .descendants()
+        .find(|n| n.has_tag_name(CLIP_PATH_TAG_NAME) && n.attribute("id") == Some(id))
+}
+
+fn is_shape(node: &Node) -> bool {
+    matches!(
+        node.tag_name().name(),
+        PATH_TAG_NAME
+            | POLYLINE_TAG_NAME
+            | POLYGON_TAG_NAME
+            | RECT_TAG_NAME
+            | CIRCLE_TAG_NAME
+            | ELLIPSE_TAG_NAME
+            | LINE_TAG_NAME
+    )
+}
+
+/// Whether a node is a top-level group, which is a layer
+fn is_layer(node: Node) -> bool {
+    node.has_tag_name(GROUP_TAG_NAME)
+        && node.parent_element() == Some(node.document().root_element())
+}
+
+/// Zero-based position of a layer among the layers of its document
+fn layer_index(node: Node) -> usize {
+    // Counting this layer
+    node.prev_siblings()
+        .filter(|sib| should_render_node(*sib) && sib.has_tag_name(GROUP_TAG_NAME))
+        .count()
+        - 1
+}
+
+/// Number of top-level groups (layers) in a document
+pub fn layer_count(doc: &Document) -> usize {
+    doc.root_element()
+        .children()
+        .filter(|child| should_render_node(*child) && child.has_tag_name(GROUP_TAG_NAME))
+        .count()
+}
 
+pub fn depth_first_visit(doc: &Document, visitor: &mut impl XmlVisitor) {
     doc.root()
         .children()
         .for_each(|child| visit_node(child, visitor));
 }
 
+/// Same as [depth_first_visit], reporting the fraction of the children of the root element
+/// visited so far after each one
+pub fn depth_first_visit_with_progress(
+    doc: &Document,
+    visitor: &mut impl XmlVisitor,
+    progress: &mut dyn FnMut(f32),
+) {
+    let root = doc.root_element();
+    if !should_render_node(root) || !visitor.is_enabled(root) {
+        return;
+    }
+    #[cfg(feature = "tracing")]
+    let _span = element_span(root).entered();
+    visitor.visit_enter(root);
+    let count = root.children().count();
+    for (i, child) in root.children().enumerate() {
+        visit_node(child, visitor);
+        progress((i + 1) as f32 / count as f32);
+    }
+    visitor.visit_exit(root);
+}
+
+impl<'a, T: Turtle> ConversionVisitor<'a, T> {
+    /// Whether a shape should be left out under [crate::PaintServerPolicy::Skip]
+    fn is_painted_by_skipped_server(&self, node: &Node) -> bool {
+        is_shape(node)
+            && ["fill", "stroke"].into_iter().any(|property| {
+                resolve_paint(*node, property, self._config.paint_server_policy, &self.stylesheet)
+                    == ResolvedPaint::Skip
+            })
+    }
+
+    /// Whether a shape is fainter than [crate::ConversionConfig::min_opacity], by the opacity
+    /// of its stroke, or of its fill if it is only traced by that
+    fn is_below_min_opacity(&self, node: &Node) -> bool {
+        self._config.min_opacity.is_some_and(|min_opacity| {
+            let stroked = matches!(
+                resolve_paint(*node, "stroke", self._config.paint_server_policy, &self.stylesheet),
+                ResolvedPaint::Color(_)
+            );
+            let paint_opacity = if stroked {
+                "stroke-opacity"
+            } else {
+                "fill-opacity"
+            };
+            is_shape(node)
+                && effective_opacity(*node, paint_opacity, &self.stylesheet) < min_opacity
+        })
+    }
+
+    /// Whether a shape should be left out because it has no stroke and
+    /// [crate::ConversionConfig::trace_fill_as_outline] is disabled
+    fn is_unstroked(&self, node: &Node) -> bool {
+        !self._config.trace_fill_as_outline
+            && is_shape(node)
+            && !matches!(
+                resolve_paint(*node, "stroke", self._config.paint_server_policy, &self.stylesheet),
+                ResolvedPaint::Color(_)
+            )
+    }
+
+    /// Whether a shape has no length or area, so tracing it would only produce meaningless moves
+    fn is_degenerate(&self, node: &Node) -> bool {
+        match node.tag_name().name() {
+            RECT_TAG_NAME => ["width", "height"]
+                .into_iter()
+                .any(|attr| self.length_attr_to_user_units(node, attr) == Some(0.)),
+            POLYLINE_TAG_NAME | POLYGON_TAG_NAME => {
+                let mut points = node
+                    .attribute("points")
+                    .map(PointsParser::from)
+                    .into_iter()
+                    .flatten();
+                points
+                    .next()
+                    .is_some_and(|first| points.all(|point| point == first))
+            }
+            LINE_TAG_NAME => {
+                match ["x1", "y1", "x2", "y2"].map(|attr| self.length_attr_to_user_units(node, attr)) {
+                    [Some(x1), Some(y1), Some(x2), Some(y2)] => x1 == x2 && y1 == y2,
+                    _ => false,
+                }
+            }
+            _ => false,
+        }
+    }
+
+    /// Report an element that is skipped according to [crate::ConversionConfig::on_unsupported]
+    fn unsupported(&mut self, node: &Node, reason: &str) {
+        if !self.report_problems {
+            return;
+        }
+        let tag_name = node.tag_name().name();
+        match self.skipped.iter_mut().find(|(name, _)| name == tag_name) {
+            Some((_, count)) => *count += 1,
+            None => self.skipped.push((tag_name.to_string(), 1)),
+        }
+        match self._config.on_unsupported {
+            UnsupportedPolicy::Warn => warn!("Skipping {}: {reason}", node_name(node, &None)),
+            UnsupportedPolicy::Ignore => debug!("Skipping {}: {reason}", node_name(node, &None)),
+            UnsupportedPolicy::Error => {
+                self.error.get_or_insert_with(|| ConversionError::Unsupported {
+                    element: node_name(node, &None),
+                    reason: reason.to_string(),
+                });
+            }
+        }
+    }
+
+    /// Report an attribute that is ignored according to
+    /// [crate::ConversionConfig::on_unsupported]
+    fn invalid_attribute(&mut self, node: &Node, attribute: &str, message: impl ToString) {
+        if !self.report_problems {
+            return;
+        }
+        let element = node_name(node, &None);
+        let message = message.to_string();
+        match self._config.on_unsupported {
+            UnsupportedPolicy::Warn => warn!("Ignoring {attribute} of {element}: {message}"),
+            UnsupportedPolicy::Ignore => debug!("Ignoring {attribute} of {element}: {message}"),
+            UnsupportedPolicy::Error => {
+                self.error.get_or_insert_with(|| ConversionError::InvalidAttribute {
+                    element,
+                    attribute: attribute.to_string(),
+                    value: node.attribute(attribute).unwrap_or_default().to_string(),
+                    message,
+                });
+            }
+        }
+    }
+
+    /// Trace `nodes` into flattened outlines in the current user space, with `transform` applied
+    fn outlines<'b, 'input: 'b>(
+        &self,
+        nodes: impl Iterator<Item = Node<'b, 'input>>,
+        transform: Transform2D<f64>,
+    ) -> Vec<Vec<Point<f64>>> {
+        let mut visitor = ConversionVisitor {
+            viewport_dim_stack: self.viewport_dim_stack.clone(),
+            resolve_bounding_boxes: false,
+            stylesheet: self.stylesheet.clone(),
+            ..ConversionVisitor::new(
+                Terrarium::new(DpiConvertingTurtle {
+                    inner: SubpathCollectingTurtle::new(self.user_units_tolerance()),
+                    // Millimeters in, millimeters out: keeps the outlines in user units
+                    dpi: MM_PER_INCH,
+                }),
+                self._config,
+                // Clip paths keep their whole outline, and groups stay where they are placed
+                ConversionOptions {
+                    command_filter: None,
+                    per_group_origins: None,
+                    ..self.options.clone()
+                },
+            )
+        };
+        visitor.terrarium.push_transform(transform);
+        nodes.for_each(|node| visit_node(node, &mut visitor));
+        visitor.terrarium.turtle.end();
+        visitor
+            .terrarium
+            .turtle
+            .inner
+            .subpaths
+            .into_iter()
+            .map(|subpath| subpath.points.into_iter().map(Point::from).collect())
+            .collect()
+    }
+
+    /// Bounding box of an element in its own user space, without any clipping
+    fn object_bounding_box(&self, node: &Node) -> Box2D<f64> {
+        // The outlines are found without resolving bounding boxes, so undo the transform as placed then
+        let outlines = self.outlines(
+            std::iter::once(*node),
+            self.element_transform(node, false)
+                .inverse()
+                .unwrap_or_else(Transform2D::identity),
+        );
+        Box2D::from_points(outlines.iter().flatten())
+    }
+
+    /// The `transform` of an element, applied around its `transform-origin`
+    ///
+    /// Percentages in the origin resolve against the box selected by `transform-box`. Resolving
+    /// `fill-box` requires the bounding box of the element, so `view-box` is used instead unless
+    /// `resolve_fill_box` is set.
+    /// <https://www.w3.org/TR/css-transforms-1/#transform-origin-property>
+    fn element_transform(&self, node: &Node, resolve_fill_box: bool) -> Transform2D<f64> {
+        // Reported when the node is visited
+        let transform = transform_attribute(node).unwrap_or_else(|_| Transform2D::identity());
+        let Some(origin) = presentation_property(node, "transform-origin", &self.stylesheet) else {
+            return transform;
+        };
+
+        // https://www.w3.org/TR/css-transforms-1/#transform-box
+        let reference_box = match presentation_property(node, "transform-box", &self.stylesheet) {
+            Some("fill-box" | "content-box" | "stroke-box" | "border-box") if resolve_fill_box => {
+                self.object_bounding_box(node)
+            }
+            _ => self.view_box(node),
+        };
+
+        let mut values = origin.split_whitespace().collect::<Vec<_>>();
+        // Keywords may be given in either order, and a lone vertical keyword centers horizontally
+        match values.as_slice() {
+            [vertical @ ("top" | "bottom")] => values = vec!["center", vertical],
+            [first, second] if matches!(*first, "top" | "bottom") || matches!(*second, "left" | "right") => {
+                values.swap(0, 1)
+            }
+            _ => {}
+        }
+        let resolve = |value: Option<&&str>, hint| {
+            let size = match hint {
+                DimensionHint::Vertical => reference_box.height(),
+                _ => reference_box.width(),
+            };
+            match value.copied().unwrap_or("center") {
+                "left" | "top" => 0.,
+                "center" => size / 2.,
+                "right" | "bottom" => size,
+                length => match Length::from_str(length) {
+                    Ok(Length {
+                        number,
+                        unit: LengthUnit::Percent,
+                    }) => number / 100. * size,
+                    Ok(length) => self.length_to_user_units(length, hint),
+                    Err(err) => {
+                        warn!("Could not parse transform-origin {origin}: {err}");
+                        0.
+                    }
+                },
+            }
+        };
+        let origin = reference_box.min
+            + vector(
+                resolve(values.first(), DimensionHint::Horizontal),
+                resolve(values.get(1), DimensionHint::Vertical),
+            );
+
+        Transform2D::translation(-origin.x, -origin.y)
+            .then(&transform)
+            .then_translate(origin.to_vector())
+    }
+
+    /// Reference box of `transform-box: view-box`: the view box of the nearest viewport
+    fn view_box(&self, node: &Node) -> Box2D<f64> {
+        let origin = node
+            .ancestors()
+            .skip(1)
+            .find(|ancestor| ancestor.has_tag_name(SVG_TAG_NAME))
+            .and_then(|svg| svg.attribute("viewBox"))
+            .and_then(|view_box| ViewBox::from_str(view_box).ok())
+            .map_or(Point::origin(), |view_box| point(view_box.x, view_box.y));
+        let [width, height] = self.viewport_dim_stack.last().copied().unwrap_or([0., 0.]);
+        Box2D::new(origin, origin + vector(width, height))
+    }
+
+    /// Origin (mm) of a layer from [ConversionOptions::per_group_origins], if it has one
+    fn group_origin(&self, node: Node) -> Option<[f64; 2]> {
+        let origins = self.options.per_group_origins.as_ref()?;
+        is_layer(node)
+            .then(|| origins.get(layer_index(node)).copied())
+            .flatten()
+    }
+
+    /// [crate::ConversionConfig::tolerance] converted from millimeters to user units, i.e. the
+    /// units the turtle is given before [DpiConvertingTurtle] converts them back to millimeters
+    fn user_units_tolerance(&self) -> f64 {
+        self._config.tolerance / MM_PER_INCH * self._config.effective_dpi()
+    }
+
+    /// Tolerance in user units set on an element with [crate::ConversionConfig::tolerance_attribute]
+    fn element_tolerance(&self, node: &Node) -> Option<f64> {
+        let attribute = self._config.tolerance_attribute.as_deref()?;
+        node.attribute(attribute)?
+            .parse::<f64>()
+            .ok()
+            .filter(|tolerance| *tolerance > 0.)
+            .map(|tolerance| tolerance / MM_PER_INCH * self._config.effective_dpi())
+    }
+
+    /// Parameters of a group in [crate::ConversionConfig::layer_params], with the feedrate of
+    /// any element's [FEEDRATE_ATTRIBUTE] taking precedence
+    fn element_layer_params(&self, node: &Node) -> Option<LayerParams> {
+        let params = node
+            .has_tag_name(GROUP_TAG_NAME)
+            .then(|| node.attribute("id"))
+            .flatten()
+            .and_then(|id| self._config.layer_params.get(id).copied());
+        match feedrate_attribute(node) {
+            Some(Ok(feedrate)) => Some(LayerParams {
+                feedrate: Some(feedrate),
+                ..params.unwrap_or_default()
+            }),
+            _ => params,
+        }
+    }
+
+    /// Restrict drawing to the area of a clip path, until [Terrarium::pop_clip]
+    ///
+    /// <https://www.w3.org/TR/css-masking-1/#ClipPathElement>
+    fn push_clip_path(&mut self, node: &Node, clip_path: Node) {
+        let mut transform = transform_attribute(&clip_path).unwrap_or_else(|err| {
+            self.invalid_attribute(&clip_path, "transform", err);
+            Transform2D::identity()
+        });
+        if clip_path.attribute("clipPathUnits") == Some("objectBoundingBox") {
+            // Bounding box of the element in its own user space, without any clipping
+            let bounding_box = self.object_bounding_box(node);
+            transform = transform.then(
+                &Transform2D::scale(bounding_box.width(), bounding_box.height())
+                    .then_translate(bounding_box.min.to_vector()),
+            );
+        }
+        let rings = self.outlines(clip_path.children(), transform);
+        let tolerance = self.user_units_tolerance();
+        self.terrarium.push_clip(rings, tolerance);
+    }
+
+    /// Visit the children of a referenced element (e.g. a marker) as if they were placed here
+    fn visit_referenced_children(&mut self, referenced: Node, transform: Transform2D<f64>) {
+        self.terrarium.push_transform(transform);
+        referenced
+            .children()
+            .for_each(|child| visit_node(child, self));
+        self.terrarium.pop_transform();
+    }
+
+    /// Trace the markers of a shape at each of its vertices
+    ///
+    /// Marker orientation is not supported, so `orient` is always treated as `0`.
+    /// <https://www.w3.org/TR/SVG2/painting.html#Markers>
+    fn draw_markers(&mut self, node: &Node) {
+        let vertices = match node.tag_name().name() {
+            PATH_TAG_NAME => node
+                .attribute("d")
+                .map(|d| path_vertices(PathParser::from(d).filter_map(Result::ok)))
+                .unwrap_or_default(),
+            name @ (POLYLINE_TAG_NAME | POLYGON_TAG_NAME) => {
+                let mut vertices = node
+                    .attribute("points")
+                    .map(|points| PointsParser::from(points).map(|(x, y)| point(x, y)).collect::<Vec<_>>())
+                    .unwrap_or_default();
+                if name == POLYGON_TAG_NAME {
+                    vertices.extend(vertices.first().copied());
+                }
+                vertices
+            }
+            LINE_TAG_NAME => ["x1", "y1", "x2", "y2"]
+                .map(|attr| self.length_attr_to_user_units(node, attr).unwrap_or(0.))
+                .chunks(2)
+                .map(|p| point(p[0], p[1]))
+                .collect(),
+            _ => return,
+        };
+
+        let last = vertices.len().saturating_sub(1);
+        for (i, vertex) in vertices.into_iter().enumerate() {
+            let property = match i {
+                0 => "marker-start",
+                i if i == last => "marker-end",
+                _ => "marker-mid",
+            };
+            let Some(marker) = inherited_property(node, property, &self.stylesheet)
+                .and_then(|value| FuncIRI::from_str(value).ok())
+                .and_then(|FuncIRI(id)| {
+                    node.document()
+                        .descendants()
+                        .find(|n| n.has_tag_name(MARKER_TAG_NAME) && n.attribute("id") == Some(id))
+                })
+            else {
+                continue;
+            };
+            if self.marker_stack.contains(&marker.id()) {
+                warn!("Skipping {} in {}: it references itself", property, node_name(&marker, &None));
+                continue;
+            }
+
+            let scale = if marker.attribute("markerUnits") == Some("userSpaceOnUse") {
+                1.
+            } else {
+                inherited_property(node, "stroke-width", &self.stylesheet)
+                    .and_then(|width| svgtypes::Length::from_str(width).ok())
+                    .map(|width| self.length_to_user_units(width, DimensionHint::Other))
+                    .unwrap_or(1.)
+            };
+            let reference = ["refX", "refY"]
+                .map(|attr| self.length_attr_to_user_units(&marker, attr).unwrap_or(0.));
+            let transform = Transform2D::translation(-reference[0], -reference[1])
+                .then_scale(scale, scale)
+                .then_translate(vertex.to_vector());
+            self.marker_stack.push(marker.id());
+            self.visit_referenced_children(marker, transform);
+            self.marker_stack.pop();
+        }
+    }
+
+    /// Trace the element referenced by a `<use>`, fitting a `<symbol>` into the `width` and
+    /// `height` of the `<use>` through its `viewBox`
+    ///
+    /// <https://www.w3.org/TR/SVG2/struct.html#UseElement>
+    fn draw_use(&mut self, node: &Node) {
+        let Some(referenced) = node
+            .attribute("href")
+            .or_else(|| node.attribute((XLINK_NAMESPACE, "href")))
+            .and_then(|href| IRI::from_str(href).ok())
+            .and_then(|IRI(id)| {
+                node.document()
+                    .descendants()
+                    .find(|n| n.attribute("id") == Some(id))
+            })
+        else {
+            warn!("Could not find the element referenced by {}", node_name(node, &None));
+            return;
+        };
+        if self.use_stack.contains(&referenced.id()) || node.ancestors().any(|a| a == referenced) {
+            warn!("Skipping {}: it references itself", node_name(node, &None));
+            return;
+        }
+
+        let [x, y] = ["x", "y"].map(|attr| self.length_attr_to_user_units(node, attr).unwrap_or(0.));
+        self.use_stack.push(referenced.id());
+        if referenced.has_tag_name(SYMBOL_TAG_NAME) {
+            let view_box = referenced
+                .attribute("viewBox")
+                .and_then(|view_box| ViewBox::from_str(view_box).ok())
+                .filter(|view_box| view_box.w > 0. && view_box.h > 0.);
+            let transform = match view_box {
+                Some(view_box) => {
+                    let size = [("width", DimensionHint::Horizontal), ("height", DimensionHint::Vertical)]
+                        .map(|(attr, hint)| {
+                            self.length_attr_to_user_units(node, attr)
+                                .or_else(|| self.length_attr_to_user_units(&referenced, attr))
+                                .unwrap_or_else(|| {
+                                    self.length_to_user_units(Length::new(100., LengthUnit::Percent), hint)
+                                })
+                        });
+                    let preserve_aspect_ratio = referenced
+                        .attribute("preserveAspectRatio")
+                        .and_then(|attr| AspectRatio::from_str(attr).ok());
+                    get_viewport_transform(view_box, preserve_aspect_ratio, size, [Some(x), Some(y)])
+                }
+                None => Transform2D::translation(x, y),
+            };
+            self.visit_referenced_children(referenced, transform);
+        } else {
+            self.terrarium.push_transform(Transform2D::translation(x, y));
+            visit_node(referenced, self);
+            self.terrarium.pop_transform();
+        }
+        self.use_stack.pop();
+    }
+}
+
 impl<'a, T: Turtle> XmlVisitor for ConversionVisitor<'a, T> {
+    /// <https://www.w3.org/TR/SVG2/struct.html#ConditionalProcessing>
+    fn is_enabled(&self, node: Node) -> bool {
+        // No extensions are supported
+        if node.has_attribute("requiredExtensions") {
+            return false;
+        }
+        // Elements outside of any layer are drawn with the first one, and layers can still be
+        // referenced by <use> elements in other layers
+        if let (Some(layer), true) = (self.layer, self.use_stack.is_empty()) {
+            if node.parent_element() == Some(node.document().root_element()) {
+                let index = if is_layer(node) { layer_index(node) } else { 0 };
+                if index != layer {
+                    return false;
+                }
+            }
+        }
+        // requiredFeatures was removed in SVG 2 and always evaluates to true
+        match (
+            node.attribute("systemLanguage"),
+            self.options.system_language.as_deref(),
+        ) {
+            (Some(languages), Some(user_languages)) => {
+                languages.split(',').map(str::trim).any(|language| {
+                    user_languages.split(',').map(str::trim).any(|user_language| {
+                        // A language tag also matches its more specific variants, e.g. en and en-US
+                        user_language.eq_ignore_ascii_case(language)
+                            || user_language
+                                .get(..language.len())
+                                .is_some_and(|prefix| prefix.eq_ignore_ascii_case(language))
+                                && user_language[language.len()..].starts_with('-')
+                    })
+                })
+            }
+            _ => true,
+        }
+    }
+
     fn visit_enter(&mut self, node: Node) {
         use PathSegment::*;
 
-        if node.tag_name().name() == CLIP_PATH_TAG_NAME {
-            warn!("Clip paths are not supported: {:?}", node);
+        if let Err(err) = transform_attribute(&node) {
+            self.invalid_attribute(&node, "transform", err);
+        }
+        if let Some(Err(message)) = feedrate_attribute(&node) {
+            self.invalid_attribute(&node, FEEDRATE_ATTRIBUTE, message);
         }
 
-        // TODO: https://www.w3.org/TR/css-transforms-1/#transform-origin-property
-        if let Some(mut origin) = node.attribute("transform-origin").map(PointsParser::from) {
-            let _origin = origin.next();
-            warn!("transform-origin not supported yet");
+        if let Some(params) = self.element_layer_params(&node) {
+            let params = params.or(self.layer_params_stack.last().copied().unwrap_or_default());
+            self.layer_params_stack.push(params);
+            self.terrarium.turtle.layer_params(&params);
         }
 
-        let mut flattened_transform = if let Some(transform) = node.attribute("transform") {
-            // https://stackoverflow.com/questions/18582935/the-applying-order-of-svg-transforms
-            TransformListParser::from(transform)
-                .map(|token| token.expect("could not parse a transform in a list of transforms"))
-                .map(svg_transform_into_euclid_transform)
-                .fold(Transform2D::identity(), |acc, t| t.then(&acc))
-        } else {
-            Transform2D::identity()
-        };
+        if let Some(tolerance) = self.element_tolerance(&node) {
+            self.tolerance_stack.push(tolerance);
+            self.terrarium.turtle.tolerance(tolerance);
+        } else if let Some(value) = self
+            ._config
+            .tolerance_attribute
+            .as_deref()
+            .and_then(|attribute| node.attribute(attribute))
+        {
+            warn!("Ignoring invalid tolerance: {value}");
+        }
+
+        // Top-level groups are layers, numbered in order unless they name their tool
+        if is_layer(node) {
+            let tool = node
+                .attribute("data-tool")
+                .and_then(|tool| match tool.trim().parse::<u32>() {
+                    Ok(tool) => Some(tool),
+                    Err(err) => {
+                        warn!("Ignoring invalid data-tool {tool}: {err}");
+                        None
+                    }
+                })
+                .unwrap_or_else(|| layer_index(node) as u32 + 1);
+            self.terrarium.turtle.tool(tool);
+        }
+
+        let mut flattened_transform = self.element_transform(&node, self.resolve_bounding_boxes);
 
         // https://www.w3.org/TR/SVG/coords.html#EstablishingANewSVGViewport
         if node.has_tag_name(SVG_TAG_NAME) {
@@ -104,16 +746,22 @@ impl<'a, T: Turtle> XmlVisitor for ConversionVisitor<'a, T> {
             });
             let mut viewport_size =
                 ["width", "height"].map(|attr| self.length_attr_to_user_units(&node, attr));
+            // Nested svgs establish viewports within the outermost one, which alone is sized by
+            // the conversion options and placed on the machine
+            let is_outermost = self.viewport_dim_stack.is_empty();
 
-            let dimensions_override: [_; 2] = self
-                .options
-                .dimensions
-                .map(|l| l.map(|l| self.length_to_user_units(l, DimensionHint::Horizontal)));
-            for (original_dim, override_dim) in viewport_size
-                .iter_mut()
-                .zip(dimensions_override.into_iter())
-            {
-                *original_dim = override_dim.or(*original_dim);
+            if is_outermost {
+                let [width_override, height_override] = self.options.dimensions;
+                let dimensions_override = [
+                    width_override.map(|l| self.length_to_user_units(l, DimensionHint::Horizontal)),
+                    height_override.map(|l| self.length_to_user_units(l, DimensionHint::Vertical)),
+                ];
+                for (original_dim, override_dim) in viewport_size
+                    .iter_mut()
+                    .zip(dimensions_override)
+                {
+                    *original_dim = override_dim.or(*original_dim);
+                }
             }
 
             // https://www.w3.org/TR/SVG/coords.html#SizingSVGInCSS
@@ -153,7 +801,7 @@ impl<'a, T: Turtle> XmlVisitor for ConversionVisitor<'a, T> {
 
             if let Some(view_box) = view_box {
                 // If we will apply our own alignment (non-default or trim specified), neutralize built-in centering by forcing XMinYMin.
-                let pa = if self.options.trim {
+                let pa = if self.options.trim && is_outermost {
                     Some(svgtypes::AspectRatio { defer:false, align: svgtypes::Align::XMinYMin, slice:false })
                 } else { preserve_aspect_ratio };
                 let viewport_transform = get_viewport_transform(
@@ -163,27 +811,86 @@ impl<'a, T: Turtle> XmlVisitor for ConversionVisitor<'a, T> {
                     viewport_pos,
                 );
                 flattened_transform = flattened_transform.then(&viewport_transform);
+            } else if !is_outermost {
+                let [x, y] = viewport_pos.map(|pos| pos.unwrap_or(0.));
+                flattened_transform = flattened_transform.then(&Transform2D::translation(x, y));
             }
             // Part 2 of converting from SVG to GCode coordinates
-            flattened_transform = flattened_transform.then(&Transform2D::translation(
-                0.,
-                -(viewport_size[1] + viewport_pos[1].unwrap_or(0.)),
-            ));
+            if is_outermost {
+                flattened_transform = flattened_transform.then(&Transform2D::translation(
+                    0.,
+                    -(viewport_size[1] + viewport_pos[1].unwrap_or(0.)),
+                ));
+            }
         } else if node.has_attribute("viewBox") {
             warn!("View box is not supported on a {}", node.tag_name().name());
         }
 
+        if let Some([x, y]) = self.group_origin(node) {
+            // The origin is in output coordinates, so the move goes after everything applied so far
+            let current = self.terrarium.current_transform();
+            let points = self
+                .outlines(std::iter::once(node), current)
+                .into_iter()
+                .flatten()
+                .collect::<Vec<_>>();
+            // Nothing is drawn from an empty group, so there is nothing to move
+            if let (Some(inverse), false) = (current.inverse(), points.is_empty()) {
+                let placed = Box2D::from_points(points);
+                let user_units = |mm: f64| mm / MM_PER_INCH * self._config.effective_dpi();
+                flattened_transform = flattened_transform
+                    .then(&current)
+                    .then_translate(point(user_units(x), user_units(y)) - placed.min)
+                    .then(&inverse);
+            }
+        }
+
         self.terrarium.push_transform(flattened_transform);
 
+        if self.tracked_transform.is_none()
+            && self.tracked_id.is_some()
+            && node.attribute("id") == self.tracked_id.as_deref()
+        {
+            self.tracked_transform = Some(self.terrarium.current_transform());
+        }
+
+        if self.resolve_bounding_boxes {
+            if let Some(clip_path) = referenced_clip_path(&node, &self.stylesheet) {
+                self.push_clip_path(&node, clip_path);
+            } else if let Some(value) =
+                presentation_property(&node, "clip-path", &self.stylesheet).filter(|value| *value != "none")
+            {
+                warn!("Could not find clip path {value}");
+            }
+        }
+
         match node.tag_name().name() {
+            _ if self.is_painted_by_skipped_server(&node) => {
+                debug!("Skipping {} painted by a gradient or pattern", node_name(&node, &None));
+            }
+            _ if self.is_below_min_opacity(&node) => {
+                debug!("Skipping {} below minimum opacity", node_name(&node, &None));
+            }
+            _ if self.is_unstroked(&node) => {
+                debug!("Skipping {} without a stroke", node_name(&node, &None));
+            }
+            _ if self.is_degenerate(&node) => self.unsupported(&node, "degenerate geometry"),
             PATH_TAG_NAME => {
                 if let Some(d) = node.attribute("d") {
                     self.comment(&node);
-                    apply_path(
-                        &mut self.terrarium,
-                        PathParser::from(d)
-                            .map(|segment| segment.expect("could not parse path segment")),
-                    );
+                    // Like browsers, draw the path up to the error
+                    let mut segments = vec![];
+                    for segment in PathParser::from(d) {
+                        match segment {
+                            Ok(segment) => segments.push(segment),
+                            Err(err) => {
+                                self.error
+                                    .get_or_insert_with(|| ConversionError::path_parse(&node, d, err));
+                                break;
+                            }
+                        }
+                    }
+                    apply_path(&mut self.terrarium, segments, self.options.command_filter);
                 } else {
                     warn!("There is a path node containing no actual path: {node:?}");
                 }
@@ -208,7 +915,7 @@ impl<'a, T: Turtle> XmlVisitor for ConversionVisitor<'a, T> {
                             },
                         );
 
-                    apply_path(&mut self.terrarium, path);
+                    apply_path(&mut self.terrarium, path, self.options.command_filter);
                 } else {
                     warn!("There is a {name} node containing no actual path: {node:?}");
                 }
@@ -218,12 +925,32 @@ impl<'a, T: Turtle> XmlVisitor for ConversionVisitor<'a, T> {
                 let y = self.length_attr_to_user_units(&node, "y").unwrap_or(0.);
                 let width = self.length_attr_to_user_units(&node, "width");
                 let height = self.length_attr_to_user_units(&node, "height");
-                let rx = self.length_attr_to_user_units(&node, "rx").unwrap_or(0.);
-                let ry = self.length_attr_to_user_units(&node, "ry").unwrap_or(0.);
-                let has_radius = rx > 0. && ry > 0.;
+                // Negative radii are invalid and treated like `auto`
+                let [rx, ry] = ["rx", "ry"].map(|attr| {
+                    self.length_attr_to_user_units(&node, attr)
+                        .filter(|r| *r >= 0.)
+                });
 
                 match (width, height) {
                     (Some(width), Some(height)) => {
+                        // https://www.w3.org/TR/SVG2/geometry.html#RxProperty
+                        let (rx, ry) = match (rx, ry) {
+                            (Some(rx), Some(ry)) => (rx, ry),
+                            (Some(r), None) | (None, Some(r)) => (r, r),
+                            (None, None) => (0., 0.),
+                        };
+                        let rx = rx.min(width / 2.);
+                        let ry = ry.min(height / 2.);
+                        // Corners rounded by less than the tolerance can't be told from sharp
+                        // ones, and would only give arcs too small to cut
+                        let tolerance = self
+                            .tolerance_stack
+                            .last()
+                            .copied()
+                            .unwrap_or_else(|| self.user_units_tolerance());
+                        let has_radius = rx > tolerance && ry > tolerance;
+                        let (rx, ry) = if has_radius { (rx, ry) } else { (0., 0.) };
+
                         self.comment(&node);
                         apply_path(
                             &mut self.terrarium,
@@ -293,6 +1020,7 @@ impl<'a, T: Turtle> XmlVisitor for ConversionVisitor<'a, T> {
                             ]
                             .into_iter()
                             .filter(|p| has_radius || !matches!(p, EllipticalArc { .. })),
+                            self.options.command_filter,
                         )
                     }
                     _other => {
@@ -330,6 +1058,7 @@ impl<'a, T: Turtle> XmlVisitor for ConversionVisitor<'a, T> {
                             ),
                         )
                         .chain(std::iter::once(ClosePath { abs: true })),
+                        self.options.command_filter,
                     );
                 } else {
                     warn!("Invalid {} node: {node:?}", node.tag_name().name());
@@ -357,6 +1086,7 @@ impl<'a, T: Turtle> XmlVisitor for ConversionVisitor<'a, T> {
                                     y: y2,
                                 },
                             ],
+                            self.options.command_filter,
                         );
                     }
                     _other => {
@@ -364,22 +1094,50 @@ impl<'a, T: Turtle> XmlVisitor for ConversionVisitor<'a, T> {
                     }
                 }
             }
-            USE_TAG_NAME => {
-                warn!("Unsupported node: {node:?}");
+            #[cfg(feature = "text")]
+            TEXT_TAG_NAME => {
+                let path = self.text_path(&node);
+                self.comment(&node);
+                apply_path(&mut self.terrarium, path, self.options.command_filter);
             }
+            USE_TAG_NAME => self.draw_use(&node),
             // No-op tags
-            SVG_TAG_NAME | GROUP_TAG_NAME => {}
+            SVG_TAG_NAME | GROUP_TAG_NAME | ANCHOR_TAG_NAME => {}
+            name if UNSUPPORTED_TAG_NAMES.contains(&name)
+                || (!cfg!(feature = "text") && name == TEXT_TAG_NAME) =>
+            {
+                self.unsupported(&node, "element is not supported")
+            }
             _ => {
                 debug!("Unknown node: {}", node.tag_name().name());
             }
         }
 
+        if self._config.honor_markers {
+            self.draw_markers(&node);
+        }
+
         self.name_stack.push(node_name(&node,&self._config.extra_attribute_name));
     }
 
     fn visit_exit(&mut self, node: Node) {
+        if self.resolve_bounding_boxes && referenced_clip_path(&node, &self.stylesheet).is_some() {
+            self.terrarium.pop_clip();
+        }
         self.terrarium.pop_transform();
         self.name_stack.pop();
+        if self.element_tolerance(&node).is_some() {
+            self.tolerance_stack.pop();
+            let tolerance = self.tolerance_stack.last().copied();
+            self.terrarium
+                .turtle
+                .tolerance(tolerance.unwrap_or_else(|| self.user_units_tolerance()));
+        }
+        if self.element_layer_params(&node).is_some() {
+            self.layer_params_stack.pop();
+            let params = self.layer_params_stack.last().copied().unwrap_or_default();
+            self.terrarium.turtle.layer_params(&params);
+        }
         if node.tag_name().name() == SVG_TAG_NAME {
             self.viewport_dim_stack.pop();
         }
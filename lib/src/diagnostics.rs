@@ -0,0 +1,12 @@
+//! Macros for reporting problems found during conversion
+//!
+//! With the `tracing` feature, these are [tracing](https://docs.rs/tracing) events inside a
+//! debug level `element` span for each SVG element being converted, carrying its tag name and
+//! `id`. Without the feature they are [log] records. With it they are only also logged as long
+//! as no tracing subscriber has been set in the process: once one has, even a scoped one that
+//! is no longer in use, tracing stops falling back to [log].
+
+#[cfg(not(feature = "tracing"))]
+pub(crate) use log::{debug, error, warn};
+#[cfg(feature = "tracing")]
+pub(crate) use tracing::{debug, error, warn};
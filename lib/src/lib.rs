@@ -1,7 +1,10 @@
 /// Approximate [Bézier curves](https://en.wikipedia.org/wiki/B%C3%A9zier_curve) with [Circular arcs](https://en.wikipedia.org/wiki/Circular_arc)
 mod arc;
+#[cfg(feature = "cache")]
+mod cache;
 /// Converts an SVG to an internal representation
 mod converter;
+mod diagnostics;
 /// Emulates the state of an arbitrary machine that can run G-Code
 mod machine;
 /// Operations that are easier to implement while/after G-Code is generated, or would
@@ -11,10 +14,18 @@ mod postprocess;
 /// This concept is referred to as [Turtle graphics](https://en.wikipedia.org/wiki/Turtle_graphics).
 mod turtle;
 
-pub use converter::{svg2program, ConversionConfig, ConversionOptions, HorizontalAlign, VerticalAlign};
-pub use machine::{Machine, MachineConfig, SupportedFunctionality};
-pub use postprocess::PostprocessConfig;
-pub use turtle::Turtle;
+#[cfg(feature = "cache")]
+pub use cache::ConversionCache;
+#[cfg(feature = "serde")]
+pub use converter::svg2paths_json;
+pub use converter::{
+    svg2paths, svg2program, svg2program_multi, svg2program_per_layer, svg2program_with_post_processor, svg2program_with_progress,
+    resolve_element_transform, svg_bounding_box, svg_physical_size, try_svg2program, parse_dimension, CommandFilter, ConversionConfig, ConversionError, ConversionOptions,
+    FeedrateUnits, HorizontalAlign, LayerParams, PaintServerPolicy, PerLayerMode, PocketConfig, ToleranceMode, UnsupportedPolicy, VerticalAlign,
+};
+pub use machine::{ArcDistanceMode, ArcFeedConfig, ArcFormat, EndCommand, Machine, MachineConfig, MultiPassConfig, PathMode, PrimingConfig, SupportedFunctionality, ZAxisConfig};
+pub use postprocess::{tokens_to_gcode, CommentStyle, PostprocessConfig, RoundtripError, TokenPostProcessor};
+pub use turtle::{ElementInfo, LineCap, LineJoin, SubpathInfo, Turtle};
 
 /// A cross-platform type used to store all configuration types.
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
@@ -102,10 +113,163 @@ mod test {
     use roxmltree::ParsingOptions;
     use svgtypes::{Length, LengthUnit};
 
-    /// The values change between debug and release builds for circular interpolation,
-    /// so only check within a rough tolerance
+    /// Coordinates are only checked within a rough tolerance, so that fixtures don't need to be
+    /// regenerated for floating point noise in the last digits
     const TOLERANCE: f64 = 1E-10;
 
+    #[cfg(not(feature = "tracing"))]
+    thread_local! {
+        static WARNINGS: std::cell::RefCell<Vec<String>> = const { std::cell::RefCell::new(vec![]) };
+    }
+
+    /// Records warnings logged on the current thread, so tests running in parallel don't interfere
+    #[cfg(not(feature = "tracing"))]
+    struct WarningCapture;
+
+    #[cfg(not(feature = "tracing"))]
+    impl log::Log for WarningCapture {
+        fn enabled(&self, metadata: &log::Metadata) -> bool {
+            metadata.level() <= log::Level::Warn
+        }
+
+        fn log(&self, record: &log::Record) {
+            if self.enabled(record.metadata()) {
+                WARNINGS.with(|warnings| warnings.borrow_mut().push(record.args().to_string()));
+            }
+        }
+
+        fn flush(&self) {}
+    }
+
+    /// Warnings logged while running `f`
+    #[cfg(not(feature = "tracing"))]
+    fn capture_warnings(f: impl FnOnce()) -> Vec<String> {
+        static INIT: std::sync::Once = std::sync::Once::new();
+        INIT.call_once(|| {
+            log::set_logger(&WarningCapture).unwrap();
+            log::set_max_level(log::LevelFilter::Warn);
+        });
+        WARNINGS.with(|warnings| warnings.borrow_mut().clear());
+        f();
+        WARNINGS.with(|warnings| warnings.take())
+    }
+
+    /// An event and the fields of the spans it happened in, innermost last
+    #[cfg(feature = "tracing")]
+    struct TracedEvent {
+        level: tracing::Level,
+        message: String,
+        context: Vec<String>,
+    }
+
+    /// Collects `name=value` pairs of fields
+    #[cfg(feature = "tracing")]
+    struct Fields(Vec<String>);
+
+    #[cfg(feature = "tracing")]
+    impl tracing::field::Visit for Fields {
+        fn record_debug(&mut self, field: &tracing::field::Field, value: &dyn std::fmt::Debug) {
+            self.0.push(format!("{}={value:?}", field.name()));
+        }
+
+        fn record_str(&mut self, field: &tracing::field::Field, value: &str) {
+            self.0.push(format!("{}={value}", field.name()));
+        }
+    }
+
+    /// Records each event along with the fields of the spans it happened in
+    ///
+    /// Set as the default subscriber of the current thread only, so tests running in parallel
+    /// don't interfere. Once a subscriber has been set, tracing no longer falls back to `log`.
+    #[cfg(feature = "tracing")]
+    #[derive(Default)]
+    struct TracingCapture {
+        next_id: std::sync::atomic::AtomicU64,
+        spans: std::sync::Mutex<Vec<(tracing::span::Id, Vec<String>)>>,
+        entered: std::sync::Mutex<Vec<tracing::span::Id>>,
+        events: std::sync::Arc<std::sync::Mutex<Vec<TracedEvent>>>,
+    }
+
+    #[cfg(feature = "tracing")]
+    impl tracing::Subscriber for TracingCapture {
+        fn enabled(&self, _metadata: &tracing::Metadata<'_>) -> bool {
+            true
+        }
+
+        fn new_span(&self, span: &tracing::span::Attributes<'_>) -> tracing::span::Id {
+            let id = self.next_id.fetch_add(1, std::sync::atomic::Ordering::Relaxed) + 1;
+            let id = tracing::span::Id::from_u64(id);
+            let mut fields = Fields(vec![]);
+            span.record(&mut fields);
+            self.spans.lock().unwrap().push((id.clone(), fields.0));
+            id
+        }
+
+        fn record(&self, _span: &tracing::span::Id, _values: &tracing::span::Record<'_>) {}
+
+        fn record_follows_from(&self, _span: &tracing::span::Id, _follows: &tracing::span::Id) {}
+
+        fn event(&self, event: &tracing::Event<'_>) {
+            let mut fields = Fields(vec![]);
+            event.record(&mut fields);
+            let spans = self.spans.lock().unwrap();
+            let context = self
+                .entered
+                .lock()
+                .unwrap()
+                .iter()
+                .flat_map(|entered| {
+                    spans
+                        .iter()
+                        .filter(move |(id, _)| id == entered)
+                        .flat_map(|(_, fields)| fields.clone())
+                })
+                .collect();
+            let message = fields
+                .0
+                .iter()
+                .find_map(|field| field.strip_prefix("message="))
+                .unwrap_or_default()
+                .to_string();
+            self.events.lock().unwrap().push(TracedEvent {
+                level: *event.metadata().level(),
+                message,
+                context,
+            });
+        }
+
+        fn enter(&self, span: &tracing::span::Id) {
+            self.entered.lock().unwrap().push(span.clone());
+        }
+
+        fn exit(&self, span: &tracing::span::Id) {
+            let mut entered = self.entered.lock().unwrap();
+            if let Some(position) = entered.iter().rposition(|entered| entered == span) {
+                entered.remove(position);
+            }
+        }
+    }
+
+    /// Events traced while running `f`
+    #[cfg(feature = "tracing")]
+    fn capture_events(f: impl FnOnce()) -> Vec<TracedEvent> {
+        let capture = TracingCapture::default();
+        let events = capture.events.clone();
+        tracing::subscriber::with_default(capture, f);
+        let mut events = events.lock().unwrap();
+        std::mem::take(&mut *events)
+    }
+
+    /// Warnings traced while running `f`
+    #[cfg(feature = "tracing")]
+    fn capture_warnings(f: impl FnOnce()) -> Vec<String> {
+        capture_events(f)
+            .into_iter()
+            .filter(|event| event.level <= tracing::Level::WARN)
+            .map(|event| event.message)
+            .collect()
+    }
+
     fn get_actual(
         input: &str,
         circular_interpolation: bool,
@@ -122,173 +286,3117 @@ mod test {
         )
         .unwrap();
 
-        let machine = Machine::new(
-            SupportedFunctionality {
-                circular_interpolation,
-            },
-            None,
-            None,
-            None,
-            None,
-            None,
+        let machine = Machine::new(
+            SupportedFunctionality {
+                circular_interpolation,
+            },
+            None,
+            None,
+            None,
+            None,
+            None,
+        );
+        converter::svg2program(&document, &config, options, machine)
+    }
+
+    /// Convert `svg` into g-code formatted with the default [FormatOptions], on a machine with
+    /// `machine_config` and its sequences
+    fn to_gcode(
+        svg: &str,
+        config: &ConversionConfig,
+        options: ConversionOptions,
+        machine_config: &MachineConfig,
+    ) -> String {
+        fn snippet(sequence: &Option<String>) -> Option<g_code::parse::ast::Snippet<'_>> {
+            sequence
+                .as_deref()
+                .map(|sequence| g_code::parse::snippet_parser(sequence).unwrap())
+        }
+
+        let document = roxmltree::Document::parse(svg).unwrap();
+        let machine = Machine::new(
+            machine_config.supported_functionality.clone(),
+            snippet(&machine_config.tool_on_sequence),
+            snippet(&machine_config.tool_off_sequence),
+            snippet(&machine_config.begin_sequence),
+            snippet(&machine_config.end_sequence),
+            snippet(&machine_config.between_layers_sequence),
+        )
+        .with_config(machine_config)
+        .with_accessory_sequences(
+            snippet(&machine_config.accessory_on_sequence),
+            snippet(&machine_config.accessory_off_sequence),
+        );
+        let program = svg2program(&document, config, options, machine);
+        let mut code = String::new();
+        g_code::emit::format_gcode_fmt(program.iter(), FormatOptions::default(), &mut code)
+            .unwrap();
+        code
+    }
+
+    fn assert_close(left: Vec<Token<'_>>, right: Vec<Token<'_>>) {
+        let mut code = String::new();
+        g_code::emit::format_gcode_fmt(left.iter(), FormatOptions::default(), &mut code).unwrap();
+        assert_eq!(left.len(), right.len(), "{code}");
+        for (i, pair) in left.into_iter().zip(right.into_iter()).enumerate() {
+            match pair {
+                (Token::Field(l), Token::Field(r)) => {
+                    assert_eq!(l.letters, r.letters);
+                    if let (Some(l_value), Some(r_value)) = (l.value.as_f64(), r.value.as_f64()) {
+                        assert!(
+                            (l_value - r_value).abs() < TOLERANCE,
+                            "Values differ significantly at {i}: {l} vs {r} ({})",
+                            (l_value - r_value).abs()
+                        );
+                    } else {
+                        assert_eq!(l, r);
+                    }
+                }
+                (l, r) => {
+                    assert_eq!(l, r, "Differs at {i}");
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn square_produces_expected_gcode() {
+        let expected = g_code::parse::file_parser(include_str!("../tests/square.gcode"))
+            .unwrap()
+            .iter_emit_tokens()
+            .collect::<Vec<_>>();
+        let actual = get_actual(include_str!("../tests/square.svg"), false, [None; 2]);
+
+        assert_close(actual, expected);
+    }
+
+    #[test]
+    fn tokens_to_gcode_matches_manual_formatting() {
+        let program = get_actual(include_str!("../tests/square.svg"), false, [None; 2]);
+        let config = PostprocessConfig {
+            checksums: true,
+            line_numbers: true,
+            line_number_start: 100,
+            line_number_increment: 10,
+            newline_before_comment: true,
+            ..Default::default()
+        };
+
+        let mut code = String::new();
+        g_code::emit::format_gcode_fmt(program.iter(), (&config).into(), &mut code).unwrap();
+        let manual = config.number_lines(&code);
+        assert_eq!(tokens_to_gcode(&program, &config).unwrap(), manual);
+        assert!(manual.starts_with("N100 "), "{manual}");
+    }
+
+    #[test]
+    fn square_dimension_override_produces_expected_gcode() {
+        let side_length = Length {
+            number: 10.,
+            unit: LengthUnit::Mm,
+        };
+
+        let expected = g_code::parse::file_parser(include_str!("../tests/square.gcode"))
+            .unwrap()
+            .iter_emit_tokens()
+            .collect::<Vec<_>>();
+
+        for square in [
+            include_str!("../tests/square.svg"),
+            include_str!("../tests/square_dimensionless.svg"),
+        ] {
+            assert_close(
+                get_actual(square, false, [Some(side_length); 2]),
+                expected.clone(),
+            );
+            assert_close(
+                get_actual(square, false, [Some(side_length), None]),
+                expected.clone(),
+            );
+            assert_close(
+                get_actual(square, false, [None, Some(side_length)]),
+                expected.clone(),
+            );
+        }
+    }
+
+    #[test]
+    fn square_transformed_produces_expected_gcode() {
+        let square_transformed = include_str!("../tests/square_transformed.svg");
+        let expected =
+            g_code::parse::file_parser(include_str!("../tests/square_transformed.gcode"))
+                .unwrap()
+                .iter_emit_tokens()
+                .collect::<Vec<_>>();
+        let actual = get_actual(square_transformed, false, [None; 2]);
+
+        assert_close(actual, expected)
+    }
+
+    #[test]
+    fn square_transformed_nested_produces_expected_gcode() {
+        let square_transformed = include_str!("../tests/square_transformed_nested.svg");
+        let expected =
+            g_code::parse::file_parser(include_str!("../tests/square_transformed_nested.gcode"))
+                .unwrap()
+                .iter_emit_tokens()
+                .collect::<Vec<_>>();
+        let actual = get_actual(square_transformed, false, [None; 2]);
+
+        assert_close(actual, expected)
+    }
+
+    #[test]
+    fn square_viewport_produces_expected_gcode() {
+        let square_viewport = include_str!("../tests/square_viewport.svg");
+        let expected = g_code::parse::file_parser(include_str!("../tests/square_viewport.gcode"))
+            .unwrap()
+            .iter_emit_tokens()
+            .collect::<Vec<_>>();
+        let actual = get_actual(square_viewport, false, [None; 2]);
+
+        assert_close(actual, expected);
+    }
+
+    #[test]
+    fn circular_interpolation_produces_expected_gcode() {
+        let circular_interpolation = include_str!("../tests/circular_interpolation.svg");
+        let expected =
+            g_code::parse::file_parser(include_str!("../tests/circular_interpolation.gcode"))
+                .unwrap()
+                .iter_emit_tokens()
+                .collect::<Vec<_>>();
+        let actual = get_actual(circular_interpolation, true, [None; 2]);
+
+        assert_close(actual, expected)
+    }
+
+    #[test]
+    fn svg_with_smooth_curves_produces_expected_gcode() {
+        let svg = include_str!("../tests/smooth_curves.svg");
+
+        let expected = g_code::parse::file_parser(include_str!("../tests/smooth_curves.gcode"))
+            .unwrap()
+            .iter_emit_tokens()
+            .collect::<Vec<_>>();
+
+        // A single fixture for every build profile, which CI checks by also testing a release
+        // build: optimizations never reorder or fuse floating point operations, so arcs are fit
+        // the same with and without them
+        let expected_circular_interpolation = g_code::parse::file_parser(include_str!(
+            "../tests/smooth_curves_circular_interpolation.gcode"
+        ))
+        .unwrap()
+        .iter_emit_tokens()
+        .collect::<Vec<_>>();
+        assert_close(get_actual(svg, false, [None; 2]), expected);
+
+        assert_close(
+            get_actual(svg, true, [None; 2]),
+            expected_circular_interpolation,
+        );
+    }
+
+    #[test]
+    fn shapes_produces_expected_gcode() {
+        let shapes = include_str!("../tests/shapes.svg");
+        let expected = g_code::parse::file_parser(include_str!("../tests/shapes.gcode"))
+            .unwrap()
+            .iter_emit_tokens()
+            .collect::<Vec<_>>();
+        let actual = get_actual(shapes, false, [None; 2]);
+
+        assert_close(actual, expected)
+    }
+
+    #[test]
+    fn negative_view_box_places_origin_at_center() {
+        let svg = r#"<svg xmlns="http://www.w3.org/2000/svg" width="10mm" height="10mm" viewBox="-5 -5 10 10">
+            <line x1="0" y1="0" x2="50%" y2="0"/>
+        </svg>"#;
+        let config = ConversionConfig {
+            origin: [None, None],
+            ..Default::default()
+        };
+        let code = to_gcode(
+            svg,
+            &config,
+            ConversionOptions::default(),
+            &MachineConfig::default(),
+        );
+
+        let coordinates = code
+            .lines()
+            .filter(|line| line.starts_with('G'))
+            .flat_map(|line| line.split(' '))
+            .filter_map(|word| word.strip_prefix(['X', 'Y']))
+            .map(|value| value.parse::<f64>().unwrap())
+            .collect::<Vec<_>>();
+        // (0,0) is the center of the viewport, and 50% resolves against the view box width
+        for (actual, expected) in coordinates.iter().zip([5., 5., 10., 5.]) {
+            assert!((actual - expected).abs() < TOLERANCE, "{coordinates:?}");
+        }
+        assert_eq!(coordinates.len(), 4);
+    }
+
+    #[test]
+    fn rounded_rect_corners_are_arcs() {
+        let svg = r#"<svg xmlns="http://www.w3.org/2000/svg" width="20mm" height="20mm" viewBox="0 0 20 20">
+            <rect x="5" y="5" width="10" height="6" rx="2"/>
+        </svg>"#;
+        let machine_config = MachineConfig {
+            supported_functionality: SupportedFunctionality {
+                circular_interpolation: true,
+            },
+            ..Default::default()
+        };
+        let code = to_gcode(
+            svg,
+            &ConversionConfig::default(),
+            ConversionOptions::default(),
+            &machine_config,
+        );
+        let arcs = code
+            .lines()
+            .filter(|line| line.starts_with("G2 ") || line.starts_with("G3 "))
+            .collect::<Vec<_>>();
+        // ry defaults to rx, giving one quarter circle per corner
+        assert_eq!(arcs.len(), 4, "{code}");
+        assert!(arcs.iter().all(|arc| arc.contains("I") && arc.contains("J")));
+        assert_eq!(
+            code.lines().filter(|line| line.starts_with("G1 ")).count(),
+            4,
+            "{code}"
+        );
+    }
+
+    #[test]
+    fn rect_radius_below_tolerance_gives_sharp_corners() {
+        let svg = r#"<svg xmlns="http://www.w3.org/2000/svg" width="20mm" height="20mm" viewBox="0 0 20 20">
+            <rect x="5" y="5" width="10" height="6" rx="0.0001"/>
+        </svg>"#;
+        let machine_config = MachineConfig {
+            supported_functionality: SupportedFunctionality {
+                circular_interpolation: true,
+            },
+            ..Default::default()
+        };
+        let code = to_gcode(
+            svg,
+            &ConversionConfig::default(),
+            ConversionOptions::default(),
+            &machine_config,
+        );
+        assert!(
+            !code.lines().any(|line| line.starts_with("G2 ") || line.starts_with("G3 ")),
+            "{code}"
+        );
+        // One cut per side, from corner to corner
+        let ends = code
+            .lines()
+            .filter(|line| line.starts_with("G1 ") && line.contains('X'))
+            .map(|line| {
+                ['X', 'Y'].map(|axis| {
+                    let word = line.split_whitespace().find(|word| word.starts_with(axis));
+                    let value = word.unwrap()[1..].parse::<f64>().unwrap();
+                    // Round away floating point noise
+                    (value * 1e6).round() / 1e6
+                })
+            })
+            .collect::<Vec<_>>();
+        assert!(code.contains("G0 X5 Y15"), "{code}");
+        assert_eq!(ends[..4], [[15., 15.], [15., 9.], [5., 9.], [5., 15.]], "{code}");
+        // Closing may only add a cut too short to tell from the last corner
+        assert!(ends[4..].iter().all(|end| *end == [5., 15.]), "{code}");
+    }
+
+    #[test]
+    fn marker_end_is_traced_at_line_endpoint() {
+        let svg = r##"<svg xmlns="http://www.w3.org/2000/svg" width="20mm" height="20mm" viewBox="0 0 20 20">
+            <defs>
+                <marker id="dot" markerUnits="userSpaceOnUse">
+                    <circle r="1"/>
+                </marker>
+            </defs>
+            <line x1="0" y1="10" x2="10" y2="10" marker-end="url(#dot)"/>
+        </svg>"##;
+        let rapids = |honor_markers| {
+            let config = ConversionConfig {
+                origin: [None, None],
+                honor_markers,
+                ..Default::default()
+            };
+            to_gcode(
+                svg,
+                &config,
+                ConversionOptions::default(),
+                &MachineConfig::default(),
+            )
+            .lines()
+            .filter_map(|line| line.strip_prefix("G0 "))
+            .map(|rapid| {
+                let mut words = rapid.split(' ');
+                [words.next(), words.next()].map(|word| word.unwrap()[1..].parse::<f64>().unwrap())
+            })
+            .collect::<Vec<_>>()
+        };
+
+        assert_eq!(rapids(false).len(), 1);
+        let with_markers = rapids(true);
+        assert_eq!(with_markers.len(), 2);
+        // Circles start at their rightmost point
+        assert!((with_markers[1][0] - 11.).abs() < TOLERANCE, "{with_markers:?}");
+        assert!((with_markers[1][1] - 10.).abs() < TOLERANCE, "{with_markers:?}");
+    }
+
+    #[test]
+    fn self_referencing_marker_is_traced_once() {
+        let svg = r##"<svg xmlns="http://www.w3.org/2000/svg" width="20mm" height="20mm" viewBox="0 0 20 20" style="marker-start:url(#tick)">
+            <defs>
+                <marker id="tick" markerUnits="userSpaceOnUse">
+                    <path d="M 0 -1 L 0 1" marker-end="url(#tick)"/>
+                </marker>
+            </defs>
+            <line x1="0" y1="10" x2="10" y2="10" marker-end="url(#tick)"/>
+        </svg>"##;
+        let config = ConversionConfig {
+            origin: [None, None],
+            honor_markers: true,
+            ..Default::default()
+        };
+        let rapids = to_gcode(
+            svg,
+            &config,
+            ConversionOptions::default(),
+            &MachineConfig::default(),
+        )
+        .lines()
+        .filter_map(|line| line.strip_prefix("G0 "))
+        .map(|rapid| {
+            let mut words = rapid.split(' ');
+            [words.next(), words.next()].map(|word| word.unwrap()[1..].parse::<f64>().unwrap())
+        })
+        .collect::<Vec<_>>();
+
+        // The line, then a tick at each of its ends, whose own markers are the tick itself
+        assert_eq!(rapids.len(), 3, "{rapids:?}");
+        for (rapid, expected) in rapids.iter().zip([[0., 10.], [0., 11.], [10., 11.]]) {
+            assert!((rapid[0] - expected[0]).abs() < TOLERANCE, "{rapids:?}");
+            assert!((rapid[1] - expected[1]).abs() < TOLERANCE, "{rapids:?}");
+        }
+    }
+
+    #[test]
+    fn marker_scale_reads_stroke_width_units() {
+        let svg = r##"<svg xmlns="http://www.w3.org/2000/svg" width="20mm" height="20mm" viewBox="0 0 20 20">
+            <defs>
+                <marker id="dot">
+                    <circle r="1"/>
+                </marker>
+            </defs>
+            <line x1="0" y1="10" x2="10" y2="10" stroke-width="2mm" marker-end="url(#dot)"/>
+        </svg>"##;
+        let config = ConversionConfig {
+            origin: [None, None],
+            honor_markers: true,
+            ..Default::default()
+        };
+        let code = to_gcode(
+            svg,
+            &config,
+            ConversionOptions::default(),
+            &MachineConfig::default(),
+        );
+        let marker_rapid = code
+            .lines()
+            .filter(|line| line.starts_with("G0 "))
+            .nth(1)
+            .unwrap_or_else(|| panic!("{code}"));
+        // The circle is scaled by the stroke width in user units, 2mm at 96 DPI
+        let x = marker_rapid.split(' ').nth(1).unwrap()[1..].parse::<f64>().unwrap();
+        assert!((x - (10. + 2. / 25.4 * 96.)).abs() < 1e-6, "{code}");
+    }
+
+    #[test]
+    fn faint_group_is_skipped_below_min_opacity() {
+        let svg = r#"<svg xmlns="http://www.w3.org/2000/svg" width="20mm" height="20mm" viewBox="0 0 20 20">
+            <g opacity="0.2">
+                <path d="M 0 0 L 10 10"/>
+            </g>
+        </svg>"#;
+        let cuts = |min_opacity| {
+            let config = ConversionConfig {
+                min_opacity: Some(min_opacity),
+                ..Default::default()
+            };
+            to_gcode(
+                svg,
+                &config,
+                ConversionOptions::default(),
+                &MachineConfig::default(),
+            )
+            .lines()
+            .filter(|line| line.starts_with("G1 "))
+            .count()
+        };
+
+        assert_eq!(cuts(0.3), 0);
+        assert_eq!(cuts(0.1), 1);
+    }
+
+    #[test]
+    fn outlines_are_as_faint_as_their_stroke() {
+        let svg = r#"<svg xmlns="http://www.w3.org/2000/svg" width="20mm" height="20mm" viewBox="0 0 20 20">
+            <path id="outline" d="M 0 0 L 10 10" stroke="black" fill-opacity="0"/>
+            <path id="faint-outline" d="M 0 5 L 10 15" stroke="black" stroke-opacity="0.2"/>
+            <path id="faint-fill" d="M 0 10 L 10 20" fill-opacity="0.2" stroke-opacity="1"/>
+        </svg>"#;
+        let config = ConversionConfig {
+            min_opacity: Some(0.3),
+            ..Default::default()
+        };
+        let code = to_gcode(
+            svg,
+            &config,
+            ConversionOptions::default(),
+            &MachineConfig::default(),
+        );
+
+        assert!(code.contains("path#outline"), "{code}");
+        assert!(!code.contains("path#faint-outline"), "{code}");
+        // Traced by its fill, so its stroke opacity doesn't apply
+        assert!(!code.contains("path#faint-fill"), "{code}");
+    }
+
+    #[test]
+    fn z_axis_moves_are_separated_by_default() {
+        let mut machine_config = MachineConfig {
+            z_axis: Some(ZAxisConfig {
+                safe_z: 5.,
+                cut_z: -1.,
+            }),
+            ..Default::default()
+        };
+        let convert = |machine_config: &MachineConfig| {
+            let code = to_gcode(
+                include_str!("../tests/square.svg"),
+                &ConversionConfig::default(),
+                ConversionOptions::default(),
+                machine_config,
+            );
+            // Only keep the axis letters of X/Y words, their values aren't relevant here
+            code.lines()
+                .filter(|line| line.starts_with("G0") || line.contains('Z'))
+                .map(|line| {
+                    line.split(' ')
+                        .map(|word| if word.starts_with(['X', 'Y']) { &word[..1] } else { word })
+                        .collect::<Vec<_>>()
+                        .join(" ")
+                })
+                .collect::<Vec<_>>()
+        };
+
+        assert_eq!(
+            convert(&machine_config)[..4],
+            ["G0 Z5", "G0 X Y", "G1 Z-1 F300", "G0 Z5"]
+        );
+
+        machine_config.separate_z_moves = false;
+        assert_eq!(
+            convert(&machine_config)[..2],
+            ["G0 X Y Z5", "G1 Z-1 F300"]
+        );
+    }
+
+    #[test]
+    fn multi_pass_cuts_deeper_each_pass() {
+        let machine_config = MachineConfig {
+            z_axis: Some(ZAxisConfig {
+                safe_z: 5.,
+                cut_z: -1.,
+            }),
+            passes: Some(MultiPassConfig {
+                count: 3,
+                depth_per_pass: 1.,
+            }),
+            ..Default::default()
+        };
+        let code = to_gcode(
+            include_str!("../tests/square.svg"),
+            &ConversionConfig::default(),
+            ConversionOptions::default(),
+            &machine_config,
+        );
+
+        let mut plunges = code
+            .lines()
+            .filter(|line| line.starts_with("G1 Z"))
+            .collect::<Vec<_>>();
+        let plunges_per_pass = plunges.len() / 3;
+        assert!(plunges_per_pass > 0);
+        plunges.dedup();
+        assert_eq!(plunges, ["G1 Z-1 F300", "G1 Z-2 F300", "G1 Z-3 F300"]);
+        assert_eq!(code.lines().last(), Some("G0 Z5"));
+    }
+
+    #[test]
+    fn multiple_documents_are_offset_into_one_program() {
+        let svg = r#"<svg xmlns="http://www.w3.org/2000/svg" width="10mm" height="10mm" viewBox="0 0 10 10">
+            <rect width="10" height="10"/>
+        </svg>"#;
+        let inputs = [
+            (roxmltree::Document::parse(svg).unwrap(), [0., 0.]),
+            (roxmltree::Document::parse(svg).unwrap(), [50., 0.]),
+        ];
+        let machine = Machine::new(
+            SupportedFunctionality {
+                circular_interpolation: false,
+            },
+            None,
+            None,
+            None,
+            None,
+            None,
+        );
+        let program = svg2program_multi(
+            &inputs,
+            &ConversionConfig::default(),
+            ConversionOptions::default(),
+            machine,
+        );
+
+        let rapids = program
+            .iter()
+            .enumerate()
+            .filter(|(_, token)| {
+                matches!(token, Token::Field(f) if f.letters == "G" && f.value.as_f64() == Some(0.))
+            })
+            .map(|(i, _)| [&program[i + 1], &program[i + 2]].map(|t| match t {
+                Token::Field(f) => f.value.as_f64().unwrap(),
+                _ => unreachable!(),
+            }))
+            .collect::<Vec<_>>();
+        assert_eq!(rapids.len(), 2, "{rapids:?}");
+        assert!((rapids[1][0] - rapids[0][0] - 50.).abs() < TOLERANCE, "{rapids:?}");
+        assert!((rapids[1][1] - rapids[0][1]).abs() < TOLERANCE, "{rapids:?}");
+        assert_eq!(
+            program
+                .iter()
+                .filter(|token| {
+                    matches!(token, Token::Field(f) if f.letters == "G" && f.value.as_f64() == Some(21.))
+                })
+                .count(),
+            1
+        );
+    }
+
+    #[test]
+    fn zero_width_rect_is_skipped_with_warning() {
+        let svg = r#"<svg xmlns="http://www.w3.org/2000/svg" width="20mm" height="20mm" viewBox="0 0 20 20">
+            <rect id="sliver" width="0" height="10"/>
+        </svg>"#;
+        let convert = |on_unsupported| {
+            let config = ConversionConfig {
+                on_unsupported,
+                summarize_skipped: true,
+                ..Default::default()
+            };
+            let mut code = String::new();
+            let warnings = capture_warnings(|| {
+                code = to_gcode(
+                    svg,
+                    &config,
+                    ConversionOptions::default(),
+                    &MachineConfig::default(),
+                );
+            });
+            let moves = code
+                .lines()
+                .filter(|line| line.starts_with("G0 ") || line.starts_with("G1 "))
+                .count();
+            (moves, warnings, code)
+        };
+
+        let (moves, warnings, code) = convert(UnsupportedPolicy::Warn);
+        assert_eq!(moves, 0);
+        assert_eq!(warnings, ["Skipping rect#sliver: degenerate geometry"]);
+        assert!(code.contains("; Skipped: rect(1)"), "{code}");
+
+        let (moves, warnings, code) = convert(UnsupportedPolicy::Ignore);
+        assert_eq!(moves, 0);
+        assert!(warnings.is_empty(), "{warnings:?}");
+        assert!(code.contains("; Skipped: rect(1)"), "{code}");
+    }
+
+    #[test]
+    fn invalid_data_feedrate_fails_under_error_policy() {
+        let convert = |feedrate: &str, on_unsupported| {
+            let svg = format!(
+                r#"<svg xmlns="http://www.w3.org/2000/svg" width="20mm" height="20mm" viewBox="0 0 20 20">
+                    <path id="outline" data-feedrate="{feedrate}" d="M 1 1 L 5 1"/>
+                </svg>"#
+            );
+            let document = roxmltree::Document::parse(&svg).unwrap();
+            let config = ConversionConfig {
+                on_unsupported,
+                ..Default::default()
+            };
+            let machine =
+                Machine::new(SupportedFunctionality::default(), None, None, None, None, None);
+            try_svg2program(&document, &config, ConversionOptions::default(), machine).map(
+                |program| {
+                    let mut code = String::new();
+                    g_code::emit::format_gcode_fmt(program.iter(), FormatOptions::default(), &mut code)
+                        .unwrap();
+                    code
+                },
+            )
+        };
+
+        let err = convert("fast", UnsupportedPolicy::Error).unwrap_err();
+        assert!(
+            matches!(
+                &err,
+                ConversionError::InvalidAttribute { element, attribute, value, .. }
+                    if element == "path#outline" && attribute == "data-feedrate" && value == "fast"
+            ),
+            "{err:?}"
+        );
+        assert!(err.to_string().contains("path#outline"), "{err}");
+
+        // Otherwise it is ignored
+        let code = convert("fast", UnsupportedPolicy::Warn).unwrap();
+        assert!(code.contains("F300"), "{code}");
+        let code = convert("1234", UnsupportedPolicy::Error).unwrap();
+        assert!(
+            code.lines().any(|line| line.starts_with("G1 ") && line.ends_with(" F1234")),
+            "{code}"
+        );
+    }
+
+    #[test]
+    fn program_end_command_is_emitted_last() {
+        let convert = |program_end_command| {
+            let machine_config = MachineConfig {
+                program_end_command,
+                ..Default::default()
+            };
+            to_gcode(
+                include_str!("../tests/square.svg"),
+                &ConversionConfig::default(),
+                ConversionOptions::default(),
+                &machine_config,
+            )
+        };
+
+        assert_eq!(convert(Some(EndCommand::M30)).lines().last(), Some("M30"));
+        assert_eq!(convert(Some(EndCommand::M2)).lines().last(), Some("M2"));
+        let code = convert(None);
+        assert!(!code.lines().any(|line| line.starts_with('M')), "{code}");
+    }
+
+    #[test]
+    fn path_mode_is_selected_at_program_start() {
+        let config = ConversionConfig {
+            tolerance: 0.05,
+            ..Default::default()
+        };
+        let convert = |path_mode| {
+            let machine_config = MachineConfig {
+                path_mode,
+                ..Default::default()
+            };
+            to_gcode(
+                include_str!("../tests/square.svg"),
+                &config,
+                ConversionOptions::default(),
+                &machine_config,
+            )
+        };
+
+        let code = convert(Some(PathMode::Blend { tolerance: None }));
+        assert!(code.lines().take(3).any(|line| line.starts_with("G64 P0.05")), "{code}");
+        let code = convert(Some(PathMode::Blend {
+            tolerance: Some(0.2),
+        }));
+        assert!(code.lines().take(3).any(|line| line.starts_with("G64 P0.2")), "{code}");
+        let code = convert(Some(PathMode::ExactStop));
+        assert!(code.lines().take(3).any(|line| line.starts_with("G61")), "{code}");
+        let code = convert(None);
+        assert!(!code.contains("G64") && !code.contains("G61"), "{code}");
+    }
+
+    #[test]
+    fn physical_size_is_read_from_width_height_or_view_box() {
+        let size = |attributes: &str| {
+            let svg = format!(r#"<svg xmlns="http://www.w3.org/2000/svg" {attributes}/>"#);
+            let document = roxmltree::Document::parse(&svg).unwrap();
+            svg_physical_size(&document, &ConversionConfig::default())
+        };
+        let assert_size = |actual: Option<[f64; 2]>, expected: [f64; 2]| {
+            let actual = actual.unwrap();
+            assert!(
+                (actual[0] - expected[0]).abs() < TOLERANCE && (actual[1] - expected[1]).abs() < TOLERANCE,
+                "{actual:?}"
+            );
+        };
+
+        assert_size(size(r#"width="210mm" height="297mm""#), [210., 297.]);
+        assert_size(size(r#"width="21cm" viewBox="0 0 210 297""#), [210., 297.]);
+        // Pixels at 96 DPI
+        assert_size(size(r#"viewBox="0 0 96 48""#), [25.4, 12.7]);
+        assert_eq!(size(r#"width="100%" height="100%""#), None);
+        assert_eq!(size(""), None);
+    }
+
+    #[test]
+    fn groups_are_moved_to_their_own_origins() {
+        let svg = r#"<svg xmlns="http://www.w3.org/2000/svg" width="100mm" height="100mm" viewBox="0 0 100 100">
+            <g><rect id="first" x="10" y="80" width="10" height="10"/></g>
+            <g transform="translate(20 5)"><rect id="second" x="10" y="80" width="10" height="10"/></g>
+            <g><rect id="third" x="10" y="80" width="10" height="10"/></g>
+        </svg>"#;
+        let document = roxmltree::Document::parse(svg).unwrap();
+        let options = ConversionOptions {
+            per_group_origins: Some(vec![[0., 0.], [50., 30.]]),
+            ..Default::default()
+        };
+        let subpaths = svg2paths(&document, &ConversionConfig::default(), options);
+        let min = |id: &str| {
+            let points = subpaths
+                .iter()
+                .filter(|subpath| subpath.id.as_deref() == Some(id))
+                .flat_map(|subpath| subpath.points.iter());
+            points.fold([f64::INFINITY; 2], |[x, y], p| [x.min(p[0]), y.min(p[1])])
+        };
+
+        // Y is flipped, so the last rect starts at (10, 10) in the document's placement
+        for (id, expected) in [("first", [0., 0.]), ("second", [50., 30.]), ("third", [10., 10.])] {
+            let actual = min(id);
+            assert!(
+                (actual[0] - expected[0]).abs() < TOLERANCE && (actual[1] - expected[1]).abs() < TOLERANCE,
+                "{id}: {actual:?}"
+            );
+        }
+    }
+
+    #[test]
+    fn global_transform_is_applied_before_placement() {
+        let svg = r#"<svg xmlns="http://www.w3.org/2000/svg" width="20mm" height="20mm" viewBox="0 0 20 20">
+            <path d="M 1 1 L 5 1 L 5 3"/>
+        </svg>"#;
+        let document = roxmltree::Document::parse(svg).unwrap();
+        let points = |origin, global_transform| {
+            let config = ConversionConfig {
+                origin,
+                ..Default::default()
+            };
+            let options = ConversionOptions {
+                global_transform,
+                ..Default::default()
+            };
+            svg2paths(&document, &config, options)
+                .into_iter()
+                .flat_map(|subpath| subpath.points)
+                .collect::<Vec<_>>()
+        };
+        let assert_points = |actual: Vec<[f64; 2]>, expected: &[[f64; 2]]| {
+            assert_eq!(actual.len(), expected.len(), "{actual:?}");
+            for (a, e) in actual.iter().zip(expected) {
+                assert!(
+                    (a[0] - e[0]).abs() < TOLERANCE && (a[1] - e[1]).abs() < TOLERANCE,
+                    "{actual:?}"
+                );
+            }
+        };
+
+        // Shear X by half of Y, then translate by (10, 20), with Y pointing up
+        assert_points(
+            points([None, None], Some([1., 0., 0.5, 1., 10., 20.])),
+            &[[20.5, 39.], [24.5, 39.], [23.5, 37.]],
+        );
+        // The origin is found from the transformed drawing, which reaches past the SVG's corner
+        assert_points(
+            points([Some(0.), Some(0.)], Some([1., 0., 0., 1., -100., -100.])),
+            &[[0., 2.], [4., 2.], [4., 0.]],
+        );
+    }
+
+    #[test]
+    fn nested_element_transform_is_composed() {
+        let svg = r#"<svg xmlns="http://www.w3.org/2000/svg" width="100mm" height="100mm" viewBox="0 0 100 100">
+            <g transform="translate(10 20)">
+                <g transform="scale(2)">
+                    <rect id="target" transform="translate(1 1)" width="5" height="5"/>
+                </g>
+            </g>
+        </svg>"#;
+        let document = roxmltree::Document::parse(svg).unwrap();
+        let config = ConversionConfig {
+            origin: [None, None],
+            ..Default::default()
+        };
+
+        let transform =
+            resolve_element_transform(&document, &config, ConversionOptions::default(), "target")
+                .unwrap();
+        // x' = 2 (x + 1) + 10, y' = 100 - (2 (y + 1) + 20) since Y is flipped
+        let expected = [2., 0., 0., -2., 12., 78.];
+        for (actual, expected) in transform.to_array().into_iter().zip(expected) {
+            assert!((actual - expected).abs() < TOLERANCE, "{transform:?}");
+        }
+        assert_eq!(
+            resolve_element_transform(&document, &config, ConversionOptions::default(), "missing"),
+            None
+        );
+    }
+
+    #[test]
+    fn origin_places_each_given_axis_with_and_without_trim() {
+        let svg = r#"<svg xmlns="http://www.w3.org/2000/svg" width="100mm" height="100mm" viewBox="0 0 100 100">
+            <rect x="20" y="30" width="20" height="20"/>
+        </svg>"#;
+        let document = roxmltree::Document::parse(svg).unwrap();
+        let extents = |origin, trim| {
+            let config = ConversionConfig {
+                origin,
+                ..Default::default()
+            };
+            let options = ConversionOptions {
+                trim,
+                ..Default::default()
+            };
+            let points = svg2paths(&document, &config, options)
+                .into_iter()
+                .flat_map(|subpath| subpath.points)
+                .collect::<Vec<_>>();
+            [0, 1].map(|axis| {
+                let min = points.iter().map(|p| p[axis]).fold(f64::INFINITY, f64::min);
+                let max = points.iter().map(|p| p[axis]).fold(f64::NEG_INFINITY, f64::max);
+                [min, max]
+            })
+        };
+
+        // Without trim, the origin is where the corner of the SVG goes and an axis without one
+        // stays where the SVG put it (Y is flipped)
+        for (origin, expected) in [
+            ([None, None], [[20., 40.], [50., 70.]]),
+            ([Some(5.), None], [[25., 45.], [50., 70.]]),
+            ([None, Some(5.)], [[20., 40.], [55., 75.]]),
+            ([Some(5.), Some(5.)], [[25., 45.], [55., 75.]]),
+        ] {
+            let actual = extents(origin, false);
+            for axis in 0..2 {
+                for bound in 0..2 {
+                    assert!(
+                        (actual[axis][bound] - expected[axis][bound]).abs() < TOLERANCE,
+                        "{origin:?}: {actual:?}"
+                    );
+                }
+            }
+        }
+        // With trim, the drawing is aligned to zero and the origin moves it from there
+        for (origin, expected) in [
+            ([None, None], [[0., 20.], [0., 20.]]),
+            ([Some(5.), None], [[5., 25.], [0., 20.]]),
+            ([None, Some(5.)], [[0., 20.], [5., 25.]]),
+            ([Some(5.), Some(5.)], [[5., 25.], [5., 25.]]),
+        ] {
+            let actual = extents(origin, true);
+            for axis in 0..2 {
+                for bound in 0..2 {
+                    assert!(
+                        (actual[axis][bound] - expected[axis][bound]).abs() < TOLERANCE,
+                        "trim {origin:?}: {actual:?}"
+                    );
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn detected_polygon_arcs_below_min_radius_follow_the_curve() {
+        // A circle of radius 0.5mm, too small for arcs
+        let points = (0..=24)
+            .map(|i| {
+                let angle = i as f64 / 24. * std::f64::consts::TAU;
+                format!("{},{}", 10. + 0.5 * angle.cos(), 10. + 0.5 * angle.sin())
+            })
+            .collect::<Vec<_>>()
+            .join(" ");
+        let svg = format!(
+            r#"<svg xmlns="http://www.w3.org/2000/svg" width="20mm" height="20mm" viewBox="0 0 20 20">
+                <polyline points="{points}"/>
+            </svg>"#
+        );
+        let config = ConversionConfig {
+            detect_polygon_arcs: true,
+            min_arc_radius: Some(1.),
+            origin: [None, None],
+            ..Default::default()
+        };
+        let machine_config = MachineConfig {
+            supported_functionality: SupportedFunctionality {
+                circular_interpolation: true,
+            },
+            ..Default::default()
+        };
+        let code = to_gcode(&svg, &config, ConversionOptions::default(), &machine_config);
+
+        assert!(
+            !code.lines().any(|line| line.starts_with("G2 ") || line.starts_with("G3 ")),
+            "{code}"
+        );
+        // Flattened like the arcs of a path rather than cut across as a chord
+        let cut_points = code
+            .lines()
+            .filter(|line| line.starts_with("G0") || line.starts_with("G1"))
+            .filter_map(|line| {
+                let word = |letter| {
+                    line.split_whitespace()
+                        .find_map(|word| word.strip_prefix(letter)?.parse::<f64>().ok())
+                };
+                Some([word('X')?, word('Y')?])
+            })
+            .collect::<Vec<_>>();
+        for pair in cut_points.windows(2) {
+            let [[x0, y0], [x1, y1]] = [pair[0], pair[1]];
+            assert!((x1 - x0).hypot(y1 - y0) < 0.2, "{code}");
+        }
+    }
+
+    #[test]
+    fn units_can_be_left_out() {
+        let convert = |emit_units| {
+            let machine_config = MachineConfig {
+                emit_units,
+                ..Default::default()
+            };
+            to_gcode(
+                include_str!("../tests/square.svg"),
+                &ConversionConfig::default(),
+                ConversionOptions::default(),
+                &machine_config,
+            )
+        };
+
+        assert!(convert(true).starts_with("G21"), "{}", convert(true));
+        let code = convert(false);
+        assert!(!code.contains("G21"), "{code}");
+        assert!(code.starts_with("G90"), "{code}");
+    }
+
+    #[test]
+    fn square_is_pocketed_with_concentric_squares() {
+        let svg = r#"<svg xmlns="http://www.w3.org/2000/svg" width="20mm" height="20mm" viewBox="0 0 20 20">
+            <rect x="5" y="5" width="10" height="10"/>
+        </svg>"#;
+        let config = ConversionConfig {
+            pocket: Some(PocketConfig { stepover: 1. }),
+            ..Default::default()
+        };
+        let code = to_gcode(
+            svg,
+            &config,
+            ConversionOptions::default(),
+            &MachineConfig::default(),
+        );
+
+        // Bounding boxes of the subpaths, each starting with a rapid move
+        let mut boxes: Vec<[f64; 4]> = vec![];
+        for line in code.lines().filter(|line| line.starts_with("G0") || line.starts_with("G1")) {
+            let word = |letter| {
+                line.split_whitespace()
+                    .find_map(|word| word.strip_prefix(letter))
+                    .and_then(|value| value.parse::<f64>().ok())
+            };
+            let (Some(x), Some(y)) = (word('X'), word('Y')) else {
+                continue;
+            };
+            if line.starts_with("G0") {
+                boxes.push([x, y, x, y]);
+            } else if let Some(b) = boxes.last_mut() {
+                *b = [b[0].min(x), b[1].min(y), b[2].max(x), b[3].max(y)];
+            }
+        }
+
+        // The outline, then squares 1mm further in until they collapse at the center
+        let expected = [5., 6., 7., 8., 9.].map(|min| [min, min, 20. - min, 20. - min]);
+        assert_eq!(boxes.len(), expected.len(), "{code}");
+        for (actual, expected) in boxes.iter().zip(expected) {
+            assert!(
+                actual.iter().zip(expected).all(|(a, e)| (a - e).abs() < TOLERANCE),
+                "{actual:?} {expected:?}\n{code}"
+            );
+        }
+    }
+
+    #[test]
+    fn rounded_rect_is_pocketed_to_its_center() {
+        let svg = r#"<svg xmlns="http://www.w3.org/2000/svg" width="120mm" height="120mm" viewBox="0 0 120 120">
+            <rect x="10" y="10" width="100" height="100" rx="2"/>
+        </svg>"#;
+        let config = ConversionConfig {
+            pocket: Some(PocketConfig { stepover: 1. }),
+            ..Default::default()
+        };
+        let code = to_gcode(
+            svg,
+            &config,
+            ConversionOptions::default(),
+            &MachineConfig::default(),
+        );
+
+        // Points of the subpaths, each starting with a rapid move
+        let mut subpaths: Vec<Vec<[f64; 2]>> = vec![];
+        for line in code.lines().filter(|line| line.starts_with("G0") || line.starts_with("G1")) {
+            let word = |letter| {
+                line.split_whitespace()
+                    .find_map(|word| word.strip_prefix(letter))
+                    .and_then(|value| value.parse::<f64>().ok())
+            };
+            let (Some(x), Some(y)) = (word('X'), word('Y')) else {
+                continue;
+            };
+            if line.starts_with("G0") {
+                subpaths.push(vec![[x, y]]);
+            } else if let Some(subpath) = subpaths.last_mut() {
+                subpath.push([x, y]);
+            }
+        }
+
+        // Short edges of the rounded corners don't stop the offsets, which go on like those
+        // of a square until the last is within a stepover of the center
+        assert!(subpaths.len() >= 49, "{} subpaths\n{code}", subpaths.len());
+        let innermost = subpaths.last().unwrap();
+        assert!(
+            innermost
+                .iter()
+                .all(|point| point.iter().all(|v| (v - 60.).abs() <= 1. + 1e-6)),
+            "{innermost:?}"
+        );
+    }
+
+    #[test]
+    fn gentle_curves_are_preserved() {
+        // Bulges by about 0.001mm over 200mm, which is within the tolerance of a line
+        let svg = r#"<svg xmlns="http://www.w3.org/2000/svg" width="220mm" height="20mm" viewBox="0 0 220 20">
+            <path d="M 10 10 C 70 10.0015 150 10.0015 210 10"/>
+        </svg>"#;
+        let machine_config = MachineConfig {
+            supported_functionality: SupportedFunctionality {
+                circular_interpolation: true,
+            },
+            ..Default::default()
+        };
+        let convert = |preserve_curvature| {
+            let config = ConversionConfig {
+                preserve_curvature,
+                ..Default::default()
+            };
+            to_gcode(svg, &config, ConversionOptions::default(), &machine_config)
+        };
+        let cuts = |code: &str| {
+            code.lines()
+                .filter(|line| ["G1 ", "G2 ", "G3 "].iter().any(|g| line.starts_with(g)))
+                .map(str::to_string)
+                .collect::<Vec<_>>()
+        };
+
+        let collapsed = convert(false);
+        assert_eq!(cuts(&collapsed).len(), 1, "{collapsed}");
+        assert!(cuts(&collapsed)[0].starts_with("G1 "), "{collapsed}");
+        // Followed closely enough to reach the middle of the curve, 0.001125mm off the chord
+        let preserved = convert(true);
+        assert!(cuts(&preserved).len() > 1, "{preserved}");
+        let lowest = cuts(&preserved)
+            .iter()
+            .filter_map(|cut| {
+                let y = cut.split_whitespace().find_map(|word| word.strip_prefix('Y'))?;
+                y.parse::<f64>().ok()
+            })
+            .fold(f64::INFINITY, f64::min);
+        assert!((lowest - (10. - 0.001125)).abs() < 1e-9, "{preserved}");
+    }
+
+    #[test]
+    fn arcs_past_max_arcs_are_flattened() {
+        let paths = (0..6)
+            .map(|i| format!(r#"<path d="M {x},5 A 5 5 0 0 1 {to},0"/>"#, x = i * 20, to = i * 20 + 5))
+            .collect::<String>();
+        let svg = format!(
+            r#"<svg xmlns="http://www.w3.org/2000/svg" width="120mm" height="20mm" viewBox="0 0 120 20">{paths}</svg>"#
+        );
+        let convert = |max_arcs| {
+            let machine_config = MachineConfig {
+                supported_functionality: SupportedFunctionality {
+                    circular_interpolation: true,
+                },
+                max_arcs,
+                ..Default::default()
+            };
+            to_gcode(
+                &svg,
+                &ConversionConfig::default(),
+                ConversionOptions::default(),
+                &machine_config,
+            )
+        };
+        let is_arc = |line: &&str| line.starts_with("G2 ") || line.starts_with("G3 ");
+
+        let uncapped = convert(None);
+        assert_eq!(uncapped.lines().filter(is_arc).count(), 6, "{uncapped}");
+        let capped = convert(Some(2));
+        let lines = capped.lines().collect::<Vec<_>>();
+        let arcs = lines
+            .iter()
+            .enumerate()
+            .filter(|(_, line)| is_arc(line))
+            .map(|(i, _)| i)
+            .collect::<Vec<_>>();
+        assert_eq!(arcs.len(), 2, "{capped}");
+        // The remaining four arcs are cut as lines after the budget runs out
+        assert!(
+            lines[arcs[1]..].iter().filter(|line| line.starts_with("G1 ")).count() > 4,
+            "{capped}"
+        );
+    }
+
+    #[test]
+    fn feedrates_can_be_emitted_per_second() {
+        let feedrates = |feedrate_units| {
+            let config = ConversionConfig {
+                feedrate: 600.,
+                feedrate_units,
+                ..Default::default()
+            };
+            to_gcode(
+                include_str!("../tests/square.svg"),
+                &config,
+                ConversionOptions::default(),
+                &MachineConfig::default(),
+            )
+            .split_whitespace()
+            .filter_map(|word| word.strip_prefix('F')?.parse::<f64>().ok())
+            .collect::<Vec<_>>()
+        };
+
+        let per_minute = feedrates(FeedrateUnits::PerMinute);
+        assert!(!per_minute.is_empty());
+        assert!(per_minute.iter().all(|f| *f == 600.), "{per_minute:?}");
+        let per_second = feedrates(FeedrateUnits::PerSecond);
+        assert_eq!(per_second.len(), per_minute.len());
+        assert!(per_second.iter().all(|f| *f == 10.), "{per_second:?}");
+    }
+
+    #[test]
+    fn priming_stroke_is_cut_before_the_drawing() {
+        let machine_config = MachineConfig {
+            priming_stroke: Some(PrimingConfig {
+                position: [-20., 5.],
+                length: 8.,
+            }),
+            ..Default::default()
+        };
+        let code = to_gcode(
+            include_str!("../tests/square.svg"),
+            &ConversionConfig::default(),
+            ConversionOptions::default(),
+            &machine_config,
+        );
+
+        let moves = code
+            .lines()
+            .filter(|line| line.starts_with("G0") || line.starts_with("G1"))
+            .collect::<Vec<_>>();
+        assert!(moves[0].starts_with("G0 X-20 Y5"), "{code}");
+        assert!(moves[1].starts_with("G1 X-12 Y5"), "{code}");
+        assert!(moves[2].starts_with("G1 X-20 Y5"), "{code}");
+        assert!(moves[3].starts_with("G0 ") && !moves[3].contains("X-20"), "{code}");
+    }
+
+    #[test]
+    fn clip_path_in_bounding_box_units_keeps_half_the_width() {
+        let svg = |clip_path_units, width| {
+            format!(
+                r#"<svg xmlns="http://www.w3.org/2000/svg" width="40mm" height="20mm" viewBox="0 0 40 20">
+                    <defs>
+                        <clipPath id="left" clipPathUnits="{clip_path_units}">
+                            <rect width="{width}" height="1"/>
+                        </clipPath>
+                    </defs>
+                    <rect x="5" y="5" width="20" height="10" clip-path="url(#left)"/>
+                </svg>"#
+            )
+        };
+        let extent = |svg: String| {
+            let document = roxmltree::Document::parse(&svg).unwrap();
+            let config = ConversionConfig {
+                origin: [None, None],
+                ..Default::default()
+            };
+            let points = svg2paths(&document, &config, ConversionOptions::default())
+                .into_iter()
+                .flat_map(|subpath| subpath.points)
+                .collect::<Vec<_>>();
+            [0, 1].map(|axis| {
+                let values = points.iter().map(|point| point[axis]);
+                [
+                    values.clone().fold(f64::INFINITY, f64::min),
+                    values.fold(f64::NEG_INFINITY, f64::max),
+                ]
+            })
+        };
+
+        let [x, y] = extent(svg("objectBoundingBox", 0.5));
+        assert!((x[0] - 5.).abs() < TOLERANCE, "{x:?}");
+        assert!((x[1] - 15.).abs() < TOLERANCE, "{x:?}");
+        assert!((y[1] - y[0] - 10.).abs() < TOLERANCE, "{y:?}");
+
+        // In user space the clip rect ends at x=0.5, before the element even starts
+        let [x, _] = extent(svg("userSpaceOnUse", 0.5));
+        assert!(x[0] > x[1], "everything is clipped: {x:?}");
+    }
+
+    #[test]
+    fn clip_path_resolves_use_of_referenced_shape() {
+        let svg = r##"<svg xmlns="http://www.w3.org/2000/svg" xmlns:xlink="http://www.w3.org/1999/xlink" width="40mm" height="20mm" viewBox="0 0 40 20">
+            <defs>
+                <rect id="left-half" width="15" height="20"/>
+                <clipPath id="left">
+                    <use xlink:href="#left-half" x="-5"/>
+                </clipPath>
+            </defs>
+            <rect x="5" y="5" width="20" height="10" clip-path="url(#left)"/>
+        </svg>"##;
+        let document = roxmltree::Document::parse(svg).unwrap();
+        let config = ConversionConfig {
+            origin: [None, None],
+            ..Default::default()
+        };
+        let xs = svg2paths(&document, &config, ConversionOptions::default())
+            .into_iter()
+            .flat_map(|subpath| subpath.points)
+            .map(|point| point[0])
+            .collect::<Vec<_>>();
+
+        // The used rect is moved to end at x=10, halfway across the clipped rect
+        let min = xs.iter().copied().fold(f64::INFINITY, f64::min);
+        let max = xs.iter().copied().fold(f64::NEG_INFINITY, f64::max);
+        assert!((min - 5.).abs() < TOLERANCE, "{xs:?}");
+        assert!((max - 10.).abs() < TOLERANCE, "{xs:?}");
+    }
+
+    #[test]
+    fn semicircle_epsilon_controls_arc_splitting() {
+        // Just short of a semicircle
+        let angle = std::f64::consts::PI - 0.05;
+        let svg = format!(
+            r#"<svg xmlns="http://www.w3.org/2000/svg" width="40mm" height="40mm" viewBox="-20 -20 40 40">
+                <path d="M 10 0 A 10 10 0 0 1 {} {}"/>
+            </svg>"#,
+            10. * angle.cos(),
+            10. * angle.sin()
+        );
+        let machine_config = MachineConfig {
+            supported_functionality: SupportedFunctionality {
+                circular_interpolation: true,
+            },
+            ..Default::default()
+        };
+        let arcs = |semicircle_epsilon| {
+            let config = ConversionConfig {
+                semicircle_epsilon,
+                ..Default::default()
+            };
+            to_gcode(&svg, &config, ConversionOptions::default(), &machine_config)
+                .lines()
+                .filter(|line| line.starts_with("G2 ") || line.starts_with("G3 "))
+                .count()
+        };
+
+        assert_eq!(arcs(ConversionConfig::default().semicircle_epsilon), 1);
+        assert_eq!(arcs(1e-3), 2);
+    }
+
+    #[test]
+    fn percentage_transform_origin_resolves_against_transform_box() {
+        let x_extent = |transform_box: &str| {
+            let svg = format!(
+                r#"<svg xmlns="http://www.w3.org/2000/svg" width="40mm" height="40mm" viewBox="0 0 40 40">
+                    <rect x="10" y="10" width="10" height="10" transform="translate(5 0) rotate(90)"
+                        style="transform-origin: 50% 50%; transform-box: {transform_box}"/>
+                </svg>"#
+            );
+            let document = roxmltree::Document::parse(&svg).unwrap();
+            let config = ConversionConfig {
+                origin: [None, None],
+                ..Default::default()
+            };
+            let xs = svg2paths(&document, &config, ConversionOptions::default())
+                .into_iter()
+                .flat_map(|subpath| subpath.points)
+                .map(|point| point[0])
+                .collect::<Vec<_>>();
+            [
+                xs.iter().copied().fold(f64::INFINITY, f64::min),
+                xs.iter().copied().fold(f64::NEG_INFINITY, f64::max),
+            ]
+        };
+
+        // Rotated around its own center, then translated
+        let [min, max] = x_extent("fill-box");
+        assert!((min - 15.).abs() < TOLERANCE && (max - 25.).abs() < TOLERANCE, "{min} {max}");
+        // Rotated around the center of the view box, then translated
+        let [min, max] = x_extent("view-box");
+        assert!((min - 25.).abs() < TOLERANCE && (max - 35.).abs() < TOLERANCE, "{min} {max}");
+    }
+
+    #[cfg(feature = "text")]
+    #[test]
+    fn tspan_lines_are_drawn_at_their_baselines() {
+        let svg = r#"<svg xmlns="http://www.w3.org/2000/svg" width="40mm" height="40mm" viewBox="0 0 40 40">
+            <text x="5" y="10" font-size="10">
+                <tspan>HE</tspan>
+                <tspan x="5" dy="12">L<tspan dx="3">T</tspan></tspan>
+            </text>
+        </svg>"#;
+        let document = roxmltree::Document::parse(svg).unwrap();
+        let config = ConversionConfig {
+            origin: [None, None],
+            ..Default::default()
+        };
+        let points = svg2paths(&document, &config, ConversionOptions::default())
+            .into_iter()
+            .flat_map(|subpath| subpath.points)
+            .collect::<Vec<_>>();
+        // Capitals are 7mm tall at this size
+        let in_line = |baseline: f64| {
+            points
+                .iter()
+                .filter(|[_, y]| *y > baseline - TOLERANCE && *y < baseline + 7. + TOLERANCE)
+                .map(|[x, _]| *x)
+                .collect::<Vec<_>>()
+        };
+        // The SVG baselines at 10 and 22 are flipped
+        let first = in_line(30.);
+        let second = in_line(18.);
+        assert!(!first.is_empty() && !second.is_empty());
+        assert_eq!(first.len() + second.len(), points.len());
+        // Both lines start at x = 5, and the kerned T starts 3 after where it would have
+        let min = |xs: &[f64]| xs.iter().copied().fold(f64::INFINITY, f64::min);
+        let max = |xs: &[f64]| xs.iter().copied().fold(f64::NEG_INFINITY, f64::max);
+        assert!((min(&first) - 5.).abs() < TOLERANCE);
+        assert!((min(&second) - 5.).abs() < TOLERANCE);
+        // Glyphs advance by 7mm and are 14/3mm wide
+        assert!((max(&first) - (12. + 14. / 3.)).abs() < TOLERANCE, "{first:?}");
+        assert!((max(&second) - (15. + 14. / 3.)).abs() < TOLERANCE, "{second:?}");
+    }
+
+    #[cfg(feature = "text")]
+    #[test]
+    fn baseline_shift_raises_and_lowers_tspans() {
+        let svg = r#"<svg xmlns="http://www.w3.org/2000/svg" width="60mm" height="40mm" viewBox="0 0 60 40">
+            <text x="5" y="20" font-size="10">
+                I<tspan baseline-shift="super">I</tspan>I<tspan style="baseline-shift: sub">I</tspan><tspan baseline-shift="50%">I</tspan>
+            </text>
+        </svg>"#;
+        let document = roxmltree::Document::parse(svg).unwrap();
+        let config = ConversionConfig {
+            origin: [None, None],
+            ..Default::default()
+        };
+        // Each I is three strokes, the lowest of which is on its baseline
+        let baselines = svg2paths(&document, &config, ConversionOptions::default())
+            .chunks(3)
+            .map(|strokes| {
+                strokes
+                    .iter()
+                    .flat_map(|subpath| subpath.points.iter().map(|[_, y]| *y))
+                    .fold(f64::INFINITY, f64::min)
+            })
+            .collect::<Vec<_>>();
+
+        // The SVG baseline at 20 is flipped to 20, superscripts are raised by 0.4em and
+        // subscripts lowered by 0.2em
+        let expected = [20., 24., 20., 18., 25.];
+        assert_eq!(baselines.len(), expected.len(), "{baselines:?}");
+        for (actual, expected) in baselines.iter().zip(expected) {
+            assert!((actual - expected).abs() < TOLERANCE, "{baselines:?}");
+        }
+    }
+
+    #[test]
+    fn style_element_class_sets_stroke_color() {
+        let svg = r#"<svg xmlns="http://www.w3.org/2000/svg" width="20mm" height="20mm" viewBox="0 0 20 20">
+            <style>
+                .cut { stroke: red; fill: none }
+            </style>
+            <path class="cut" d="M 0 0 L 10 10"/>
+            <path d="M 0 10 L 10 0"/>
+        </svg>"#;
+        let document = roxmltree::Document::parse(svg).unwrap();
+        let colors = svg2paths(&document, &ConversionConfig::default(), ConversionOptions::default())
+            .into_iter()
+            .map(|subpath| subpath.color)
+            .collect::<Vec<_>>();
+        assert_eq!(
+            colors,
+            [Some("#ff0000".to_string()), Some("#000000".to_string())]
+        );
+    }
+
+    #[test]
+    fn equal_colors_in_any_notation_group_together() {
+        let svg = r##"<svg xmlns="http://www.w3.org/2000/svg" width="20mm" height="20mm" viewBox="0 0 20 20">
+            <path stroke="#f00" d="M 0 0 L 10 10"/>
+            <path stroke="red" d="M 0 10 L 10 0"/>
+            <path stroke="rgb(255,0,0)" d="M 0 0 L 10 0"/>
+            <path style="stroke: #FF0000" d="M 0 0 L 0 10"/>
+            <path stroke="hsl(0, 100%, 50%)" d="M 10 0 L 10 10"/>
+            <path stroke="rgba(255, 0, 0, 0.5)" d="M 0 5 L 10 5"/>
+        </svg>"##;
+        let document = roxmltree::Document::parse(svg).unwrap();
+        let colors = svg2paths(&document, &ConversionConfig::default(), ConversionOptions::default())
+            .into_iter()
+            .filter_map(|subpath| subpath.color)
+            .collect::<Vec<_>>();
+        assert_eq!(
+            colors,
+            ["#ff0000", "#ff0000", "#ff0000", "#ff0000", "#ff0000", "#ff00007f"]
+        );
+    }
+
+    #[test]
+    fn system_language_mismatch_is_skipped() {
+        let svg = r#"<svg xmlns="http://www.w3.org/2000/svg" width="20mm" height="20mm" viewBox="0 0 20 20">
+            <path id="french" systemLanguage="fr" d="M 0 0 L 10 10"/>
+            <path id="english" systemLanguage="en" d="M 0 10 L 10 0"/>
+            <switch>
+                <path id="switch-french" systemLanguage="fr" d="M 0 0 L 10 0"/>
+                <path id="switch-english" systemLanguage="en" d="M 0 0 L 10 0"/>
+                <path id="switch-fallback" d="M 0 0 L 10 0"/>
+            </switch>
+            <path id="extension" requiredExtensions="http://example.org/ext" d="M 0 0 L 0 10"/>
+        </svg>"#;
+        let document = roxmltree::Document::parse(svg).unwrap();
+        let options = ConversionOptions {
+            system_language: Some("en-US".to_string()),
+            ..Default::default()
+        };
+        let ids = svg2paths(&document, &ConversionConfig::default(), options)
+            .into_iter()
+            .filter_map(|subpath| subpath.id)
+            .collect::<Vec<_>>();
+        assert_eq!(ids, ["english", "switch-english"]);
+    }
+
+    #[test]
+    fn always_retract_between_touching_subpaths() {
+        let svg = r#"<svg xmlns="http://www.w3.org/2000/svg" width="20mm" height="20mm" viewBox="0 0 20 20">
+            <path d="M 0 10 L 10 10"/>
+            <path d="M 10 10 L 20 10"/>
+        </svg>"#;
+        let config = ConversionConfig {
+            join_gap: Some(0.1),
+            ..Default::default()
+        };
+        let z_moves = |always_retract_between_subpaths| {
+            let machine_config = MachineConfig {
+                z_axis: Some(ZAxisConfig {
+                    safe_z: 5.,
+                    cut_z: -1.,
+                }),
+                always_retract_between_subpaths,
+                ..Default::default()
+            };
+            to_gcode(svg, &config, ConversionOptions::default(), &machine_config)
+                .lines()
+                .filter(|line| line.starts_with("G0 Z") || line.starts_with("G1 Z"))
+                .map(str::to_string)
+                .collect::<Vec<_>>()
+        };
+
+        // The second subpath is joined to the first
+        assert_eq!(z_moves(false), ["G0 Z5", "G1 Z-1 F300", "G0 Z5"]);
+        assert_eq!(
+            z_moves(true),
+            ["G0 Z5", "G1 Z-1 F300", "G0 Z5", "G1 Z-1 F300", "G0 Z5"]
+        );
+    }
+
+    #[test]
+    fn closepath_returns_to_its_own_subpath_start() {
+        // The second subpath starts relative to where the first closed
+        let svg = r#"<svg xmlns="http://www.w3.org/2000/svg" width="20mm" height="20mm" viewBox="0 0 20 20">
+            <path d="M 1 1 L 5 1 L 5 4 Z m 9 9 l 2 0 l 0 2 z"/>
+        </svg>"#;
+        let config = ConversionConfig {
+            origin: [None, None],
+            ..Default::default()
+        };
+        let code = to_gcode(
+            svg,
+            &config,
+            ConversionOptions::default(),
+            &MachineConfig::default(),
+        );
+
+        let mut subpaths: Vec<Vec<[f64; 2]>> = vec![];
+        for line in code.lines() {
+            let words = line.split(';').next().unwrap().split_whitespace().collect::<Vec<_>>();
+            let coordinate = |letter: char| {
+                words
+                    .iter()
+                    .find_map(|word| word.strip_prefix(letter))
+                    .map(|value| value.parse::<f64>().unwrap())
+            };
+            let (Some(x), Some(y)) = (coordinate('X'), coordinate('Y')) else {
+                continue;
+            };
+            match words.first() {
+                Some(&"G0") => subpaths.push(vec![[x, y]]),
+                Some(&"G1") => subpaths.last_mut().unwrap().push([x, y]),
+                _ => {}
+            }
+        }
+
+        assert_eq!(subpaths.len(), 2, "{code}");
+        for (subpath, start) in subpaths.iter().zip([[1., 19.], [10., 10.]]) {
+            assert_eq!(subpath.len(), 4, "{code}");
+            for point in [subpath.first().unwrap(), subpath.last().unwrap()] {
+                assert!((point[0] - start[0]).abs() < TOLERANCE, "{code}");
+                assert!((point[1] - start[1]).abs() < TOLERANCE, "{code}");
+            }
+        }
+    }
+
+    #[test]
+    fn tool_change_retract_is_higher_than_travel_retract() {
+        let svg = r#"<svg xmlns="http://www.w3.org/2000/svg" width="20mm" height="20mm" viewBox="0 0 20 20">
+            <g>
+                <path d="M 0 10 L 5 10"/>
+                <path d="M 0 15 L 5 15"/>
+            </g>
+            <g>
+                <path d="M 10 10 L 15 10"/>
+            </g>
+        </svg>"#;
+        let machine_config = MachineConfig {
+            between_layers_sequence: Some("M0".to_string()),
+            z_axis: Some(ZAxisConfig {
+                safe_z: 5.,
+                cut_z: -1.,
+            }),
+            travel_retract: Some(2.),
+            tool_change_retract: Some(10.),
+            ..Default::default()
+        };
+        let code = to_gcode(
+            svg,
+            &ConversionConfig::default(),
+            ConversionOptions::default(),
+            &machine_config,
+        );
+        let retracts = code
+            .lines()
+            .filter(|line| line.starts_with("G0 Z"))
+            .map(str::to_string)
+            .collect::<Vec<_>>();
+
+        // Before the first subpath, between subpaths, before the next layer, and at the end
+        assert_eq!(retracts, ["G0 Z2", "G0 Z2", "G0 Z10", "G0 Z2"], "{code}");
+    }
+
+    #[test]
+    fn duplicate_paths_are_cut_once() {
+        let svg = r#"<svg xmlns="http://www.w3.org/2000/svg" width="20mm" height="20mm" viewBox="0 0 20 20">
+            <rect x="2" y="2" width="10" height="5" fill="black"/>
+            <rect x="2" y="2" width="10" height="5" fill="none" stroke="black"/>
+        </svg>"#;
+        let cutting_moves = |dedupe_paths| {
+            let config = ConversionConfig {
+                dedupe_paths,
+                ..Default::default()
+            };
+            to_gcode(
+                svg,
+                &config,
+                ConversionOptions::default(),
+                &MachineConfig::default(),
+            )
+            .lines()
+            .filter(|line| line.starts_with("G1 X"))
+            .count()
+        };
+
+        let single = cutting_moves(true);
+        assert!(single > 0);
+        assert_eq!(cutting_moves(false), 2 * single);
+    }
+
+    #[test]
+    fn nearly_touching_lines_are_joined_within_gap() {
+        let svg = r#"<svg xmlns="http://www.w3.org/2000/svg" width="40mm" height="40mm" viewBox="0 0 40 40">
+            <path d="M 0 10 L 10 10"/>
+            <path d="M 10.05 10 L 20 10"/>
+        </svg>"#;
+        let travels = |join_gap| {
+            let config = ConversionConfig {
+                join_gap,
+                ..Default::default()
+            };
+            to_gcode(
+                svg,
+                &config,
+                ConversionOptions::default(),
+                &MachineConfig::default(),
+            )
+            .lines()
+            .filter(|line| line.starts_with("G0 "))
+            .count()
+        };
+
+        assert_eq!(travels(None), 2);
+        assert_eq!(travels(Some(0.1)), 1);
+        assert_eq!(travels(Some(0.01)), 2);
+    }
+
+    #[test]
+    fn accessory_runs_for_the_whole_job() {
+        let machine_config = MachineConfig {
+            tool_on_sequence: Some("M3".to_string()),
+            tool_off_sequence: Some("M5".to_string()),
+            accessory_on_sequence: Some("M7".to_string()),
+            accessory_off_sequence: Some("M9".to_string()),
+            ..Default::default()
+        };
+        let code = to_gcode(
+            include_str!("../tests/square.svg"),
+            &ConversionConfig::default(),
+            ConversionOptions::default(),
+            &machine_config,
+        );
+        let m_codes = code
+            .lines()
+            .filter(|line| line.starts_with('M'))
+            .collect::<Vec<_>>();
+
+        // Several subpaths turn the tool on and off, but the accessory only once
+        assert!(m_codes.iter().filter(|code| **code == "M3").count() > 1);
+        assert_eq!(m_codes.iter().filter(|code| **code == "M7").count(), 1);
+        assert_eq!(m_codes.iter().filter(|code| **code == "M9").count(), 1);
+        let first_cut = m_codes.iter().position(|code| *code == "M3").unwrap();
+        assert_eq!(m_codes[first_cut..first_cut + 2], ["M3", "M7"]);
+        assert_eq!(m_codes[m_codes.len() - 2..], ["M5", "M9"]);
+    }
+
+    #[test]
+    fn arc_endpoints_follow_arc_distance_mode() {
+        // Clockwise quarter circle from (0, 10) to (10, 20) around (10, 10), in G-code coordinates
+        let svg = r#"<svg xmlns="http://www.w3.org/2000/svg" width="40mm" height="40mm" viewBox="0 0 40 40">
+            <path d="M 0 30 A 10 10 0 0 1 10 20"/>
+        </svg>"#;
+        let convert = |arc_distance_mode| {
+            let machine_config = MachineConfig {
+                supported_functionality: SupportedFunctionality {
+                    circular_interpolation: true,
+                },
+                arc_distance_mode,
+                ..Default::default()
+            };
+            let config = ConversionConfig {
+                origin: [None, None],
+                ..Default::default()
+            };
+            let code = to_gcode(svg, &config, ConversionOptions::default(), &machine_config);
+            // Round away floating point noise
+            code.split_whitespace()
+                .map(|word| match word[1..].parse::<f64>() {
+                    Ok(value) => format!("{}{}", &word[..1], (value * 1e6).round() / 1e6 + 0.),
+                    Err(_) => word.to_string(),
+                })
+                .collect::<Vec<_>>()
+        };
+
+        let absolute = convert(ArcDistanceMode::AbsoluteEndpoints);
+        let arc = absolute.iter().position(|word| word == "G2").unwrap();
+        assert_eq!(absolute[arc..arc + 5], ["G2", "X10", "Y20", "I10", "J0"]);
+        assert!(!absolute.contains(&"G91".to_string()));
+
+        let relative = convert(ArcDistanceMode::RelativeEndpoints);
+        let arc = relative.iter().position(|word| word == "G2").unwrap();
+        assert_eq!(relative[arc - 1..arc + 5], ["G91", "G2", "X10", "Y10", "I10", "J0"]);
+        // Back to absolute distance mode for the rest of the program
+        assert_eq!(relative[arc + 5..arc + 7], ["F300", "G90"]);
+    }
+
+    #[test]
+    fn arcs_can_be_given_by_radius() {
+        // Clockwise quarter circle from (0, 10) to (10, 20) around (10, 10), in G-code coordinates
+        let svg = r#"<svg xmlns="http://www.w3.org/2000/svg" width="40mm" height="40mm" viewBox="0 0 40 40">
+            <path d="M 0 30 A 10 10 0 0 1 10 20"/>
+        </svg>"#;
+        let machine_config = MachineConfig {
+            supported_functionality: SupportedFunctionality {
+                circular_interpolation: true,
+            },
+            arc_format: ArcFormat::R,
+            ..Default::default()
+        };
+        let config = ConversionConfig {
+            origin: [None, None],
+            ..Default::default()
+        };
+        let code = to_gcode(svg, &config, ConversionOptions::default(), &machine_config);
+        // Round away floating point noise
+        let words = code
+            .split_whitespace()
+            .map(|word| match word[1..].parse::<f64>() {
+                Ok(value) => format!("{}{}", &word[..1], (value * 1e6).round() / 1e6 + 0.),
+                Err(_) => word.to_string(),
+            })
+            .collect::<Vec<_>>();
+
+        let arc = words.iter().position(|word| word == "G2").unwrap();
+        assert_eq!(words[arc..arc + 5], ["G2", "X10", "Y20", "R10", "F300"], "{code}");
+        assert!(!words.iter().any(|word| word.starts_with(['I', 'J'])), "{code}");
+    }
+
+    #[test]
+    fn tight_arcs_are_cut_slower() {
+        // Quarter circles with radius 10 and 1
+        let svg = r#"<svg xmlns="http://www.w3.org/2000/svg" width="40mm" height="40mm" viewBox="0 0 40 40">
+            <path d="M 0 30 A 10 10 0 0 1 10 20"/>
+            <path d="M 30 30 A 1 1 0 0 1 31 29"/>
+        </svg>"#;
+        let machine_config = MachineConfig {
+            supported_functionality: SupportedFunctionality {
+                circular_interpolation: true,
+            },
+            arc_feedrate_scaling: Some(ArcFeedConfig {
+                reference_radius: 5.,
+            }),
+            ..Default::default()
+        };
+        let config = ConversionConfig {
+            origin: [None, None],
+            feedrate: 300.,
+            ..Default::default()
+        };
+        let code = to_gcode(svg, &config, ConversionOptions::default(), &machine_config);
+
+        // Inline comments are attached to the last word
+        let words = code
+            .split_whitespace()
+            .map(|word| word.split(';').next().unwrap())
+            .collect::<Vec<_>>();
+        let arc_feedrates = words
+            .iter()
+            .enumerate()
+            .filter(|(_, word)| **word == "G2")
+            .map(|(i, _)| {
+                let feedrate = words[i..].iter().find_map(|word| word.strip_prefix('F'));
+                feedrate.unwrap().parse::<f64>().unwrap()
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(arc_feedrates.len(), 2, "{code}");
+        // At or above the reference radius, the full feedrate is used
+        assert!((arc_feedrates[0] - 300.).abs() < TOLERANCE);
+        assert!((arc_feedrates[1] - 300. / 5.).abs() < TOLERANCE);
+    }
+
+    #[test]
+    fn tool_change_per_layer() {
+        let svg = r#"<svg xmlns="http://www.w3.org/2000/svg" width="20mm" height="20mm" viewBox="0 0 20 20">
+            <g data-tool="2"><path d="M 1 1 L 9 1"/></g>
+            <g data-tool="1"><path d="M 1 9 L 9 9"/></g>
+            <g><path d="M 1 19 L 9 19"/></g>
+        </svg>"#;
+        let machine_config = MachineConfig {
+            tool_change: true,
+            ..Default::default()
+        };
+        let code = to_gcode(
+            svg,
+            &ConversionConfig::default(),
+            ConversionOptions::default(),
+            &machine_config,
+        );
+
+        let tool_changes = code
+            .lines()
+            .filter(|line| line.starts_with("M6"))
+            .collect::<Vec<_>>();
+        // The last group has no data-tool and is numbered by its position
+        assert_eq!(tool_changes, ["M6 T2", "M6 T1", "M6 T3"], "{code}");
+    }
+
+    #[test]
+    fn shared_per_layer_programs_begin_and_end_once() {
+        let svg = r#"<svg xmlns="http://www.w3.org/2000/svg" width="20mm" height="20mm" viewBox="0 0 20 20">
+            <path id="outside" d="M 1 5 L 9 5"/>
+            <g id="first"><path d="M 1 1 L 9 1"/></g>
+            <g id="second"><path d="M 1 9 L 9 9"/></g>
+            <g id="third"><path d="M 1 19 L 9 19"/></g>
+        </svg>"#;
+        let document = roxmltree::Document::parse(svg).unwrap();
+        let begin = g_code::parse::snippet_parser("G28").unwrap();
+        let end = g_code::parse::snippet_parser("M5").unwrap();
+        let machine_config = MachineConfig {
+            tool_change: true,
+            program_end_command: Some(EndCommand::M30),
+            ..Default::default()
+        };
+        let programs = |mode| {
+            let machine = Machine::new(
+                machine_config.supported_functionality.clone(),
+                None,
+                None,
+                Some(begin.clone()),
+                Some(end.clone()),
+                None,
+            )
+            .with_config(&machine_config);
+            svg2program_per_layer(
+                &document,
+                &ConversionConfig::default(),
+                ConversionOptions::default(),
+                machine,
+                mode,
+            )
+            .into_iter()
+            .map(|program| {
+                let mut code = String::new();
+                g_code::emit::format_gcode_fmt(program.iter(), FormatOptions::default(), &mut code)
+                    .unwrap();
+                code
+            })
+            .collect::<Vec<_>>()
+        };
+        let has = |code: &str, word: &str| code.lines().any(|line| line.starts_with(word));
+
+        let shared = programs(PerLayerMode::Shared);
+        assert_eq!(shared.len(), 3);
+        assert_eq!(
+            shared.iter().map(|code| has(code, "G28")).collect::<Vec<_>>(),
+            [true, false, false]
+        );
+        for word in ["M5", "M30"] {
+            assert_eq!(
+                shared.iter().map(|code| has(code, word)).collect::<Vec<_>>(),
+                [false, false, true]
+            );
+        }
+        let layers = [("first", "M6 T1"), ("second", "M6 T2"), ("third", "M6 T3")];
+        for (code, (id, tool)) in shared.iter().zip(layers) {
+            assert!(code.contains(id) && has(code, tool), "{code}");
+            assert_eq!(code.contains("outside"), id == "first", "{code}");
+            assert_eq!(code.matches("g#").count(), 1, "{code}");
+        }
+
+        let independent = programs(PerLayerMode::Independent);
+        assert!(independent
+            .iter()
+            .all(|code| has(code, "G28") && has(code, "M5") && has(code, "M30")));
+    }
+
+    #[test]
+    fn malformed_path_reports_where_parsing_failed() {
+        let svg = r#"<svg xmlns="http://www.w3.org/2000/svg" width="20mm" height="20mm" viewBox="0 0 20 20">
+            <path id="broken" d="M 1 1 L 9 1 L 9 x"/>
+        </svg>"#;
+        let document = roxmltree::Document::parse(svg).unwrap();
+        let machine = Machine::new(SupportedFunctionality::default(), None, None, None, None, None);
+        let err = try_svg2program(
+            &document,
+            &ConversionConfig::default(),
+            ConversionOptions::default(),
+            machine,
+        )
+        .unwrap_err();
+        let ConversionError::PathParse {
+            node_id,
+            byte_offset,
+            ..
+        } = err
+        else {
+            panic!("{err}");
+        };
+        assert_eq!(node_id.as_deref(), Some("broken"));
+        // The offending character
+        assert_eq!(byte_offset, "M 1 1 L 9 1 L 9 ".len());
+    }
+
+    #[test]
+    fn feedrate_does_not_depend_on_dpi() {
+        let svg = r#"<svg xmlns="http://www.w3.org/2000/svg" width="20px" height="20px">
+            <path d="M 1 1 L 9 1 L 9 9"/>
+        </svg>"#;
+        let feedrates = |config: &ConversionConfig| {
+            to_gcode(
+                svg,
+                config,
+                ConversionOptions::default(),
+                &MachineConfig::default(),
+            )
+            .split_whitespace()
+            .filter_map(|word| word.strip_prefix('F'))
+            .map(|feedrate| feedrate.parse::<f64>().unwrap())
+            .collect::<Vec<_>>()
+        };
+
+        let at_96 = feedrates(&ConversionConfig::default());
+        let at_72 = feedrates(&ConversionConfig {
+            dpi: 72.,
+            ..Default::default()
+        });
+        assert!(!at_96.is_empty());
+        assert_eq!(at_96, at_72);
+        assert!(at_96.iter().all(|feedrate| (feedrate - 300.).abs() < TOLERANCE));
+
+        // 300 px/min at 96 DPI
+        let in_user_units = feedrates(&ConversionConfig {
+            feedrate_is_physical: false,
+            ..Default::default()
+        });
+        assert!(in_user_units
+            .iter()
+            .all(|feedrate| (feedrate - 300. * 25.4 / 96.).abs() < TOLERANCE));
+    }
+
+    #[test]
+    fn progress_is_reported_up_to_completion() {
+        let svg = r#"<svg xmlns="http://www.w3.org/2000/svg" width="20mm" height="20mm" viewBox="0 0 20 20">
+            <path d="M 1 1 L 9 1"/>
+            <g><path d="M 1 9 L 9 9"/></g>
+            <rect x="1" y="11" width="8" height="8"/>
+        </svg>"#;
+        let document = roxmltree::Document::parse(svg).unwrap();
+        let machine = Machine::new(SupportedFunctionality::default(), None, None, None, None, None);
+        let mut fractions = vec![];
+        svg2program_with_progress(
+            &document,
+            &ConversionConfig::default(),
+            ConversionOptions::default(),
+            machine,
+            &mut |fraction| fractions.push(fraction),
+        );
+
+        assert!(!fractions.is_empty());
+        assert!(fractions.windows(2).all(|pair| pair[0] <= pair[1]), "{fractions:?}");
+        assert!((fractions.last().unwrap() - 1.).abs() < 1e-6, "{fractions:?}");
+    }
+
+    #[test]
+    fn min_feedrate_floors_scaled_feedrates() {
+        let svg = r#"<svg xmlns="http://www.w3.org/2000/svg" width="40mm" height="40mm" viewBox="0 0 40 40">
+            <path d="M 30 30 A 1 1 0 0 1 31 29"/>
+        </svg>"#;
+        let arc_feedrate = |min_feedrate: Option<f64>| {
+            let machine_config = MachineConfig {
+                supported_functionality: SupportedFunctionality {
+                    circular_interpolation: true,
+                },
+                arc_feedrate_scaling: Some(ArcFeedConfig {
+                    reference_radius: 1000.,
+                }),
+                min_feedrate,
+                ..Default::default()
+            };
+            let config = ConversionConfig {
+                origin: [None, None],
+                feedrate: 300.,
+                ..Default::default()
+            };
+            let code = to_gcode(svg, &config, ConversionOptions::default(), &machine_config);
+            let words = code
+                .split_whitespace()
+                .map(|word| word.split(';').next().unwrap().to_string())
+                .collect::<Vec<_>>();
+            let arc = words.iter().position(|word| word == "G2").unwrap();
+            let feedrate = words[arc..].iter().find_map(|word| word.strip_prefix('F'));
+            feedrate.unwrap().parse::<f64>().unwrap()
+        };
+
+        assert!(arc_feedrate(None) < 1.);
+        assert!((arc_feedrate(Some(10.)) - 10.).abs() < TOLERANCE);
+    }
+
+    #[test]
+    fn layer_params_override_feedrate_per_group() {
+        let svg = r#"<svg xmlns="http://www.w3.org/2000/svg" width="20mm" height="20mm" viewBox="0 0 20 20">
+            <g id="layer1"><path d="M 1 1 L 9 1 L 9 5"/></g>
+            <g id="layer2"><path d="M 1 9 L 9 9 L 9 13"/></g>
+            <path d="M 1 19 L 9 19"/>
+        </svg>"#;
+        let config = ConversionConfig {
+            layer_params: [("layer1", 500., 1000., -2.), ("layer2", 1500., 2000., -3.)]
+                .map(|(id, feedrate, power, cut_z)| {
+                    let params = LayerParams {
+                        feedrate: Some(feedrate),
+                        power: Some(power),
+                        cut_z: Some(cut_z),
+                    };
+                    (id.to_string(), params)
+                })
+                .into(),
+            ..Default::default()
+        };
+        let machine_config = MachineConfig {
+            z_axis: Some(ZAxisConfig {
+                safe_z: 5.,
+                cut_z: -1.,
+            }),
+            ..Default::default()
+        };
+        let code = to_gcode(svg, &config, ConversionOptions::default(), &machine_config);
+
+        // Inline comments are attached to the last word
+        let words = code
+            .split_whitespace()
+            .map(|word| word.split(';').next().unwrap())
+            .collect::<Vec<_>>();
+        let feedrates = words
+            .iter()
+            .filter_map(|word| word.strip_prefix('F'))
+            .map(|feedrate| feedrate.parse::<f64>().unwrap())
+            .collect::<Vec<_>>();
+        // Back to the configured feedrate outside of the groups
+        assert_eq!(
+            feedrates,
+            [500., 500., 500., 1500., 1500., 1500., 300., 300.],
+            "{code}"
+        );
+        // Only when the tool is turned on, not on every cut
+        let powers = words.iter().filter(|word| word.starts_with('S'));
+        assert_eq!(powers.collect::<Vec<_>>(), [&"S1000", &"S2000"], "{code}");
+        let plunges = code
+            .lines()
+            .filter(|line| line.starts_with("G1 Z"))
+            .collect::<Vec<_>>();
+        assert_eq!(plunges, ["G1 Z-2 F500", "G1 Z-3 F1500", "G1 Z-1 F300"], "{code}");
+    }
+
+    #[test]
+    fn fill_only_shapes_are_traced_as_outlines() {
+        let svg = r#"<svg xmlns="http://www.w3.org/2000/svg" width="20mm" height="20mm" viewBox="0 0 20 20">
+            <rect id="filled" x="1" y="1" width="8" height="8" fill="red"/>
+            <rect id="stroked" x="11" y="11" width="8" height="8" fill="none" stroke="blue"/>
+        </svg>"#;
+        let document = roxmltree::Document::parse(svg).unwrap();
+        let traced = |trace_fill_as_outline| {
+            let config = ConversionConfig {
+                trace_fill_as_outline,
+                ..Default::default()
+            };
+            svg2paths(&document, &config, ConversionOptions::default())
+                .into_iter()
+                .map(|subpath| (subpath.id.unwrap(), subpath.closed))
+                .collect::<Vec<_>>()
+        };
+
+        assert_eq!(
+            traced(true),
+            [("filled".to_string(), true), ("stroked".to_string(), true)]
+        );
+        assert_eq!(traced(false), [("stroked".to_string(), true)]);
+    }
+
+    #[test]
+    fn stroke_repeats_cut_back_and_forth() {
+        let svg = r#"<svg xmlns="http://www.w3.org/2000/svg" width="20mm" height="20mm" viewBox="0 0 20 20">
+            <path d="M 1 1 L 9 1 L 9 9"/>
+            <path d="M 15 15 Q 19 15 19 19"/>
+        </svg>"#;
+        let moves = |stroke_repeats| {
+            let config = ConversionConfig {
+                stroke_repeats,
+                ..Default::default()
+            };
+            let code = to_gcode(
+                svg,
+                &config,
+                ConversionOptions::default(),
+                &MachineConfig::default(),
+            );
+            let count = |command: &str| {
+                code.lines()
+                    .filter(|line| line.starts_with(command))
+                    .count()
+            };
+            (count("G0"), count("G1"))
+        };
+
+        let (travels, cuts) = moves(1);
+        assert_eq!(travels, 2);
+        assert_eq!(moves(3), (travels, 3 * cuts));
+    }
+
+    #[test]
+    fn order_by_containment_cuts_hole_first() {
+        let svg = r#"<svg xmlns="http://www.w3.org/2000/svg" width="20mm" height="20mm" viewBox="0 0 20 20">
+            <path d="M 0 0 L 20 0 L 20 20 L 0 20 Z"/>
+            <path d="M 5 5 L 15 5 L 15 15 L 5 15 Z"/>
+        </svg>"#;
+        let first_rapid = |order_by_containment: bool| {
+            let config = ConversionConfig {
+                order_by_containment,
+                ..Default::default()
+            };
+            let code = to_gcode(
+                svg,
+                &config,
+                ConversionOptions::default(),
+                &MachineConfig::default(),
+            );
+            let line = code.lines().find(|line| line.starts_with("G0 X")).unwrap();
+            let mut coordinates = line
+                .split_whitespace()
+                .skip(1)
+                .map(|word| word.split(';').next().unwrap()[1..].parse::<f64>().unwrap());
+            [coordinates.next().unwrap(), coordinates.next().unwrap()]
+        };
+
+        let outer = first_rapid(false);
+        assert!(outer[0].abs() < TOLERANCE && (outer[1] - 20.).abs() < TOLERANCE, "{outer:?}");
+        // Y is flipped, so the inner square starts at (5, 20 - 5)
+        let inner = first_rapid(true);
+        assert!((inner[0] - 5.).abs() < TOLERANCE && (inner[1] - 15.).abs() < TOLERANCE, "{inner:?}");
+    }
+
+    #[test]
+    fn order_by_containment_keeps_titles_with_their_subpaths() {
+        let svg = r#"<svg xmlns="http://www.w3.org/2000/svg" width="20mm" height="20mm" viewBox="0 0 20 20">
+            <path d="M 0 0 L 20 0 L 20 20 L 0 20 Z">
+                <title>Outer</title>
+            </path>
+            <path d="M 5 5 L 15 5 L 15 15 L 5 15 Z">
+                <title>Inner</title>
+                <desc>Hole</desc>
+            </path>
+        </svg>"#;
+        let document = roxmltree::Document::parse(svg).unwrap();
+        let conversion = ConversionConfig {
+            emit_titles: true,
+            order_by_containment: true,
+            ..Default::default()
+        };
+        let machine = Machine::new(Default::default(), None, None, None, None, None);
+        let program = svg2program(&document, &conversion, Default::default(), machine);
+        let config = PostprocessConfig {
+            newline_before_comment: true,
+            ..Default::default()
+        };
+        let code = tokens_to_gcode(&program, &config).unwrap();
+
+        let lines = code.lines().collect::<Vec<_>>();
+        let position = |expected: &str| lines.iter().position(|line| *line == expected);
+        let inner_title = position(";title: Inner");
+        let inner_desc = position(";desc: Hole");
+        let inner_rapid = position("G0 X5 Y15");
+        let outer_title = position(";title: Outer");
+        let outer_rapid = position("G0 X0 Y20");
+        assert!(
+            inner_title.is_some()
+                && inner_title < inner_desc
+                && inner_desc < inner_rapid
+                && inner_rapid < outer_title
+                && outer_title < outer_rapid,
+            "{code}"
+        );
+    }
+
+    #[test]
+    fn subpath_order_is_deterministic() {
+        // Subpaths are only reordered by containment, which has no random component, so every
+        // run cuts them in the same order
+        let svg = r#"<svg xmlns="http://www.w3.org/2000/svg" width="40mm" height="20mm" viewBox="0 0 40 20">
+            <path d="M 0 0 L 20 0 L 20 20 L 0 20 Z M 5 5 L 15 5 L 15 15 L 5 15 Z"/>
+            <path d="M 20 0 L 40 0 L 40 20 L 20 20 Z"/>
+            <path d="M 25 5 L 35 5 L 35 15 L 25 15 Z"/>
+            <path d="M 25 5 L 35 5 L 35 15 L 25 15 Z"/>
+        </svg>"#;
+        let config = ConversionConfig {
+            order_by_containment: true,
+            ..Default::default()
+        };
+        let convert = || {
+            to_gcode(
+                svg,
+                &config,
+                ConversionOptions::default(),
+                &MachineConfig::default(),
+            )
+        };
+
+        let first = convert();
+        for _ in 0..10 {
+            assert_eq!(convert(), first);
+        }
+    }
+
+    #[test]
+    fn bounding_box_matches_program_extents() {
+        for svg in [
+            include_str!("../tests/square.svg"),
+            include_str!("../tests/square_transformed.svg"),
+            include_str!("../tests/square_viewport.svg"),
+            include_str!("../tests/shapes.svg"),
+            include_str!("../tests/smooth_curves.svg"),
+        ] {
+            let document = roxmltree::Document::parse(svg).unwrap();
+            let config = ConversionConfig::default();
+            let code = to_gcode(
+                svg,
+                &config,
+                ConversionOptions::default(),
+                &MachineConfig::default(),
+            );
+            let mut extents = lyon_geom::Box2D::new(
+                lyon_geom::point(f64::INFINITY, f64::INFINITY),
+                lyon_geom::point(f64::NEG_INFINITY, f64::NEG_INFINITY),
+            );
+            // Inline comments are attached to the last word
+            for word in code
+                .split_whitespace()
+                .map(|word| word.split(';').next().unwrap())
+            {
+                match (word.get(..1), word.get(1..).map(str::parse::<f64>)) {
+                    (Some("X"), Some(Ok(x))) => {
+                        extents.min.x = extents.min.x.min(x);
+                        extents.max.x = extents.max.x.max(x);
+                    }
+                    (Some("Y"), Some(Ok(y))) => {
+                        extents.min.y = extents.min.y.min(y);
+                        extents.max.y = extents.max.y.max(y);
+                    }
+                    _ => {}
+                }
+            }
+
+            let bounding_box = svg_bounding_box(&document, &config, ConversionOptions::default());
+            // Curves are flattened a little differently for g-code
+            for (actual, expected) in [
+                (bounding_box.min, extents.min),
+                (bounding_box.max, extents.max),
+            ] {
+                assert!(
+                    (actual - expected).length() < config.tolerance,
+                    "{bounding_box:?} != {extents:?}"
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn stroke_line_style_is_surfaced_on_subpaths() {
+        let svg = r#"<svg xmlns="http://www.w3.org/2000/svg" width="20mm" height="20mm" viewBox="0 0 20 20">
+            <path d="M 1 1 L 9 1 L 9 9" stroke-linejoin="round"/>
+            <g style="stroke-linecap: square">
+                <path d="M 1 19 L 9 19"/>
+            </g>
+        </svg>"#;
+        let document = roxmltree::Document::parse(svg).unwrap();
+        let subpaths = svg2paths(&document, &ConversionConfig::default(), ConversionOptions::default());
+
+        assert_eq!(subpaths.len(), 2);
+        assert_eq!(subpaths[0].stroke_linejoin, LineJoin::Round);
+        assert_eq!(subpaths[0].stroke_linecap, LineCap::Butt);
+        // Inherited from the group
+        assert_eq!(subpaths[1].stroke_linejoin, LineJoin::Miter);
+        assert_eq!(subpaths[1].stroke_linecap, LineCap::Square);
+    }
+
+    #[test]
+    fn stroke_miterlimit_is_surfaced_on_subpaths() {
+        let svg = r#"<svg xmlns="http://www.w3.org/2000/svg" width="20mm" height="20mm" viewBox="0 0 20 20">
+            <path d="M 1 1 L 9 1 L 1 2"/>
+            <g stroke-miterlimit="10">
+                <path d="M 1 9 L 9 9 L 1 10"/>
+                <path d="M 1 19 L 9 19 L 1 18" style="stroke-miterlimit: 0.5"/>
+            </g>
+        </svg>"#;
+        let document = roxmltree::Document::parse(svg).unwrap();
+        let subpaths = svg2paths(&document, &ConversionConfig::default(), ConversionOptions::default());
+
+        assert_eq!(subpaths.len(), 3);
+        assert_eq!(subpaths[0].stroke_miterlimit, 4.);
+        // Inherited from the group
+        assert_eq!(subpaths[1].stroke_miterlimit, 10.);
+        // Below 1 is invalid and discarded, so it is still inherited from the group
+        assert_eq!(subpaths[2].stroke_miterlimit, 10.);
+    }
+
+    #[cfg(feature = "tracing")]
+    #[test]
+    fn warnings_are_traced_with_element_context() {
+        let svg = r#"<svg xmlns="http://www.w3.org/2000/svg" width="20mm" height="20mm" viewBox="0 0 20 20">
+            <g id="layer">
+                <rect id="sized-in-em" width="1em" height="1em"/>
+            </g>
+        </svg>"#;
+        let document = roxmltree::Document::parse(svg).unwrap();
+        let events = capture_events(|| {
+            svg2paths(&document, &ConversionConfig::default(), ConversionOptions::default());
+        });
+
+        let context = &events
+            .iter()
+            .find(|event| event.message.contains("em/ex"))
+            .expect("em/ex conversion is reported")
+            .context;
+        // Innermost span last
+        assert_eq!(
+            context[context.len() - 4..],
+            ["tag=g", "id=layer", "tag=rect", "id=sized-in-em"],
+            "{context:?}"
         );
-        converter::svg2program(&document, &config, options, machine)
     }
 
-    fn assert_close(left: Vec<Token<'_>>, right: Vec<Token<'_>>) {
-        let mut code = String::new();
-        g_code::emit::format_gcode_fmt(left.iter(), FormatOptions::default(), &mut code).unwrap();
-        assert_eq!(left.len(), right.len(), "{code}");
-        for (i, pair) in left.into_iter().zip(right.into_iter()).enumerate() {
-            match pair {
-                (Token::Field(l), Token::Field(r)) => {
-                    assert_eq!(l.letters, r.letters);
-                    if let (Some(l_value), Some(r_value)) = (l.value.as_f64(), r.value.as_f64()) {
-                        assert!(
-                            (l_value - r_value).abs() < TOLERANCE,
-                            "Values differ significantly at {i}: {l} vs {r} ({})",
-                            (l_value - r_value).abs()
-                        );
-                    } else {
-                        assert_eq!(l, r);
-                    }
-                }
-                (l, r) => {
-                    assert_eq!(l, r, "Differs at {i}");
-                }
-            }
+    #[test]
+    fn rect_percentages_resolve_against_innermost_viewport() {
+        let svg = r#"<svg xmlns="http://www.w3.org/2000/svg" width="400mm" height="400mm" viewBox="0 0 400 400">
+            <svg x="10" y="10" width="200" height="100">
+                <g>
+                    <rect x="10%" y="10%" width="50%" height="50%"/>
+                </g>
+            </svg>
+        </svg>"#;
+        let document = roxmltree::Document::parse(svg).unwrap();
+        let bounding_box = svg_bounding_box(
+            &document,
+            &ConversionConfig::default(),
+            ConversionOptions::default(),
+        );
+        // The 200 by 100 unit viewport, not the outer 400 by 400 one
+        assert!((bounding_box.width() - 100.).abs() < TOLERANCE, "{bounding_box:?}");
+        assert!((bounding_box.height() - 50.).abs() < TOLERANCE, "{bounding_box:?}");
+        assert!((bounding_box.min.x - 30.).abs() < TOLERANCE, "{bounding_box:?}");
+    }
+
+    #[test]
+    fn anchor_children_are_converted() {
+        let svg = r#"<svg xmlns="http://www.w3.org/2000/svg" width="20mm" height="20mm" viewBox="0 0 20 20">
+            <a href="https://example.com" transform="translate(5 0)">
+                <rect x="1" y="1" width="4" height="2"/>
+            </a>
+        </svg>"#;
+        let document = roxmltree::Document::parse(svg).unwrap();
+        let bounding_box = svg_bounding_box(
+            &document,
+            &ConversionConfig::default(),
+            ConversionOptions::default(),
+        );
+        assert!((bounding_box.min.x - 6.).abs() < TOLERANCE, "{bounding_box:?}");
+        assert!((bounding_box.width() - 4.).abs() < TOLERANCE, "{bounding_box:?}");
+        assert!((bounding_box.height() - 2.).abs() < TOLERANCE, "{bounding_box:?}");
+    }
+
+    #[test]
+    fn tolerance_is_in_millimeters_at_any_dpi() {
+        // A circle of radius 10mm centered at (10mm, 10mm)
+        let svg = r#"<svg xmlns="http://www.w3.org/2000/svg" width="20mm" height="20mm" viewBox="0 0 20 20">
+            <circle cx="10" cy="10" r="10"/>
+        </svg>"#;
+        let max_deviation = |dpi| {
+            let config = ConversionConfig {
+                tolerance: 0.1,
+                dpi,
+                origin: [None, None],
+                ..Default::default()
+            };
+            let code = to_gcode(
+                svg,
+                &config,
+                ConversionOptions::default(),
+                &MachineConfig::default(),
+            );
+            let points = code
+                .lines()
+                .filter_map(|line| {
+                    let word = |letter| {
+                        line.split_whitespace()
+                            .find_map(|word| word.strip_prefix(letter)?.parse::<f64>().ok())
+                    };
+                    Some(lyon_geom::point(word('X')?, word('Y')?))
+                })
+                .collect::<Vec<_>>();
+            // Chords are furthest from the circle at their midpoints
+            let center = lyon_geom::point(10., 10.);
+            points
+                .windows(2)
+                .map(|chord| 10. - (chord[0].lerp(chord[1], 0.5) - center).length())
+                .fold(0., f64::max)
+        };
+
+        for dpi in [96., 300.] {
+            let deviation = max_deviation(dpi);
+            // Not flattened any finer than needed, as a tolerance in user units would be
+            assert!(deviation > 0.05 && deviation <= 0.1 + TOLERANCE, "{dpi}: {deviation}");
         }
     }
 
     #[test]
-    fn square_produces_expected_gcode() {
-        let expected = g_code::parse::file_parser(include_str!("../tests/square.gcode"))
-            .unwrap()
-            .iter_emit_tokens()
+    fn skipped_elements_are_summarized() {
+        let svg = r#"<svg xmlns="http://www.w3.org/2000/svg" width="20mm" height="20mm" viewBox="0 0 20 20">
+            <text x="1" y="5">A</text>
+            <image href="photo.png" width="5" height="5"/>
+            <rect width="0" height="5"/>
+            <text x="1" y="15">B</text>
+            <path d="M 1 1 L 5 1"/>
+        </svg>"#;
+        let config = ConversionConfig {
+            summarize_skipped: true,
+            on_unsupported: UnsupportedPolicy::Ignore,
+            ..Default::default()
+        };
+        let code = to_gcode(
+            svg,
+            &config,
+            ConversionOptions::default(),
+            &MachineConfig::default(),
+        );
+        // Comments are only on a line of their own when a command word follows
+        let comments = code
+            .split(';')
+            .skip(1)
+            .filter_map(|comment| comment.lines().next())
+            .filter(|comment| comment.starts_with(" Skipped"))
             .collect::<Vec<_>>();
-        let actual = get_actual(include_str!("../tests/square.svg"), false, [None; 2]);
 
-        assert_close(actual, expected);
+        // Text is only traced with the text feature
+        let expected = if cfg!(feature = "text") {
+            " Skipped: image(1), rect(1)"
+        } else {
+            " Skipped: text(2), image(1), rect(1)"
+        };
+        assert_eq!(comments, [expected]);
     }
 
     #[test]
-    fn square_dimension_override_produces_expected_gcode() {
-        let side_length = Length {
-            number: 10.,
-            unit: LengthUnit::Mm,
+    fn titles_are_commented_when_emitted() {
+        let svg = r#"<svg xmlns="http://www.w3.org/2000/svg" width="20mm" height="20mm" viewBox="0 0 20 20">
+            <path id="outline" d="M 1 1 L 5 1">
+                <title>Outline</title>
+                <desc>
+                    Cut   last
+                </desc>
+            </path>
+        </svg>"#;
+        let document = roxmltree::Document::parse(svg).unwrap();
+        let format = |emit_titles| {
+            let conversion = ConversionConfig {
+                emit_titles,
+                ..Default::default()
+            };
+            let machine = Machine::new(Default::default(), None, None, None, None, None);
+            let mut program = svg2program(&document, &conversion, Default::default(), machine);
+            let config = PostprocessConfig {
+                annotate_subpaths: true,
+                newline_before_comment: true,
+                ..Default::default()
+            };
+            config.apply(&mut program, conversion.feedrate_units);
+            tokens_to_gcode(&program, &config).unwrap()
         };
 
-        let expected = g_code::parse::file_parser(include_str!("../tests/square.gcode"))
-            .unwrap()
-            .iter_emit_tokens()
-            .collect::<Vec<_>>();
+        let emitted = format(true);
+        let lines = emitted.lines().collect::<Vec<_>>();
+        let title = lines.iter().position(|line| *line == ";title: Outline");
+        let desc = lines.iter().position(|line| *line == ";desc: Cut last");
+        let element = lines.iter().position(|line| line.ends_with("path#outline"));
+        let first_move = lines.iter().position(|line| line.starts_with("G0"));
+        assert!(
+            title.is_some() && title < desc && desc < element && element < first_move,
+            "{emitted}"
+        );
+        // Titles are not taken for elements of their own
+        assert!(!emitted.contains(";begin title"), "{emitted}");
+        assert!(emitted.contains(";begin path#outline bbox [1,19]-[5,19]"), "{emitted}");
 
-        for square in [
-            include_str!("../tests/square.svg"),
-            include_str!("../tests/square_dimensionless.svg"),
-        ] {
-            assert_close(
-                get_actual(square, false, [Some(side_length); 2]),
-                expected.clone(),
-            );
-            assert_close(
-                get_actual(square, false, [Some(side_length), None]),
-                expected.clone(),
+        let omitted = format(false);
+        assert!(!omitted.contains("title:"), "{omitted}");
+        assert!(!omitted.contains("desc:"), "{omitted}");
+    }
+
+    #[test]
+    fn drawing_is_scaled_to_cut_length() {
+        let svg = r#"<svg xmlns="http://www.w3.org/2000/svg" width="20mm" height="20mm" viewBox="0 0 20 20">
+            <rect x="0" y="0" width="10" height="5"/>
+            <circle cx="15" cy="15" r="3"/>
+        </svg>"#;
+        let document = roxmltree::Document::parse(svg).unwrap();
+        let config = ConversionConfig::default();
+        let cut_length = |scale_to_cut_length| {
+            let options = ConversionOptions {
+                scale_to_cut_length,
+                ..Default::default()
+            };
+            svg2paths(&document, &config, options)
+                .iter()
+                .map(|subpath| subpath.length)
+                .sum::<f64>()
+        };
+        assert!((cut_length(None) - 30. - 6. * std::f64::consts::PI).abs() < 0.1);
+        // Curves are flattened again at the new scale, so they come out a little longer
+        assert!((cut_length(Some(250.)) - 250.).abs() < 0.05);
+    }
+
+    #[test]
+    fn command_filter_draws_only_curves() {
+        let config = ConversionConfig::default();
+        let subpaths = |d: &str, command_filter| {
+            let svg = format!(
+                r#"<svg xmlns="http://www.w3.org/2000/svg" width="20mm" height="20mm" viewBox="0 0 20 20">
+                    <path d="{d}"/>
+                </svg>"#
             );
-            assert_close(
-                get_actual(square, false, [None, Some(side_length)]),
-                expected.clone(),
+            let document = roxmltree::Document::parse(&svg).unwrap();
+            let options = ConversionOptions {
+                command_filter,
+                ..Default::default()
+            };
+            svg2paths(&document, &config, options)
+                .into_iter()
+                .filter(|subpath| subpath.length > 0.)
+                .collect::<Vec<_>>()
+        };
+        let curves_only = CommandFilter {
+            lines: false,
+            curves: true,
+            arcs: false,
+        };
+
+        let filtered = subpaths(
+            "M 0 0 L 10 0 C 10 5 5 10 0 10 A 5 5 0 0 1 0 0 Z",
+            Some(curves_only),
+        );
+        let curve = subpaths("M 10 0 C 10 5 5 10 0 10", None);
+        assert_eq!(filtered.len(), 1);
+        assert!((filtered[0].length - curve[0].length).abs() < TOLERANCE);
+        assert_eq!(filtered[0].points.first(), curve[0].points.first());
+        assert_eq!(filtered[0].points.last(), curve[0].points.last());
+    }
+
+    #[test]
+    fn relative_tolerance_flattens_any_size_into_as_many_segments() {
+        let segment_count = |size: f64, tolerance_mode| {
+            let svg = format!(
+                r#"<svg xmlns="http://www.w3.org/2000/svg" width="{size}mm" height="{size}mm" viewBox="0 0 20 20">
+                    <circle cx="10" cy="10" r="8"/>
+                </svg>"#
             );
+            let document = roxmltree::Document::parse(&svg).unwrap();
+            let config = ConversionConfig {
+                tolerance: 0.001,
+                tolerance_mode,
+                ..Default::default()
+            };
+            svg2paths(&document, &config, ConversionOptions::default())
+                .iter()
+                .map(|subpath| subpath.points.len() - 1)
+                .sum::<usize>()
+        };
+
+        let small = segment_count(20., ToleranceMode::RelativeToDiagonal);
+        let large = segment_count(200., ToleranceMode::RelativeToDiagonal);
+        assert_eq!(small, large);
+        // An absolute tolerance flattens the larger circle more finely
+        assert!(
+            segment_count(200., ToleranceMode::Absolute) > segment_count(20., ToleranceMode::Absolute)
+        );
+    }
+
+    #[test]
+    fn unknown_transform_function_falls_back_to_identity() {
+        let svg = r#"<svg xmlns="http://www.w3.org/2000/svg" width="20mm" height="20mm" viewBox="0 0 20 20">
+            <g transform="translate(5) ref(svg)">
+                <path d="M 1 1 L 9 1"/>
+            </g>
+            <path transform="ref(svg, 2, 3)" d="M 1 5 L 9 5"/>
+            <path transform="translate(0, 10)" d="M 1 5 L 9 5"/>
+        </svg>"#;
+        let document = roxmltree::Document::parse(svg).unwrap();
+        let points = svg2paths(&document, &ConversionConfig::default(), ConversionOptions::default())
+            .into_iter()
+            .map(|subpath| subpath.points)
+            .collect::<Vec<_>>();
+        assert_eq!(points.len(), 3);
+        // The whole transform list is ignored, not just the unknown function
+        for (points, y) in points.iter().zip([19., 15., 5.]) {
+            assert!((points[0][0] - 1.).abs() < TOLERANCE, "{points:?}");
+            assert!((points[0][1] - y).abs() < TOLERANCE, "{points:?}");
         }
     }
 
     #[test]
-    fn square_transformed_produces_expected_gcode() {
-        let square_transformed = include_str!("../tests/square_transformed.svg");
-        let expected =
-            g_code::parse::file_parser(include_str!("../tests/square_transformed.gcode"))
-                .unwrap()
-                .iter_emit_tokens()
-                .collect::<Vec<_>>();
-        let actual = get_actual(square_transformed, false, [None; 2]);
+    fn unknown_transform_function_is_reported_once() {
+        let svg = r#"<svg xmlns="http://www.w3.org/2000/svg" width="20mm" height="20mm" viewBox="0 0 20 20">
+            <path id="referenced" transform="ref(svg)" d="M 1 5 L 9 5"/>
+        </svg>"#;
+        let machine_config = MachineConfig {
+            z_axis: Some(ZAxisConfig {
+                safe_z: 5.,
+                cut_z: -1.,
+            }),
+            passes: Some(MultiPassConfig {
+                count: 2,
+                depth_per_pass: 1.,
+            }),
+            ..Default::default()
+        };
+        let options = ConversionOptions {
+            emit_bounding_frame: true,
+            ..Default::default()
+        };
+        let warnings = capture_warnings(|| {
+            to_gcode(svg, &ConversionConfig::default(), options, &machine_config);
+        });
 
-        assert_close(actual, expected)
+        // Not repeated by the placement preprocessing, the bounding frame or the second pass
+        assert_eq!(
+            warnings
+                .iter()
+                .filter(|warning| warning.starts_with("Ignoring transform of path#referenced"))
+                .count(),
+            1,
+            "{warnings:?}"
+        );
     }
 
     #[test]
-    fn square_transformed_nested_produces_expected_gcode() {
-        let square_transformed = include_str!("../tests/square_transformed_nested.svg");
-        let expected =
-            g_code::parse::file_parser(include_str!("../tests/square_transformed_nested.gcode"))
-                .unwrap()
-                .iter_emit_tokens()
-                .collect::<Vec<_>>();
-        let actual = get_actual(square_transformed, false, [None; 2]);
+    fn dimension_accepts_comma_decimal() {
+        use svgtypes::{Length, LengthUnit};
 
-        assert_close(actual, expected)
+        assert_eq!(parse_dimension("12,5mm").unwrap(), Length::new(12.5, LengthUnit::Mm));
+        assert_eq!(parse_dimension(" 12.5mm ").unwrap(), Length::new(12.5, LengthUnit::Mm));
+        assert!(matches!(
+            parse_dimension("12mm 5mm"),
+            Err(ConversionError::InvalidDimension { .. })
+        ));
     }
 
     #[test]
-    fn square_viewport_produces_expected_gcode() {
-        let square_viewport = include_str!("../tests/square_viewport.svg");
-        let expected = g_code::parse::file_parser(include_str!("../tests/square_viewport.gcode"))
-            .unwrap()
-            .iter_emit_tokens()
+    fn user_units_per_mm_overrides_dpi() {
+        let svg = r#"<svg xmlns="http://www.w3.org/2000/svg" width="20" height="20" viewBox="0 0 20 20">
+            <rect x="5" y="5" width="10" height="10"/>
+        </svg>"#;
+        let document = roxmltree::Document::parse(svg).unwrap();
+        let size = |config: ConversionConfig| {
+            let subpaths = svg2paths(&document, &config, ConversionOptions::default());
+            let points = subpaths[0].points.iter().copied().map(lyon_geom::Point::from);
+            lyon_geom::Box2D::from_points(points).size()
+        };
+
+        let size_mm = size(ConversionConfig {
+            user_units_per_mm: Some(1.),
+            ..Default::default()
+        });
+        assert!((size_mm.width - 10.).abs() < TOLERANCE);
+        assert!((size_mm.height - 10.).abs() < TOLERANCE);
+        // Otherwise user units are pixels at 96 DPI
+        let size_px = size(ConversionConfig::default());
+        assert!((size_px.width - 10. / 96. * 25.4).abs() < TOLERANCE);
+    }
+
+    #[test]
+    fn astronomical_coordinates_are_reported() {
+        let svg = r#"<svg xmlns="http://www.w3.org/2000/svg" width="20mm" height="20mm" viewBox="0 0 20 20">
+            <path id="fine" d="M 1 1 L 10 10"/>
+            <path id="huge" transform="scale(1000000000)" d="M 1 1 L 10 10 M 2 2 L 3 3"/>
+        </svg>"#;
+        let config = ConversionConfig {
+            origin: [None, None],
+            max_abs_coordinate: Some(10000.),
+            ..Default::default()
+        };
+        let warnings = capture_warnings(|| {
+            to_gcode(
+                svg,
+                &config,
+                ConversionOptions::default(),
+                &MachineConfig::default(),
+            );
+        });
+        // Reported once for each subpath of the offending path only
+        assert_eq!(warnings.len(), 2, "{warnings:?}");
+        assert!(
+            warnings
+                .iter()
+                .all(|warning| warning.contains("path#huge") && warning.contains("exceeds")),
+            "{warnings:?}"
+        );
+    }
+
+    #[test]
+    fn arcs_below_min_radius_are_flattened() {
+        // Clockwise quarter circle from (0, 10) to (10, 20) around (10, 10), in G-code coordinates
+        let svg = r#"<svg xmlns="http://www.w3.org/2000/svg" width="40mm" height="40mm" viewBox="0 0 40 40">
+            <path d="M 0 30 A 10 10 0 0 1 10 20"/>
+        </svg>"#;
+        let machine_config = MachineConfig {
+            supported_functionality: SupportedFunctionality {
+                circular_interpolation: true,
+            },
+            ..Default::default()
+        };
+        let config = ConversionConfig {
+            origin: [None, None],
+            tolerance: 0.1,
+            min_arc_radius: Some(20.),
+            ..Default::default()
+        };
+        let code = to_gcode(svg, &config, ConversionOptions::default(), &machine_config);
+
+        let points = code
+            .lines()
+            .filter(|line| line.starts_with("G1 "))
+            .filter_map(|line| {
+                let word = |letter| {
+                    line.split_whitespace()
+                        .find_map(|word| word.strip_prefix(letter)?.parse::<f64>().ok())
+                };
+                Some([word('X')?, word('Y')?])
+            })
             .collect::<Vec<_>>();
-        let actual = get_actual(square_viewport, false, [None; 2]);
+        assert!(!code.lines().any(|line| line.starts_with("G2 ")), "{code}");
+        // Several lines instead of a single chord, all of them on the circle
+        assert!(points.len() > 3, "{points:?}");
+        for [x, y] in &points {
+            let radius = ((x - 10.).powi(2) + (y - 10.).powi(2)).sqrt();
+            assert!((radius - 10.).abs() < 1e-6, "{points:?}");
+        }
+        let [x, y] = points.last().unwrap();
+        assert!((x - 10.).abs() < 1e-6 && (y - 20.).abs() < 1e-6);
+    }
 
-        assert_close(actual, expected);
+    #[test]
+    fn tolerance_attribute_tightens_flattening() {
+        let svg = r#"<svg xmlns="http://www.w3.org/2000/svg" width="40mm" height="20mm" viewBox="0 0 40 20">
+            <circle id="coarse" cx="10" cy="10" r="8"/>
+            <g data-tolerance="0.0005">
+                <circle id="fine" cx="30" cy="10" r="8"/>
+            </g>
+            <circle id="after" cx="10" cy="10" r="8"/>
+        </svg>"#;
+        let document = roxmltree::Document::parse(svg).unwrap();
+        let config = ConversionConfig {
+            tolerance: 0.1,
+            tolerance_attribute: Some("data-tolerance".to_string()),
+            ..Default::default()
+        };
+        let subpaths = svg2paths(&document, &config, ConversionOptions::default());
+        let points = |id: &str| {
+            subpaths
+                .iter()
+                .filter(|subpath| subpath.id.as_deref() == Some(id))
+                .map(|subpath| subpath.points.len())
+                .sum::<usize>()
+        };
+        assert!(points("fine") > points("coarse"));
+        // The global tolerance applies again after the group
+        assert_eq!(points("after"), points("coarse"));
     }
 
     #[test]
-    fn circular_interpolation_produces_expected_gcode() {
-        let circular_interpolation = include_str!("../tests/circular_interpolation.svg");
-        let expected =
-            g_code::parse::file_parser(include_str!("../tests/circular_interpolation.gcode"))
-                .unwrap()
-                .iter_emit_tokens()
-                .collect::<Vec<_>>();
-        let actual = get_actual(circular_interpolation, true, [None; 2]);
+    fn inner_svg_is_letterboxed_within_its_viewport() {
+        // The square view box is scaled by 2 to fit the height of the 40x20 viewport, and centered
+        // horizontally
+        let svg = r#"<svg xmlns="http://www.w3.org/2000/svg" width="100mm" height="100mm" viewBox="0 0 100 100">
+            <svg x="10" y="20" width="40" height="20" viewBox="0 0 10 10">
+                <rect width="10" height="10"/>
+            </svg>
+        </svg>"#;
+        let document = roxmltree::Document::parse(svg).unwrap();
+        let config = ConversionConfig {
+            origin: [None, None],
+            ..Default::default()
+        };
+        let subpaths = svg2paths(&document, &config, ConversionOptions::default());
+        let points = subpaths[0].points.iter().copied().map(lyon_geom::Point::from);
+        let lyon_geom::Box2D { min, max } = lyon_geom::Box2D::from_points(points);
+        // Y is flipped from the 100mm tall document
+        assert!((min - lyon_geom::point(20., 60.)).length() < TOLERANCE, "{min:?}");
+        assert!((max - lyon_geom::point(40., 80.)).length() < TOLERANCE, "{max:?}");
+    }
 
-        assert_close(actual, expected)
+    #[test]
+    fn origin_element_center_becomes_origin() {
+        let svg = r#"<svg xmlns="http://www.w3.org/2000/svg" width="20mm" height="20mm" viewBox="0 0 20 20">
+            <circle id="origin" cx="5" cy="15" r="1"/>
+            <path d="M 5 15 L 10 15"/>
+        </svg>"#;
+        let document = roxmltree::Document::parse(svg).unwrap();
+        let config = ConversionConfig {
+            origin_element: Some("origin".to_string()),
+            ..Default::default()
+        };
+        let subpaths = svg2paths(&document, &config, ConversionOptions::default());
+        // The marker is at (5, 5) once Y is flipped
+        let line = subpaths.last().unwrap();
+        let [start, end] = [0, 1].map(|i| lyon_geom::Point::from(line.points[i]));
+        assert!((start - lyon_geom::point(0., 0.)).length() < TOLERANCE, "{start:?}");
+        assert!((end - lyon_geom::point(5., 0.)).length() < TOLERANCE, "{end:?}");
     }
 
     #[test]
-    fn svg_with_smooth_curves_produces_expected_gcode() {
-        let svg = include_str!("../tests/smooth_curves.svg");
+    fn symbol_is_scaled_into_use_size() {
+        let svg = r##"<svg xmlns="http://www.w3.org/2000/svg" width="100mm" height="100mm" viewBox="0 0 100 100">
+            <symbol id="square" viewBox="0 0 10 10">
+                <rect width="10" height="10"/>
+            </symbol>
+            <use href="#square" x="10" y="20" width="20" height="20"/>
+        </svg>"##;
+        let document = roxmltree::Document::parse(svg).unwrap();
+        let config = ConversionConfig {
+            origin: [None, None],
+            ..Default::default()
+        };
+        let subpaths = svg2paths(&document, &config, ConversionOptions::default());
+        assert_eq!(subpaths.len(), 1);
+        let points = subpaths[0].points.iter().copied().map(lyon_geom::Point::from);
+        let lyon_geom::Box2D { min, max } = lyon_geom::Box2D::from_points(points);
+        // Y is flipped from the 100mm tall document
+        assert!((min - lyon_geom::point(10., 60.)).length() < TOLERANCE, "{min:?}");
+        assert!((max - lyon_geom::point(30., 80.)).length() < TOLERANCE, "{max:?}");
+    }
 
-        let expected = g_code::parse::file_parser(include_str!("../tests/smooth_curves.gcode"))
-            .unwrap()
-            .iter_emit_tokens()
-            .collect::<Vec<_>>();
+    #[test]
+    fn closepath_cuts_the_closing_side() {
+        let convert = |d: &str| {
+            let svg = format!(
+                r#"<svg xmlns="http://www.w3.org/2000/svg" width="20mm" height="20mm" viewBox="0 0 20 20">
+                    <path d="{d}"/>
+                </svg>"#
+            );
+            let document = roxmltree::Document::parse(&svg).unwrap();
+            let config = ConversionConfig {
+                origin: [None, None],
+                ..Default::default()
+            };
+            let cuts = to_gcode(
+                &svg,
+                &config,
+                ConversionOptions::default(),
+                &MachineConfig::default(),
+            )
+            .lines()
+            .filter(|line| line.starts_with("G1 "))
+            .count();
+            let subpaths = svg2paths(&document, &config, ConversionOptions::default());
+            (cuts, subpaths)
+        };
 
-        let file = if cfg!(debug) {
-            include_str!("../tests/smooth_curves_circular_interpolation.gcode")
-        } else {
-            include_str!("../tests/smooth_curves_circular_interpolation_release.gcode")
+        let (closed_cuts, closed_subpaths) = convert("M 0 0 L 10 0 L 10 10 Z");
+        assert_eq!(closed_cuts, 3);
+        assert_eq!(closed_subpaths.len(), 1);
+        assert!(closed_subpaths[0].closed);
+        assert_eq!(closed_subpaths[0].points.first(), closed_subpaths[0].points.last());
+
+        let (open_cuts, open_subpaths) = convert("M 0 0 L 10 0 L 10 10");
+        assert_eq!(open_cuts, 2);
+        assert_eq!(open_subpaths.len(), 1);
+        assert!(!open_subpaths[0].closed);
+    }
+
+    #[test]
+    fn diagonal_feedrate_is_limited_by_slower_axis() {
+        let svg = r#"<svg xmlns="http://www.w3.org/2000/svg" width="40mm" height="40mm" viewBox="0 0 40 40">
+            <path d="M 0 40 L 10 30"/>
+        </svg>"#;
+        let machine_config = MachineConfig {
+            axis_max_feedrate: Some([1000., 100.]),
+            ..Default::default()
         };
-        let expected_circular_interpolation = g_code::parse::file_parser(file)
-            .unwrap()
-            .iter_emit_tokens()
+        let config = ConversionConfig {
+            origin: [None, None],
+            feedrate: 300.,
+            ..Default::default()
+        };
+        let code = to_gcode(svg, &config, ConversionOptions::default(), &machine_config);
+
+        let words = code.split_whitespace().collect::<Vec<_>>();
+        let cut = words.iter().position(|word| *word == "G1").unwrap();
+        assert_eq!(words[cut + 1..cut + 3], ["X10", "Y10"]);
+        // Moving at 45°, Y travels at F / √2, which must not exceed 100
+        let feedrate = words[cut + 3].strip_prefix('F').unwrap().parse::<f64>().unwrap();
+        assert!((feedrate - 100. * 2f64.sqrt()).abs() < TOLERANCE, "{code}");
+    }
+
+    #[test]
+    fn bounding_frame_is_traced_before_cutting() {
+        let svg = r#"<svg xmlns="http://www.w3.org/2000/svg" width="40mm" height="40mm" viewBox="0 0 40 40">
+            <rect x="5" y="10" width="20" height="10"/>
+        </svg>"#;
+        let config = ConversionConfig {
+            origin: [None, None],
+            ..Default::default()
+        };
+        let options = ConversionOptions {
+            emit_bounding_frame: true,
+            ..Default::default()
+        };
+        let machine_config = MachineConfig {
+            tool_on_sequence: Some("M3".to_string()),
+            tool_off_sequence: Some("M5".to_string()),
+            ..Default::default()
+        };
+        let gcode = to_gcode(svg, &config, options, &machine_config);
+        let lines = gcode.lines().collect::<Vec<_>>();
+
+        let start = lines
+            .iter()
+            .position(|line| line.contains("Bounding frame"))
+            .unwrap();
+        let first_corner = start
+            + lines[start..]
+                .iter()
+                .position(|line| line.starts_with("G0 X"))
+                .unwrap();
+        // The tool is turned off before it travels around the frame, and not turned on
+        assert!(lines[start..first_corner].contains(&"M5"), "{gcode}");
+        assert!(!lines[..first_corner].contains(&"M3"), "{gcode}");
+        let corners = lines[first_corner..]
+            .iter()
+            .filter_map(|line| line.split(';').next())
+            .filter(|line| line.starts_with("G0 "))
+            .take(5)
+            .map(|line| {
+                let coord = |axis: &str| {
+                    line.split_whitespace()
+                        .find_map(|word| word.strip_prefix(axis))
+                        .and_then(|value| value.parse::<f64>().ok())
+                        .unwrap()
+                };
+                [coord("X"), coord("Y")]
+            })
             .collect::<Vec<_>>();
-        assert_close(get_actual(svg, false, [None; 2]), expected);
+        let expected = [[5., 20.], [25., 20.], [25., 30.], [5., 30.], [5., 20.]];
+        assert_eq!(corners.len(), expected.len());
+        for (corner, expected) in corners.iter().zip(expected) {
+            assert!(
+                (corner[0] - expected[0]).abs() < TOLERANCE
+                    && (corner[1] - expected[1]).abs() < TOLERANCE,
+                "{corner:?} != {expected:?}"
+            );
+        }
+        // No cutting happens before the frame is traced
+        assert!(!lines[..start].iter().any(|line| line.starts_with("G1")));
+    }
 
-        assert_close(
-            get_actual(svg, true, [None; 2]),
-            expected_circular_interpolation,
+    #[test]
+    fn cut_moves_are_annotated_with_source_coordinates() {
+        let svg = r#"<svg xmlns="http://www.w3.org/2000/svg" width="20mm" height="20mm" viewBox="0 0 20 20">
+            <g transform="translate(5 5) scale(0.5)">
+                <path d="M 2 4 L 12 8"/>
+            </g>
+        </svg>"#;
+        let config = ConversionConfig {
+            annotate_source_coords: true,
+            ..Default::default()
+        };
+        let code = to_gcode(
+            svg,
+            &config,
+            ConversionOptions::default(),
+            &MachineConfig::default(),
         );
+
+        let cut = code
+            .lines()
+            .find(|line| line.starts_with("G1 X"))
+            .expect("there is a cut move");
+        assert!(cut.ends_with("(svg 12 8)"), "{code}");
     }
 
     #[test]
-    fn shapes_produces_expected_gcode() {
-        let shapes = include_str!("../tests/shapes.svg");
-        let expected = g_code::parse::file_parser(include_str!("../tests/shapes.gcode"))
-            .unwrap()
-            .iter_emit_tokens()
+    fn detected_polygon_arcs_are_annotated_with_source_coordinates() {
+        // Part of a circle, ending at (15, 10), detected as two arcs of five points
+        let points = (0..=8)
+            .rev()
+            .map(|i| {
+                let angle = f64::from(i * 10).to_radians();
+                format!("{},{}", 10. + 5. * angle.cos(), 10. + 5. * angle.sin())
+            })
+            .collect::<Vec<_>>()
+            .join(" ");
+        let svg = format!(
+            r#"<svg xmlns="http://www.w3.org/2000/svg" width="20mm" height="20mm" viewBox="0 0 20 20">
+            <polyline points="{points}"/>
+        </svg>"#
+        );
+        let config = ConversionConfig {
+            annotate_source_coords: true,
+            detect_polygon_arcs: true,
+            ..Default::default()
+        };
+        let code = to_gcode(
+            &svg,
+            &config,
+            ConversionOptions::default(),
+            &MachineConfig::default(),
+        );
+
+        let arcs = code
+            .lines()
+            .filter(|line| line.starts_with("G2 ") || line.starts_with("G3 "))
             .collect::<Vec<_>>();
-        let actual = get_actual(shapes, false, [None; 2]);
+        assert!(arcs.iter().all(|arc| arc.contains("(svg ")), "{code}");
+        assert!(arcs.last().is_some_and(|arc| arc.ends_with("(svg 15 10)")), "{code}");
+    }
 
-        assert_close(actual, expected)
+    #[test]
+    fn post_processor_can_annotate_rapid_moves() {
+        let document = roxmltree::Document::parse(include_str!("../tests/square.svg")).unwrap();
+        let machine = Machine::new(
+            SupportedFunctionality {
+                circular_interpolation: false,
+            },
+            None,
+            None,
+            None,
+            None,
+            None,
+        );
+        let mut annotate_rapids = |program: &mut Vec<Token<'_>>| {
+            let mut i = 0;
+            while i < program.len() {
+                let is_rapid = matches!(&program[i], Token::Field(f) if f.letters == "G" && f.value.as_f64() == Some(0.));
+                i += 1;
+                if is_rapid {
+                    while matches!(&program.get(i), Some(Token::Field(f)) if f.letters != "G" && f.letters != "M")
+                    {
+                        i += 1;
+                    }
+                    program.insert(
+                        i,
+                        Token::Comment {
+                            is_inline: true,
+                            inner: "rapid".into(),
+                        },
+                    );
+                }
+            }
+        };
+        let program = svg2program_with_post_processor(
+            &document,
+            &ConversionConfig::default(),
+            ConversionOptions::default(),
+            machine,
+            &mut annotate_rapids,
+        );
+
+        let mut code = String::new();
+        g_code::emit::format_gcode_fmt(program.iter(), FormatOptions::default(), &mut code)
+            .unwrap();
+        let rapids = code.lines().filter(|l| l.starts_with("G0 ")).count();
+        assert!(rapids > 0);
+        assert_eq!(
+            code.lines().filter(|l| l.starts_with("G0 ") && l.ends_with("(rapid)")).count(),
+            rapids,
+            "{code}"
+        );
     }
 
     #[test]
@@ -437,4 +3545,46 @@ mod test {
         "#;
         serde_json::from_str::<Settings>(json).unwrap();
     }
+
+    #[test]
+    #[cfg(feature = "serde")]
+    fn settings_roundtrip_through_json_with_kebab_case_values() {
+        let settings = Settings {
+            conversion: ConversionConfig {
+                tolerance_mode: ToleranceMode::RelativeToDiagonal,
+                feedrate_units: FeedrateUnits::PerSecond,
+                paint_server_policy: PaintServerPolicy::SolidColor,
+                on_unsupported: UnsupportedPolicy::Ignore,
+                ..Default::default()
+            },
+            machine: MachineConfig {
+                program_end_command: Some(EndCommand::M30),
+                arc_distance_mode: ArcDistanceMode::RelativeEndpoints,
+                arc_format: ArcFormat::IJ,
+                path_mode: Some(PathMode::ExactStop),
+                ..Default::default()
+            },
+            postprocess: PostprocessConfig {
+                comment_style: CommentStyle::Parentheses,
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+
+        let json = serde_json::to_string(&settings).unwrap();
+        for value in [
+            r#""relative-to-diagonal""#,
+            r#""per-second""#,
+            r#""solid-color""#,
+            r#""ignore""#,
+            r#""m30""#,
+            r#""relative-endpoints""#,
+            r#""ij""#,
+            r#""exact-stop""#,
+            r#""parentheses""#,
+        ] {
+            assert!(json.contains(value), "{value} in {json}");
+        }
+        assert_eq!(serde_json::from_str::<Settings>(&json).unwrap(), settings);
+    }
 }
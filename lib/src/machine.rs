@@ -1,8 +1,9 @@
 use g_code::{
     command,
-    emit::Token,
+    emit::{Field, Token, Value},
     parse::{ast::Snippet, snippet_parser},
 };
+use lyon_geom::Vector;
 #[cfg(feature = "serde")]
 use serde::{Deserialize, Serialize};
 
@@ -32,11 +33,40 @@ pub struct Machine<'input> {
     program_begin_sequence: Snippet<'input>,
     program_end_sequence: Snippet<'input>,
     between_layers_sequence: Snippet<'input>,
+    accessory_on_sequence: Snippet<'input>,
+    accessory_off_sequence: Snippet<'input>,
+    /// Whether the accessory on sequence has been emitted
+    accessory_on: bool,
+    z_axis: Option<ZAxisConfig>,
+    separate_z_moves: bool,
+    passes: Option<MultiPassConfig>,
+    program_end_command: Option<EndCommand>,
+    arc_distance_mode: ArcDistanceMode,
+    arc_format: ArcFormat,
+    axis_max_feedrate: Option<[f64; 2]>,
+    arc_feedrate_scaling: Option<ArcFeedConfig>,
+    min_feedrate: Option<f64>,
+    tool_change: bool,
+    always_retract_between_subpaths: bool,
+    travel_retract: Option<f64>,
+    tool_change_retract: Option<f64>,
+    path_mode: Option<PathMode>,
+    priming_stroke: Option<PrimingConfig>,
+    emit_units: bool,
+    max_arcs: Option<usize>,
+    /// Number of the tool last changed to, if any
+    tool: Option<u32>,
+    /// Zero-based index of the pass being cut
+    pass: usize,
+    /// Overrides [ZAxisConfig::cut_z] for the current layer
+    cut_z: Option<f64>,
+    /// Last commanded Z height, if known
+    z: Option<f64>,
     /// Empty snippet used to provide the same iterator type when a sequence must be empty
     empty_snippet: Snippet<'input>,
 }
 
-#[derive(Debug, Default, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct MachineConfig {
     pub supported_functionality: SupportedFunctionality,
@@ -46,6 +76,214 @@ pub struct MachineConfig {
     pub end_sequence: Option<String>,
     /// G-Code sequence inserted between sibling SVG groups (layers)
     pub between_layers_sequence: Option<String>,
+    /// G-Code sequence turning on an accessory like air assist or coolant (e.g. `M7`), emitted
+    /// once when cutting first begins
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub accessory_on_sequence: Option<String>,
+    /// G-Code sequence turning the accessory off again (e.g. `M9`), emitted once at the end of
+    /// the program
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub accessory_off_sequence: Option<String>,
+    /// Drive the Z axis directly: retract before traveling and plunge before cutting
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub z_axis: Option<ZAxisConfig>,
+    /// Retract and plunge with Z-only moves instead of combining the retract into the travel move
+    #[cfg_attr(feature = "serde", serde(default = "default_separate_z_moves"))]
+    pub separate_z_moves: bool,
+    /// Repeat the whole program, cutting deeper each time. Requires [MachineConfig::z_axis].
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub passes: Option<MultiPassConfig>,
+    /// Command ending the program, emitted after [MachineConfig::end_sequence]
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub program_end_command: Option<EndCommand>,
+    /// How the endpoints of circular interpolation moves are given
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub arc_distance_mode: ArcDistanceMode,
+    /// How the center of circular interpolation moves is given
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub arc_format: ArcFormat,
+    /// Maximum X and Y axis speeds (mm/min). Cutting moves are slowed down so that neither axis
+    /// exceeds its limit.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub axis_max_feedrate: Option<[f64; 2]>,
+    /// Slow down circular interpolation moves with a small radius
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub arc_feedrate_scaling: Option<ArcFeedConfig>,
+    /// Lowest feedrate (mm/min) that [MachineConfig::axis_max_feedrate] and
+    /// [MachineConfig::arc_feedrate_scaling] may slow a move down to, so that very short or tight
+    /// moves don't stall the machine
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub min_feedrate: Option<f64>,
+    /// Change tools (`M6 T<n>`) before cutting each top-level SVG group (layer). The tool number
+    /// is taken from the group's `data-tool` attribute, else its position among the groups.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub tool_change: bool,
+    /// Turn the tool off and retract before every subpath, even one starting where the last
+    /// ended. Takes precedence over [crate::ConversionConfig::join_gap].
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub always_retract_between_subpaths: bool,
+    /// Height (mm) to retract to for traveling between subpaths, instead of
+    /// [ZAxisConfig::safe_z]. Requires [MachineConfig::z_axis].
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub travel_retract: Option<f64>,
+    /// Height (mm) to retract to for traveling to the next layer, i.e. before the
+    /// [MachineConfig::between_layers_sequence] or a [MachineConfig::tool_change], instead of
+    /// [ZAxisConfig::safe_z]. Requires [MachineConfig::z_axis].
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub tool_change_retract: Option<f64>,
+    /// Path control mode selected at the start of the program
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub path_mode: Option<PathMode>,
+    /// Cut a short back-and-forth stroke after the program begins and before the drawing, e.g.
+    /// to get ink flowing or test the tool
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub priming_stroke: Option<PrimingConfig>,
+    /// Select millimeters (G21) at the start of the program. Turn off when appending to a job
+    /// that already sets the units.
+    #[cfg_attr(feature = "serde", serde(default = "default_emit_units"))]
+    pub emit_units: bool,
+    /// Most circular interpolation moves (G2/G3) in the program, for controllers with a limited
+    /// arc buffer. Arcs past it are flattened into lines.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub max_arcs: Option<usize>,
+}
+
+const fn default_separate_z_moves() -> bool {
+    true
+}
+
+const fn default_emit_units() -> bool {
+    true
+}
+
+impl Default for MachineConfig {
+    fn default() -> Self {
+        Self {
+            supported_functionality: Default::default(),
+            tool_on_sequence: None,
+            tool_off_sequence: None,
+            begin_sequence: None,
+            end_sequence: None,
+            between_layers_sequence: None,
+            accessory_on_sequence: None,
+            accessory_off_sequence: None,
+            z_axis: None,
+            separate_z_moves: default_separate_z_moves(),
+            passes: None,
+            program_end_command: None,
+            arc_distance_mode: Default::default(),
+            arc_format: Default::default(),
+            axis_max_feedrate: None,
+            arc_feedrate_scaling: None,
+            min_feedrate: None,
+            tool_change: false,
+            always_retract_between_subpaths: false,
+            travel_retract: None,
+            tool_change_retract: None,
+            path_mode: None,
+            priming_stroke: None,
+            emit_units: default_emit_units(),
+            max_arcs: None,
+        }
+    }
+}
+
+/// Standard commands for ending a program
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "kebab-case"))]
+pub enum EndCommand {
+    /// End the program
+    M2,
+    /// End the program and rewind it to the beginning
+    M30,
+}
+
+/// Path control modes, trading accuracy at corners for continuous motion
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "kebab-case"))]
+pub enum PathMode {
+    /// Blend consecutive moves, deviating at most `tolerance` (mm) from the path (G64 P)
+    ///
+    /// The tolerance defaults to [crate::ConversionConfig::tolerance].
+    Blend { tolerance: Option<f64> },
+    /// Stop exactly at the end of every move (G61)
+    ExactStop,
+}
+
+/// Stroke cut before the drawing, see [MachineConfig::priming_stroke]
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct PrimingConfig {
+    /// Point (mm) the stroke starts and ends at
+    pub position: [f64; 2],
+    /// Distance (mm) the stroke goes in the +X direction before returning
+    pub length: f64,
+}
+
+/// Coordinates used by G2/G3 moves
+///
+/// The arc center (I/J) is always given relative to the start of the arc, which is the default
+/// arc distance mode (G91.1) of most controllers.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "kebab-case"))]
+pub enum ArcDistanceMode {
+    /// Absolute X/Y endpoints
+    #[default]
+    AbsoluteEndpoints,
+    /// X/Y endpoints relative to the start of the arc, switching to relative distance mode (G91)
+    /// for the arc and back to absolute (G90) afterwards
+    RelativeEndpoints,
+}
+
+/// Words describing the arc of G2/G3 moves besides its endpoint
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "kebab-case"))]
+pub enum ArcFormat {
+    /// Center offset from the start of the arc (I/J)
+    #[default]
+    #[cfg_attr(feature = "serde", serde(rename = "ij"))]
+    IJ,
+    /// Radius (R), negative for arcs sweeping more than 180 degrees
+    ///
+    /// Large arcs and those close to a semicircle, where the radius is ill-conditioned, are split
+    /// in two beforehand (see [crate::ConversionConfig::semicircle_epsilon]).
+    R,
+}
+
+/// Feedrate reduction for tight arcs, which overshoot when cut at full speed
+///
+/// Arcs are cut at `feedrate * min(1, radius / reference_radius)`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct ArcFeedConfig {
+    /// Radius (mm) at and above which arcs are cut at the full feedrate
+    pub reference_radius: f64,
+}
+
+/// Tool heights used when the Z axis is driven by svg2gcode rather than the tool on/off sequences
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct ZAxisConfig {
+    /// Height the tool travels at between cuts (mm)
+    pub safe_z: f64,
+    /// Height the tool cuts at (mm)
+    pub cut_z: f64,
+}
+
+/// Cutting depth schedule for material thicker than a single pass can handle
+///
+/// The first pass cuts at [ZAxisConfig::cut_z], and each following one `depth_per_pass` deeper.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct MultiPassConfig {
+    /// Number of times the program is cut
+    pub count: usize,
+    /// Additional depth of each pass (mm)
+    pub depth_per_pass: f64,
 }
 
 #[derive(Debug, Default, Clone, PartialEq)]
@@ -74,12 +312,165 @@ impl<'input> Machine<'input> {
             program_begin_sequence: program_begin_sequence.unwrap_or_else(|| empty_snippet.clone()),
             program_end_sequence: program_end_sequence.unwrap_or_else(|| empty_snippet.clone()),
             between_layers_sequence: between_layers_sequence.unwrap_or_else(|| empty_snippet.clone()),
+            accessory_on_sequence: empty_snippet.clone(),
+            accessory_off_sequence: empty_snippet.clone(),
+            accessory_on: false,
+            z_axis: None,
+            separate_z_moves: default_separate_z_moves(),
+            passes: None,
+            program_end_command: None,
+            arc_distance_mode: Default::default(),
+            arc_format: Default::default(),
+            axis_max_feedrate: None,
+            arc_feedrate_scaling: None,
+            min_feedrate: None,
+            tool_change: false,
+            always_retract_between_subpaths: false,
+            travel_retract: None,
+            tool_change_retract: None,
+            path_mode: None,
+            priming_stroke: None,
+            emit_units: default_emit_units(),
+            max_arcs: None,
+            tool: None,
+            pass: 0,
+            cut_z: None,
+            z: None,
             empty_snippet,
             tool_state: Default::default(),
             distance_mode: Default::default(),
         }
     }
 
+    /// Apply the non-sequence settings of a [MachineConfig].
+    ///
+    /// Sequences borrow from their source text, so they are parsed by the caller and passed to [Machine::new].
+    pub fn with_config(mut self, config: &MachineConfig) -> Self {
+        self.z_axis = config.z_axis;
+        self.separate_z_moves = config.separate_z_moves;
+        self.passes = config.passes;
+        self.program_end_command = config.program_end_command;
+        self.arc_distance_mode = config.arc_distance_mode;
+        self.arc_format = config.arc_format;
+        self.axis_max_feedrate = config.axis_max_feedrate;
+        self.arc_feedrate_scaling = config.arc_feedrate_scaling;
+        self.min_feedrate = config.min_feedrate;
+        self.tool_change = config.tool_change;
+        self.always_retract_between_subpaths = config.always_retract_between_subpaths;
+        self.travel_retract = config.travel_retract;
+        self.tool_change_retract = config.tool_change_retract;
+        self.path_mode = config.path_mode;
+        self.priming_stroke = config.priming_stroke;
+        self.emit_units = config.emit_units;
+        self.max_arcs = config.max_arcs;
+        self
+    }
+
+    /// Set the sequences for [MachineConfig::accessory_on_sequence] and
+    /// [MachineConfig::accessory_off_sequence], parsed by the caller like those of [Machine::new].
+    pub fn with_accessory_sequences(
+        mut self,
+        accessory_on_sequence: Option<Snippet<'input>>,
+        accessory_off_sequence: Option<Snippet<'input>>,
+    ) -> Self {
+        if let Some(sequence) = accessory_on_sequence {
+            self.accessory_on_sequence = sequence;
+        }
+        if let Some(sequence) = accessory_off_sequence {
+            self.accessory_off_sequence = sequence;
+        }
+        self
+    }
+
+    /// Leave out the user-defined setup gcode and the priming stroke, e.g. for a program
+    /// continuing another
+    pub fn without_program_begin(mut self) -> Self {
+        self.program_begin_sequence = self.empty_snippet.clone();
+        self.priming_stroke = None;
+        self
+    }
+
+    /// Leave out the user-defined teardown gcode and the program end command, e.g. for a program
+    /// that another continues
+    pub fn without_program_end(mut self) -> Self {
+        self.program_end_sequence = self.empty_snippet.clone();
+        self.program_end_command = None;
+        self
+    }
+
+    /// Number of times the program should be cut, always at least one.
+    pub fn pass_count(&self) -> usize {
+        match (self.z_axis, self.passes) {
+            (Some(_), Some(passes)) => passes.count.max(1),
+            _ => 1,
+        }
+    }
+
+    /// Cut subsequent moves at the depth of the given zero-based pass.
+    pub fn start_pass(&mut self, pass: usize) {
+        self.pass = pass;
+    }
+
+    pub fn separate_z_moves(&self) -> bool {
+        self.separate_z_moves
+    }
+
+    pub fn always_retract_between_subpaths(&self) -> bool {
+        self.always_retract_between_subpaths
+    }
+
+    pub fn priming_stroke(&self) -> Option<PrimingConfig> {
+        self.priming_stroke
+    }
+
+    pub fn emit_units(&self) -> bool {
+        self.emit_units
+    }
+
+    pub fn max_arcs(&self) -> Option<usize> {
+        self.max_arcs
+    }
+
+    pub fn arc_distance_mode(&self) -> ArcDistanceMode {
+        self.arc_distance_mode
+    }
+
+    pub fn arc_format(&self) -> ArcFormat {
+        self.arc_format
+    }
+
+    /// Largest feedrate up to `feedrate` that keeps the X and Y components of a move by `delta`
+    /// within [MachineConfig::axis_max_feedrate]
+    pub fn limit_feedrate(&self, feedrate: f64, delta: Vector<f64>) -> f64 {
+        let (Some([max_x, max_y]), length) = (self.axis_max_feedrate, delta.length()) else {
+            return feedrate;
+        };
+        let mut limited = feedrate;
+        for (component, max) in [(delta.x.abs(), max_x), (delta.y.abs(), max_y)] {
+            if component > 0. {
+                limited = limited.min(max * length / component);
+            }
+        }
+        self.floor_feedrate(feedrate, limited)
+    }
+
+    /// Feedrate for an arc of the given radius according to [MachineConfig::arc_feedrate_scaling]
+    pub fn arc_feedrate(&self, feedrate: f64, radius: f64) -> f64 {
+        match self.arc_feedrate_scaling {
+            Some(ArcFeedConfig { reference_radius }) if reference_radius > 0. => {
+                self.floor_feedrate(feedrate, feedrate * (radius / reference_radius).min(1.))
+            }
+            _ => feedrate,
+        }
+    }
+
+    /// Raise a feedrate slowed down from `feedrate` to [MachineConfig::min_feedrate], without
+    /// exceeding `feedrate` itself
+    fn floor_feedrate(&self, feedrate: f64, slowed: f64) -> f64 {
+        self.min_feedrate
+            .map_or(slowed, |min_feedrate| slowed.max(min_feedrate.min(feedrate)))
+    }
+
     pub fn supported_functionality(&self) -> &SupportedFunctionality {
         &self.supported_functionality
     }
@@ -94,6 +485,26 @@ impl<'input> Machine<'input> {
         }
     }
 
+    /// Output gcode to turn the accessory on, only the first time this is called.
+    pub fn accessory_on(&mut self) -> impl Iterator<Item = Token<'input>> + '_ {
+        if !self.accessory_on {
+            self.accessory_on = true;
+            self.accessory_on_sequence.iter_emit_tokens()
+        } else {
+            self.empty_snippet.iter_emit_tokens()
+        }
+    }
+
+    /// Output gcode to turn the accessory off, if it was turned on.
+    pub fn accessory_off(&mut self) -> impl Iterator<Item = Token<'input>> + '_ {
+        if self.accessory_on {
+            self.accessory_on = false;
+            self.accessory_off_sequence.iter_emit_tokens()
+        } else {
+            self.empty_snippet.iter_emit_tokens()
+        }
+    }
+
     /// Output gcode to turn the tool off.
     pub fn tool_off(&mut self) -> impl Iterator<Item = Token<'input>> + '_ {
         if self.tool_state == Some(Tool::On) || self.tool_state.is_none() {
@@ -114,11 +525,109 @@ impl<'input> Machine<'input> {
         self.program_end_sequence.iter_emit_tokens()
     }
 
+    /// Output the configured program end command, if any
+    pub fn program_end_command(&self) -> Vec<Token<'input>> {
+        match self.program_end_command {
+            Some(EndCommand::M2) => command!(ProgramEnd {}).into_token_vec(),
+            Some(EndCommand::M30) => vec![Token::Field(Field {
+                letters: "M".into(),
+                value: Value::Integer(30),
+            })],
+            None => vec![],
+        }
+    }
+
+    /// Output the configured path control mode, if any, blending within `tolerance` (mm) unless
+    /// [PathMode::Blend] gives its own
+    pub fn path_mode(&self, tolerance: f64) -> Vec<Token<'input>> {
+        let g = |number| {
+            Token::Field(Field {
+                letters: "G".into(),
+                value: Value::Integer(number),
+            })
+        };
+        match self.path_mode {
+            Some(PathMode::Blend { tolerance: blend }) => vec![
+                g(64),
+                Token::Field(Field {
+                    letters: "P".into(),
+                    value: Value::Float(blend.unwrap_or(tolerance)),
+                }),
+            ],
+            Some(PathMode::ExactStop) => vec![g(61)],
+            None => vec![],
+        }
+    }
+
     /// Output user-defined sequence between layers/groups
     pub fn between_layers(&self) -> impl Iterator<Item = Token<'input>> + '_ {
         self.between_layers_sequence.iter_emit_tokens()
     }
 
+    /// Output a tool change to the given tool number if [MachineConfig::tool_change] is enabled
+    /// and that tool isn't already in use.
+    pub fn tool_change(&mut self, tool: u32) -> Vec<Token<'input>> {
+        if !self.changes_tool(tool) {
+            return vec![];
+        }
+        self.tool = Some(tool);
+        vec![
+            Token::Field(Field {
+                letters: "M".into(),
+                value: Value::Integer(6),
+            }),
+            Token::Field(Field {
+                letters: "T".into(),
+                value: Value::Integer(tool as usize),
+            }),
+        ]
+    }
+
+    /// Whether [Machine::tool_change] would output a tool change to the given tool number
+    pub fn changes_tool(&self, tool: u32) -> bool {
+        self.tool_change && self.tool != Some(tool)
+    }
+
+    /// Height to retract to before traveling, if the Z axis is driven and not already there.
+    pub fn retract_z(&mut self) -> Option<f64> {
+        let safe_z = self.travel_retract.unwrap_or(self.z_axis?.safe_z);
+        self.retract_z_to(safe_z)
+    }
+
+    /// Height to retract to before traveling to the next layer, if the Z axis is driven and not
+    /// already there. See [MachineConfig::tool_change_retract].
+    pub fn tool_change_retract_z(&mut self) -> Option<f64> {
+        let safe_z = self
+            .tool_change_retract
+            .or(self.travel_retract)
+            .unwrap_or(self.z_axis?.safe_z);
+        self.retract_z_to(safe_z)
+    }
+
+    fn retract_z_to(&mut self, safe_z: f64) -> Option<f64> {
+        (self.z != Some(safe_z)).then(|| {
+            self.z = Some(safe_z);
+            safe_z
+        })
+    }
+
+    /// Cut at the given height instead of [ZAxisConfig::cut_z], or at that height again if `None`
+    pub fn set_cut_z(&mut self, cut_z: Option<f64>) {
+        self.cut_z = cut_z;
+    }
+
+    /// Height to plunge to before cutting, if the Z axis is driven and not already there.
+    pub fn plunge_z(&mut self) -> Option<f64> {
+        let cut_z = self.cut_z.unwrap_or(self.z_axis?.cut_z)
+            - self
+                .passes
+                .map_or(0., |passes| passes.depth_per_pass * self.pass as f64);
+        (self.z != Some(cut_z)).then(|| {
+            self.z = Some(cut_z);
+            cut_z
+        })
+    }
+
     /// Output absolute distance field if mode was relative or unknown.
     pub fn absolute(&mut self) -> Vec<Token<'input>> {
         if self.distance_mode == Some(Distance::Relative) || self.distance_mode.is_none() {
@@ -1,8 +1,17 @@
+use std::borrow::Cow;
+use std::fmt;
+
+use g_code::{
+    emit::{format_gcode_fmt, Field, FormatOptions, Token, Value},
+    parse::{file_parser, ParseError},
+};
 #[cfg(feature = "serde")]
 use serde::{Deserialize, Serialize};
 
+use crate::FeedrateUnits;
+
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
-#[derive(Debug, Default, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq)]
 pub struct PostprocessConfig {
     /// Convenience field for [g_code::emit::FormatOptions] field
     #[cfg_attr(feature = "serde", serde(default))]
@@ -10,9 +19,991 @@ pub struct PostprocessConfig {
     /// Convenience field for [g_code::emit::FormatOptions] field
     #[cfg_attr(feature = "serde", serde(default))]
     pub line_numbers: bool,
+    /// Number of the first line when [Self::line_numbers] is set, e.g. to append to an existing
+    /// program
+    ///
+    /// See [PostprocessConfig::number_lines].
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub line_number_start: u32,
+    /// Step between consecutive line numbers when [Self::line_numbers] is set
+    #[cfg_attr(feature = "serde", serde(default = "default_line_number_increment"))]
+    pub line_number_increment: u32,
     /// Convenience field for [g_code::emit::FormatOptions] field
     #[cfg_attr(feature = "serde", serde(default))]
     pub newline_before_comment: bool,
+    /// Round feedrate (F) words to this many decimal places, leaving other words untouched
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub feedrate_decimals: Option<u8>,
+    /// Shift every absolute X/Y word by this offset (mm), e.g. to move the program to a fixture
+    /// datum
+    ///
+    /// Applied after everything else, so unlike [crate::ConversionConfig::origin] it does not
+    /// depend on the drawing. Arc centers (I/J) and relative moves are not shifted.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub datum_offset: [f64; 2],
+    /// Round the X/Y position of every move to the nearest multiple of this step (mm), for
+    /// machines with coarse step resolution
+    ///
+    /// Unlike rounding to a number of decimals, the step need not be a power of ten (e.g. 0.05).
+    /// Relative moves go between snapped positions, so the rounding doesn't build up. Arc centers
+    /// (I/J or R) are moved to be as far from the snapped start as from the snapped end, and arcs
+    /// shorter than the step become straight moves.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub grid_snap: Option<f64>,
+    /// Bracket the moves of each element with `begin <element> bbox [x0,y0]-[x1,y1]` and
+    /// `end <element>` comments, for reviewing the program in CAM software
+    ///
+    /// The bounding box covers the endpoints of the element's moves in machine coordinates.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub annotate_subpaths: bool,
+    /// Leave out motion words (G0/G1/G2/G3) that repeat the motion mode already in effect, for
+    /// controllers that expect modal g-code
+    ///
+    /// Lines are broken at command words when formatting, so the words are left out of the
+    /// formatted program, see [PostprocessConfig::drop_repeated_motion_words].
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub modal_gcodes: bool,
+    /// Leave out feedrate (F) words equal to the feedrate already in effect, for smaller files
+    ///
+    /// Feedrates that change, like those of [crate::MachineConfig::arc_feedrate_scaling], are
+    /// still emitted.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub modal_feedrate: bool,
+    /// End each move with a `; t=<seconds>` comment estimating the time elapsed since the start
+    /// of the program, for simulators
+    ///
+    /// Only feed moves and dwells (`G4 P<seconds>`) take time, since rapid speeds depend on the
+    /// machine. Acceleration is not taken into account.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub annotate_time: bool,
+    /// Parse the formatted program and format it again from what was parsed, failing if that
+    /// doesn't give back the same program
+    ///
+    /// See [PostprocessConfig::roundtrip].
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub validate_roundtrip: bool,
+    /// How comments are written, for controllers that only accept one kind
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub comment_style: CommentStyle,
+    /// Number of blank lines separating layers, after the blank `;` comment that ends the line
+    /// before each [crate::MachineConfig::between_layers_sequence]
+    ///
+    /// [PostprocessConfig::roundtrip] ignores blank lines, which don't parse into anything.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub blank_lines_between_layers: usize,
+}
+
+const fn default_line_number_increment() -> u32 {
+    1
+}
+
+impl Default for PostprocessConfig {
+    fn default() -> Self {
+        Self {
+            checksums: false,
+            line_numbers: false,
+            line_number_start: 0,
+            line_number_increment: default_line_number_increment(),
+            newline_before_comment: false,
+            feedrate_decimals: None,
+            datum_offset: [0., 0.],
+            grid_snap: None,
+            annotate_subpaths: false,
+            modal_gcodes: false,
+            modal_feedrate: false,
+            annotate_time: false,
+            validate_roundtrip: false,
+            comment_style: Default::default(),
+            blank_lines_between_layers: 0,
+        }
+    }
+}
+
+/// Kinds of comments written, see [PostprocessConfig::comment_style]
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "kebab-case"))]
+pub enum CommentStyle {
+    /// Comments on a line of their own start with `;`, those within a line are in parentheses
+    #[default]
+    Mixed,
+    /// Only `;` comments, each ending its line
+    ///
+    /// Comments within a line are moved to the end of it.
+    Semicolon,
+    /// Only `(parenthesized)` comments, with parentheses in them replaced by brackets
+    ///
+    /// Comments on a line of their own are moved to the end of the line after them, which is
+    /// what they describe. Empty `;` comments only end a line, so they are kept.
+    Parentheses,
+}
+
+impl PostprocessConfig {
+    /// Token post-pass applied to a program before it is formatted
+    ///
+    /// `feedrate_units` are the [crate::ConversionConfig::feedrate_units] the program was
+    /// generated with, which [Self::annotate_time] reads its feedrates in.
+    pub fn apply(&self, program: &mut Vec<Token<'_>>, feedrate_units: FeedrateUnits) {
+        // First, since the other passes add blank comments of their own
+        if self.blank_lines_between_layers > 0 {
+            let blank_lines = "\n".repeat(self.blank_lines_between_layers);
+            for token in program.iter_mut() {
+                if let Token::Comment {
+                    is_inline: false,
+                    inner,
+                } = token
+                {
+                    if inner.is_empty() {
+                        *inner = Cow::Owned(blank_lines.clone());
+                    }
+                }
+            }
+        }
+        if let Some(decimals) = self.feedrate_decimals {
+            let scale = 10f64.powi(decimals.into());
+            for token in program.iter_mut() {
+                if let Token::Field(field) = token {
+                    if field.letters == "F" {
+                        if let Some(feedrate) = field.value.as_f64() {
+                            field.value = Value::Float((feedrate * scale).round() / scale);
+                        }
+                    }
+                }
+            }
+        }
+        if self.datum_offset != [0., 0.] {
+            let mut relative = false;
+            for token in program.iter_mut() {
+                let Token::Field(field) = token else {
+                    continue;
+                };
+                match (field.letters.as_ref(), field.value.as_f64()) {
+                    ("G", Some(90.)) => relative = false,
+                    ("G", Some(91.)) => relative = true,
+                    ("X", Some(x)) if !relative => {
+                        field.value = Value::Float(x + self.datum_offset[0])
+                    }
+                    ("Y", Some(y)) if !relative => {
+                        field.value = Value::Float(y + self.datum_offset[1])
+                    }
+                    _ => {}
+                }
+            }
+        }
+        if let Some(step) = self.grid_snap.filter(|step| *step > 0.) {
+            snap_to_grid(program, step);
+        }
+        if self.annotate_subpaths {
+            annotate_subpaths(program);
+        }
+        if self.modal_feedrate {
+            remove_repeated_feedrates(program);
+        }
+        // Last, since the time comments would otherwise be taken for element comments
+        if self.annotate_time {
+            annotate_time(program, feedrate_units);
+        }
+        if self.comment_style != CommentStyle::Mixed {
+            restyle_comments(program, self.comment_style);
+        }
+    }
+
+    /// Text post-pass validating a program after it is formatted with `options`, before
+    /// [Self::number_lines], if [Self::validate_roundtrip] is set
+    ///
+    /// The program is parsed with [g_code::parse::file_parser] and formatted again from what was
+    /// parsed, which must give back the same program. This catches tokens that don't format
+    /// into valid g-code, like a `NaN` coordinate, which is either rejected by the parser or
+    /// parsed into something else.
+    pub fn roundtrip(&self, gcode: &str, options: FormatOptions) -> Result<String, RoundtripError> {
+        if !self.validate_roundtrip {
+            return Ok(gcode.to_string());
+        }
+        let file = file_parser(gcode).map_err(RoundtripError::Parse)?;
+        if let Some(line) = file
+            .iter()
+            .position(|line| matches!(line.validate_checksum(), Some(Err(_))))
+        {
+            return Err(RoundtripError::Checksum { line: line + 1 });
+        }
+        // Line numbers are added again when formatting
+        let tokens = file
+            .iter()
+            .flat_map(|line| line.iter_emit_tokens())
+            .filter(|token| !matches!(token, Token::Field(field) if field.letters == "N"));
+        let mut roundtripped = String::with_capacity(gcode.len());
+        format_gcode_fmt(tokens, options, &mut roundtripped)
+            .expect("formatting into a string can't fail");
+        let without_blank_lines = gcode
+            .split_inclusive('\n')
+            .filter(|line| *line != "\n")
+            .collect::<String>();
+        if roundtripped != without_blank_lines {
+            let same_lines = without_blank_lines
+                .lines()
+                .zip(roundtripped.lines())
+                .take_while(|(line, roundtripped)| line == roundtripped)
+                .count();
+            return Err(RoundtripError::Mismatch {
+                line: same_lines + 1,
+            });
+        }
+        Ok(gcode.to_string())
+    }
+
+    /// Text post-pass applied to a program after [Self::roundtrip] and before
+    /// [Self::number_lines], leaving out the motion words (G0/G1/G2/G3) that start a line if they
+    /// repeat the motion mode already in effect, when [Self::modal_gcodes] is set
+    ///
+    /// Formatting only starts a new line at a command word, so the words can't be left out of the
+    /// tokens without joining lines. The checksum of a line is updated if there is one. The mode
+    /// in effect follows every G word of a line, including those of user sequences that don't
+    /// start it, see [motion_mode_after].
+    pub fn drop_repeated_motion_words(&self, gcode: &str) -> String {
+        if !self.modal_gcodes {
+            return gcode.to_string();
+        }
+        let mut modal = String::with_capacity(gcode.len());
+        let mut motion_mode = None;
+        for line in gcode.split_inclusive('\n') {
+            // After the line number, if there is one
+            let start = line
+                .strip_prefix('N')
+                .and_then(|rest| rest.split_once(' '))
+                .filter(|(number, _)| number.bytes().all(|b| b.is_ascii_digit()))
+                .map_or(0, |(number, _)| number.len() + 2);
+            let (number, rest) = line.split_at(start);
+            let word_len = rest
+                .find(|c: char| !(c.is_ascii_alphanumeric() || c == '.'))
+                .unwrap_or(rest.len());
+            let (word, words) = rest.split_at(word_len);
+            let repeated = motion_mode.is_some()
+                && word
+                    .strip_prefix('G')
+                    .and_then(|value| value.parse::<f64>().ok())
+                    == motion_mode;
+            match words.strip_prefix(' ') {
+                Some(words) if repeated => {
+                    modal += number;
+                    if self.checksums {
+                        let fix = word.bytes().fold(b' ', |acc, b| acc ^ b);
+                        modal += &fix_checksum(words, fix);
+                    } else {
+                        modal += words;
+                    }
+                }
+                _ => modal += line,
+            }
+            motion_mode = motion_mode_after(motion_mode, line);
+        }
+        modal
+    }
+
+    /// Text post-pass applied to a program after it is formatted, renumbering its lines from
+    /// [Self::line_number_start] by [Self::line_number_increment]
+    ///
+    /// [g_code::emit::format_gcode_fmt] always numbers lines from zero and drops `N` words in
+    /// the program, so this rewrites the `N` word starting each line instead, updating the
+    /// checksum if there is one.
+    pub fn number_lines(&self, gcode: &str) -> String {
+        if !self.line_numbers || (self.line_number_start, self.line_number_increment) == (0, 1) {
+            return gcode.to_string();
+        }
+        let mut numbered = String::with_capacity(gcode.len());
+        let mut number = u64::from(self.line_number_start);
+        for line in gcode.split_inclusive('\n') {
+            let renumbered = line
+                .strip_prefix('N')
+                .and_then(|rest| rest.split_once(' '))
+                .filter(|(old_number, _)| old_number.bytes().all(|b| b.is_ascii_digit()))
+                .map(|(old_number, rest)| {
+                    let new_prefix = format!("N{number} ");
+                    let rest = if self.checksums {
+                        let prefix_xor = |prefix: &str| prefix.bytes().fold(0, |acc, b| acc ^ b);
+                        let fix = prefix_xor(&format!("N{old_number} ")) ^ prefix_xor(&new_prefix);
+                        fix_checksum(rest, fix)
+                    } else {
+                        rest.to_string()
+                    };
+                    new_prefix + &rest
+                });
+            match renumbered {
+                Some(renumbered) => {
+                    numbered += &renumbered;
+                    number += u64::from(self.line_number_increment);
+                }
+                None => numbered += line,
+            }
+        }
+        numbered
+    }
+}
+
+/// Reasons a formatted program fails [PostprocessConfig::roundtrip]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum RoundtripError {
+    /// The program isn't valid g-code
+    Parse(ParseError),
+    /// The checksum of a line doesn't match it
+    Checksum {
+        /// Line number, starting from one
+        line: usize,
+    },
+    /// A line was parsed into something else than what it says
+    Mismatch {
+        /// Line number, starting from one
+        line: usize,
+    },
+}
+
+impl fmt::Display for RoundtripError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Parse(err) => write!(f, "generated g-code does not parse: {err}"),
+            Self::Checksum { line } => write!(f, "generated g-code has a bad checksum on line {line}"),
+            Self::Mismatch { line } => write!(f, "generated g-code is malformed on line {line}"),
+        }
+    }
+}
+
+impl std::error::Error for RoundtripError {}
+
+/// XOR the checksum (`*<checksum>`) in the rest of a formatted line with `fix`
+///
+/// The checksum ends the words of a line, coming right before its `;` comment if there is one.
+fn fix_checksum(rest: &str, fix: u8) -> String {
+    let checksum = rest.match_indices('*').find_map(|(star, _)| {
+        let digits = &rest[star + 1..];
+        let len = digits.find(|c: char| !c.is_ascii_digit()).unwrap_or(digits.len());
+        let ends_words = matches!(digits[len..].chars().next(), None | Some(';' | '\n'));
+        digits[..len]
+            .parse::<u8>()
+            .ok()
+            .filter(|_| ends_words)
+            .map(|checksum| (star + 1..star + 1 + len, checksum))
+    });
+    match checksum {
+        Some((range, checksum)) => {
+            format!("{}{}{}", &rest[..range.start], checksum ^ fix, &rest[range.end..])
+        }
+        None => rest.to_string(),
+    }
+}
+
+/// Motion mode (G0/G1/G2/G3) in effect after a formatted line that starts in `mode`, from the
+/// G words anywhere in its code, leaving out its comments and checksum
+///
+/// The other motion modes, like probing (G38.x), canned cycles and their cancellation (G80),
+/// leave no mode a motion word can be left out for.
+fn motion_mode_after(mut mode: Option<f64>, line: &str) -> Option<f64> {
+    let mut code = String::with_capacity(line.len());
+    let mut in_comment = false;
+    for c in line.chars() {
+        match c {
+            '(' => in_comment = true,
+            ')' => in_comment = false,
+            ';' | '*' if !in_comment => break,
+            c if !in_comment => code.push(c),
+            _ => {}
+        }
+    }
+    let g_words = code
+        .split_whitespace()
+        .filter_map(|word| word.strip_prefix('G'))
+        .filter_map(|value| value.parse::<f64>().ok());
+    for value in g_words {
+        if matches!(value, 0. | 1. | 2. | 3.) {
+            mode = Some(value);
+        } else if [33., 73., 76.].contains(&value)
+            || (38. ..39.).contains(&value)
+            || (80. ..=89.).contains(&value)
+        {
+            mode = None;
+        }
+    }
+    mode
+}
+
+/// Whether a comment only ends its line, possibly followed by
+/// [PostprocessConfig::blank_lines_between_layers]
+fn is_blank(inner: &str) -> bool {
+    inner.chars().all(|c| c == '\n')
+}
+
+/// See [PostprocessConfig::comment_style]
+fn restyle_comments(program: &mut Vec<Token<'_>>, style: CommentStyle) {
+    // Words starting a new line when formatting, as a `;` comment ends one
+    let ends_line = |token: &Token| match token {
+        Token::Field(field) => matches!(field.letters.as_ref(), "G" | "g" | "M" | "m" | "D" | "d"),
+        Token::Comment {
+            is_inline: false,
+            inner,
+        } => is_blank(inner),
+        Token::Comment { is_inline: true, .. } | Token::Flag(_) => false,
+    };
+    let parenthesized = |inner: Cow<'_, str>| Token::Comment {
+        is_inline: true,
+        inner: inner.replace('(', "[").replace(')', "]").into(),
+    };
+    let mut restyled = Vec::with_capacity(program.len());
+    // Comments waiting for the end of their line
+    let mut pending = vec![];
+    // Whether the line that the pending comments describe has started
+    let mut started = false;
+    let mut tokens = std::mem::take(program).into_iter().peekable();
+    while let Some(token) = tokens.next() {
+        let token = match (style, token) {
+            (CommentStyle::Semicolon, Token::Comment { is_inline: true, inner }) => {
+                pending.push(Token::Comment {
+                    is_inline: false,
+                    inner,
+                });
+                continue;
+            }
+            (CommentStyle::Parentheses, Token::Comment { is_inline: false, inner })
+                if !is_blank(&inner) =>
+            {
+                if started {
+                    restyled.append(&mut pending);
+                    started = false;
+                }
+                pending.push(parenthesized(inner));
+                match tokens.peek() {
+                    // The comment was all that ended the line
+                    Some(next @ Token::Field(_)) if !ends_line(next) => Token::Comment {
+                        is_inline: false,
+                        inner: Cow::Borrowed(""),
+                    },
+                    _ => continue,
+                }
+            }
+            (CommentStyle::Parentheses, Token::Comment { is_inline: true, inner }) => {
+                restyled.push(parenthesized(inner));
+                continue;
+            }
+            (_, token) => token,
+        };
+        if ends_line(&token) || matches!(token, Token::Comment { .. }) {
+            if style == CommentStyle::Semicolon || started {
+                restyled.append(&mut pending);
+                started = false;
+            } else if !pending.is_empty() {
+                started = true;
+            }
+        }
+        restyled.push(token);
+    }
+    restyled.append(&mut pending);
+    *program = restyled;
+}
+
+/// See [PostprocessConfig::modal_feedrate]
+fn remove_repeated_feedrates(program: &mut Vec<Token<'_>>) {
+    let mut feedrate = None;
+    program.retain(|token| match token {
+        Token::Field(field) if field.letters == "F" => match field.value.as_f64() {
+            Some(value) => feedrate.replace(value) != Some(value),
+            None => true,
+        },
+        _ => true,
+    });
+}
+
+/// Center of a G2/G3 arc from `from` to `to`, given by its center offset from `from` (I/J) or
+/// its radius (R), negative for arcs sweeping more than 180 degrees
+fn arc_center(
+    from: [f64; 2],
+    to: [f64; 2],
+    clockwise: bool,
+    offset: Option<[f64; 2]>,
+    radius: Option<f64>,
+) -> Option<[f64; 2]> {
+    if let Some([i, j]) = offset {
+        return Some([from[0] + i, from[1] + j]);
+    }
+    let radius = radius?;
+    let chord = [to[0] - from[0], to[1] - from[1]];
+    let length = chord[0].hypot(chord[1]);
+    if length == 0. {
+        return None;
+    }
+    // Short clockwise arcs have their center on the right of the chord, the others on the left
+    let side = if clockwise != (radius < 0.) { -1. } else { 1. };
+    let height = side * (radius * radius - length * length / 4.).max(0.).sqrt() / length;
+    Some([
+        (from[0] + to[0]) / 2. - height * chord[1],
+        (from[1] + to[1]) / 2. + height * chord[0],
+    ])
+}
+
+/// See [PostprocessConfig::grid_snap]
+fn snap_to_grid(program: &mut Vec<Token<'_>>, step: f64) {
+    fn value(program: &[Token<'_>], i: usize) -> Option<f64> {
+        match &program[i] {
+            Token::Field(field) => field.value.as_f64(),
+            _ => None,
+        }
+    }
+
+    fn set(program: &mut [Token<'_>], i: usize, value: f64) {
+        if let Token::Field(field) = &mut program[i] {
+            field.value = Value::Float(value);
+        }
+    }
+
+    // Rounding the quotient leaves noise like 1.2500000000000002, which dividing by the inverse
+    // step avoids for common steps
+    let snap = |value: f64| (value / step).round() / step.recip() + 0.;
+
+    // Index of the command word of each command, and of the words following it
+    let mut commands: Vec<(Option<usize>, Vec<usize>)> = vec![];
+    let mut in_command = false;
+    for (i, token) in program.iter().enumerate() {
+        match token {
+            Token::Comment {
+                is_inline: false, ..
+            } => in_command = false,
+            Token::Field(field) if matches!(field.letters.as_ref(), "G" | "M") => {
+                commands.push((Some(i), vec![]));
+                in_command = true;
+            }
+            Token::Field(_) if in_command => {
+                if let Some((_, words)) = commands.last_mut() {
+                    words.push(i);
+                }
+            }
+            // Words without a command continue the motion mode in effect
+            Token::Field(_) => {
+                commands.push((None, vec![i]));
+                in_command = true;
+            }
+            _ => {}
+        }
+    }
+
+    let mut relative = false;
+    let mut motion_mode = None;
+    // Position as programmed, the machine is at it snapped
+    let mut position = [None, None];
+    let mut removed = vec![];
+    // Words to insert after the token at an index, in order
+    let mut inserted = vec![];
+    for (command, words) in commands {
+        let mode = match command.map(|i| &program[i]) {
+            Some(Token::Field(field)) => match (field.letters.as_ref(), field.value.as_f64()) {
+                ("G", Some(90.)) => {
+                    relative = false;
+                    None
+                }
+                ("G", Some(91.)) => {
+                    relative = true;
+                    None
+                }
+                ("G", Some(mode @ (0. | 1. | 2. | 3.))) => {
+                    motion_mode = Some(mode);
+                    Some(mode)
+                }
+                _ => None,
+            },
+            _ => motion_mode,
+        };
+        let Some(mode) = mode else {
+            continue;
+        };
+        let word = |program: &[Token<'_>], letter: &str| {
+            words
+                .iter()
+                .rev()
+                .copied()
+                .find(|i| matches!(&program[*i], Token::Field(field) if field.letters == letter))
+        };
+
+        let axes = ["X", "Y"].map(|letter| word(program, letter));
+        let from = position;
+        for (axis, i) in axes.into_iter().enumerate() {
+            let Some((i, value)) = i.and_then(|i| value(program, i).map(|value| (i, value))) else {
+                continue;
+            };
+            position[axis] = if relative {
+                from[axis].map(|current: f64| current + value)
+            } else {
+                Some(value)
+            };
+            let snapped = match (relative, from[axis], position[axis]) {
+                (false, _, Some(to)) => snap(to),
+                // Between snapped positions, so the rounding doesn't build up
+                (true, Some(start), Some(end)) => snap(snap(end) - snap(start)),
+                // Relative to an unknown position
+                _ => snap(value),
+            };
+            set(program, i, snapped);
+        }
+
+        let ([Some(x0), Some(y0)], [Some(x1), Some(y1)]) = (from, position) else {
+            continue;
+        };
+        if !matches!(mode, 2. | 3.) {
+            continue;
+        }
+        let [i, j, r] = ["I", "J", "R"].map(|letter| word(program, letter));
+        let offset = (i.is_some() || j.is_some())
+            .then(|| [i, j].map(|i| i.and_then(|i| value(program, i)).unwrap_or_default()));
+        let radius = r.and_then(|r| value(program, r));
+        let Some(center) = arc_center([x0, y0], [x1, y1], mode == 2., offset, radius) else {
+            continue;
+        };
+        let [start, end] = [[x0, y0], [x1, y1]].map(|point| point.map(snap));
+        let chord = [end[0] - start[0], end[1] - start[1]];
+        let center = if chord != [0., 0.] {
+            // Onto the perpendicular bisector of the snapped endpoints
+            let along = ((center[0] - (start[0] + end[0]) / 2.) * chord[0]
+                + (center[1] - (start[1] + end[1]) / 2.) * chord[1])
+                / (chord[0] * chord[0] + chord[1] * chord[1]);
+            [center[0] - along * chord[0], center[1] - along * chord[1]]
+        } else if [x0, y0] == [x1, y1] {
+            // A full circle
+            center
+        } else {
+            // Snapped into a full circle, so moved straight instead
+            if let Some(command) = command {
+                if let Token::Field(field) = &mut program[command] {
+                    field.value = Value::Integer(1);
+                }
+                removed.extend([i, j, r].into_iter().flatten());
+            }
+            continue;
+        };
+        if offset.is_some() {
+            for (letter, index, value) in [
+                ("I", i, center[0] - start[0]),
+                ("J", j, center[1] - start[1]),
+            ] {
+                match index {
+                    Some(index) => set(program, index, value),
+                    None => inserted.push((
+                        words.last().copied().unwrap_or_default(),
+                        Token::Field(Field {
+                            letters: letter.into(),
+                            value: Value::Float(value),
+                        }),
+                    )),
+                }
+            }
+        } else if let (Some(r), Some(radius)) = (r, radius) {
+            let snapped = (center[0] - start[0]).hypot(center[1] - start[1]);
+            set(program, r, snapped.copysign(radius));
+        }
+    }
+
+    if removed.is_empty() && inserted.is_empty() {
+        return;
+    }
+    let mut inserted = inserted.into_iter().peekable();
+    let mut edited = Vec::with_capacity(program.len() + inserted.len());
+    for (i, token) in std::mem::take(program).into_iter().enumerate() {
+        if !removed.contains(&i) {
+            edited.push(token);
+        }
+        while let Some((_, token)) = inserted.next_if(|(after, _)| *after == i) {
+            edited.push(token);
+        }
+    }
+    *program = edited;
+}
+
+/// Moves following an element comment, up to the next one
+struct Subpath {
+    /// Index of the element comment
+    comment: usize,
+    /// Last segment of the element comment, e.g. `path#foo`
+    name: String,
+    /// Index of the last token of the last move
+    end: usize,
+    bounding_box: Option<[[f64; 2]; 2]>,
+}
+
+impl Subpath {
+    fn include(&mut self, position: [Option<f64>; 2]) {
+        if let [Some(x), Some(y)] = position {
+            let [min, max] = self.bounding_box.get_or_insert([[x, y], [x, y]]);
+            *min = [min[0].min(x), min[1].min(y)];
+            *max = [max[0].max(x), max[1].max(y)];
+        }
+    }
+}
+
+/// See [PostprocessConfig::annotate_subpaths]
+fn annotate_subpaths(program: &mut Vec<Token<'_>>) {
+    let mut subpaths: Vec<Subpath> = vec![];
+    let mut position = [None, None];
+    let mut relative = false;
+    // Whether the command being read is a move, and whether it changed the position
+    let mut in_move = false;
+    let mut moved = false;
+    for (i, token) in program.iter().enumerate() {
+        let ends_command = match token {
+            Token::Comment { is_inline, .. } => !is_inline,
+            Token::Field(field) => matches!(field.letters.as_ref(), "G" | "M"),
+            Token::Flag(_) => false,
+        };
+        if ends_command {
+            // Only the complete position is included, X and Y words may be modal
+            if std::mem::take(&mut moved) {
+                if let Some(subpath) = subpaths.last_mut() {
+                    subpath.include(position);
+                }
+            }
+            in_move = false;
+        }
+        match token {
+            // Blank comments only separate sequences, not elements
+            Token::Comment {
+                is_inline: false,
+                inner,
+            } if !is_blank(inner) => subpaths.push(Subpath {
+                comment: i,
+                name: inner.rsplit(" > ").next().unwrap_or_default().to_string(),
+                end: i,
+                bounding_box: None,
+            }),
+            Token::Field(field) if field.letters == "G" => match field.value.as_f64() {
+                Some(90.) => relative = false,
+                Some(91.) => relative = true,
+                Some(0. | 1. | 2. | 3.) => in_move = true,
+                _ => {}
+            },
+            Token::Field(field) if in_move => {
+                let axis = match field.letters.as_ref() {
+                    "X" => Some(0),
+                    "Y" => Some(1),
+                    _ => None,
+                };
+                if let (Some(axis), Some(value)) = (axis, field.value.as_f64()) {
+                    position[axis] = if relative {
+                        position[axis].map(|current| current + value)
+                    } else {
+                        Some(value)
+                    };
+                    moved = true;
+                }
+            }
+            _ => {}
+        }
+        if in_move {
+            if let Some(subpath) = subpaths.last_mut() {
+                subpath.end = i;
+            }
+        }
+    }
+    if moved {
+        if let Some(subpath) = subpaths.last_mut() {
+            subpath.include(position);
+        }
+    }
+
+    // Insert from the back so earlier indices stay valid
+    for subpath in subpaths.into_iter().rev() {
+        let Some([min, max]) = subpath.bounding_box else {
+            continue;
+        };
+        // Hide floating point noise
+        let round = |v: f64| (v * 1e6).round() / 1e6 + 0.;
+        program.insert(
+            subpath.end + 1,
+            Token::Comment {
+                is_inline: false,
+                inner: Cow::Owned(format!("end {}", subpath.name)),
+            },
+        );
+        program.insert(
+            subpath.comment + 1,
+            Token::Comment {
+                is_inline: false,
+                inner: Cow::Owned(format!(
+                    "begin {} bbox [{},{}]-[{},{}]",
+                    subpath.name,
+                    round(min[0]),
+                    round(min[1]),
+                    round(max[0]),
+                    round(max[1])
+                )),
+            },
+        );
+    }
+}
+
+/// A command word and the words following it, up to the next command
+struct Command {
+    letter: char,
+    number: f64,
+    words: Vec<(char, f64)>,
+    /// Index of the last token of the command
+    end: usize,
+}
+
+impl Command {
+    fn word(&self, letter: char) -> Option<f64> {
+        self.words
+            .iter()
+            .rev()
+            .find(|(word, _)| *word == letter)
+            .map(|(_, value)| *value)
+    }
+}
+
+/// Machine state needed to estimate how long commands take, see [PostprocessConfig::annotate_time]
+#[derive(Default)]
+struct TimeEstimate {
+    /// Seconds elapsed
+    time: f64,
+    position: [Option<f64>; 3],
+    relative: bool,
+    /// Units of the feedrate words
+    feedrate_units: FeedrateUnits,
+    /// mm/min
+    feedrate: Option<f64>,
+    motion_mode: Option<f64>,
+}
+
+impl TimeEstimate {
+    /// Simulate a command, returning whether it was a move
+    fn run(&mut self, command: &Command) -> bool {
+        if let Some(feedrate) = command.word('F') {
+            self.feedrate = Some(self.feedrate_units.to_mm_per_min(feedrate));
+        }
+        match (command.letter, command.number) {
+            ('G', 90.) => self.relative = false,
+            ('G', 91.) => self.relative = true,
+            ('G', 4.) => self.time += command.word('P').unwrap_or_default(),
+            ('G', mode @ (0. | 1. | 2. | 3.)) => {
+                self.motion_mode = Some(mode);
+                let from = self.position;
+                for (axis, letter) in ['X', 'Y', 'Z'].into_iter().enumerate() {
+                    if let Some(value) = command.word(letter) {
+                        self.position[axis] = if self.relative {
+                            from[axis].map(|current| current + value)
+                        } else {
+                            Some(value)
+                        };
+                    }
+                }
+                let distance = match (from, self.position) {
+                    ([Some(x0), Some(y0), z0], [Some(x1), Some(y1), z1]) => {
+                        let dz = z0.zip(z1).map_or(0., |(z0, z1)| z1 - z0);
+                        let planar = match mode {
+                            2. | 3. => {
+                                let offset = [command.word('I'), command.word('J')];
+                                let offset = offset
+                                    .iter()
+                                    .any(Option::is_some)
+                                    .then(|| offset.map(Option::unwrap_or_default));
+                                let Some(center) = arc_center(
+                                    [x0, y0],
+                                    [x1, y1],
+                                    mode == 2.,
+                                    offset,
+                                    command.word('R'),
+                                ) else {
+                                    // Not a valid arc
+                                    return true;
+                                };
+                                let radius = (x0 - center[0]).hypot(y0 - center[1]);
+                                let start = (y0 - center[1]).atan2(x0 - center[0]);
+                                let end = (y1 - center[1]).atan2(x1 - center[0]);
+                                let sweep = if mode == 2. { start - end } else { end - start }
+                                    .rem_euclid(std::f64::consts::TAU);
+                                // Coinciding endpoints make a full circle
+                                let sweep = if sweep == 0. { std::f64::consts::TAU } else { sweep };
+                                radius * sweep
+                            }
+                            _ => (x1 - x0).hypot(y1 - y0),
+                        };
+                        planar.hypot(dz)
+                    }
+                    // Only Z can be tracked before X and Y are known
+                    ([_, _, Some(z0)], [_, _, Some(z1)]) => (z1 - z0).abs(),
+                    _ => 0.,
+                };
+                if let (true, Some(feedrate)) = (mode != 0., self.feedrate) {
+                    if feedrate > 0. {
+                        self.time += distance / feedrate * 60.;
+                    }
+                }
+                return true;
+            }
+            _ => {}
+        }
+        false
+    }
+}
+
+/// See [PostprocessConfig::annotate_time]
+fn annotate_time(program: &mut Vec<Token<'_>>, feedrate_units: FeedrateUnits) {
+    let mut estimate = TimeEstimate {
+        feedrate_units,
+        ..Default::default()
+    };
+    let mut annotations = vec![];
+    let mut command: Option<Command> = None;
+    let mut finish = |command: Option<Command>, estimate: &mut TimeEstimate| {
+        if let Some(command) = command {
+            if estimate.run(&command) {
+                annotations.push((command.end, estimate.time));
+            }
+        }
+    };
+    for (i, token) in program.iter().enumerate() {
+        match token {
+            Token::Comment {
+                is_inline: false, ..
+            } => finish(command.take(), &mut estimate),
+            Token::Field(field) => {
+                let (Some(letter), Some(value)) =
+                    (field.letters.chars().next(), field.value.as_f64())
+                else {
+                    continue;
+                };
+                if matches!(letter, 'G' | 'M') {
+                    finish(command.take(), &mut estimate);
+                    command = Some(Command {
+                        letter,
+                        number: value,
+                        words: vec![],
+                        end: i,
+                    });
+                    continue;
+                }
+                // Axis words without a command continue the motion mode in effect
+                if command.is_none() && matches!(letter, 'X' | 'Y' | 'Z') {
+                    if let Some(mode) = estimate.motion_mode {
+                        command = Some(Command {
+                            letter: 'G',
+                            number: mode,
+                            words: vec![],
+                            end: i,
+                        });
+                    }
+                }
+                if let Some(command) = command.as_mut() {
+                    command.words.push((letter, value));
+                    command.end = i;
+                }
+            }
+            _ => {}
+        }
+    }
+    finish(command, &mut estimate);
+
+    // Insert from the back so earlier indices stay valid
+    for (end, time) in annotations.into_iter().rev() {
+        program.insert(
+            end + 1,
+            Token::Comment {
+                is_inline: false,
+                inner: Cow::Owned(format!(" t={time:.2}")),
+            },
+        );
+    }
 }
 
 impl From<&PostprocessConfig> for g_code::emit::FormatOptions {
@@ -25,3 +1016,648 @@ impl From<&PostprocessConfig> for g_code::emit::FormatOptions {
         }
     }
 }
+
+/// Format a program into g-code with the options of `config`, check it with
+/// [PostprocessConfig::roundtrip], then leave out motion words with
+/// [PostprocessConfig::drop_repeated_motion_words] and number its lines with
+/// [PostprocessConfig::number_lines]
+///
+/// The tokens are formatted as they are: run [PostprocessConfig::apply] on them first.
+pub fn tokens_to_gcode(
+    program: &[Token<'_>],
+    config: &PostprocessConfig,
+) -> Result<String, RoundtripError> {
+    let mut gcode = String::new();
+    format_gcode_fmt(program.iter(), config.into(), &mut gcode)
+        .expect("formatting into a string can't fail");
+    let gcode = config.roundtrip(&gcode, config.into())?;
+    Ok(config.number_lines(&config.drop_repeated_motion_words(&gcode)))
+}
+
+/// Custom pass over a generated program, run once conversion is complete.
+///
+/// Useful for injecting machine-specific codes, renaming axes, or adding comments without
+/// having to re-parse the emitted g-code. Implemented for any `FnMut(&mut Vec<Token>)`.
+pub trait TokenPostProcessor<'input> {
+    fn process(&mut self, program: &mut Vec<Token<'input>>);
+}
+
+impl<'input, F> TokenPostProcessor<'input> for F
+where
+    F: FnMut(&mut Vec<Token<'input>>),
+{
+    fn process(&mut self, program: &mut Vec<Token<'input>>) {
+        self(program)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use g_code::{
+        command,
+        emit::{format_gcode_fmt, FormatOptions},
+    };
+
+    #[test]
+    fn feedrate_decimals_only_rounds_feedrate() {
+        let mut program = command!(LinearInterpolation {
+            X: 1.23456,
+            Y: 2.5,
+            F: 299.7,
+        })
+        .into_token_vec();
+        PostprocessConfig {
+            feedrate_decimals: Some(0),
+            ..Default::default()
+        }
+        .apply(&mut program, FeedrateUnits::default());
+
+        let mut code = String::new();
+        format_gcode_fmt(program.iter(), Default::default(), &mut code).unwrap();
+        assert_eq!(code.trim(), "G1 X1.23456 Y2.5 F300");
+    }
+
+    #[test]
+    fn datum_offset_shifts_absolute_coordinates() {
+        let mut program = command!(RapidPositioning { X: 1., Y: 2. }).into_token_vec();
+        program.append(
+            &mut command!(ClockwiseCircularInterpolation {
+                X: 3.,
+                Y: 4.,
+                I: 1.,
+                J: 0.,
+            })
+            .into_token_vec(),
+        );
+        program.append(&mut command!(RelativeDistanceMode {}).into_token_vec());
+        program.append(&mut command!(LinearInterpolation { X: 1., Y: 1. }).into_token_vec());
+        program.append(&mut command!(AbsoluteDistanceMode {}).into_token_vec());
+        program.append(&mut command!(LinearInterpolation { X: 0., Y: 0., Z: -1. }).into_token_vec());
+        PostprocessConfig {
+            datum_offset: [10., 20.],
+            ..Default::default()
+        }
+        .apply(&mut program, FeedrateUnits::default());
+
+        let mut code = String::new();
+        format_gcode_fmt(program.iter(), Default::default(), &mut code).unwrap();
+        assert_eq!(
+            code.lines().collect::<Vec<_>>(),
+            [
+                "G0 X11 Y22",
+                "G2 X13 Y24 I1 J0",
+                "G91",
+                "G1 X1 Y1",
+                "G90",
+                "G1 X10 Y20 Z-1"
+            ]
+        );
+    }
+
+    #[test]
+    fn grid_snap_rounds_to_nearest_multiple() {
+        let mut program = command!(LinearInterpolation {
+            X: 1.23,
+            Y: -0.76,
+            Z: 1.23,
+            F: 300.,
+        })
+        .into_token_vec();
+        PostprocessConfig {
+            grid_snap: Some(0.05),
+            ..Default::default()
+        }
+        .apply(&mut program, FeedrateUnits::default());
+
+        let mut code = String::new();
+        format_gcode_fmt(program.iter(), Default::default(), &mut code).unwrap();
+        assert_eq!(code.trim(), "G1 X1.25 Y-0.75 Z1.23 F300");
+    }
+
+    #[test]
+    fn grid_snap_keeps_arc_radius_consistent() {
+        let mut program = command!(RapidPositioning { X: 0.02, Y: 0. }).into_token_vec();
+        program.append(
+            &mut command!(ClockwiseCircularInterpolation {
+                X: 1.,
+                Y: 0.98,
+                I: 0.98,
+                J: 0.,
+            })
+            .into_token_vec(),
+        );
+        program.append(&mut command!(RapidPositioning { X: 0.02, Y: 0. }).into_token_vec());
+        program.append(
+            &mut command!(ClockwiseCircularInterpolation {
+                X: 1.,
+                Y: 0.98,
+                R: 0.98,
+            })
+            .into_token_vec(),
+        );
+        program.append(&mut command!(RapidPositioning { X: 0.02, Y: 0. }).into_token_vec());
+        // Shorter than the step
+        program.append(
+            &mut command!(ClockwiseCircularInterpolation {
+                X: 0.01,
+                Y: 0.02,
+                I: 0.5,
+                J: 0.,
+            })
+            .into_token_vec(),
+        );
+        PostprocessConfig {
+            grid_snap: Some(0.05),
+            ..Default::default()
+        }
+        .apply(&mut program, FeedrateUnits::default());
+
+        let mut code = String::new();
+        format_gcode_fmt(program.iter(), Default::default(), &mut code).unwrap();
+        let lines = code.lines().collect::<Vec<_>>();
+        assert_eq!(lines.len(), 6, "{code}");
+        // The center moves onto the perpendicular bisector of the snapped endpoints
+        assert_eq!(lines[..3], ["G0 X0 Y0", "G2 X1 Y1 I1 J0", "G0 X0 Y0"]);
+        let (move_to, radius) = lines[3].split_once(" R").unwrap();
+        assert_eq!(move_to, "G2 X1 Y1");
+        assert!((radius.parse::<f64>().unwrap() - 1.).abs() < 1e-9, "{code}");
+        assert_eq!(lines[4..], ["G0 X0 Y0", "G1 X0 Y0"]);
+    }
+
+    #[test]
+    fn grid_snap_does_not_accumulate_relative_moves() {
+        let mut program = command!(RapidPositioning { X: 0., Y: 0. }).into_token_vec();
+        program.append(&mut command!(RelativeDistanceMode {}).into_token_vec());
+        for _ in 0..3 {
+            program.append(&mut command!(LinearInterpolation { X: 0.03, Y: 0. }).into_token_vec());
+        }
+        program.append(&mut command!(AbsoluteDistanceMode {}).into_token_vec());
+        program.append(&mut command!(LinearInterpolation { X: 1.01, Y: 0. }).into_token_vec());
+        PostprocessConfig {
+            grid_snap: Some(0.05),
+            ..Default::default()
+        }
+        .apply(&mut program, FeedrateUnits::default());
+
+        let mut code = String::new();
+        format_gcode_fmt(program.iter(), Default::default(), &mut code).unwrap();
+        // 0.03, 0.06 and 0.09 snap to 0.05, 0.05 and 0.1
+        assert_eq!(
+            code.lines().collect::<Vec<_>>(),
+            [
+                "G0 X0 Y0",
+                "G91",
+                "G1 X0.05 Y0",
+                "G1 X0 Y0",
+                "G1 X0.05 Y0",
+                "G90",
+                "G1 X1 Y0"
+            ]
+        );
+    }
+
+    #[test]
+    fn modal_gcodes_drops_repeated_motion_words() {
+        let mut program = command!(RapidPositioning { X: 0., Y: 0. }).into_token_vec();
+        for x in [1., 2., 3.] {
+            program.append(&mut command!(LinearInterpolation { X: x, Y: 1. }).into_token_vec());
+        }
+        program.append(&mut command!(RapidPositioning { X: 0., Y: 0. }).into_token_vec());
+        let mut config = PostprocessConfig {
+            modal_gcodes: true,
+            ..Default::default()
+        };
+        config.apply(&mut program, FeedrateUnits::default());
+
+        let code = tokens_to_gcode(&program, &config).unwrap();
+        assert_eq!(
+            code.lines().collect::<Vec<_>>(),
+            ["G0 X0 Y0", "G1 X1 Y1", "X2 Y1", "X3 Y1", "G0 X0 Y0"]
+        );
+
+        config.line_numbers = true;
+        config.checksums = true;
+        let code = tokens_to_gcode(&program, &config).unwrap();
+        for line in code.lines() {
+            let (numbered, checksum) = line.split_once('*').unwrap();
+            assert_eq!(
+                checksum.parse::<u8>().unwrap(),
+                numbered.bytes().fold(0, |acc, b| acc ^ b),
+                "{line}"
+            );
+        }
+        assert!(code.lines().nth(2).unwrap().contains(" X2 Y1*"), "{code}");
+    }
+
+    #[test]
+    fn modal_gcodes_follow_motion_words_inside_user_sequences() {
+        let config = PostprocessConfig {
+            modal_gcodes: true,
+            ..Default::default()
+        };
+        // As a begin, tool or between-layers sequence could be formatted
+        let code = [
+            "G1 X1 Y1",
+            "G90 G0 Z5",
+            "G1 X2 Y2 F300",
+            "G1 X3 Y3",
+            "M5 G0 X0 (G1)",
+            "G0 X1",
+            "G1 X4 Y4",
+            "G38.2 Z-1",
+            "G1 X5 Y5",
+            "G81 Z-1 R1",
+            "G80",
+            "G1 X6 Y6",
+            "G1 X7 Y7",
+            "",
+        ]
+        .join("\n");
+
+        assert_eq!(
+            config.drop_repeated_motion_words(&code).lines().collect::<Vec<_>>(),
+            [
+                "G1 X1 Y1",
+                "G90 G0 Z5",
+                "G1 X2 Y2 F300",
+                "X3 Y3",
+                "M5 G0 X0 (G1)",
+                "X1",
+                "G1 X4 Y4",
+                "G38.2 Z-1",
+                "G1 X5 Y5",
+                "G81 Z-1 R1",
+                "G80",
+                "G1 X6 Y6",
+                "X7 Y7",
+            ]
+        );
+    }
+
+    #[test]
+    fn modal_feedrate_drops_repeated_feedrates() {
+        let mut program = command!(RapidPositioning { X: 0., Y: 0. }).into_token_vec();
+        for x in [1., 2., 3.] {
+            program.append(
+                &mut command!(LinearInterpolation { X: x, Y: 1., F: 300. }).into_token_vec(),
+            );
+        }
+        // A changed feedrate, e.g. from arc feedrate scaling, and back again
+        program.append(&mut command!(LinearInterpolation { X: 4., Y: 1., F: 150. }).into_token_vec());
+        program.append(&mut command!(LinearInterpolation { X: 5., Y: 1., F: 300. }).into_token_vec());
+        PostprocessConfig {
+            modal_feedrate: true,
+            ..Default::default()
+        }
+        .apply(&mut program, FeedrateUnits::default());
+
+        let mut code = String::new();
+        format_gcode_fmt(program.iter(), Default::default(), &mut code).unwrap();
+        assert_eq!(
+            code.lines().collect::<Vec<_>>(),
+            [
+                "G0 X0 Y0",
+                "G1 X1 Y1 F300",
+                "G1 X2 Y1",
+                "G1 X3 Y1",
+                "G1 X4 Y1 F150",
+                "G1 X5 Y1 F300"
+            ]
+        );
+    }
+
+    #[test]
+    fn annotate_time_increases_over_cutting_moves() {
+        let mut program = command!(RapidPositioning { X: 0., Y: 0. }).into_token_vec();
+        program.append(&mut command!(LinearInterpolation { X: 10., Y: 0., F: 300. }).into_token_vec());
+        program.append(&mut command!(LinearInterpolation { X: 10., Y: 10. }).into_token_vec());
+        // Half a circle of radius 5
+        program.append(
+            &mut command!(ClockwiseCircularInterpolation {
+                X: 0.,
+                Y: 10.,
+                I: -5.,
+                J: 0.,
+            })
+            .into_token_vec(),
+        );
+        program.append(&mut command!(RapidPositioning { X: 0., Y: 0. }).into_token_vec());
+        PostprocessConfig {
+            annotate_time: true,
+            ..Default::default()
+        }
+        .apply(&mut program, FeedrateUnits::default());
+
+        let mut code = String::new();
+        format_gcode_fmt(program.iter(), Default::default(), &mut code).unwrap();
+        let times = code
+            .lines()
+            .map(|line| line.split_once("; t=").unwrap().1.parse::<f64>().unwrap())
+            .collect::<Vec<_>>();
+        assert_eq!(times.len(), 5, "{code}");
+        assert!(times[..4].windows(2).all(|pair| pair[0] < pair[1]), "{code}");
+        assert_eq!(times[..3], [0., 2., 4.], "{code}");
+        assert!((times[3] - (4. + 5. * std::f64::consts::PI / 300. * 60.)).abs() < 0.01);
+        // Rapids aren't estimated
+        assert_eq!(times[4], times[3]);
+    }
+
+    #[test]
+    fn annotate_time_reads_feedrate_units() {
+        let times = |feedrate, feedrate_units| {
+            let mut program = command!(RapidPositioning { X: 0., Y: 0. }).into_token_vec();
+            program.append(
+                &mut command!(LinearInterpolation { X: 10., Y: 0., F: feedrate }).into_token_vec(),
+            );
+            PostprocessConfig {
+                annotate_time: true,
+                ..Default::default()
+            }
+            .apply(&mut program, feedrate_units);
+
+            let mut code = String::new();
+            format_gcode_fmt(program.iter(), Default::default(), &mut code).unwrap();
+            code
+        };
+
+        let per_minute = times(300., FeedrateUnits::PerMinute);
+        assert!(per_minute.trim_end().ends_with("; t=2.00"), "{per_minute}");
+        assert_eq!(times(5., FeedrateUnits::PerSecond), per_minute.replace("F300", "F5"));
+    }
+
+    #[test]
+    fn annotate_time_measures_radius_arcs_like_center_arcs() {
+        let time = |arc: Vec<Token<'static>>| {
+            let mut program =
+                command!(LinearInterpolation { X: 10., Y: 0., F: 300. }).into_token_vec();
+            program.extend(arc);
+            PostprocessConfig {
+                annotate_time: true,
+                ..Default::default()
+            }
+            .apply(&mut program, FeedrateUnits::default());
+
+            let mut code = String::new();
+            format_gcode_fmt(program.iter(), Default::default(), &mut code).unwrap();
+            code.lines()
+                .next_back()
+                .and_then(|line| line.split_once("; t="))
+                .map(|(_, time)| time.parse::<f64>().unwrap())
+                .unwrap()
+        };
+
+        // A quarter of a circle of radius 5 around (15, 0), then the rest of it
+        let quarter = time(
+            command!(ClockwiseCircularInterpolation { X: 15., Y: 5., R: 5. }).into_token_vec(),
+        );
+        assert!((quarter - 2.5 * std::f64::consts::PI / 300. * 60.).abs() < 0.01, "{quarter}");
+        let center_quarter = time(
+            command!(ClockwiseCircularInterpolation { X: 15., Y: 5., I: 5., J: 0. })
+                .into_token_vec(),
+        );
+        assert!((quarter - center_quarter).abs() < 0.01, "{quarter} {center_quarter}");
+        let rest = time(
+            command!(CounterclockwiseCircularInterpolation { X: 15., Y: 5., R: -5. })
+                .into_token_vec(),
+        );
+        assert!((rest - 7.5 * std::f64::consts::PI / 300. * 60.).abs() < 0.01, "{rest}");
+    }
+
+    #[test]
+    fn annotate_subpaths_brackets_element_moves() {
+        let svg = r#"<svg xmlns="http://www.w3.org/2000/svg" width="20mm" height="20mm" viewBox="0 0 20 20">
+            <path id="foo" d="M 1 1 L 5 1 L 5 4"/>
+            <path id="bar" d="M 10 10 L 12 10"/>
+        </svg>"#;
+        let document = roxmltree::Document::parse(svg).unwrap();
+        let config = crate::ConversionConfig {
+            origin: [None, None],
+            ..Default::default()
+        };
+        let machine = crate::Machine::new(Default::default(), None, None, None, None, None);
+        let mut program = crate::svg2program(&document, &config, Default::default(), machine);
+        PostprocessConfig {
+            annotate_subpaths: true,
+            ..Default::default()
+        }
+        .apply(&mut program, FeedrateUnits::default());
+
+        let mut code = String::new();
+        let options = FormatOptions {
+            newline_before_comment: true,
+            ..Default::default()
+        };
+        format_gcode_fmt(program.iter(), options, &mut code).unwrap();
+        let lines = code.lines().collect::<Vec<_>>();
+        let begin_foo = lines
+            .iter()
+            .position(|line| *line == ";begin path#foo bbox [1,16]-[5,19]")
+            .unwrap();
+        let end_foo = lines.iter().position(|line| *line == ";end path#foo").unwrap();
+        let begin_bar = lines
+            .iter()
+            .position(|line| *line == ";begin path#bar bbox [10,10]-[12,10]")
+            .unwrap();
+        let end_bar = lines.iter().position(|line| *line == ";end path#bar").unwrap();
+        assert!(begin_foo < end_foo && end_foo < begin_bar && begin_bar < end_bar);
+        // The moves of each path are inside its brackets
+        assert_eq!(
+            lines[begin_foo + 1..end_foo]
+                .iter()
+                .filter(|line| line.starts_with("G0") || line.starts_with("G1"))
+                .count(),
+            3,
+            "{code}"
+        );
+        assert_eq!(
+            lines[begin_bar + 1..end_bar]
+                .iter()
+                .filter(|line| line.starts_with("G0") || line.starts_with("G1"))
+                .count(),
+            2,
+            "{code}"
+        );
+    }
+
+    #[test]
+    fn number_lines_from_start_by_increment() {
+        let svg = r#"<svg xmlns="http://www.w3.org/2000/svg" width="20mm" height="20mm" viewBox="0 0 20 20">
+            <path d="M 1 1 L 5 1 L 5 4"/>
+        </svg>"#;
+        let document = roxmltree::Document::parse(svg).unwrap();
+        let machine = crate::Machine::new(Default::default(), None, None, None, None, None);
+        let program =
+            crate::svg2program(&document, &Default::default(), Default::default(), machine);
+        let config = PostprocessConfig {
+            line_numbers: true,
+            checksums: true,
+            line_number_start: 1000,
+            line_number_increment: 10,
+            ..Default::default()
+        };
+
+        let mut code = String::new();
+        format_gcode_fmt(program.iter(), (&config).into(), &mut code).unwrap();
+        let code = config.number_lines(&code);
+        let lines = code.lines().collect::<Vec<_>>();
+        assert!(lines.len() > 3, "{code}");
+        for (i, line) in lines.iter().enumerate() {
+            assert!(line.starts_with(&format!("N{} ", 1000 + 10 * i)), "{code}");
+            // The checksum covers everything before it
+            let star = line.find(['*']).unwrap();
+            let end = line[star + 1..]
+                .find(';')
+                .map_or(line.len(), |semicolon| star + 1 + semicolon);
+            let checksum = line[..star].bytes().fold(0, |acc, b| acc ^ b);
+            assert_eq!(line[star + 1..end].parse::<u8>().unwrap(), checksum, "{line}");
+        }
+    }
+
+    #[test]
+    fn roundtrip_validates_generated_gcode() {
+        let svg = r#"<svg xmlns="http://www.w3.org/2000/svg" width="20mm" height="20mm" viewBox="0 0 20 20">
+            <path d="M 1 1 L 5 1 A 2 2 0 0 1 5 5 Z"/>
+        </svg>"#;
+        let document = roxmltree::Document::parse(svg).unwrap();
+        let machine = crate::Machine::new(Default::default(), None, None, None, None, None);
+        let mut program =
+            crate::svg2program(&document, &Default::default(), Default::default(), machine);
+        let config = PostprocessConfig {
+            checksums: true,
+            line_numbers: true,
+            validate_roundtrip: true,
+            ..Default::default()
+        };
+
+        let format = |program: &[Token]| {
+            let mut code = String::new();
+            format_gcode_fmt(program.iter(), (&config).into(), &mut code).unwrap();
+            code
+        };
+        let code = format(&program);
+        assert_eq!(config.roundtrip(&code, (&config).into()), Ok(code.clone()));
+
+        // A corrupt checksum is caught
+        let second_line = code.lines().nth(1).unwrap();
+        let corrupted = code.replacen(second_line, &second_line.replace('*', "*1"), 1);
+        assert_eq!(
+            config.roundtrip(&corrupted, (&config).into()),
+            Err(RoundtripError::Checksum { line: 2 })
+        );
+
+        // As is a token that doesn't format into valid g-code, here parsed as flags
+        program.extend(command!(LinearInterpolation { X: f64::NAN }).into_token_vec());
+        let code = format(&program);
+        assert_eq!(
+            config.roundtrip(&code, (&config).into()),
+            Err(RoundtripError::Mismatch {
+                line: code.lines().count()
+            })
+        );
+        assert_eq!(
+            tokens_to_gcode(&program, &config),
+            Err(RoundtripError::Mismatch {
+                line: code.lines().count()
+            })
+        );
+        assert!(matches!(
+            config.roundtrip("G1 X1 (unterminated", (&config).into()),
+            Err(RoundtripError::Parse(_))
+        ));
+    }
+
+    #[test]
+    fn comment_style_rewrites_comments() {
+        let comment = |is_inline, inner: &'static str| Token::Comment {
+            is_inline,
+            inner: Cow::Borrowed(inner),
+        };
+        let mut program = vec![comment(false, "svg > path#a (copy)")];
+        program.append(&mut command!(RapidPositioning { X: 1., Y: 1. }).into_token_vec());
+        program.push(comment(true, "x(1)"));
+        program.append(&mut command!(LinearInterpolation { X: 2., Y: 2. }).into_token_vec());
+        let format = |comment_style| {
+            let config = PostprocessConfig {
+                comment_style,
+                ..Default::default()
+            };
+            let mut program = program.clone();
+            config.apply(&mut program, FeedrateUnits::default());
+            let mut code = String::new();
+            format_gcode_fmt(program.iter(), FormatOptions::default(), &mut code).unwrap();
+            code
+        };
+
+        assert_eq!(
+            format(CommentStyle::Mixed),
+            ";svg > path#a (copy)\nG0 X1 Y1(x(1))\nG1 X2 Y2\n"
+        );
+        // Parentheses in the comments are escaped, and the comment before a line ends it
+        assert_eq!(
+            format(CommentStyle::Parentheses),
+            "G0 X1 Y1(x[1])(svg > path#a [copy])\nG1 X2 Y2\n"
+        );
+        assert_eq!(
+            format(CommentStyle::Semicolon),
+            ";svg > path#a (copy)\nG0 X1 Y1;x(1)\nG1 X2 Y2\n"
+        );
+    }
+
+    #[test]
+    fn parenthesized_comment_keeps_modal_line_break() {
+        let config = PostprocessConfig {
+            modal_gcodes: true,
+            comment_style: CommentStyle::Parentheses,
+            ..Default::default()
+        };
+        let mut program = command!(LinearInterpolation { X: 1., Y: 1. }).into_token_vec();
+        program.push(Token::Comment {
+            is_inline: false,
+            inner: Cow::Borrowed("next"),
+        });
+        program.append(&mut command!(LinearInterpolation { X: 2., Y: 2. }).into_token_vec());
+        config.apply(&mut program, FeedrateUnits::default());
+
+        assert_eq!(
+            tokens_to_gcode(&program, &config).unwrap(),
+            "G1 X1 Y1\nX2 Y2(next)\n"
+        );
+    }
+
+    #[test]
+    fn blank_lines_separate_layers() {
+        let svg = r#"<svg xmlns="http://www.w3.org/2000/svg" width="20mm" height="20mm" viewBox="0 0 20 20">
+            <g id="a"><path d="M 1 1 L 5 1"/></g>
+            <g id="b"><path d="M 1 5 L 5 5"/></g>
+            <g id="c"><path d="M 1 9 L 5 9"/></g>
+        </svg>"#;
+        let document = roxmltree::Document::parse(svg).unwrap();
+        let machine = crate::Machine::new(Default::default(), None, None, None, None, None);
+        let program =
+            crate::svg2program(&document, &Default::default(), Default::default(), machine);
+        let format = |blank_lines_between_layers| {
+            let config = PostprocessConfig {
+                blank_lines_between_layers,
+                validate_roundtrip: true,
+                ..Default::default()
+            };
+            let mut program = program.clone();
+            config.apply(&mut program, FeedrateUnits::default());
+            let mut code = String::new();
+            format_gcode_fmt(program.iter(), (&config).into(), &mut code).unwrap();
+            config.roundtrip(&code, (&config).into()).unwrap()
+        };
+
+        let unseparated = format(0);
+        assert!(!unseparated.contains("\n\n"), "{unseparated}");
+        let separated = format(2);
+        // One separator before each layer after the first
+        assert_eq!(separated.matches(";\n\n\n").count(), 2, "{separated}");
+        assert!(!separated.contains("\n\n\n\n"), "{separated}");
+        assert_eq!(
+            separated.lines().filter(|line| !line.is_empty()).collect::<Vec<_>>(),
+            unseparated.lines().collect::<Vec<_>>()
+        );
+    }
+}
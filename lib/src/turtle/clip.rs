@@ -0,0 +1,103 @@
+use lyon_geom::{LineSegment, Point};
+
+/// Area outside of which nothing is drawn, as set by an SVG `clip-path`
+///
+/// Each ring is an implicitly closed polygon. A point is inside the region if any ring winds
+/// around it, so multiple shapes in a clip path act as their union.
+#[derive(Debug, Clone)]
+pub struct ClipRegion {
+    pub rings: Vec<Vec<Point<f64>>>,
+    /// Flattening tolerance for curves drawn within the region
+    pub tolerance: f64,
+}
+
+impl ClipRegion {
+    fn edges(&self) -> impl Iterator<Item = LineSegment<f64>> + '_ {
+        self.rings.iter().flat_map(|ring| {
+            ring.iter()
+                .zip(ring.iter().cycle().skip(1))
+                .map(|(from, to)| LineSegment {
+                    from: *from,
+                    to: *to,
+                })
+        })
+    }
+
+    fn contains(&self, point: Point<f64>) -> bool {
+        self.rings.iter().any(|ring| winds_around(ring, point))
+    }
+}
+
+/// Whether the implicitly closed polygon `ring` has a nonzero winding number around `point`
+pub fn winds_around(ring: &[Point<f64>], point: Point<f64>) -> bool {
+    let winding: i32 = ring
+        .iter()
+        .zip(ring.iter().cycle().skip(1))
+        .map(|(from, to)| {
+            let side = (*to - *from).cross(point - *from);
+            if from.y <= point.y && to.y > point.y && side > 0. {
+                1
+            } else if from.y > point.y && to.y <= point.y && side < 0. {
+                -1
+            } else {
+                0
+            }
+        })
+        .sum();
+    winding != 0
+}
+
+/// Pieces of the line from `from` to `to` that lie inside all of the `regions`
+pub fn clip_line(
+    regions: &[ClipRegion],
+    from: Point<f64>,
+    to: Point<f64>,
+) -> Vec<LineSegment<f64>> {
+    let line = LineSegment { from, to };
+    let mut crossings = vec![0., 1.];
+    crossings.extend(
+        regions
+            .iter()
+            .flat_map(ClipRegion::edges)
+            .filter_map(|edge| line.intersection_t(&edge).map(|(t, _)| t)),
+    );
+    crossings.sort_by(f64::total_cmp);
+    crossings.dedup();
+
+    let mut pieces: Vec<LineSegment<f64>> = vec![];
+    for t in crossings.windows(2) {
+        let piece = line.split_range(t[0]..t[1]);
+        if !regions.iter().all(|region| region.contains(piece.sample(0.5))) {
+            continue;
+        }
+        match pieces.last_mut() {
+            Some(last) if last.to == piece.from => last.to = piece.to,
+            _ => pieces.push(piece),
+        }
+    }
+    pieces
+}
+
+#[cfg(test)]
+mod test {
+    use lyon_geom::point;
+
+    use super::*;
+
+    #[test]
+    fn line_crossing_square_is_clipped_to_its_inside() {
+        let square = ClipRegion {
+            rings: vec![vec![
+                point(0., 0.),
+                point(10., 0.),
+                point(10., 10.),
+                point(0., 10.),
+            ]],
+            tolerance: 0.1,
+        };
+        let pieces = clip_line(&[square], point(-5., 5.), point(15., 5.));
+        assert_eq!(pieces.len(), 1);
+        assert_eq!(pieces[0].from, point(0., 5.));
+        assert_eq!(pieces[0].to, point(10., 5.));
+    }
+}
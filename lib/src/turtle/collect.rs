@@ -0,0 +1,117 @@
+use lyon_geom::{CubicBezierSegment, Point, QuadraticBezierSegment, SvgArc};
+#[cfg(feature = "serde")]
+use serde::Serialize;
+
+use super::{canonical_color, ElementInfo, LineCap, LineJoin, Turtle};
+
+/// A flattened subpath along with metadata about the element it was traced from
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize))]
+pub struct SubpathInfo {
+    /// `id` attribute of the element
+    pub id: Option<String>,
+    /// Stroke color of the element, else its fill color, as `#rrggbb` or `#rrggbbaa` if it is
+    /// translucent
+    pub color: Option<String>,
+    /// Length of the flattened subpath in millimeters
+    pub length: f64,
+    /// Flattened vertices in millimeters
+    pub points: Vec<[f64; 2]>,
+    /// Whether the subpath was ended with closepath rather than left open
+    pub closed: bool,
+    /// `stroke-linecap` of the element
+    pub stroke_linecap: LineCap,
+    /// `stroke-linejoin` of the element
+    pub stroke_linejoin: LineJoin,
+    /// `stroke-miterlimit` of the element
+    pub stroke_miterlimit: f64,
+}
+
+/// Collects flattened subpaths instead of generating g-code
+#[derive(Debug, Default)]
+pub struct SubpathCollectingTurtle {
+    pub tolerance: f64,
+    pub subpaths: Vec<SubpathInfo>,
+    element: ElementInfo,
+    current: Option<Point<f64>>,
+}
+
+impl SubpathCollectingTurtle {
+    pub fn new(tolerance: f64) -> Self {
+        Self {
+            tolerance,
+            ..Default::default()
+        }
+    }
+}
+
+impl Turtle for SubpathCollectingTurtle {
+    fn begin(&mut self) {}
+
+    fn end(&mut self) {
+        // A move without any drawing does not make a subpath
+        self.subpaths.retain(|subpath| subpath.points.len() > 1);
+    }
+
+    fn comment(&mut self, _comment: String) {}
+
+    fn element(&mut self, element: &ElementInfo) {
+        self.element = element.clone();
+    }
+
+    fn tolerance(&mut self, tolerance: f64) {
+        self.tolerance = tolerance;
+    }
+
+    fn move_to(&mut self, to: Point<f64>) {
+        self.subpaths.push(SubpathInfo {
+            id: self.element.id.clone(),
+            color: self.element.color.map(canonical_color),
+            length: 0.,
+            points: vec![to.to_array()],
+            closed: false,
+            stroke_linecap: self.element.line_cap,
+            stroke_linejoin: self.element.line_join,
+            stroke_miterlimit: self.element.miter_limit,
+        });
+        self.current = Some(to);
+    }
+
+    fn close(&mut self) {
+        if let Some(subpath) = self.subpaths.last_mut() {
+            subpath.closed = true;
+        }
+    }
+
+    fn line_to(&mut self, to: Point<f64>) {
+        if self.subpaths.is_empty() {
+            self.move_to(self.current.unwrap_or_default());
+        }
+        let subpath = self.subpaths.last_mut().expect("subpath was started");
+        if let Some(from) = self.current {
+            subpath.length += (to - from).length();
+        }
+        subpath.points.push(to.to_array());
+        self.current = Some(to);
+    }
+
+    fn arc(&mut self, svg_arc: SvgArc<f64>) {
+        if svg_arc.is_straight_line() {
+            self.line_to(svg_arc.to);
+        } else {
+            svg_arc
+                .to_arc()
+                .flattened(self.tolerance)
+                .for_each(|point| self.line_to(point));
+        }
+    }
+
+    fn cubic_bezier(&mut self, cbs: CubicBezierSegment<f64>) {
+        cbs.flattened(self.tolerance)
+            .for_each(|point| self.line_to(point));
+    }
+
+    fn quadratic_bezier(&mut self, qbs: QuadraticBezierSegment<f64>) {
+        self.cubic_bezier(qbs.to_cubic());
+    }
+}
@@ -0,0 +1,323 @@
+use std::mem;
+
+use lyon_geom::{Box2D, CubicBezierSegment, Point, QuadraticBezierSegment, SvgArc};
+
+use super::{clip::winds_around, ElementInfo, Turtle};
+use crate::LayerParams;
+
+/// Number of kinds of calls that set state lasting until the next call of the same kind
+const STATE_KINDS: usize = 3;
+
+/// A [Turtle] call, recorded to be replayed later
+#[derive(Debug, Clone)]
+pub(super) enum Call {
+    Comment(String),
+    BetweenLayers,
+    Tool(u32),
+    Element(ElementInfo),
+    Tolerance(f64),
+    LayerParams(LayerParams),
+    Source(Point<f64>),
+    MoveTo(Point<f64>),
+    Close,
+    LineTo(Point<f64>),
+    Arc(SvgArc<f64>),
+    CubicBezier(CubicBezierSegment<f64>),
+    QuadraticBezier(QuadraticBezierSegment<f64>),
+}
+
+impl Call {
+    /// Index of the state this call sets, if any
+    fn state_kind(&self) -> Option<usize> {
+        match self {
+            Self::Element(_) => Some(0),
+            Self::Tolerance(_) => Some(1),
+            Self::LayerParams(_) => Some(2),
+            _ => None,
+        }
+    }
+
+    /// Add the points of a drawing call to a flattened outline
+    pub(super) fn flatten(&self, tolerance: f64, points: &mut Vec<Point<f64>>) {
+        match self {
+            Self::MoveTo(to) | Self::LineTo(to) => points.push(*to),
+            Self::Arc(svg_arc) => {
+                points.push(svg_arc.from);
+                svg_arc.for_each_flattened(tolerance, &mut |line| points.push(line.to));
+            }
+            Self::CubicBezier(cbs) => {
+                points.push(cbs.from);
+                cbs.for_each_flattened(tolerance, &mut |line| points.push(line.to));
+            }
+            Self::QuadraticBezier(qbs) => {
+                points.push(qbs.from);
+                qbs.for_each_flattened(tolerance, &mut |line| points.push(line.to));
+            }
+            _ => {}
+        }
+        points.dedup();
+    }
+
+    pub(super) fn replay(self, turtle: &mut impl Turtle) {
+        match self {
+            Self::Comment(comment) => turtle.comment(comment),
+            Self::BetweenLayers => turtle.between_layers(),
+            Self::Tool(tool) => turtle.tool(tool),
+            Self::Element(element) => turtle.element(&element),
+            Self::Tolerance(tolerance) => turtle.tolerance(tolerance),
+            Self::LayerParams(params) => turtle.layer_params(&params),
+            Self::Source(point) => turtle.source(point),
+            Self::MoveTo(to) => turtle.move_to(to),
+            Self::Close => turtle.close(),
+            Self::LineTo(to) => turtle.line_to(to),
+            Self::Arc(svg_arc) => turtle.arc(svg_arc),
+            Self::CubicBezier(cbs) => turtle.cubic_bezier(cbs),
+            Self::QuadraticBezier(qbs) => turtle.quadratic_bezier(qbs),
+        }
+    }
+}
+
+/// State calls in effect, each with the order it was recorded in to tell repeated values apart
+type State = [Option<(usize, Call)>; STATE_KINDS];
+
+#[derive(Debug)]
+struct RecordedSubpath {
+    /// State in effect when the subpath started
+    state: State,
+    /// One-off calls made since the previous subpath, like comments and [Turtle::between_layers]
+    events: Vec<Call>,
+    calls: Vec<Call>,
+    /// Flattened outline, to test containment with
+    points: Vec<Point<f64>>,
+    closed: bool,
+}
+
+impl RecordedSubpath {
+    fn is_closed(&self) -> bool {
+        self.closed || self.points.len() > 2 && self.points.first() == self.points.last()
+    }
+}
+
+/// Wrapper turtle that cuts subpaths inside of others first, see
+/// [crate::ConversionConfig::order_by_containment]
+///
+/// Calls are buffered until [ContainmentOrderingTurtle::flush] or the end of the program, since
+/// any subpath may turn out to contain one drawn later.
+#[derive(Debug)]
+pub struct ContainmentOrderingTurtle<T: Turtle> {
+    pub inner: T,
+    /// Whether subpaths are reordered, else calls pass straight through
+    pub enabled: bool,
+    /// Flattening tolerance for containment tests
+    tolerance: f64,
+    state: State,
+    recorded: usize,
+    events: Vec<Call>,
+    source: Option<Point<f64>>,
+    subpaths: Vec<RecordedSubpath>,
+    /// State last replayed into the inner turtle, by recording order
+    replayed: [Option<usize>; STATE_KINDS],
+}
+
+impl<T: Turtle> ContainmentOrderingTurtle<T> {
+    pub fn new(inner: T, enabled: bool, tolerance: f64) -> Self {
+        Self {
+            inner,
+            enabled,
+            tolerance,
+            state: Default::default(),
+            recorded: 0,
+            events: vec![],
+            source: None,
+            subpaths: vec![],
+            replayed: [None; STATE_KINDS],
+        }
+    }
+
+    /// Pass on everything buffered so far
+    fn replay_buffered(&mut self) {
+        let order = containment_order(&self.subpaths);
+        let mut subpaths = mem::take(&mut self.subpaths).into_iter().map(Some).collect::<Vec<_>>();
+        for i in order {
+            if let Some(subpath) = subpaths[i].take() {
+                self.replay(subpath.events, subpath.state, subpath.calls);
+            }
+        }
+        // Anything recorded after the last subpath
+        let events = mem::take(&mut self.events);
+        let state = self.state.clone();
+        self.replay(events, state, vec![]);
+        if let Some(source) = self.source.take() {
+            self.inner.source(source);
+        }
+    }
+
+    fn replay(&mut self, events: Vec<Call>, state: State, calls: Vec<Call>) {
+        for call in events {
+            call.replay(&mut self.inner);
+        }
+        for (kind, state) in state.into_iter().enumerate() {
+            if let Some((index, call)) = state {
+                if self.replayed[kind] != Some(index) {
+                    self.replayed[kind] = Some(index);
+                    call.replay(&mut self.inner);
+                }
+            }
+        }
+        for call in calls {
+            call.replay(&mut self.inner);
+        }
+    }
+
+    fn record(&mut self, call: Call) {
+        if !self.enabled {
+            call.replay(&mut self.inner);
+            return;
+        }
+        if let Some(kind) = call.state_kind() {
+            if let Call::Tolerance(tolerance) = call {
+                self.tolerance = tolerance;
+            }
+            self.state[kind] = Some((self.recorded, call));
+            self.recorded += 1;
+        } else if matches!(call, Call::Comment(_) | Call::BetweenLayers | Call::Tool(_)) {
+            self.events.push(call);
+        } else if let Call::Source(point) = call {
+            self.source = Some(point);
+        } else {
+            self.draw(call);
+        }
+    }
+
+    fn draw(&mut self, call: Call) {
+        if matches!(call, Call::MoveTo(_)) || self.subpaths.is_empty() {
+            self.subpaths.push(RecordedSubpath {
+                state: self.state.clone(),
+                events: mem::take(&mut self.events),
+                calls: vec![],
+                points: vec![],
+                closed: false,
+            });
+        }
+        let subpath = self.subpaths.last_mut().expect("subpath was just started");
+        if matches!(call, Call::Close) {
+            subpath.closed = true;
+        }
+        call.flatten(self.tolerance, &mut subpath.points);
+        if let Some(source) = self.source.take() {
+            subpath.calls.push(Call::Source(source));
+        }
+        subpath.calls.push(call);
+    }
+}
+
+/// Order in which to cut `subpaths` so that each comes after the closed subpaths inside of it,
+/// otherwise keeping the order they were drawn in
+fn containment_order(subpaths: &[RecordedSubpath]) -> Vec<usize> {
+    let bounding_boxes = subpaths
+        .iter()
+        .map(|subpath| Box2D::from_points(&subpath.points))
+        .collect::<Vec<_>>();
+    let contains = |outer: usize, inner: usize| {
+        outer != inner
+            && subpaths[outer].is_closed()
+            && bounding_boxes[outer].contains_box(&bounding_boxes[inner])
+            && subpaths[inner]
+                .points
+                .iter()
+                .all(|point| winds_around(&subpaths[outer].points, *point))
+    };
+
+    fn visit(
+        i: usize,
+        contains: &dyn Fn(usize, usize) -> bool,
+        visited: &mut [bool],
+        order: &mut Vec<usize>,
+    ) {
+        if visited[i] {
+            return;
+        }
+        visited[i] = true;
+        for inner in 0..visited.len() {
+            if !visited[inner] && contains(i, inner) {
+                visit(inner, contains, visited, order);
+            }
+        }
+        order.push(i);
+    }
+
+    let mut visited = vec![false; subpaths.len()];
+    let mut order = Vec::with_capacity(subpaths.len());
+    for i in 0..subpaths.len() {
+        visit(i, &contains, &mut visited, &mut order);
+    }
+    order
+}
+
+impl<T: Turtle> Turtle for ContainmentOrderingTurtle<T> {
+    fn begin(&mut self) {
+        self.inner.begin()
+    }
+
+    fn end(&mut self) {
+        self.replay_buffered();
+        self.inner.end()
+    }
+
+    fn comment(&mut self, comment: String) {
+        self.record(Call::Comment(comment))
+    }
+
+    fn between_layers(&mut self) {
+        self.record(Call::BetweenLayers)
+    }
+
+    fn tool(&mut self, tool: u32) {
+        self.record(Call::Tool(tool))
+    }
+
+    fn element(&mut self, element: &ElementInfo) {
+        self.record(Call::Element(element.clone()))
+    }
+
+    fn tolerance(&mut self, tolerance: f64) {
+        self.record(Call::Tolerance(tolerance))
+    }
+
+    fn layer_params(&mut self, params: &LayerParams) {
+        self.record(Call::LayerParams(*params))
+    }
+
+    fn source(&mut self, point: Point<f64>) {
+        self.record(Call::Source(point))
+    }
+
+    fn move_to(&mut self, to: Point<f64>) {
+        self.record(Call::MoveTo(to))
+    }
+
+    fn close(&mut self) {
+        self.record(Call::Close)
+    }
+
+    fn flush(&mut self) {
+        self.replay_buffered();
+        self.inner.flush()
+    }
+
+    fn line_to(&mut self, to: Point<f64>) {
+        self.record(Call::LineTo(to))
+    }
+
+    fn arc(&mut self, svg_arc: SvgArc<f64>) {
+        self.record(Call::Arc(svg_arc))
+    }
+
+    fn cubic_bezier(&mut self, cbs: CubicBezierSegment<f64>) {
+        self.record(Call::CubicBezier(cbs))
+    }
+
+    fn quadratic_bezier(&mut self, qbs: QuadraticBezierSegment<f64>) {
+        self.record(Call::QuadraticBezier(qbs))
+    }
+}
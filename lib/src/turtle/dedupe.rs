@@ -0,0 +1,162 @@
+use std::mem;
+
+use lyon_geom::{CubicBezierSegment, Point, QuadraticBezierSegment, SvgArc};
+
+use super::{containment::Call, ElementInfo, Turtle};
+use crate::diagnostics::debug;
+use crate::LayerParams;
+
+/// Wrapper turtle that leaves out subpaths tracing the same outline as one already drawn, see
+/// [crate::ConversionConfig::dedupe_paths]
+///
+/// The calls of each subpath are held back until it is complete, since only then can it be
+/// compared.
+#[derive(Debug)]
+pub struct DedupingTurtle<T: Turtle> {
+    pub inner: T,
+    /// Whether duplicates are left out, else calls pass straight through
+    pub enabled: bool,
+    /// Flattening tolerance, also the distance within which points are considered the same
+    tolerance: f64,
+    /// Calls of the subpath being drawn
+    calls: Vec<Call>,
+    /// Flattened outlines of the subpaths drawn so far
+    drawn: Vec<Vec<Point<f64>>>,
+}
+
+impl<T: Turtle> DedupingTurtle<T> {
+    pub fn new(inner: T, enabled: bool, tolerance: f64) -> Self {
+        Self {
+            inner,
+            enabled,
+            tolerance,
+            calls: vec![],
+            drawn: vec![],
+        }
+    }
+
+    fn finish_subpath(&mut self) {
+        let calls = mem::take(&mut self.calls);
+        let mut points = vec![];
+        for call in &calls {
+            call.flatten(self.tolerance, &mut points);
+        }
+        if points.len() > 1 {
+            if self
+                .drawn
+                .iter()
+                .any(|drawn| same_outline(drawn, &points, self.tolerance))
+            {
+                debug!("Skipping subpath that duplicates one already drawn");
+                return;
+            }
+            self.drawn.push(points);
+        }
+        for call in calls {
+            call.replay(&mut self.inner);
+        }
+    }
+
+    /// Hold back a call of the subpath being drawn
+    fn draw(&mut self, call: Call) {
+        if !self.enabled {
+            call.replay(&mut self.inner);
+            return;
+        }
+        if matches!(call, Call::MoveTo(_)) {
+            // The source of the move comes just before it
+            let source = match self.calls.last() {
+                Some(Call::Source(_)) => self.calls.pop(),
+                _ => None,
+            };
+            self.finish_subpath();
+            self.calls.extend(source);
+        }
+        self.calls.push(call);
+    }
+
+    /// Pass on a call that isn't part of a subpath, after the subpath being drawn
+    fn pass(&mut self, call: Call) {
+        if let Call::Tolerance(tolerance) = call {
+            self.tolerance = tolerance;
+        }
+        self.finish_subpath();
+        call.replay(&mut self.inner);
+    }
+}
+
+/// Whether two flattened outlines have the same points within `tolerance`, in either direction
+fn same_outline(a: &[Point<f64>], b: &[Point<f64>], tolerance: f64) -> bool {
+    let close = |(p, q): (&Point<f64>, &Point<f64>)| (*p - *q).length() <= tolerance;
+    a.len() == b.len() && (a.iter().zip(b).all(close) || a.iter().zip(b.iter().rev()).all(close))
+}
+
+impl<T: Turtle> Turtle for DedupingTurtle<T> {
+    fn begin(&mut self) {
+        self.inner.begin()
+    }
+
+    fn end(&mut self) {
+        self.finish_subpath();
+        self.inner.end()
+    }
+
+    fn comment(&mut self, comment: String) {
+        self.pass(Call::Comment(comment))
+    }
+
+    fn between_layers(&mut self) {
+        self.pass(Call::BetweenLayers)
+    }
+
+    fn tool(&mut self, tool: u32) {
+        self.pass(Call::Tool(tool))
+    }
+
+    fn element(&mut self, element: &ElementInfo) {
+        self.pass(Call::Element(element.clone()))
+    }
+
+    fn tolerance(&mut self, tolerance: f64) {
+        self.pass(Call::Tolerance(tolerance))
+    }
+
+    fn layer_params(&mut self, params: &LayerParams) {
+        self.pass(Call::LayerParams(*params))
+    }
+
+    fn source(&mut self, point: Point<f64>) {
+        self.draw(Call::Source(point))
+    }
+
+    fn move_to(&mut self, to: Point<f64>) {
+        self.draw(Call::MoveTo(to))
+    }
+
+    fn close(&mut self) {
+        self.draw(Call::Close)
+    }
+
+    fn flush(&mut self) {
+        self.finish_subpath();
+        // Another pass cuts them all again
+        self.drawn.clear();
+        self.inner.flush()
+    }
+
+    fn line_to(&mut self, to: Point<f64>) {
+        self.draw(Call::LineTo(to))
+    }
+
+    fn arc(&mut self, svg_arc: SvgArc<f64>) {
+        self.draw(Call::Arc(svg_arc))
+    }
+
+    fn cubic_bezier(&mut self, cbs: CubicBezierSegment<f64>) {
+        self.draw(Call::CubicBezier(cbs))
+    }
+
+    fn quadratic_bezier(&mut self, qbs: QuadraticBezierSegment<f64>) {
+        self.draw(Call::QuadraticBezier(qbs))
+    }
+}
@@ -6,7 +6,8 @@ use uom::si::{
     length::{inch, millimeter},
 };
 
-use crate::Turtle;
+use super::ElementInfo;
+use crate::{LayerParams, Turtle};
 
 /// Wrapper turtle that converts from user units to millimeters at a given DPI
 #[derive(Debug)]
@@ -46,10 +47,40 @@ impl<T: Turtle> Turtle for DpiConvertingTurtle<T> {
         self.inner.between_layers()
     }
 
+    fn tool(&mut self, tool: u32) {
+        self.inner.tool(tool)
+    }
+
+    fn element(&mut self, element: &ElementInfo) {
+        self.inner.element(element)
+    }
+
+    fn tolerance(&mut self, tolerance: f64) {
+        self.inner.tolerance(self.to_mm(tolerance))
+    }
+
+    fn layer_params(&mut self, params: &LayerParams) {
+        // Already in millimeters
+        self.inner.layer_params(params)
+    }
+
+    fn source(&mut self, point: Point<f64>) {
+        // Already in user units, not machine coordinates
+        self.inner.source(point)
+    }
+
     fn move_to(&mut self, to: Point<f64>) {
         self.inner.move_to(self.point_to_mm(to))
     }
 
+    fn close(&mut self) {
+        self.inner.close()
+    }
+
+    fn flush(&mut self) {
+        self.inner.flush()
+    }
+
     fn line_to(&mut self, to: Point<f64>) {
         self.inner.line_to(self.point_to_mm(to))
     }
@@ -1,12 +1,17 @@
 use std::borrow::Cow;
 use std::fmt::Debug;
 
-use ::g_code::{command, emit::Token};
-use lyon_geom::{CubicBezierSegment, Point, QuadraticBezierSegment, SvgArc};
+use ::g_code::{
+    command,
+    emit::{Field, Token, Value},
+};
+use crate::diagnostics::error;
+use lyon_geom::{ArcFlags, Box2D, CubicBezierSegment, Point, QuadraticBezierSegment, SvgArc};
 
 use super::Turtle;
 use crate::arc::{detect_polygon_arcs, ArcOrLineSegment, FlattenWithArcs};
-use crate::machine::Machine;
+use crate::machine::{ArcDistanceMode, ArcFormat, Machine};
+use crate::{FeedrateUnits, LayerParams};
 
 /// Maps path segments into g-code operations
 #[derive(Debug)]
@@ -18,10 +23,90 @@ pub struct GCodeTurtle<'input> {
     pub program: Vec<Token<'input>>,
     // When true, emit the user between-layers sequence right before the next tool_on
     pub pending_between_layers: bool,
+    // Tool to change to right before the next tool_on
+    pending_tool: Option<u32>,
     // Polygon arc detection configuration
     pub polygon_arc_config: PolygonArcConfig,
+    // Thresholds for splitting and dropping arcs
+    pub arc_split_config: ArcSplitConfig,
+    // When true, annotate cut moves with the SVG user space coordinate they end at
+    pub annotate_source_coords: bool,
+    // Subpaths starting within this distance of the last cut are joined to it
+    pub join_gap: Option<f64>,
+    // Coordinates farther than this from the origin are reported as errors
+    pub max_abs_coordinate: Option<f64>,
+    // Number of times each subpath is cut
+    pub stroke_repeats: usize,
+    // Units feedrates are converted to when emitted
+    pub feedrate_units: FeedrateUnits,
+    // When true, only curves much closer than the tolerance to a line are cut as one
+    pub preserve_curvature: bool,
+    // Segments drawn since the last move, to be cut again
+    subpath: Vec<Segment>,
+    // End of the last segment drawn, where the next one starts
+    pen: Point<f64>,
+    // Feedrate used when a layer doesn't override it
+    default_feedrate: f64,
+    // Tool power of the current layer, if it sets one
+    power: Option<f64>,
+    // Tool power emitted since the tool was last turned on
+    emitted_power: Option<f64>,
+    // Last comment, naming the element being drawn
+    element: String,
+    // Whether an out of range coordinate was already reported for the subpath
+    reported_out_of_range: bool,
+    // SVG user space coordinate of the next point drawn
+    source: Option<Point<f64>>,
+    // Where the last cut ended, if nothing but cutting happened since
+    last_cut_end: Option<Point<f64>>,
+    // Where the tool is, if known
+    position: Option<Point<f64>>,
     // Buffer for line segments to enable polygon arc detection
     line_buffer: Vec<Point<f64>>,
+    // SVG user space coordinates of the buffered points that are annotated
+    line_buffer_sources: Vec<Option<Point<f64>>>,
+    // Number of circular interpolation moves emitted, see [Machine::max_arcs]
+    arcs_emitted: usize,
+}
+
+/// A drawing operation, recorded for [GCodeTurtle::stroke_repeats]
+#[derive(Debug, Clone, Copy)]
+enum Segment {
+    Line { from: Point<f64>, to: Point<f64> },
+    Arc(SvgArc<f64>),
+    CubicBezier(CubicBezierSegment<f64>),
+}
+
+impl Segment {
+    fn to(&self) -> Point<f64> {
+        match self {
+            Self::Line { to, .. } => *to,
+            Self::Arc(svg_arc) => svg_arc.to,
+            Self::CubicBezier(cbs) => cbs.to,
+        }
+    }
+
+    /// The same segment, drawn from its end to its start
+    fn reversed(self) -> Self {
+        match self {
+            Self::Line { from, to } => Self::Line { from: to, to: from },
+            Self::Arc(svg_arc) => Self::Arc(SvgArc {
+                from: svg_arc.to,
+                to: svg_arc.from,
+                flags: ArcFlags {
+                    sweep: !svg_arc.flags.sweep,
+                    ..svg_arc.flags
+                },
+                ..svg_arc
+            }),
+            Self::CubicBezier(cbs) => Self::CubicBezier(CubicBezierSegment {
+                from: cbs.to,
+                ctrl1: cbs.ctrl2,
+                ctrl2: cbs.ctrl1,
+                to: cbs.from,
+            }),
+        }
+    }
 }
 
 /// Configuration for polygon arc detection
@@ -32,6 +117,15 @@ pub struct PolygonArcConfig {
     pub tolerance: f64,
 }
 
+/// Thresholds used when emitting circular interpolation
+#[derive(Debug, Clone)]
+pub struct ArcSplitConfig {
+    /// Relative tolerance within which an arc is considered a semicircle and split in two
+    pub semicircle_epsilon: f64,
+    /// Sweep angle (radians) below which an arc is emitted as a line
+    pub min_sweep: f64,
+}
+
 impl<'input> GCodeTurtle<'input> {
     /// Create a new GCodeTurtle with polygon arc detection configuration
     pub fn new(
@@ -40,6 +134,7 @@ impl<'input> GCodeTurtle<'input> {
         feedrate: f64,
         min_arc_radius: f64,
         polygon_arc_config: PolygonArcConfig,
+        arc_split_config: ArcSplitConfig,
     ) -> Self {
         Self {
             machine,
@@ -48,19 +143,137 @@ impl<'input> GCodeTurtle<'input> {
             min_arc_radius,
             program: Vec::new(),
             pending_between_layers: false,
+            pending_tool: None,
             polygon_arc_config,
+            arc_split_config,
+            annotate_source_coords: false,
+            join_gap: None,
+            max_abs_coordinate: None,
+            stroke_repeats: 1,
+            feedrate_units: FeedrateUnits::PerMinute,
+            preserve_curvature: false,
+            subpath: Vec::new(),
+            pen: Point::origin(),
+            default_feedrate: feedrate,
+            power: None,
+            emitted_power: None,
+            element: String::new(),
+            reported_out_of_range: false,
+            source: None,
+            last_cut_end: None,
+            position: None,
             line_buffer: Vec::new(),
+            line_buffer_sources: Vec::new(),
+            arcs_emitted: 0,
+        }
+    }
+
+    /// Annotate cut moves with the SVG user space coordinate they end at, as given by
+    /// [Turtle::source]
+    pub fn with_annotate_source_coords(mut self, annotate_source_coords: bool) -> Self {
+        self.annotate_source_coords = annotate_source_coords;
+        self
+    }
+
+    /// Join subpaths starting within `join_gap` of the end of the last cut to it
+    pub fn with_join_gap(mut self, join_gap: Option<f64>) -> Self {
+        self.join_gap = join_gap;
+        self
+    }
+
+    /// Report coordinates beyond `max_abs_coordinate`, as produced by corrupt transforms, that
+    /// could crash a controller
+    pub fn with_max_abs_coordinate(mut self, max_abs_coordinate: Option<f64>) -> Self {
+        self.max_abs_coordinate = max_abs_coordinate;
+        self
+    }
+
+    /// Cut each subpath `stroke_repeats` times, e.g. to darken marker strokes
+    pub fn with_stroke_repeats(mut self, stroke_repeats: usize) -> Self {
+        self.stroke_repeats = stroke_repeats;
+        self
+    }
+
+    /// Emit feedrates, which are given in millimeters / minute, in `feedrate_units`
+    pub fn with_feedrate_units(mut self, feedrate_units: FeedrateUnits) -> Self {
+        self.feedrate_units = feedrate_units;
+        self
+    }
+
+    /// Keep gentle curves curved instead of cutting them as lines when they are within the
+    /// tolerance of one, see [FlattenWithArcs::flattened_preserving_curvature]
+    pub fn with_preserve_curvature(mut self, preserve_curvature: bool) -> Self {
+        self.preserve_curvature = preserve_curvature;
+        self
+    }
+
+    /// Value of the `F` word for `feedrate` in millimeters / minute
+    fn feedrate_word(&self, feedrate: f64) -> f64 {
+        self.feedrate_units.from_mm_per_min(feedrate)
+    }
+
+    fn record(&mut self, segment: Segment) {
+        self.pen = segment.to();
+        if self.stroke_repeats > 1 {
+            self.subpath.push(segment);
         }
     }
 
+    /// Cut the subpath drawn since the last move until it has been cut
+    /// [GCodeTurtle::stroke_repeats] times, alternating direction so the tool doesn't travel in
+    /// between
+    fn repeat_subpath(&mut self) {
+        let subpath = std::mem::take(&mut self.subpath);
+        for repeat in 1..self.stroke_repeats {
+            if repeat % 2 == 1 {
+                subpath
+                    .iter()
+                    .rev()
+                    .for_each(|segment| self.draw(segment.reversed()));
+            } else {
+                subpath.iter().for_each(|segment| self.draw(*segment));
+            }
+        }
+        if let Some(end) = self.last_cut_end {
+            self.pen = end;
+        }
+    }
+
+    fn draw(&mut self, segment: Segment) {
+        match segment {
+            Segment::Line { to, .. } => self.draw_line(to),
+            Segment::Arc(svg_arc) => self.draw_arc(svg_arc),
+            Segment::CubicBezier(cbs) => self.draw_cubic_bezier(cbs),
+        }
+    }
+
+    /// Check that a point the tool moves to is within [GCodeTurtle::max_abs_coordinate]
+    fn check_coordinate(&mut self, to: Point<f64>) {
+        let Some(max) = self.max_abs_coordinate else {
+            return;
+        };
+        // Also catches NaN
+        let in_range = |v: f64| v.abs() <= max;
+        if (in_range(to.x) && in_range(to.y)) || self.reported_out_of_range {
+            return;
+        }
+        error!(
+            "Coordinate ({}, {}) of {} exceeds the maximum absolute coordinate of {max}mm",
+            to.x, to.y, self.element
+        );
+        self.reported_out_of_range = true;
+    }
+
     /// Flush the line buffer, analyzing for arcs and generating appropriate G-code
     fn flush_line_buffer(&mut self) {
         if self.line_buffer.is_empty() {
             return;
         }
+        let line_buffer = std::mem::take(&mut self.line_buffer);
+        let sources = std::mem::take(&mut self.line_buffer_sources);
 
         if self.polygon_arc_config.enabled
-            && self.line_buffer.len() >= self.polygon_arc_config.min_points
+            && line_buffer.len() >= self.polygon_arc_config.min_points
             && self
                 .machine
                 .supported_functionality()
@@ -68,84 +281,153 @@ impl<'input> GCodeTurtle<'input> {
         {
             // Analyze buffer for arcs
             let segments = detect_polygon_arcs(
-                &self.line_buffer,
+                &line_buffer,
                 self.polygon_arc_config.tolerance,
                 self.polygon_arc_config.min_points,
             );
 
+            // Index of the buffered point the last segment ended at
+            let mut end = 0;
             for segment in segments {
+                let to = match segment {
+                    ArcOrLineSegment::Arc(arc) => arc.to,
+                    ArcOrLineSegment::Line(line) => line.to,
+                };
                 match segment {
+                    // Same as the arcs of paths, e.g. flattened below the minimum radius
+                    ArcOrLineSegment::Arc(arc) if !arc.is_straight_line() => self.emit_arc(arc),
                     ArcOrLineSegment::Arc(arc) => {
-                        // Double-check that this arc is valid and meets our requirements
-                        if !arc.is_straight_line() && 
-                           arc.radii.x >= self.min_arc_radius &&
-                           arc.radii.y >= self.min_arc_radius {
-                            self.program.append(&mut self.circular_interpolation(arc));
-                        } else {
-                            // Arc is invalid or too small, emit as line
-                            self.program.append(
-                                &mut command!(LinearInterpolation {
-                                    X: arc.to.x,
-                                    Y: arc.to.y,
-                                    F: self.feedrate,
-                                })
-                                .into_token_vec(),
-                            );
-                        }
+                        self.program.append(
+                            &mut command!(LinearInterpolation {
+                                X: arc.to.x,
+                                Y: arc.to.y,
+                                F: self.feedrate_word(
+                                    self.machine.limit_feedrate(self.feedrate, arc.to - arc.from),
+                                ),
+                            })
+                            .into_token_vec(),
+                        );
                     }
                     ArcOrLineSegment::Line(line) => {
                         self.program.append(
                             &mut command!(LinearInterpolation {
                                 X: line.to.x,
                                 Y: line.to.y,
-                                F: self.feedrate,
+                                F: self.feedrate_word(
+                                    self.machine.limit_feedrate(self.feedrate, line.to - line.from),
+                                ),
                             })
                             .into_token_vec(),
                         );
                     }
                 }
+                // Segments end at buffered points, an arc at the last of those it fits
+                if let Some(next) = (end + 1..line_buffer.len()).find(|i| line_buffer[*i] == to) {
+                    let source = sources[end + 1..=next].iter().rev().find_map(|source| *source);
+                    self.annotate_source(source);
+                    end = next;
+                }
             }
         } else {
             // No arc detection or insufficient points - emit all as lines
-            for point in self.line_buffer.iter().skip(1) {
+            for (window, source) in line_buffer.windows(2).zip(sources.into_iter().skip(1)) {
+                let [from, to] = [window[0], window[1]];
                 self.program.append(
                     &mut command!(LinearInterpolation {
-                        X: point.x,
-                        Y: point.y,
-                        F: self.feedrate,
+                        X: to.x,
+                        Y: to.y,
+                        F: self.feedrate_word(self.machine.limit_feedrate(self.feedrate, to - from)),
                     })
                     .into_token_vec(),
                 );
+                self.annotate_source(source);
             }
         }
 
-        self.line_buffer.clear();
+        self.position = line_buffer.last().copied().or(self.position);
+    }
+
+    /// Buffer a point for polygon arc detection, with the SVG user space coordinate it is at if
+    /// it is annotated
+    fn buffer_point(&mut self, point: Point<f64>, source: Option<Point<f64>>) {
+        self.line_buffer.push(point);
+        self.line_buffer_sources.push(source);
+    }
+
+    /// Append a circular interpolation move, in relative distance mode if the machine wants
+    /// relative arc endpoints
+    fn emit_arc(&mut self, svg_arc: SvgArc<f64>) {
+        let relative = self.machine.arc_distance_mode() == ArcDistanceMode::RelativeEndpoints;
+        if relative {
+            self.program.extend(self.machine.relative());
+        }
+        let mut tokens = self.circular_interpolation(svg_arc);
+        self.program.append(&mut tokens);
+        if relative {
+            self.program.extend(self.machine.absolute());
+        }
+        self.position = Some(svg_arc.to);
     }
-    fn circular_interpolation(&self, svg_arc: SvgArc<f64>) -> Vec<Token<'input>> {
+
+    /// Whether as many arcs were emitted as [Machine::max_arcs] allows
+    fn arc_budget_spent(&self) -> bool {
+        self.machine.max_arcs().is_some_and(|max_arcs| self.arcs_emitted >= max_arcs)
+    }
+
+    fn circular_interpolation(&mut self, svg_arc: SvgArc<f64>) -> Vec<Token<'input>> {
         debug_assert!((svg_arc.radii.x.abs() - svg_arc.radii.y.abs()).abs() < f64::EPSILON);
         // Geometry helpers
         let from = svg_arc.from;
         let to = svg_arc.to;
+        // Where X/Y moves to, which may differ from `to` in relative distance mode
+        let end = match self.machine.arc_distance_mode() {
+            ArcDistanceMode::AbsoluteEndpoints => to,
+            ArcDistanceMode::RelativeEndpoints => (to - from).to_point(),
+        };
         let chord = (to - from).length();
+        // Limited along the chord, which is close to the direction of travel for the short arcs
+        // produced by flattening
+        let feedrate = self.machine.limit_feedrate(self.feedrate, to - from);
         let radius = svg_arc.radii.x.abs();
         let arc_struct = svg_arc.to_arc();
         let sweep_angle = arc_struct.sweep_angle.radians.abs();
 
-        // 1. Fallback to a linear move when arc is too small to be meaningful or numerically stable.
-        //    (radius extremely small OR chord almost zero OR sweep negligible)
-        if radius < self.min_arc_radius
-            || chord < self.min_arc_radius
-            || sweep_angle < 1e-6
-        {
-            return command!(LinearInterpolation { X: to.x, Y: to.y, F: self.feedrate })
+        // 1. Fallback to a linear move when the sweep is negligible, the arc is then as good as
+        //    straight.
+        if sweep_angle < self.arc_split_config.min_sweep {
+            return command!(LinearInterpolation { X: end.x, Y: end.y, F: self.feedrate_word(feedrate) })
                 .into_token_vec();
         }
+        //    Flatten arcs that are too small to be numerically stable (radius extremely small OR
+        //    chord almost zero) into lines, which keeps following the curve unlike a single chord.
+        //    Arcs past the machine's budget are flattened the same way.
+        if radius < self.min_arc_radius || chord < self.min_arc_radius || self.arc_budget_spent() {
+            let mut token_vec = vec![];
+            arc_struct.for_each_flattened(self.tolerance, &mut |line| {
+                let end = match self.machine.arc_distance_mode() {
+                    ArcDistanceMode::AbsoluteEndpoints => line.to,
+                    ArcDistanceMode::RelativeEndpoints => (line.to - line.from).to_point(),
+                };
+                token_vec.append(
+                    &mut command!(LinearInterpolation {
+                        X: end.x,
+                        Y: end.y,
+                        F: self.feedrate_word(
+                            self.machine.limit_feedrate(self.feedrate, line.to - line.from),
+                        ),
+                    })
+                    .into_token_vec(),
+                );
+            });
+            return token_vec;
+        }
 
         // 2. Auto-split if (a) SVG flagged large arc OR (b) arc is (near) a semicircle which is
         //    ill-conditioned for R-mode validation (even though we now emit I/J, splitting keeps centers cleaner).
         //    Near-semicircle detection: chord ~ 2R OR sweep ~ PI within a tolerance.
-        let near_semi = (chord - 2.0 * radius).abs() / (2.0 * radius) < 1e-5
-            || (sweep_angle - std::f64::consts::PI).abs() < 1e-5;
+        let epsilon = self.arc_split_config.semicircle_epsilon;
+        let near_semi = (chord - 2.0 * radius).abs() / (2.0 * radius) < epsilon
+            || (sweep_angle - std::f64::consts::PI).abs() < epsilon;
         if svg_arc.flags.large_arc || near_semi {
             let (left, right) = arc_struct.split(0.5);
             let mut token_vec = self.circular_interpolation(left.to_svg_arc());
@@ -153,133 +435,107 @@ impl<'input> GCodeTurtle<'input> {
             return token_vec;
         }
 
-        // 3. Emit using I/J center offsets (avoids R ambiguity/validation issues in controllers for tight arcs).
+        let feedrate = self.feedrate_word(self.machine.arc_feedrate(feedrate, radius));
+        self.arcs_emitted += 1;
+        if self.machine.arc_format() == ArcFormat::R {
+            // 3a. Emit the radius, negative for the longer of the two arcs between the endpoints
+            let r = if svg_arc.flags.large_arc { -radius } else { radius };
+            return match svg_arc.flags.sweep {
+                true => command!(CounterclockwiseCircularInterpolation {
+                    X: end.x,
+                    Y: end.y,
+                    R: r,
+                    F: feedrate,
+                })
+                .into_token_vec(),
+                false => command!(ClockwiseCircularInterpolation {
+                    X: end.x,
+                    Y: end.y,
+                    R: r,
+                    F: feedrate,
+                })
+                .into_token_vec(),
+            };
+        }
+
+        // 3b. Emit using I/J center offsets (avoids R ambiguity/validation issues in controllers for tight arcs).
+        //    These are relative to the start of the arc regardless of the arc distance mode.
         let center = arc_struct.center;
         let i = center.x - from.x;
         let j = center.y - from.y;
 
         match svg_arc.flags.sweep {
             true => command!(CounterclockwiseCircularInterpolation {
-                X: to.x,
-                Y: to.y,
+                X: end.x,
+                Y: end.y,
                 I: i,
                 J: j,
-                F: self.feedrate,
+                F: feedrate,
             })
             .into_token_vec(),
             false => command!(ClockwiseCircularInterpolation {
-                X: to.x,
-                Y: to.y,
+                X: end.x,
+                Y: end.y,
                 I: i,
                 J: j,
-                F: self.feedrate,
+                F: feedrate,
             })
             .into_token_vec(),
         }
     }
 
-    fn tool_on(&mut self) {
-        // Inject deferred between-layers sequence (after travel, before tool activation)
-        if self.pending_between_layers {
-            // Add a blank line for readability before between-layers sequence
-            self.program.push(Token::Comment { is_inline: false, inner: std::borrow::Cow::Borrowed("") });
-            self.program.extend(self.machine.between_layers());
-            // Do NOT emit absolute here; the tool_on sequence below will restore absolute
-            self.pending_between_layers = false;
-        }
-        self.program.extend(self.machine.tool_on());
-        self.program.extend(self.machine.absolute());
-    }
-
-    fn tool_off(&mut self) {
-        self.program.extend(self.machine.tool_off());
-        self.program.extend(self.machine.absolute());
-    }
-}
-
-impl<'input> Turtle for GCodeTurtle<'input> {
-    fn begin(&mut self) {
-        self.program
-            .append(&mut command!(UnitsMillimeters {}).into_token_vec());
-        self.program.extend(self.machine.absolute());
-        self.program.extend(self.machine.program_begin());
-        self.program.extend(self.machine.absolute());
-    }
-
-    fn end(&mut self) {
-        // Flush any remaining line buffer
-        self.flush_line_buffer();
-        self.program.extend(self.machine.tool_off());
-        self.program.extend(self.machine.absolute());
-        self.program.extend(self.machine.program_end());
-    }
-
-    fn comment(&mut self, comment: String) {
-        self.program.push(Token::Comment {
-            is_inline: false,
-            inner: Cow::Owned(comment),
-        });
-    }
-
-    fn between_layers(&mut self) {
-    // Mark for deferred emission. Actual G-Code emitted right before next tool_on() call.
-    self.pending_between_layers = true;
-    }
-
-    fn move_to(&mut self, to: Point<f64>) {
-        // Flush any pending line buffer before moving
-        self.flush_line_buffer();
-        self.tool_off();
-        self.program
-            .append(&mut command!(RapidPositioning { X: to.x, Y: to.y }).into_token_vec());
-        
-        // Start new buffer with the move destination
-        self.line_buffer.clear();
-        self.line_buffer.push(to);
-    }
-
-    fn line_to(&mut self, to: Point<f64>) {
+    fn draw_line(&mut self, to: Point<f64>) {
+        self.check_coordinate(to);
+        let source = self.source.take();
         self.tool_on();
+        self.last_cut_end = Some(to);
         
         if self.polygon_arc_config.enabled {
             // If buffer is empty, we need to track the starting position
             if self.line_buffer.is_empty() {
                 // This should be the current position, but we need to get it somehow
                 // For now, we'll use the 'to' point as both start and end if buffer is empty
-                self.line_buffer.push(to);
+                self.buffer_point(to, None);
             }
             
             // Add point to buffer for potential arc detection
-            self.line_buffer.push(to);
+            self.buffer_point(to, source);
             
             // Flush buffer if it gets too large to prevent memory issues
             const MAX_BUFFER_SIZE: usize = 1000;
             if self.line_buffer.len() > MAX_BUFFER_SIZE {
                 self.flush_line_buffer();
                 // Keep the last point as start of new buffer
-                self.line_buffer.push(to);
+                self.buffer_point(to, None);
             }
         } else {
             // Direct line generation (original behavior)
+            let feedrate = self
+                .position
+                .replace(to)
+                .map_or(self.feedrate, |from| self.machine.limit_feedrate(self.feedrate, to - from));
             self.program.append(
                 &mut command!(LinearInterpolation {
                     X: to.x,
                     Y: to.y,
-                    F: self.feedrate,
+                    F: self.feedrate_word(feedrate),
                 })
                 .into_token_vec(),
             );
+            self.annotate_source(source);
         }
     }
 
-    fn arc(&mut self, svg_arc: SvgArc<f64>) {
+    fn draw_arc(&mut self, svg_arc: SvgArc<f64>) {
+        self.check_coordinate(svg_arc.to);
         // Flush line buffer before processing arc
         self.flush_line_buffer();
         
         if svg_arc.is_straight_line() {
-            self.line_to(svg_arc.to);
+            self.draw_line(svg_arc.to);
             return;
         }
+        let source = self.source.take();
 
         self.tool_on();
 
@@ -292,23 +548,27 @@ impl<'input> Turtle for GCodeTurtle<'input> {
                 .into_iter()
                 .for_each(|segment| match segment {
                     ArcOrLineSegment::Arc(arc) => {
-                        self.program.append(&mut self.circular_interpolation(arc))
+                        self.emit_arc(arc)
                     }
                     ArcOrLineSegment::Line(line) => {
-                        self.line_to(line.to);
+                        self.draw_line(line.to);
                     }
                 });
         } else {
             svg_arc
                 .to_arc()
                 .flattened(self.tolerance)
-                .for_each(|point| self.line_to(point));
+                .for_each(|point| self.draw_line(point));
         };
+        self.annotate_source_at(source, svg_arc.to);
+        self.last_cut_end = Some(svg_arc.to);
     }
 
-    fn cubic_bezier(&mut self, cbs: CubicBezierSegment<f64>) {
+    fn draw_cubic_bezier(&mut self, cbs: CubicBezierSegment<f64>) {
+        self.check_coordinate(cbs.to);
         // Flush line buffer before processing bezier
         self.flush_line_buffer();
+        let source = self.source.take();
         
         self.tool_on();
 
@@ -317,18 +577,281 @@ impl<'input> Turtle for GCodeTurtle<'input> {
             .supported_functionality()
             .circular_interpolation
         {
-            FlattenWithArcs::<f64>::flattened(&cbs, self.tolerance)
+            let segments = if self.preserve_curvature {
+                FlattenWithArcs::<f64>::flattened_preserving_curvature(&cbs, self.tolerance)
+            } else {
+                FlattenWithArcs::<f64>::flattened(&cbs, self.tolerance)
+            };
+            segments
                 .into_iter()
                 .for_each(|segment| match segment {
                     ArcOrLineSegment::Arc(arc) => {
-                        self.program.append(&mut self.circular_interpolation(arc))
+                        self.emit_arc(arc)
                     }
-                    ArcOrLineSegment::Line(line) => self.line_to(line.to),
+                    ArcOrLineSegment::Line(line) => self.draw_line(line.to),
                 });
         } else {
             cbs.flattened(self.tolerance)
-                .for_each(|point| self.line_to(point));
+                .for_each(|point| self.draw_line(point));
+        };
+        self.annotate_source_at(source, cbs.to);
+        self.last_cut_end = Some(cbs.to);
+    }
+
+    /// Annotate the cut move ending at `to` with its SVG user space coordinate, once it is
+    /// flushed if its last piece is still in the line buffer
+    fn annotate_source_at(&mut self, source: Option<Point<f64>>, to: Point<f64>) {
+        if self.line_buffer.last() != Some(&to) {
+            self.annotate_source(source);
+        } else if let (Some(buffered), Some(source)) = (self.line_buffer_sources.last_mut(), source)
+        {
+            *buffered = Some(source);
+        }
+    }
+
+    /// Append an inline comment with the SVG user space coordinate of the last cut move
+    fn annotate_source(&mut self, source: Option<Point<f64>>) {
+        if let (true, Some(source)) = (self.annotate_source_coords, source) {
+            // Hide floating point noise from inverting the transform
+            let round = |v: f64| (v * 1e6).round() / 1e6 + 0.;
+            self.program.push(Token::Comment {
+                is_inline: true,
+                inner: Cow::Owned(format!("svg {} {}", round(source.x), round(source.y))),
+            });
+        }
+    }
+
+    /// Start cutting pass number `pass` (zero-based) of [Machine::pass_count]
+    pub fn start_pass(&mut self, pass: usize) {
+        self.repeat_subpath();
+        self.machine.start_pass(pass);
+        // Each pass cuts at a different depth, so it must not be joined to the last
+        self.last_cut_end = None;
+    }
+
+    /// Trace the corners of `frame` with rapid moves, keeping the tool off
+    pub fn bounding_frame(&mut self, frame: Box2D<f64>) {
+        self.comment("Bounding frame".to_string());
+        self.tool_off();
+        if let Some(safe_z) = self.machine.retract_z() {
+            self.program
+                .append(&mut command!(RapidPositioning { Z: safe_z }).into_token_vec());
+        }
+        for corner in [
+            frame.min,
+            Point::new(frame.max.x, frame.min.y),
+            frame.max,
+            Point::new(frame.min.x, frame.max.y),
+            frame.min,
+        ] {
+            self.program.append(
+                &mut command!(RapidPositioning {
+                    X: corner.x,
+                    Y: corner.y,
+                })
+                .into_token_vec(),
+            );
+        }
+        self.position = Some(frame.min);
+    }
+
+    /// Cut the [crate::MachineConfig::priming_stroke] out and back, if there is one
+    pub fn priming_stroke(&mut self) {
+        let Some(priming) = self.machine.priming_stroke() else {
+            return;
+        };
+        self.comment("Priming stroke".to_string());
+        let start = Point::new(priming.position[0], priming.position[1]);
+        self.move_to(start);
+        self.draw_line(Point::new(start.x + priming.length, start.y));
+        self.draw_line(start);
+        self.flush_line_buffer();
+        self.pen = start;
+        // The drawing must not be joined to the stroke
+        self.last_cut_end = None;
+    }
+
+    fn tool_on(&mut self) {
+        // Inject deferred between-layers sequence (after travel, before tool activation)
+        if self.pending_between_layers {
+            // Add a blank line for readability before between-layers sequence
+            self.program.push(Token::Comment { is_inline: false, inner: std::borrow::Cow::Borrowed("") });
+            self.program.extend(self.machine.between_layers());
+            // Do NOT emit absolute here; the tool_on sequence below will restore absolute
+            self.pending_between_layers = false;
+        }
+        if let Some(tool) = self.pending_tool.take() {
+            self.program.append(&mut self.machine.tool_change(tool));
+        }
+        self.program.extend(self.machine.tool_on());
+        if let Some(power) = self
+            .power
+            .filter(|power| self.emitted_power != Some(*power))
+        {
+            self.emitted_power = Some(power);
+            self.program.push(Token::Field(Field {
+                letters: "S".into(),
+                value: Value::Float(power),
+            }));
+        }
+        self.program.extend(self.machine.accessory_on());
+        self.program.extend(self.machine.absolute());
+        if let Some(cut_z) = self.machine.plunge_z() {
+            self.program.append(
+                &mut command!(LinearInterpolation {
+                    Z: cut_z,
+                    F: self.feedrate_word(self.feedrate),
+                })
+                .into_token_vec(),
+            );
+        }
+    }
+
+    fn tool_off(&mut self) {
+        self.emitted_power = None;
+        self.program.extend(self.machine.tool_off());
+        self.program.extend(self.machine.absolute());
+    }
+}
+
+impl<'input> Turtle for GCodeTurtle<'input> {
+    fn begin(&mut self) {
+        if self.machine.emit_units() {
+            self.program
+                .append(&mut command!(UnitsMillimeters {}).into_token_vec());
+        }
+        self.program.extend(self.machine.absolute());
+        self.program.append(&mut self.machine.path_mode(self.tolerance));
+        self.program.extend(self.machine.program_begin());
+        self.program.extend(self.machine.absolute());
+    }
+
+    fn end(&mut self) {
+        self.repeat_subpath();
+        // Flush any remaining line buffer
+        self.flush_line_buffer();
+        self.program.extend(self.machine.tool_off());
+        self.program.extend(self.machine.accessory_off());
+        self.program.extend(self.machine.absolute());
+        if let Some(safe_z) = self.machine.retract_z() {
+            self.program
+                .append(&mut command!(RapidPositioning { Z: safe_z }).into_token_vec());
+        }
+        self.program.extend(self.machine.program_end());
+        self.program.append(&mut self.machine.program_end_command());
+    }
+
+    fn comment(&mut self, comment: String) {
+        self.repeat_subpath();
+        self.element.clone_from(&comment);
+        self.program.push(Token::Comment {
+            is_inline: false,
+            inner: Cow::Owned(comment),
+        });
+    }
+
+    fn between_layers(&mut self) {
+        self.repeat_subpath();
+        // Mark for deferred emission. Actual G-Code emitted right before next tool_on() call.
+        self.pending_between_layers = true;
+    }
+
+    fn tool(&mut self, tool: u32) {
+        self.repeat_subpath();
+        self.pending_tool = Some(tool);
+    }
+
+    fn move_to(&mut self, to: Point<f64>) {
+        self.repeat_subpath();
+        self.reported_out_of_range = false;
+        self.check_coordinate(to);
+        if let (Some(join_gap), Some(from), false, None, false) = (
+            self.join_gap,
+            self.last_cut_end,
+            self.pending_between_layers,
+            self.pending_tool,
+            self.machine.always_retract_between_subpaths(),
+        )
+        {
+            if (to - from).length() <= join_gap {
+                // Keep cutting across the gap rather than lifting the tool
+                self.line_to(to);
+                return;
+            }
+        }
+        self.last_cut_end = None;
+
+        // Flush any pending line buffer before moving
+        self.flush_line_buffer();
+        self.tool_off();
+        let changes_tool = self
+            .pending_tool
+            .is_some_and(|tool| self.machine.changes_tool(tool));
+        let retract = if self.pending_between_layers || changes_tool {
+            self.machine.tool_change_retract_z()
+        } else {
+            self.machine.retract_z()
         };
+        match retract {
+            Some(safe_z) if !self.machine.separate_z_moves() => {
+                self.program.append(
+                    &mut command!(RapidPositioning {
+                        X: to.x,
+                        Y: to.y,
+                        Z: safe_z,
+                    })
+                    .into_token_vec(),
+                );
+            }
+            retract => {
+                if let Some(safe_z) = retract {
+                    self.program
+                        .append(&mut command!(RapidPositioning { Z: safe_z }).into_token_vec());
+                }
+                self.program
+                    .append(&mut command!(RapidPositioning { X: to.x, Y: to.y }).into_token_vec());
+            }
+        }
+        
+        self.position = Some(to);
+        self.pen = to;
+
+        // Start new buffer with the move destination
+        self.line_buffer.clear();
+        self.line_buffer_sources.clear();
+        self.buffer_point(to, None);
+    }
+
+    fn tolerance(&mut self, tolerance: f64) {
+        self.tolerance = tolerance;
+    }
+
+    fn layer_params(&mut self, params: &LayerParams) {
+        self.repeat_subpath();
+        // Buffered lines belong to the previous layer
+        self.flush_line_buffer();
+        self.feedrate = params.feedrate.unwrap_or(self.default_feedrate);
+        self.power = params.power;
+        self.machine.set_cut_z(params.cut_z);
+    }
+
+    fn source(&mut self, point: Point<f64>) {
+        self.source = Some(point);
+    }
+
+    fn line_to(&mut self, to: Point<f64>) {
+        self.record(Segment::Line { from: self.pen, to });
+        self.draw_line(to);
+    }
+
+    fn arc(&mut self, svg_arc: SvgArc<f64>) {
+        self.record(Segment::Arc(svg_arc));
+        self.draw_arc(svg_arc);
+    }
+
+    fn cubic_bezier(&mut self, cbs: CubicBezierSegment<f64>) {
+        self.record(Segment::CubicBezier(cbs));
+        self.draw_cubic_bezier(cbs);
     }
 
     fn quadratic_bezier(&mut self, qbs: QuadraticBezierSegment<f64>) {
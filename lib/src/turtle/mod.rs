@@ -6,12 +6,23 @@ use lyon_geom::{
 };
 
 use crate::arc::Transformed;
+use crate::LayerParams;
 
+mod clip;
+mod collect;
+mod containment;
+mod dedupe;
 mod dpi;
 mod g_code;
+mod pocket;
 mod preprocess;
+pub use self::clip::ClipRegion;
+pub use self::collect::{SubpathCollectingTurtle, SubpathInfo};
+pub use self::containment::ContainmentOrderingTurtle;
+pub use self::dedupe::DedupingTurtle;
 pub use self::dpi::DpiConvertingTurtle;
-pub use self::g_code::{GCodeTurtle, PolygonArcConfig};
+pub use self::g_code::{ArcSplitConfig, GCodeTurtle, PolygonArcConfig};
+pub use self::pocket::PocketingTurtle;
 pub use self::preprocess::PreprocessTurtle;
 
 /// Abstraction for drawing paths based on [Turtle graphics](https://en.wikipedia.org/wiki/Turtle_graphics)
@@ -21,13 +32,118 @@ pub trait Turtle: Debug {
     fn comment(&mut self, comment: String);
     /// Hook called between sibling SVG group (layer) elements
     fn between_layers(&mut self) {}
+    /// Hook called when entering a top-level SVG group (layer) with the number of the tool it
+    /// should be cut with
+    fn tool(&mut self, _tool: u32) {}
+    /// Hook called before the outline of an SVG element is traced
+    fn element(&mut self, _element: &ElementInfo) {}
+    /// Hook called when the elements that follow should be flattened with a different tolerance,
+    /// given in the same units as coordinates
+    fn tolerance(&mut self, _tolerance: f64) {}
+    /// Hook called when the elements that follow should be cut with different machine
+    /// parameters, see [crate::ConversionConfig::layer_params]
+    fn layer_params(&mut self, _params: &LayerParams) {}
+    /// Hook called with the untransformed SVG user space coordinate of the point the next
+    /// drawing operation ends at
+    fn source(&mut self, _point: Point<f64>) {}
     fn move_to(&mut self, to: Point<f64>);
+    /// Hook called when the current subpath is closed by a closepath command, after the closing
+    /// segment (if any) has been drawn
+    fn close(&mut self) {}
+    /// Hook called when calls held back so far must be passed on, e.g. before starting another
+    /// pass. Wrapper turtles pass theirs on, then forward the hook to the turtle they wrap.
+    fn flush(&mut self) {}
     fn line_to(&mut self, to: Point<f64>);
     fn arc(&mut self, svg_arc: SvgArc<f64>);
     fn cubic_bezier(&mut self, cbs: CubicBezierSegment<f64>);
     fn quadratic_bezier(&mut self, qbs: QuadraticBezierSegment<f64>);
 }
 
+/// Metadata about the SVG element currently being traced
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ElementInfo {
+    pub id: Option<String>,
+    /// Stroke color, else fill color
+    pub color: Option<svgtypes::Color>,
+    pub line_cap: LineCap,
+    pub line_join: LineJoin,
+    /// `stroke-miterlimit`, see [LineJoin::Miter]
+    pub miter_limit: f64,
+}
+
+/// Canonical form of a color for grouping by it: `#rrggbb`, or `#rrggbbaa` unless opaque
+///
+/// Hex, `rgb()`, `rgba()`, `hsl()` and named colors are all parsed into the same
+/// [svgtypes::Color], so equal colors always have the same canonical form.
+pub fn canonical_color(color: svgtypes::Color) -> String {
+    let rgb = format!("#{:02x}{:02x}{:02x}", color.red, color.green, color.blue);
+    if color.alpha == u8::MAX {
+        rgb
+    } else {
+        format!("{rgb}{:02x}", color.alpha)
+    }
+}
+
+/// Shape at the ends of open subpaths, from the `stroke-linecap` property
+///
+/// <https://www.w3.org/TR/SVG2/painting.html#LineCaps>
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize),
+    serde(rename_all = "kebab-case")
+)]
+pub enum LineCap {
+    #[default]
+    Butt,
+    Round,
+    Square,
+}
+
+impl LineCap {
+    pub fn parse(value: &str) -> Option<Self> {
+        match value {
+            "butt" => Some(Self::Butt),
+            "round" => Some(Self::Round),
+            "square" => Some(Self::Square),
+            _ => None,
+        }
+    }
+}
+
+/// Shape at the corners of subpaths, from the `stroke-linejoin` property
+///
+/// <https://www.w3.org/TR/SVG2/painting.html#LineJoin>
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize),
+    serde(rename_all = "kebab-case")
+)]
+pub enum LineJoin {
+    /// Sharp corners, drawn as [LineJoin::Bevel] where the miter would be longer than
+    /// `stroke-miterlimit` times the stroke width
+    #[default]
+    Miter,
+    MiterClip,
+    Round,
+    Bevel,
+    Arcs,
+}
+
+impl LineJoin {
+    pub fn parse(value: &str) -> Option<Self> {
+        match value {
+            "miter" => Some(Self::Miter),
+            "miter-clip" => Some(Self::MiterClip),
+            "round" => Some(Self::Round),
+            "bevel" => Some(Self::Bevel),
+            "arcs" => Some(Self::Arcs),
+            _ => None,
+        }
+    }
+}
+
 /// Wrapper for [Turtle] that handles transforms, position, offsets, etc.  See https://www.w3.org/TR/SVG/paths.html
 #[derive(Debug)]
 pub struct Terrarium<T: Turtle + std::fmt::Debug> {
@@ -38,6 +154,15 @@ pub struct Terrarium<T: Turtle + std::fmt::Debug> {
     pub transform_stack: Vec<Transform2D<f64>>,
     previous_quadratic_control: Option<Point<f64>>,
     previous_cubic_control: Option<Point<f64>>,
+    /// Active clip regions, in turtle coordinates
+    clip_stack: Vec<ClipRegion>,
+    /// Last position passed to the turtle while clipping
+    clipped_position: Option<Point<f64>>,
+    /// Move to the end of segments instead of drawing them, see [crate::CommandFilter]
+    pub skip_drawing: bool,
+    /// Tell the turtle where the points drawn are in the user space of their element, see
+    /// [Turtle::source]
+    pub track_source: bool,
 }
 
 impl<T: Turtle + std::fmt::Debug> Terrarium<T> {
@@ -51,9 +176,18 @@ impl<T: Turtle + std::fmt::Debug> Terrarium<T> {
             transform_stack: vec![],
             previous_quadratic_control: None,
             previous_cubic_control: None,
+            clip_stack: vec![],
+            clipped_position: None,
+            skip_drawing: false,
+            track_source: false,
         }
     }
 
+    /// Transform from the user units of the element being drawn to those of the turtle
+    pub fn current_transform(&self) -> Transform2D<f64> {
+        self.current_transform
+    }
+
     /// Move the turtle to the given absolute/relative coordinates in the current transform
     /// https://www.w3.org/TR/SVG/paths.html#PathDataMovetoCommands
     pub fn move_to<X, Y>(&mut self, abs: bool, x: X, y: Y)
@@ -92,7 +226,7 @@ impl<T: Turtle + std::fmt::Debug> Terrarium<T> {
         self.initial_position = to;
         self.previous_quadratic_control = None;
         self.previous_cubic_control = None;
-        self.turtle.move_to(to);
+        self.draw_move(to);
     }
 
     /// Close an SVG path, cutting back to its initial position
@@ -105,7 +239,11 @@ impl<T: Turtle + std::fmt::Debug> Terrarium<T> {
             .lower_than(vector(f64::EPSILON, f64::EPSILON))
             .all()
         {
-            self.turtle.line_to(self.initial_position);
+            self.draw_line(self.current_position, self.initial_position);
+        }
+        // A clipped subpath may have been cut into open pieces
+        if self.clip_stack.is_empty() && !self.skip_drawing {
+            self.turtle.close();
         }
         self.current_position = self.initial_position;
         self.previous_quadratic_control = None;
@@ -145,12 +283,13 @@ impl<T: Turtle + std::fmt::Debug> Terrarium<T> {
             })
             .unwrap_or(original_current_position.y);
 
+        let from = self.current_position;
         let to = self.current_transform.transform_point(point(x, y));
         self.current_position = to;
         self.previous_quadratic_control = None;
         self.previous_cubic_control = None;
 
-        self.turtle.line_to(to);
+        self.draw_line(from, to);
     }
 
     /// Draw a cubic curve from the current point to (x, y) with specified control points (x1, y1) and (x2, y2)
@@ -190,7 +329,7 @@ impl<T: Turtle + std::fmt::Debug> Terrarium<T> {
         ));
         self.previous_quadratic_control = None;
 
-        self.turtle.cubic_bezier(cbs);
+        self.draw_cubic_bezier(cbs);
     }
 
     /// Draw a shorthand/smooth cubic bezier segment, where the first control point was already given
@@ -226,7 +365,7 @@ impl<T: Turtle + std::fmt::Debug> Terrarium<T> {
         ));
         self.previous_quadratic_control = None;
 
-        self.turtle.cubic_bezier(cbs);
+        self.draw_cubic_bezier(cbs);
     }
 
     /// Draw a shorthand/smooth cubic bezier segment, where the control point was already given
@@ -257,7 +396,7 @@ impl<T: Turtle + std::fmt::Debug> Terrarium<T> {
         ));
         self.previous_cubic_control = None;
 
-        self.turtle.quadratic_bezier(qbs);
+        self.draw_quadratic_bezier(qbs);
     }
 
     /// Draw a quadratic bezier segment
@@ -287,7 +426,7 @@ impl<T: Turtle + std::fmt::Debug> Terrarium<T> {
         ));
         self.previous_cubic_control = None;
 
-        self.turtle.quadratic_bezier(qbs);
+        self.draw_quadratic_bezier(qbs);
     }
 
     /// Draw an elliptical arc segment
@@ -322,7 +461,7 @@ impl<T: Turtle + std::fmt::Debug> Terrarium<T> {
         self.previous_quadratic_control = None;
         self.previous_cubic_control = None;
 
-        self.turtle.arc(svg_arc);
+        self.draw_arc(svg_arc);
     }
 
     /// Push a generic transform onto the stack
@@ -343,6 +482,130 @@ impl<T: Turtle + std::fmt::Debug> Terrarium<T> {
             .expect("pop only called when transforms remain");
     }
 
+    /// Only draw inside the given rings (in the current transform) until the matching [Terrarium::pop_clip]
+    ///
+    /// Curves drawn while clipping are flattened into lines with `tolerance`.
+    pub fn push_clip(&mut self, rings: Vec<Vec<Point<f64>>>, tolerance: f64) {
+        let rings = rings
+            .into_iter()
+            .map(|ring| {
+                ring.into_iter()
+                    .map(|p| self.current_transform.transform_point(p))
+                    .collect()
+            })
+            .collect();
+        self.clip_stack.push(ClipRegion { rings, tolerance });
+        self.clipped_position = None;
+    }
+
+    /// Pop a clip region off the stack
+    pub fn pop_clip(&mut self) {
+        self.clip_stack
+            .pop()
+            .expect("pop only called when clip regions remain");
+    }
+
+    fn draw_move(&mut self, to: Point<f64>) {
+        // The next visible piece moves to its own start
+        if self.clip_stack.is_empty() {
+            self.turtle.move_to(to);
+        }
+    }
+
+    /// Tell the turtle where a point is in the current user space
+    fn source(&mut self, point: Point<f64>) {
+        if !self.track_source {
+            return;
+        }
+        if let Some(inverse) = self.current_transform.inverse() {
+            self.turtle.source(inverse.transform_point(point));
+        }
+    }
+
+    /// Move to the end of a segment that isn't drawn
+    fn skip_to(&mut self, to: Point<f64>) {
+        if self.clip_stack.is_empty() {
+            self.turtle.move_to(to);
+        } else {
+            self.clipped_position = None;
+        }
+    }
+
+    fn draw_line(&mut self, from: Point<f64>, to: Point<f64>) {
+        if self.skip_drawing {
+            return self.skip_to(to);
+        }
+        if self.clip_stack.is_empty() {
+            self.source(to);
+            self.turtle.line_to(to);
+            return;
+        }
+        for piece in clip::clip_line(&self.clip_stack, from, to) {
+            if self.clipped_position != Some(piece.from) {
+                self.turtle.move_to(piece.from);
+            }
+            self.source(piece.to);
+            self.turtle.line_to(piece.to);
+            self.clipped_position = Some(piece.to);
+        }
+    }
+
+    /// Flattening tolerance of the active clip regions
+    fn clip_tolerance(&self) -> f64 {
+        self.clip_stack
+            .iter()
+            .map(|region| region.tolerance)
+            .fold(f64::INFINITY, f64::min)
+    }
+
+    fn draw_cubic_bezier(&mut self, cbs: CubicBezierSegment<f64>) {
+        if self.skip_drawing {
+            return self.skip_to(cbs.to);
+        }
+        if self.clip_stack.is_empty() {
+            self.source(cbs.to);
+            self.turtle.cubic_bezier(cbs);
+            return;
+        }
+        let mut from = cbs.from;
+        cbs.for_each_flattened(self.clip_tolerance(), &mut |line| {
+            self.draw_line(from, line.to);
+            from = line.to;
+        });
+    }
+
+    fn draw_quadratic_bezier(&mut self, qbs: QuadraticBezierSegment<f64>) {
+        if self.skip_drawing {
+            return self.skip_to(qbs.to);
+        }
+        if self.clip_stack.is_empty() {
+            self.source(qbs.to);
+            self.turtle.quadratic_bezier(qbs);
+        } else {
+            self.draw_cubic_bezier(qbs.to_cubic());
+        }
+    }
+
+    fn draw_arc(&mut self, svg_arc: SvgArc<f64>) {
+        if self.skip_drawing {
+            return self.skip_to(svg_arc.to);
+        }
+        if self.clip_stack.is_empty() {
+            self.source(svg_arc.to);
+            self.turtle.arc(svg_arc);
+        } else if svg_arc.is_straight_line() {
+            self.draw_line(svg_arc.from, svg_arc.to);
+        } else {
+            let mut from = svg_arc.from;
+            svg_arc
+                .to_arc()
+                .for_each_flattened(self.clip_tolerance(), &mut |line| {
+                    self.draw_line(from, line.to);
+                    from = line.to;
+                });
+        }
+    }
+
     /// Reset the position of the turtle to the origin in the current transform stack
     /// Used for starting a new path
     pub fn reset(&mut self) {
@@ -0,0 +1,263 @@
+use std::mem;
+
+use lyon_geom::{CubicBezierSegment, Point, QuadraticBezierSegment, SvgArc, Vector};
+
+use super::{containment::Call, ElementInfo, Turtle};
+use crate::{LayerParams, PocketConfig};
+
+/// Wrapper turtle that fills closed subpaths with inward offsets of their outline, see
+/// [crate::ConversionConfig::pocket]
+///
+/// The calls of each subpath are held back until it is complete, since only then is it known
+/// whether it is closed.
+#[derive(Debug)]
+pub struct PocketingTurtle<T: Turtle> {
+    pub inner: T,
+    /// How closed subpaths are pocketed, else calls pass straight through
+    pub config: Option<PocketConfig>,
+    /// Flattening tolerance, also the size below which an offset outline has collapsed
+    tolerance: f64,
+    /// Calls of the subpath being drawn
+    calls: Vec<Call>,
+}
+
+impl<T: Turtle> PocketingTurtle<T> {
+    pub fn new(inner: T, config: Option<PocketConfig>, tolerance: f64) -> Self {
+        Self {
+            inner,
+            config,
+            tolerance,
+            calls: vec![],
+        }
+    }
+
+    fn finish_subpath(&mut self) {
+        let calls = mem::take(&mut self.calls);
+        let closed = calls
+            .iter()
+            .rfind(|call| !matches!(call, Call::Source(_)))
+            .is_some_and(|call| matches!(call, Call::Close));
+        let mut outline = vec![];
+        if closed {
+            for call in &calls {
+                call.flatten(self.tolerance, &mut outline);
+            }
+        }
+        for call in calls {
+            call.replay(&mut self.inner);
+        }
+        let Some(config) = self.config.filter(|_| closed) else {
+            return;
+        };
+        for offset in inward_offsets(&outline, config.stepover, self.tolerance) {
+            self.inner.move_to(offset[0]);
+            for point in &offset[1..] {
+                self.inner.line_to(*point);
+            }
+            self.inner.line_to(offset[0]);
+            self.inner.close();
+        }
+    }
+
+    /// Hold back a call of the subpath being drawn
+    fn draw(&mut self, call: Call) {
+        if self.config.is_none() {
+            call.replay(&mut self.inner);
+            return;
+        }
+        if matches!(call, Call::MoveTo(_)) {
+            // The source of the move comes just before it
+            let source = match self.calls.last() {
+                Some(Call::Source(_)) => self.calls.pop(),
+                _ => None,
+            };
+            self.finish_subpath();
+            self.calls.extend(source);
+        }
+        self.calls.push(call);
+    }
+
+    /// Pass on a call that isn't part of a subpath, after the subpath being drawn
+    fn pass(&mut self, call: Call) {
+        if let Call::Tolerance(tolerance) = call {
+            self.tolerance = tolerance;
+        }
+        self.finish_subpath();
+        call.replay(&mut self.inner);
+    }
+}
+
+/// Successive inward offsets of a closed outline, `stepover` apart, until it collapses
+///
+/// Each offset moves the corners of the one before along the bisectors of their edges. Edges
+/// that shrink to nothing on the way, like the short ones of a flattened curve, are dropped so
+/// their neighbors meet instead. This is exact for convex outlines, while concave ones can be
+/// offset across themselves where the outline would have to split in two.
+fn inward_offsets(outline: &[Point<f64>], stepover: f64, tolerance: f64) -> Vec<Vec<Point<f64>>> {
+    let mut outline = outline.to_vec();
+    dedup_outline(&mut outline, tolerance);
+    let area = signed_area(&outline);
+    if outline.len() < 3 || stepover <= 0. || area.abs() < f64::EPSILON {
+        return vec![];
+    }
+
+    let mut offsets: Vec<Vec<Point<f64>>> = vec![];
+    loop {
+        let previous = offsets.last().unwrap_or(&outline);
+        // The left of counterclockwise outlines is inside, the right of clockwise ones
+        let Some(offset) = offset_outline(previous, stepover * area.signum(), tolerance)
+            .filter(|offset| signed_area(offset) * area.signum() > tolerance * tolerance)
+        else {
+            break;
+        };
+        offsets.push(offset);
+    }
+    offsets
+}
+
+/// Move each edge of a closed outline `distance` to its left, joining them where they meet,
+/// or `None` if fewer than three are left once those that shrink to nothing are dropped
+fn offset_outline(outline: &[Point<f64>], distance: f64, tolerance: f64) -> Option<Vec<Point<f64>>> {
+    let mut points = outline.to_vec();
+    let mut remaining = distance.abs();
+    loop {
+        dedup_outline(&mut points, tolerance);
+        if points.len() < 3 {
+            return None;
+        }
+        let len = points.len();
+        let velocities = (0..len)
+            .map(|i| {
+                let [previous, next] = [(i + len - 1) % len, (i + 1) % len].map(|j| points[j]);
+                corner_velocity(previous, points[i], next) * distance.signum()
+            })
+            .collect::<Vec<_>>();
+        // The first edge to shrink to nothing, and how far the edges move until it does
+        let collapse = (0..len)
+            .filter_map(|i| {
+                let edge = points[(i + 1) % len] - points[i];
+                let shrink = (velocities[(i + 1) % len] - velocities[i]).dot(edge);
+                (shrink < 0.).then(|| (i, -edge.square_length() / shrink))
+            })
+            .min_by(|a, b| a.1.total_cmp(&b.1));
+        match collapse {
+            Some((i, at)) if at < remaining => {
+                for (point, velocity) in points.iter_mut().zip(&velocities) {
+                    *point += *velocity * at;
+                }
+                // Both of its ends are at the same point now
+                points.remove((i + 1) % len);
+                remaining -= at;
+            }
+            _ => {
+                for (point, velocity) in points.iter_mut().zip(&velocities) {
+                    *point += *velocity * remaining;
+                }
+                return Some(points);
+            }
+        }
+    }
+}
+
+/// How fast a corner moves as the edges next to it move to their left at unit speed
+fn corner_velocity(previous: Point<f64>, point: Point<f64>, next: Point<f64>) -> Vector<f64> {
+    let left = |edge: Vector<f64>| {
+        let edge = edge.normalize();
+        Vector::new(-edge.y, edge.x)
+    };
+    let (before, after) = (point - previous, next - point);
+    let (normal_before, normal_after) = (left(before), left(after));
+    let turn = before.cross(after);
+    if turn.abs() < f64::EPSILON {
+        // Straight through, the moved edges are the same line
+        normal_before
+    } else {
+        // Where the edge before, moved by a unit, meets the edge after
+        normal_before + before * ((normal_after - normal_before).cross(after) / turn)
+    }
+}
+
+/// Drop the points of a closed outline closer than `tolerance` to the one before, which would
+/// give edges without a direction, like a closing segment left by rounding
+fn dedup_outline(outline: &mut Vec<Point<f64>>, tolerance: f64) {
+    outline.dedup_by(|a, b| (*a - *b).length() < tolerance);
+    while outline.len() > 1 && (outline[0] - outline[outline.len() - 1]).length() < tolerance {
+        outline.pop();
+    }
+}
+
+/// Area of a closed outline, positive if it is counterclockwise
+fn signed_area(outline: &[Point<f64>]) -> f64 {
+    (0..outline.len())
+        .map(|i| outline[i].to_vector().cross(outline[(i + 1) % outline.len()].to_vector()))
+        .sum::<f64>()
+        / 2.
+}
+
+impl<T: Turtle> Turtle for PocketingTurtle<T> {
+    fn begin(&mut self) {
+        self.inner.begin()
+    }
+
+    fn end(&mut self) {
+        self.finish_subpath();
+        self.inner.end()
+    }
+
+    fn comment(&mut self, comment: String) {
+        self.pass(Call::Comment(comment))
+    }
+
+    fn between_layers(&mut self) {
+        self.pass(Call::BetweenLayers)
+    }
+
+    fn tool(&mut self, tool: u32) {
+        self.pass(Call::Tool(tool))
+    }
+
+    fn element(&mut self, element: &ElementInfo) {
+        self.pass(Call::Element(element.clone()))
+    }
+
+    fn tolerance(&mut self, tolerance: f64) {
+        self.pass(Call::Tolerance(tolerance))
+    }
+
+    fn layer_params(&mut self, params: &LayerParams) {
+        self.pass(Call::LayerParams(*params))
+    }
+
+    fn source(&mut self, point: Point<f64>) {
+        self.draw(Call::Source(point))
+    }
+
+    fn move_to(&mut self, to: Point<f64>) {
+        self.draw(Call::MoveTo(to))
+    }
+
+    fn close(&mut self) {
+        self.draw(Call::Close)
+    }
+
+    fn flush(&mut self) {
+        self.finish_subpath();
+        self.inner.flush()
+    }
+
+    fn line_to(&mut self, to: Point<f64>) {
+        self.draw(Call::LineTo(to))
+    }
+
+    fn arc(&mut self, svg_arc: SvgArc<f64>) {
+        self.draw(Call::Arc(svg_arc))
+    }
+
+    fn cubic_bezier(&mut self, cbs: CubicBezierSegment<f64>) {
+        self.draw(Call::CubicBezier(cbs))
+    }
+
+    fn quadratic_bezier(&mut self, qbs: QuadraticBezierSegment<f64>) {
+        self.draw(Call::QuadraticBezier(qbs))
+    }
+}
@@ -1,11 +1,46 @@
 use lyon_geom::{Box2D, CubicBezierSegment, Point, QuadraticBezierSegment, SvgArc};
 
-use super::Turtle;
+use super::{ElementInfo, Turtle};
 
 /// Generates a bounding box for all draw operations, used to properly apply [crate::ConversionConfig::origin]
 #[derive(Debug, Default)]
 pub struct PreprocessTurtle {
-    pub bounding_box: Box2D<f64>,
+    /// Bounding box of everything drawn, if anything was
+    pub bounding_box: Option<Box2D<f64>>,
+    /// `id` of an element to find the bounding box of, see
+    /// [crate::ConversionConfig::origin_element]
+    pub tracked_id: Option<String>,
+    /// Bounding box of the element with [PreprocessTurtle::tracked_id], if it was drawn
+    pub tracked_bounding_box: Option<Box2D<f64>>,
+    /// Whether the element being drawn is the tracked one
+    tracking: bool,
+}
+
+impl PreprocessTurtle {
+    /// Also find the bounding box of the element with the given `id`, if any
+    pub fn new(tracked_id: Option<String>) -> Self {
+        Self {
+            tracked_id,
+            ..Default::default()
+        }
+    }
+
+    /// Grow the bounding box to contain `other`
+    ///
+    /// [Box2D::union] can't be used here because it ignores boxes with no area, like that of a
+    /// horizontal line.
+    fn include(&mut self, other: Box2D<f64>) {
+        self.bounding_box = Some(match self.bounding_box {
+            Some(drawn) => Box2D::from_points([drawn.min, drawn.max, other.min, other.max]),
+            None => other,
+        });
+        if self.tracking {
+            self.tracked_bounding_box = Some(match self.tracked_bounding_box {
+                Some(tracked) => Box2D::from_points([tracked.min, tracked.max, other.min, other.max]),
+                None => other,
+            });
+        }
+    }
 }
 
 impl Turtle for PreprocessTurtle {
@@ -17,27 +52,60 @@ impl Turtle for PreprocessTurtle {
 
     fn between_layers(&mut self) {}
 
+    fn element(&mut self, element: &ElementInfo) {
+        self.tracking = self.tracked_id.is_some() && element.id == self.tracked_id;
+    }
+
     fn move_to(&mut self, to: Point<f64>) {
-        self.bounding_box = Box2D::from_points([self.bounding_box.min, self.bounding_box.max, to]);
+        self.include(Box2D::new(to, to));
     }
 
     fn line_to(&mut self, to: Point<f64>) {
-        self.bounding_box = Box2D::from_points([self.bounding_box.min, self.bounding_box.max, to]);
+        self.include(Box2D::new(to, to));
     }
 
     fn arc(&mut self, svg_arc: SvgArc<f64>) {
         if svg_arc.is_straight_line() {
             self.line_to(svg_arc.to);
         } else {
-            self.bounding_box = self.bounding_box.union(&svg_arc.to_arc().bounding_box());
+            // Includes the extrema where the arc bulges past its endpoints
+            self.include(svg_arc.to_arc().bounding_box());
         }
     }
 
     fn cubic_bezier(&mut self, cbs: CubicBezierSegment<f64>) {
-        self.bounding_box = self.bounding_box.union(&cbs.bounding_box());
+        self.include(cbs.bounding_box());
     }
 
     fn quadratic_bezier(&mut self, qbs: QuadraticBezierSegment<f64>) {
-        self.bounding_box = self.bounding_box.union(&qbs.bounding_box());
+        self.include(qbs.bounding_box());
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use lyon_geom::{point, vector, Angle, ArcFlags};
+
+    use super::*;
+
+    #[test]
+    fn bulging_arc_extends_past_its_endpoints() {
+        let mut turtle = PreprocessTurtle::default();
+        turtle.move_to(point(-5., 0.));
+        turtle.line_to(point(10., 0.));
+        // Half circle from (10, 0) back to (0, 0) bulging up to y = 5
+        turtle.arc(SvgArc {
+            from: point(10., 0.),
+            to: point(0., 0.),
+            radii: vector(5., 5.),
+            x_rotation: Angle::zero(),
+            flags: ArcFlags {
+                large_arc: false,
+                sweep: true,
+            },
+        });
+        let Box2D { min, max } = turtle.bounding_box.unwrap();
+        assert!((min - point(-5., 0.)).length() < 1e-9, "{min:?}");
+        assert!((max - point(10., 5.)).length() < 1e-9, "{max:?}");
     }
 }
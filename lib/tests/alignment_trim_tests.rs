@@ -36,6 +36,7 @@ fn trim_center_top_alignment() {
         h_align: HorizontalAlign::Center,
         v_align: VerticalAlign::Top,
         trim: true,
+        ..Default::default()
     };
     let gcode = run(svg, options);
     let (min_x, max_x, min_y, max_y) = extract_extents(&gcode);
@@ -53,6 +54,7 @@ fn trim_right_bottom_alignment() {
         h_align: HorizontalAlign::Right,
         v_align: VerticalAlign::Bottom,
         trim: true,
+        ..Default::default()
     };
     let gcode = run(svg, options);
     let (min_x, max_x, min_y, max_y) = extract_extents(&gcode);
@@ -70,6 +72,7 @@ fn trim_only_width() {
         h_align: HorizontalAlign::Left,
         v_align: VerticalAlign::Top,
         trim: true,
+        ..Default::default()
     };
     let gcode = run(svg, options);
     let (min_x, max_x, min_y, max_y) = extract_extents(&gcode);
@@ -1,1336 +0,0 @@
-G21
-G90;svg#Drawing > g#Layer_3 > path
-G0 X21.67995833333333 Y32.16539583333334
-G3 X24.95285416666667 Y32.86389583333334 R3.947583333333333 F300
-G3 X25.30475 Y32.96708333333333 R0.5503333333333333 F300
-G1 X25.626625136693313 Y33.181957168579096 F300
-G1 X25.94706286112467 Y33.40147025553385 F300
-G1 X26.265664457321165 Y33.62568848164876 F300
-G1 X26.582031209309896 Y33.854677734374995 F300
-G1 X26.895764401117965 Y34.08850390116373 F300
-G1 X27.206465316772466 Y34.327232869466144 F300
-G3 X27.817175455729167 Y34.81966276041666 R24.331319818019548 F300
-G3 X28.984664916992188 Y35.86625520833333 R21.18633345854456 F300
-G1 X29.262975852330527 Y36.14114863586425 F300
-G1 X29.535064783732096 Y36.42147196451822 F300
-G1 X29.800532995224 Y36.70729108174641 F300
-G1 X30.05898177083333 Y36.998671875 F300
-G3 X31.014608194986977 Y38.22112955729166 R15.027183565867247 F300
-G3 X31.8260263671875 Y39.53784505208333 R13.003673267897863 F300
-G3 X32.46771846516927 Y40.95303515624999 R11.645824797621975 F300
-G3 X32.716942916870124 Y41.69887593587239 R11.043956488386096 F300
-G3 X32.91416666666667 Y42.47091666666665 R10.864951757938792 F300
-G3 X32.91416666666667 Y42.78577083333332 R0.6270625 F300
-G3 X37.560249999999996 Y43.18529166666666 R12.520083333333332 F300
-G3 X37.1554375 Y40.719374999999985 R7.9375 F300
-G3 X40.481249999999996 Y34.05716666666667 R8.3264375 F300
-G1 X40.7460959744015 Y33.868215619455874 F300
-G1 X41.02037965997949 Y33.68915863989218 F300
-G3 X41.59450609572903 Y33.3619942901873 R8.309138256419065 F300
-G3 X42.825716743050506 Y32.840335873836096 R8.657114255058607 F300
-G3 X44.13081683898886 Y32.512469921312466 R8.606476219215399 F300
-G3 X45.46574128056857 Y32.39867493631582 R8.015823645013613 F300
-G3 X46.593255212001495 Y32.48577190376734 R7.208473915366114 F300
-G3 X47.68320833333333 Y32.75541666666667 R6.45909975131183 F300
-G1 X47.938499598185224 Y32.85165110270182 F300
-G1 X48.18169470214844 Y32.95962125651042 F300
-G1 X48.41309982808431 Y33.079094584147136 F300
-G1 X48.63302115885417 Y33.20983854166667 F300
-G3 X49.03963716634115 Y33.504208170572916 R4.172467829633583 F300
-G3 X49.4039921875 Y33.84086979166667 R4.234131419567291 F300
-G3 X49.72853568522135 Y34.21796305338542 R4.506055079787755 F300
-G3 X50.015717122395834 Y34.63362760416666 R4.997647086490047 F300
-G1 X50.14606252543132 Y34.855342753092444 F300
-G1 X50.267985961914064 Y35.08600309244791 F300
-G3 X50.487791666666666 Y35.573229166666664 R6.735839613396004 F300
-G3 X51.05135416666666 Y39.6636875 R8.466666666666665 F300
-G3 X46.894749999999995 Y46.11422916666667 R8.638645833333333 F300
-G3 X47.63558333333333 Y46.75716666666667 R7.254875 F300
-G3 X48.30247802734374 Y47.51430391438802 R7.996432235203987 F300
-G3 X48.86754947916666 Y48.3355302734375 R8.930609170039721 F300
-G3 X49.34431624348959 Y49.20952860514323 R10.536151612770063 F300
-G3 X49.746296875 Y50.12498177083333 R13.03967437015326 F300
-G3 X50.37997395833334 Y52.034984049479164 R19.733591609120445 F300
-G1 X50.51277463785807 Y52.520710861206055 F300
-G1 X50.63870751953125 Y53.00689888509115 F300
-G1 X50.876729166666664 Y53.97500000000001 F300
-G1 X50.876729166666664 Y53.97500000000001 F300
-G3 X53.2050625 Y53.50404166666667 R8.903229166666668 F300
-G3 X53.68575945456659 Y53.484724521691454 R6.013853603024488 F300
-G3 X54.81729991139474 Y53.597164061211544 R5.641374110896799 F300
-G3 X55.88422188283553 Y53.942938680826295 R4.90942136414731 F300
-G3 X56.828436318166865 Y54.53473238211743 R4.241206581365012 F300
-G3 X57.23637538144084 Y54.92685013888594 R3.958724119496574 F300
-G3 X57.42112612951854 Y55.147657900137425 R3.9304529376291666 F300
-G3 X57.59185416666667 Y55.38522916666668 R3.965273062016671 F300
-G3 X57.69504166666666 Y55.69743750000001 R0.5820833333333333 F300
-G3 X57.800875000000005 Y56.036104166666675 R2.510895833333333 F300
-G3 X57.864375 Y56.443562500000006 R2.968625 F300
-G3 X58.438520833333335 Y57.218791666666675 R1.6166041666666666 F300
-G3 X58.070750000000004 Y59.43864583333334 R2.344208333333333 F300
-G3 X58.00896048990886 Y59.87894970703125 R3.155418385779044 F300
-G3 X57.88996516927084 Y60.30102213541667 R3.410693637500709 F300
-G3 X57.50950260416667 Y61.090968750000016 R4.171199086298089 F300
-G3 X56.9876533203125 Y61.80947786458335 R5.947541875844195 F300
-G3 X56.38270833333334 Y62.45754166666668 R8.999703979043076 F300
-G3 X53.83477083333334 Y64.2858125 R10.538354166666664 F300
-G3 X52.959 Y69.33935416666667 R5.8763958333333335 F300
-G3 X48.10389583333334 Y71.45602083333334 R5.384270833333334 F300
-G1 X47.952672505696626 Y71.43453118896485 F300
-G1 X47.7976199544271 Y71.40663883463543 F300
-G3 X47.48179427083335 Y71.33083984375001 R4.115108570001383 F300
-G3 X46.86763020833334 Y71.09354166666668 R3.3133869890100067 F300
-G3 X46.59236018880209 Y70.92881787109376 R2.372866678105302 F300
-G3 X46.35367708333334 Y70.73122786458333 R1.8147273448337624 F300
-G3 X46.03220833333334 Y70.23100000000001 R1.2956241812831297 F300
-G3 X44.97387500000001 Y70.06695833333335 R6.566958333333334 F300
-G3 X43.85394335937501 Y69.73432747395833 R8.326201766825603 F300
-G3 X42.797015625 Y69.23550520833334 R7.05525667854331 F300
-G3 X41.852205078125 Y68.55709700520833 R5.871220206680482 F300
-G3 X41.43719165039063 Y68.14636238606772 R5.219218560899068 F300
-G3 X41.068625000000004 Y67.68570833333334 R4.952911518453588 F300
-G3 X40.87892903668549 Y67.39260872716187 R4.040997026422527 F300
-G3 X40.71718983350419 Y67.08849918687467 R4.072389632899762 F300
-G3 X40.474297367099965 Y66.4529884619655 R4.21957600991288 F300
-G3 X40.287865811035736 Y65.11296754089491 R4.760129535434496 F300
-G3 X40.41744506002193 Y63.927303562291755 R5.609764793103148 F300
-G3 X40.782875000000004 Y62.79091666666668 R6.1331811534284935 F300
-G3 X41.0647079264323 Y62.223359578450534 R6.052968145117116 F300
-G3 X41.39993294270833 Y61.69037402343751 R6.56306496734968 F300
-G3 X42.20501041666667 Y60.72154427083335 R7.7559676086882146 F300
-G3 X43.147009765625 Y59.87128092447918 R10.206160582199802 F300
-G3 X44.17483333333334 Y59.126437500000016 R13.947370870946854 F300
-G3 X41.936458333333334 Y57.09708333333333 R8.628062499999999 F300
-G3 X41.43320222981771 Y56.38268249511719 R9.55794314569513 F300
-G3 X40.99859309895833 Y55.631911783854164 R10.062947918383353 F300
-G3 X40.32117708333333 Y54.041476562499994 R11.179440173493926 F300
-G3 X39.875932942708324 Y52.36620930989583 R13.226023360231164 F300
-G3 X39.63458333333333 Y50.64654166666667 R15.76222131436129 F300
-G1 X39.63458333333333 Y50.64654166666667 F300
-G3 X36.46355208333334 Y50.87474479166667 R140.38474464317238 F300
-G3 X33.30045833333334 Y51.030187500000004 R138.75915726252745 F300
-G1 X32.45648848164613 Y51.06919370443893 F300
-G3 X31.608389245449104 Y51.09173783989888 R18.963210628780978 F300
-G3 X30.44825 Y51.01695833333333 R8.022289150136299 F300
-G1 X30.403270833333334 Y51.01695833333333 F300
-G3 X30.38777759297689 Y51.46403440348307 R6.463354389102704 F300
-G3 X30.34208076985677 Y51.90486279296875 R6.519567150485196 F300
-G3 X30.164773763020836 Y52.76486197916666 R6.694253590582399 F300
-G3 X29.499388020833333 Y54.37782812499999 R7.272068303396393 F300
-G3 X29.030098836263022 Y55.119136881510414 R8.008867665652629 F300
-G3 X28.482271809895835 Y55.809223958333334 R8.488197524305894 F300
-G3 X27.188583333333334 Y57.01241666666666 R9.003242545197365 F300
-G3 X23.987125000000002 Y58.705749999999995 R12.501562499999999 F300
-G2 X22.4710625 Y59.49949999999999 R5.098520833333333 F300
-G2 X21.41272916666667 Y60.59487499999999 R9.477375 F300
-G1 X21.122405802408853 Y60.93126790364582 F300
-G1 X20.829943033854164 Y61.26592447916666 F300
-G3 X20.221111979166665 Y61.90720833333333 R11.528048788011485 F300
-G3 X19.551261393229165 Y62.47308593749999 R6.130524637740131 F300
-G3 X19.18252421061198 Y62.71348470052082 R4.61513363755913 F300
-G3 X18.785416666666666 Y62.917916666666656 R4.120742538545521 F300
-G3 X18.541999999999998 Y62.98670833333333 R0.8202083333333334 F300
-G3 X17.954625 Y62.85706249999999 R0.6270625 F300
-G3 X17.494250000000005 Y61.10816666666666 R1.6801041666666665 F300
-G3 X17.7055859375 Y60.60917903645833 R2.432802384256566 F300
-G3 X18.00621875 Y60.154343749999995 R3.837003465525437 F300
-G3 X18.354476562499997 Y59.72927408854166 R8.606358585493696 F300
-G1 X18.7086875 Y59.31958333333333 F300
-G1 X20.251208333333334 Y57.491312499999985 F300
-G1 X20.38153577239876 Y57.333143007902045 F300
-G1 X20.433330585602867 Y57.26113397153107 F300
-G1 X20.476024625774407 Y57.1912248178121 F300
-G2 X20.533545880579233 Y57.0498543869301 R0.6295278157856552 F300
-G1 X20.54809084199226 Y56.97446722406707 F300
-G1 X20.55297052393219 Y56.89332817245601 F300
-G1 X20.550669420443548 Y56.833510541360205 F300
-G1 X20.543736009857017 Y56.769456134232804 F300
-G1 X20.515791666666665 Y56.626124999999995 F300
-G3 X20.389122395833333 Y56.088359374999996 R25.206769748315 F300
-G3 X20.28825 Y55.546625 R7.633610642983069 F300
-G3 X20.203754229355777 Y54.33321661965269 R8.749284147099466 F300
-G3 X20.37422297231761 Y52.623340773352226 R8.599279428401502 F300
-G3 X20.88674691369657 Y50.99451171377514 R8.055916807015429 F300
-G3 X21.74300262223285 Y49.522022618890325 R7.364714442525784 F300
-G3 X22.300553816916306 Y48.8679346763228 R6.950365157422611 F300
-G3 X22.61177693671484 Y48.56554745192793 R6.829296257738855 F300
-G3 X22.944666666666667 Y48.28116666666667 R6.795243373032743 F300
-G3 X23.833666666666666 Y47.66997916666667 R7.366 F300
-G3 X22.498471886461786 Y47.020787653925 R8.486448222486743 F300
-G3 X21.296650356039212 Y46.15907128412806 R9.057369635790543 F300
-G3 X20.24196490033748 Y45.11579023617706 R9.732096460259962 F300
-G3 X19.348178344295107 Y43.92190468897324 R10.338789429361524 F300
-G3 X18.098353230942596 Y41.20616081241197 R10.752722461642088 F300
-G3 X17.769840323509513 Y39.74622284085697 R10.508507098992736 F300
-G3 X17.657277615489924 Y38.259521085654 R9.976201239492074 F300
-G3 X17.70327083333333 Y37.32477083333334 R9.452088194797437 F300
-G3 X18.081666341145834 Y35.61969661458334 R7.1877542942382915 F300
-G3 X18.42597090657552 Y34.82073763020834 R6.562865956122846 F300
-G3 X18.873059895833336 Y34.08428645833334 R5.928282071597969 F300
-G3 X20.073730794270833 Y32.87927473958334 R5.045343523610096 F300
-G3 X20.437412083943684 Y32.64848262532553 R4.579941690061826 F300
-G3 X20.82638252766927 Y32.45089778645834 R4.518273735636973 F300
-G3 X21.240583989461264 Y32.289031697591156 R4.539183910430413 F300
-G3 X21.67995833333333 Y32.16539583333334 R4.652790681530351 F300
-G0 X27.471687499999998 Y36.152666666666676
-G3 X29.606875000000002 Y43.4895625 R23.624645833333332 F300
-G3 X29.606875000000002 Y43.595395833333335 R0.35189583333333335 F300
-G3 X31.7738125 Y42.955104166666665 R11.448520833333333 F300
-G3 X31.718249999999998 Y42.80164583333334 R0.6164791666666667 F300
-G2 X31.595941574096678 Y42.30179714457195 R10.02750215267531 F300
-G2 X31.449940795898435 Y41.814145385742194 R10.08865653251032 F300
-G2 X31.0908154296875 Y40.87461100260417 R10.33818151183179 F300
-G2 X30.648780395507814 Y39.9813993733724 R10.930804267875168 F300
-G2 X30.131742187499995 Y39.1328671875 R11.832919995013096 F300
-G2 X29.54760729980468 Y38.3273711344401 R13.049554077828791 F300
-G2 X29.232849380493157 Y37.94024812316894 R14.176149902798606 F300
-G1 X29.07023018836975 Y37.75050300280253 F300
-G1 X28.904282226562493 Y37.56326790364584 F300
-G1 X28.56289414978027 Y37.196225062052406 F300
-G1 X28.209673461914058 Y36.838914184570314 F300
-G1 X27.845608474731442 Y36.49112985738119 F300
-G1 X27.471687499999998 Y36.152666666666676 F300
-G0 X49.577625 Y40.74054166666667
-G2 X49.68875 Y37.240104166666676 R7.358062499999999 F300
-G1 X49.64470876057942 Y37.04228807576498 F300
-G1 X49.595629069010414 Y36.84150317382813 F300
-G2 X49.48055598958332 Y36.43552278645834 R8.063782822353303 F300
-G2 X49.17016666666666 Y35.63739062500001 R5.89660114836078 F300
-G2 X48.96765706380207 Y35.26322224934897 R4.325028336590413 F300
-G2 X48.728808593749996 Y34.91764127604167 R3.518399614972895 F300
-G2 X48.127708333333324 Y34.34820833333334 R2.7127010043437383 F300
-G2 X46.89551023720824 Y33.795604042178454 R4.023339080471663 F300
-G2 X46.2202124836754 Y33.666880507359195 R4.960168400250372 F300
-G2 X45.52309189040996 Y33.62576838393744 R5.784157333593861 F300
-G2 X43.69386846279228 Y33.87342875551863 R7.151250976726226 F300
-G2 X43.25508761873988 Y34.003340998687136 R7.895754292406666 F300
-G2 X42.83110259848318 Y34.15594167835888 R7.829723046290557 F300
-G2 X42.425606311349625 Y34.32897558273084 R7.548163619420258 F300
-G2 X42.042291666666664 Y34.520187500000006 R7.057712810796509 F300
-G2 X38.48629166666666 Y39.497000000000014 R6.995583333333333 F300
-G2 X38.77997916666666 Y43.01860416666668 R6.651625 F300
-G1 X38.84984651505736 Y43.188769535138036 F300
-G1 X38.92860051841822 Y43.3593428693886 F300
-G2 X39.11244422997865 Y43.69542896621666 R4.086748384413914 F300
-G2 X39.58320465195165 Y44.30336759010608 R3.271899582448446 F300
-G2 X39.868824322077 Y44.55008224112486 R2.4429817026637384 F300
-G2 X40.187072271436804 Y44.74186853416463 R2.0031317777355895 F300
-G2 X40.537299979887436 Y44.86615753120411 R1.7320992540765632 F300
-G2 X40.724203581226945 Y44.89906275059208 R1.6517026110954593 F300
-G2 X40.9188589272853 Y44.910380294221994 R1.6713148478344624 F300
-G1 X40.92310416666666 Y44.91037500000001 F300
-G3 X41.45227083333332 Y45.22258333333335 R0.5609166666666666 F300
-G3 X41.60308333333332 Y45.137916666666676 R2.8288712456604572 F300
-G3 X43.11385416666666 Y44.786020833333346 R4.034895833333333 F300
-G3 X43.21704166666666 Y44.659020833333344 R0.42862500000000003 F300
-G2 X47.48477083333332 Y36.546895833333345 R11.453812499999998 F300
-G3 X47.50803814697265 Y36.40736946614585 R0.6973914798794398 F300
-G3 X47.55556754557291 Y36.28644661458334 R0.6122682694851104 F300
-G3 X47.708839843749985 Y36.10041145833334 R0.5914314614488191 F300
-G3 X48.14622916666666 Y35.95158333333334 R0.6946632020833224 F300
-G3 X48.36312862323349 Y35.985755690127775 R0.6885925095709883 F300
-G3 X48.54981827321264 Y36.088272760511096 R0.5675832522363212 F300
-G3 X48.680644624255635 Y36.25913454448329 R0.4910543753041396 F300
-G3 X48.7170923463172 Y36.37019470406521 R0.5236640851175184 F300
-G3 X48.72995418401407 Y36.49834104204435 R0.6215724200538398 F300
-G1 X48.72831249999999 Y36.546895833333345 F300
-G3 X44.71458333333334 Y44.9976875 R12.7 F300
-G3 X45.65385416666667 Y45.3628125 R5.950479166666667 F300
-G2 X49.577625 Y40.74054166666667 R7.408333333333333 F300
-G0 X57.15 Y58.58139583333333
-G2 X57.311395833333336 Y57.8485 R1.1350625 F300
-G1 X57.299649474452025 Y57.770081267734845 F300
-G1 X57.28497996373157 Y57.69632870934704 F300
-G1 X57.266481332701964 Y57.62902680818665 F300
-G1 X57.24324761289316 Y57.56996004760375 F300
-G1 X57.2143728358351 Y57.52091291094841 F300
-G1 X57.17895103305777 Y57.4836698815707 F300
-G2 X57.084842476465155 Y57.451734078048446 R0.15165348178594487 F300
-G1 X57.048226631948744 Y57.45514833946116 F300
-G1 X57.00778126299629 Y57.46568037975608 F300
-G1 X56.96328608999531 Y57.483764083018414 F300
-G1 X56.91452083333333 Y57.50983333333333 F300
-G1 X56.83286948332703 Y57.56374743997078 F300
-G1 X56.76375108061965 Y57.61969404693262 F300
-G1 X56.706658265427535 Y57.67730827218267 F300
-G1 X56.66108367796703 Y57.73622523368474 F300
-G1 X56.62651995845448 Y57.79608004940263 F300
-G1 X56.60245974710624 Y57.856507837300164 F300
-G2 X56.58382040976802 Y57.97762280148935 R0.4052838015522693 F300
-G2 X56.62772642126207 Y58.16734780102081 R0.45136630028537617 F300
-G2 X56.679414015465426 Y58.25634439298801 R0.5823355899737997 F300
-G2 X56.74808473960482 Y58.339387965039336 R0.7018848274861531 F300
-G1 X56.831608646904115 Y58.414946706137165 F300
-G1 X56.92785579058716 Y58.48148880524386 F300
-G2 X57.15 Y58.58139583333333 R1.023537461096218 F300
-G0 X56.74518749999999 Y59.76672916666668
-G3 X55.403749999999995 Y58.52318750000001 R2.0584583333333333 F300
-G3 X55.3348936279618 Y58.26745619684176 R1.4896195766257632 F300
-G3 X55.312730676856226 Y58.01179212646037 R1.4808473465650684 F300
-G3 X55.39318518131303 Y57.51775677156198 R1.5911702744550469 F300
-G3 X55.61599985455857 Y57.064487649095184 R1.8386922628292077 F300
-G3 X56.37741666666667 Y56.38800000000001 R2.03430464642433 F300
-G3 X56.570562499999994 Y56.30333333333334 R1.381125 F300
-G2 X56.48324999999999 Y56.03875000000002 R3.0967932514588483 F300
-G3 X56.43297916666666 Y55.91968750000002 R0.39422916666666663 F300
-G2 X56.17797435556574 Y55.623870599861775 R2.50468840426919 F300
-G2 X55.88559125958261 Y55.373661874033516 R2.6393326030501107 F300
-G2 X55.21345743414084 Y55.00246712494753 R3.0397367921190326 F300
-G2 X54.46611213268359 Y54.790899612023125 R3.7720283219157005 F300
-G2 X53.69308979755312 Y54.723755694541424 R4.40928490603945 F300
-G1 X53.6813125 Y54.72377083333336 F300
-G2 X51.68899999999999 Y55.03333333333337 R7.704666666666667 F300
-G2 X51.24946093749999 Y55.16463281250003 R9.17488442409064 F300
-G2 X50.827781249999994 Y55.32768229166669 R4.185865282852383 F300
-G2 X50.435867187499994 Y55.545302083333354 R2.688428438960497 F300
-G2 X50.254792968749996 Y55.68170719401044 R2.2085182955984575 F300
-G2 X50.08562499999999 Y55.840312500000024 R2.0811684541098634 F300
-G2 X49.11724999999999 Y57.39077083333337 R6.437312499999999 F300
-G2 X48.43727083333333 Y59.213750000000005 R7.463895833333334 F300
-G2 X49.32097916666666 Y62.29085416666667 R3.391958333333333 F300
-G2 X52.92989583333334 Y63.288333333333334 R3.8708541666666667 F300
-G3 X53.07277083333333 Y63.26981250000001 R0.8810625 F300
-G2 X55.3164375 Y61.74581250000001 R9.305395833333334 F300
-G1 X55.53486344401041 Y61.53738594563803 F300
-G1 X55.75366145833333 Y61.31714615885417 F300
-G2 X56.16905729166667 Y60.84259114583333 R6.266600456013111 F300
-G2 X56.5159921875 Y60.324875976562495 R3.897123553159713 F300
-G2 X56.64921402994791 Y60.05068599446614 R2.8304618903640884 F300
-G2 X56.747833333333325 Y59.766729166666664 R2.344553494705603 F300
-G1 X56.74518749999999 Y59.76672916666668 F300
-G0 X49.15164583333333 Y70.2230625
-G2 X51.43235416666666 Y69.16472916666666 R4.212166666666666 F300
-G2 X51.75572371497768 Y68.83214490366785 R4.02658386846766 F300
-G2 X52.03438739169295 Y68.47096953765023 R4.046247946497064 F300
-G2 X52.4595573202525 Y67.6782879683376 R4.194116250792248 F300
-G2 X52.71178433217776 Y66.81756940228695 R4.507720208375989 F300
-G2 X52.79498880730118 Y65.91969878305643 R4.837034895064404 F300
-G2 X52.61239583333334 Y64.57420833333335 R5.067656141771965 F300
-G3 X49.47708333333333 Y63.94979166666667 R5.196416666666667 F300
-G2 X49.25100410970052 Y64.38819901529948 R2.873767427541601 F300
-G2 X49.09223860677083 Y64.86148795572917 R3.7797672177665995 F300
-G2 X48.985252888997394 Y65.3598916422526 R5.4924724796362385 F300
-G2 X48.914513020833326 Y65.87364322916667 R9.060764001324356 F300
-G1 X48.86448506673177 Y66.39297587076824 F300
-G1 X48.81963509114583 Y66.90812272135418 F300
-G3 X48.76442915852864 Y67.40931693522137 R12.617520890066261 F300
-G1 X48.72808835347494 Y67.65162966410321 F300
-G1 X48.68333333333334 Y67.88679166666668 F300
-G1 X48.61147725423177 Y68.18677852376302 F300
-G3 X48.52272298177084 Y68.4873544921875 R5.315531550233297 F300
-G3 X48.281497395833334 Y69.06584114583333 R3.7596525970006995 F300
-G3 X47.933611653645826 Y69.57338639322916 R2.4897648454592 F300
-G3 X47.71153218587239 Y69.78528560384115 R1.9719298282349766 F300
-G3 X47.45302083333333 Y69.961125 R1.82518064412809 F300
-G1 X47.381583333333325 Y69.992875 F300
-G2 X47.93456249999999 Y70.1754375 R3.1035625 F300
-G2 X49.15164583333333 Y70.2230625 R3.6591875000000003 F300
-G0 X45.309895833333336 Y59.87785416666667
-G1 X45.083711318969726 Y60.01863562520346 F300
-G1 X44.85897245279948 Y60.16335095214844 F300
-G1 X44.63625671895345 Y60.31227532450359 F300
-G1 X44.4161416015625 Y60.46568391927084 F300
-G2 X43.573236979166666 Y61.12966406250001 R10.499941753044274 F300
-G2 X42.81814095052083 Y61.88740592447918 R7.758338218030073 F300
-G2 X42.1878125 Y62.75652083333334 R6.200864192373816 F300
-G2 X41.908233013322935 Y63.30300315902991 R5.0546394977772096 F300
-G2 X41.69517934721364 Y63.88645284494221 R4.957878233390987 F300
-G2 X41.55943745121593 Y64.49262624478271 R4.587766977964208 F300
-G2 X41.511793274873625 Y65.10727971226389 R4.075900059426081 F300
-G2 X41.60997665261183 Y65.94024892075149 R3.4771690146768446 F300
-G2 X41.7379659193959 Y66.3410687153667 R3.0981152762599495 F300
-G2 X41.92322916666666 Y66.72527083333334 R2.96972055044605 F300
-G2 X42.19026713053385 Y67.12540661621094 R3.329258603479465 F300
-G2 X42.50626334635416 Y67.48011881510416 R3.5762152354379997 F300
-G2 X43.25573697916667 Y68.0630703125 R4.2636582854905125 F300
-G2 X44.11286295572917 Y68.49372102864585 R5.661479146083183 F300
-G2 X44.563424601236974 Y68.65805725097657 R6.979087470352966 F300
-G2 X45.018854166666664 Y68.79166666666667 R7.8599838771002455 F300
-G1 X45.28467375614044 Y68.86239059270582 F300
-G1 X45.58461892538777 Y68.9341881444951 F300
-G2 X45.90212974441552 Y68.98987125676574 R3.991611647796024 F300
-G2 X46.22064628323052 Y69.01225186424901 R2.4602475296522455 F300
-G2 X46.414866789968485 Y69.00145785306994 R1.6627711371633909 F300
-G2 X46.59870845378421 Y68.96618816519519 R1.229609528085714 F300
-G2 X46.91856249999999 Y68.80489583333333 R0.8594672765207532 F300
-G2 X47.20857063802082 Y68.44518652343751 R1.222041493827832 F300
-G2 X47.307794555664046 Y68.22990767415365 R1.8014253598078533 F300
-G2 X47.383236979166654 Y68.00089322916668 R2.4898877771733074 F300
-G2 X47.43995186360676 Y67.76555358886719 R3.5764695479446322 F300
-G1 X47.48299316406249 Y67.53129915364583 F300
-G1 X47.548270833333326 Y67.0956875 F300
-G2 X47.67903287760416 Y66.07815787760416 R28.906736540578475 F300
-G3 X47.818476562499995 Y65.07327864583333 R17.99372447499421 F300
-G3 X48.057635091145826 Y64.1046142578125 R6.542547485398715 F300
-G3 X48.243055297851555 Y63.64122652180989 R4.484591292941713 F300
-G3 X48.48754166666666 Y63.19572916666666 R3.918648228168832 F300
-G3 X47.1249375 Y60.22445833333334 R4.656666666666666 F300
-G3 X45.434250000000006 Y59.766729166666664 R7.495645833333334 F300
-G3 X45.309895833333336 Y59.877854166666665 R0.6058958333333333 F300
-G1 X45.309895833333336 Y59.87785416666667 F300
-G0 X40.825208333333336 Y49.85279166666667
-G2 X40.96018717447917 Y51.395271158854165 R15.104213059312467 F300
-G2 X41.266070312500005 Y52.91567447916667 R12.854792735392488 F300
-G2 X41.76691829427084 Y54.379027018229166 R10.887291437524723 F300
-G2 X42.48679166666667 Y55.75035416666666 R9.411758245011036 F300
-G2 X42.9097167561849 Y56.34977494303385 R7.802968211084794 F300
-G2 X43.3912119140625 Y56.911833658854164 R7.858965405867599 F300
-G2 X44.50920052083334 Y57.888518229166664 R7.5338583708383595 F300
-G2 X45.799333658854174 Y58.60971451822916 R6.679254182729873 F300
-G2 X47.2201875 Y59.00472916666666 R5.785369710155436 F300
-G3 X47.72554166666667 Y57.446333333333335 R7.654395833333332 F300
-G3 X48.868541666666665 Y55.377291666666665 R10.115020833333332 F300
-G3 X49.768125000000005 Y54.52797916666667 R3.7491458333333334 F300
-G3 X49.664937500000015 Y54.30308333333333 R0.6085416666666666 F300
-G1 X49.48057666015627 Y53.54211686197917 F300
-G1 X49.28691406250001 Y52.77891796875001 F300
-G2 X48.83745312500001 Y51.267651041666674 R22.34429461147725 F300
-G2 X48.24809375000001 Y49.812938802083345 R12.922124873196006 F300
-G2 X47.450375 Y48.45843750000001 R9.154267021751645 F300
-G2 X47.07077562893493 Y47.97770374031766 R6.572395574354612 F300
-G2 X46.6399012622995 Y47.5258850812058 R6.63417568062244 F300
-G2 X45.64726772229315 Y46.752369038184554 R6.145401750915852 F300
-G2 X45.096978638910045 Y46.45235964102068 R5.335513001014613 F300
-G2 X44.51835473993218 Y46.22464131791831 R4.740065596444629 F300
-G2 X43.29904267516785 Y46.029453867389115 R4.0253916335570405 F300
-G2 X42.92864583333334 Y46.04808333333334 R3.6796833275522336 F300
-G1 X42.76787925994091 Y46.07005802699631 F300
-G1 X42.615162284655455 Y46.10095237394625 F300
-G1 X42.4703017171224 Y46.140426100047804 F300
-G1 X42.333104366987186 Y46.18813893116561 F300
-G2 X42.080926557492 Y46.30692081190852 R1.9591191651097697 F300
-G2 X41.85708333333334 Y46.454575823092085 R1.8898761081380757 F300
-G2 X41.488218549679495 Y46.82561646444962 R2.0034353469042423 F300
-G2 X41.34010594451122 Y47.04355770845778 R2.3297580733871013 F300
-G2 X41.21414583333333 Y47.27948331057502 R2.6813568728926644 F300
-G2 X41.1087926933093 Y47.530671077718424 R3.135244682064686 F300
-G2 X41.02250100160256 Y47.7943988168051 R3.6908925834952586 F300
-G2 X40.90091987179487 Y48.348585438476704 R4.693416427009749 F300
-G2 X40.83703826121794 Y48.92026563092664 R6.359349594857548 F300
-G1 X40.82287096854968 Y49.20586033348624 F300
-G1 X40.818491987179485 Y49.48766184949173 F300
-G1 X40.825208333333336 Y49.85279166666667 F300
-G0 X30.861 Y49.834270833333335
-G2 X33.37454166666667 Y49.797229166666675 R19.438937499999998 F300
-G2 X39.57902083333333 Y49.41887500000001 R155.0737891314983 F300
-G3 X39.62019661458333 Y48.54087174479167 R8.996197285112363 F300
-G3 X39.759598958333335 Y47.67130208333334 R6.842226022592089 F300
-G3 X39.87357649739583 Y47.25192716471354 R5.451099109618057 F300
-G3 X40.021040364583335 Y46.849357421875 R4.6585311024153695 F300
-G3 X40.20496712239583 Y46.46849178059896 R4.006428861104313 F300
-G3 X40.428333333333335 Y46.11422916666667 R3.5040455601101645 F300
-G3 X38.31166666666667 Y44.71458333333334 R3.201458333333333 F300
-G3 X38.168791666666664 Y44.688125 R0.5423958333333332 F300
-G2 X33.3375 Y43.986979166666664 R11.377083333333333 F300
-G2 X30.977416666666663 Y44.423541666666665 R10.847916666666665 F300
-G1 X30.71861075846354 Y44.50120617675782 F300
-G1 X30.454781901041667 Y44.58497884114583 F300
-G2 X29.92768229166667 Y44.7803984375 R7.055247518400659 F300
-G2 X29.427371744791667 Y45.02890006510417 R4.157411904298425 F300
-G2 X28.985104166666666 Y45.349583333333335 R2.733739832942054 F300
-G2 X28.83884515546891 Y45.49790571010126 R1.8023153992110543 F300
-G2 X28.71564411962079 Y45.65608359914205 R1.7146059467196406 F300
-G2 X28.53403091031464 Y45.99766418105199 R1.708174654671899 F300
-G2 X28.43149441143056 Y46.365641613082616 R1.8810951295520988 F300
-G2 X28.3992644956509 Y46.751332429253424 R2.246034396548948 F300
-G2 X28.414415339840893 Y47.03272264368965 R2.686522430914026 F300
-G2 X28.457740251662575 Y47.31555556605913 R3.1133697516734635 F300
-G2 X28.616146553711125 Y47.87290995363067 R3.75846323959664 F300
-G2 X28.84895195281684 Y48.39811643003355 R4.419130591740661 F300
-G2 X28.985275735959974 Y48.640764463442224 R4.535041911466848 F300
-G1 X29.05702164731303 Y48.755717224780085 F300
-G1 X29.130625 Y48.86589583333333 F300
-G2 X30.861 Y49.834270833333335 R2.209270833333333 F300
-G1 X30.861 Y49.834270833333335 F300
-G0 X21.960416666666667 Y51.58581250000001
-G2 X21.486812499999996 Y55.18150000000001 R7.109354166666667 F300
-G2 X21.568874997391763 Y55.61377897561011 R3.863323758330687 F300
-G1 X21.681149526976913 Y56.05058613712835 F300
-G3 X21.780296563355 Y56.49024652705131 R5.891688729895818 F300
-G3 X21.822976581125573 Y56.93108518787561 R2.526244877657641 F300
-G1 X21.820187499999996 Y57.03358333333333 F300
-G3 X21.782592895507808 Y57.29232722981771 R1.482701669478556 F300
-G3 X21.704473632812498 Y57.53438997395834 R1.629963255737628 F300
-G3 X21.4560546875 Y57.97814583333334 R2.290904276370022 F300
-G3 X21.133717773437496 Y58.38419856770834 R5.123200094981028 F300
-G1 X20.796249999999997 Y58.77189583333333 F300
-G1 X19.253729166666663 Y60.600166666666674 F300
-G1 X19.085305338541666 Y60.799885742187506 F300
-G1 X18.992070719401042 Y60.916472941080734 F300
-G1 X18.900510416666666 Y61.041028645833336 F300
-G2 X18.745481119791666 Y61.30449576822917 R1.7781964030653254 F300
-G2 X18.693546305338543 Y61.43863228352865 R1.0200689452523979 F300
-G2 X18.666354166666665 Y61.57118750000001 R0.6913492006531761 F300
-G2 X19.18713891601562 Y61.18365559895834 R4.7899637415387915 F300
-G2 X19.66160091145833 Y60.73758463541668 R6.977099009811647 F300
-G2 X20.10691739908854 Y60.25405859375001 R12.9453289043253 F300
-G1 X20.540265625 Y59.75416145833334 F300
-G1 X20.757819529215496 Y59.504662475585945 F300
-G1 X20.978822835286458 Y59.25897721354168 F300
-G3 X21.439766276041667 Y58.78958984375001 R9.099371319546348 F300
-G3 X21.940273193359374 Y58.36708333333334 R5.7338787817418515 F300
-G3 X22.497520833333333 Y58.01254166666668 R4.321876509101776 F300
-G3 X24.148520833333333 Y57.32462500000001 R17.653 F300
-G2 X25.664583333333333 Y56.5546875 R10.342562500000001 F300
-G2 X28.085520833333334 Y54.32954166666667 R7.757583333333334 F300
-G2 X29.143854166666667 Y50.744437500000004 R5.908145833333332 F300
-G3 X29.143854166666667 Y50.57775 R0.66675 F300
-G3 X28.207229166666668 Y49.68875 R3.505729166666667 F300
-G3 X27.299708333333328 Y47.81285416666667 R5.352520833333333 F300
-G3 X26.92929166666666 Y47.955729166666664 R0.5926666666666667 F300
-G2 X26.123805948893224 Y48.07177376302083 R5.3495984962583885 F300
-G2 X25.351093424479167 Y48.304152343750005 R5.547614620928216 F300
-G2 X23.943799479166664 Y49.07822395833334 R5.888554526897006 F300
-G2 X22.78703287760417 Y50.19856901041667 R6.159134401200114 F300
-G2 X21.960416666666667 Y51.58581250000001 R6.027567859313848 F300
-G0 X20.248562500000002 Y43.05300000000001
-G2 X25.58520833333333 Y46.870937500000004 R7.911041666666667 F300
-G3 X25.70691666666666 Y46.90797916666667 R0.5450416666666666 F300
-G3 X26.929291666666668 Y46.71747916666667 R6.686020833333334 F300
-G3 X27.167416666666668 Y46.746583333333334 R0.6773333333333333 F300
-G3 X27.55635416666667 Y45.174958333333336 R3.196166666666666 F300
-G3 X28.699354166666666 Y44.039895833333325 R3.114145833333333 F300
-G3 X28.363333333333333 Y43.4895625 R0.7117291666666666 F300
-G2 X26.410708333333332 Y36.83529166666666 R22.579541666666668 F300
-G1 X26.270229996411995 Y36.542420085658215 F300
-G1 X26.119498258536794 Y36.24194186685391 F300
-G1 X25.95793153744438 Y35.937513756249395 F300
-G1 X25.784948250871427 Y35.63279249984032 F300
-G2 X25.402405652230506 Y35.037097533591066 R8.700003253822981 F300
-G2 X24.9672178045073 Y34.4841109360713 R6.287728587948274 F300
-G2 X24.728427956581488 Y34.2327751405741 R4.956548762195577 F300
-G2 X24.474732049595083 Y34.0030866752462 R4.243721246338053 F300
-G2 X23.92029572938714 Y33.62327871908093 R3.434115326455431 F300
-G2 X23.29925618577673 Y33.373941035540646 R2.8115062785356977 F300
-G2 X22.962306249537257 Y33.30734041099399 R2.657832102819824 F300
-G2 X22.60696076065714 Y33.284327592590515 R2.7306882997349353 F300
-G1 X22.568958333333335 Y33.28458333333333 F300
-G2 X22.19909331258138 Y33.31276313781738 R3.1896586553455903 F300
-G2 X21.84680086263021 Y33.38153348795572 R3.0822594263880787 F300
-G2 X21.51247630818685 Y33.48807672627767 R3.069787020953062 F300
-G2 X21.196514973958337 Y33.6295751953125 R3.141021687888647 F300
-G2 X20.621263264973962 Y34.006167195638014 R3.3859565682272446 F300
-G2 X20.124208333333332 Y34.488768229166666 R3.8914295400000665 F300
-G2 X19.708512776692707 Y35.05483703613282 R4.503322080072202 F300
-G2 X19.377339192708334 Y35.68183235677084 R5.0615585520338735 F300
-G2 X19.133850179036454 Y36.34721293131511 R5.387159317884617 F300
-G2 X19.04597569783528 Y36.68725354512533 R5.404652816619459 F300
-G2 X18.981208333333328 Y37.0284375 R5.277852671170557 F300
-G2 X18.893554654045516 Y38.19838459034822 R7.678567367489637 F300
-G2 X18.988749453063832 Y39.471950829839095 R8.741865900265596 F300
-G2 X19.26098623025723 Y40.727585791491215 R9.678022839668534 F300
-G2 X19.69024355583339 Y41.93227400498478 R10.146180636068332 F300
-G2 X19.95749822745491 Y42.50519574669717 R10.078016197933104 F300
-G2 X20.2565 Y43.05300000000001 R9.786797293544952 F300
-G1 X20.248562500000002 Y43.05300000000001 F300;svg#Drawing > g#Layer_3 > path
-G0 X26.0588125 Y16.88041666666667
-G3 X26.095275390624998 Y18.078193684895837 R95.40654617003233 F300
-G1 X26.128265625 Y19.285148437500002 F300
-G2 X26.180107421875 Y20.493591471354165 R48.60489304627256 F300
-G2 X26.273125 Y21.69583333333333 R27.44736879733596 F300
-G2 X27.44522916666666 Y25.955624999999998 R13.551958333333335 F300
-G3 X27.11185416666666 Y27.230916666666662 R0.9577916666666667 F300
-G3 X26.88745397916955 Y27.321322082914556 R0.8574358790931654 F300
-G3 X26.639368608668434 Y27.35150966214681 R1.015962196353671 F300
-G3 X26.169303748171505 Y27.23830166221168 R0.9989975645023683 F300
-G3 X25.976466707427065 Y27.096472163490994 R0.8032491572122334 F300
-G3 X25.83656249999999 Y26.897541666666662 R0.7136986269884571 F300
-G3 X24.439562499999994 Y21.978937499999997 R16.43327083333333 F300
-G1 X24.402520833333327 Y21.61645833333333 F300
-G2 X23.767520833333325 Y21.425958333333327 R5.672666666666667 F300
-G2 X22.53720833333332 Y21.161374999999996 R16.42139953627984 F300
-G2 X20.61633333333332 Y20.896791666666665 R18.80367713100031 F300
-G2 X21.674666666666656 Y25.99795833333333 R16.22689583333333 F300
-G3 X21.7204273950042 Y26.248801145836925 R0.702641061358557 F300
-G3 X21.6641115252353 Y26.54251741054982 R0.8241990584302552 F300
-G3 X21.512198764925664 Y26.807336210775148 R1.0054643176491045 F300
-G3 X21.290241387570916 Y27.016588233738794 R1.0412480648892108 F300
-G3 X21.023791666666657 Y27.143604166666666 R0.8752802589896793 F300
-G3 X19.87549999999999 Y26.492729166666663 R0.9524999999999999 F300
-G3 X18.758958333333325 Y19.436291666666662 R17.629187499999997 F300
-G3 X19.690291666666656 Y18.504958333333324 R0.9524999999999999 F300
-G3 X20.624270833333323 Y19.436291666666662 R0.9419166666666667 F300
-G1 X20.624270833333323 Y19.653249999999993 F300
-G3 X22.881166666666655 Y19.965458333333324 R23.962757432887535 F300
-G1 X23.23583235677082 Y20.03358854166666 F300
-G1 X23.595210937499992 Y20.105687499999995 F300
-G3 X24.315208333333324 Y20.27766666666666 R11.419037473213637 F300
-G3 X24.255346354166655 Y18.58234895833333 R64.1213564312747 F300
-G2 X24.209374999999987 Y16.89099999999999 R165.52817099836028 F300
-G1 X24.208040629639868 Y16.835898497836638 F300
-G1 X24.213089399936145 Y16.732118608593655 F300
-G1 X24.227817120797823 Y16.635056691380246 F300
-G3 X24.283797874054574 Y16.461074357236257 R0.8326529714088895 F300
-G3 X24.37095980908444 Y16.313926663792888 R0.7666949085704446 F300
-G3 X24.48427984556175 Y16.193588779438368 R0.7709578595972852 F300
-G3 X24.76930190155597 Y16.033243111548757 R0.8642024487003771 F300
-G3 X25.098679399431823 Y15.97983870067315 R1.0216652056598978 F300
-G3 X25.744679299850187 Y16.20527115618806 R1.0019299870081164 F300
-G3 X25.867977682749803 Y16.331964426471238 R0.8613343269725992 F300
-G3 X25.965073364997846 Y16.486737766063097 R0.8504587074107686 F300
-G3 X26.030505314709508 Y16.66956417833709 R0.9053009964852413 F300
-G1 X26.0496408174002 Y16.77148885028409 F300
-G1 X26.0588125 Y16.88041666666667 F300;svg#Drawing > g#Layer_3 > path
-G0 X27.085395833333333 Y17.790583333333334
-G3 X27.30886487953106 Y17.70017791708544 R0.8474187812615145 F300
-G3 X27.556705924326277 Y17.669990337853186 R1.0127583272652538 F300
-G3 X28.02736838614227 Y17.78319833778832 R1.0018986565065546 F300
-G3 X28.22060332628451 Y17.925027836509003 R0.8046177949115095 F300
-G3 X28.360687499999994 Y18.12395833333333 R0.7120337063447003 F300
-G1 X28.555156249999996 Y18.574328776041664 F300
-G1 X28.741687499999998 Y19.028171874999998 F300
-G1 X28.920281249999995 Y19.48499153645833 F300
-G1 X29.090937499999995 Y19.944291666666665 F300
-G3 X31.71560416666667 Y20.063354166666663 R9.17575 F300
-G2 X31.79497916666666 Y17.417520833333327 R55.21179562420983 F300
-G1 X31.80035056849554 Y17.308601424383916 F300
-G1 X31.816005063234677 Y17.206698523406367 F300
-G3 X31.875405066958724 Y17.023948068585334 R0.8717608310825562 F300
-G3 X31.96766264886881 Y16.869281115307174 R0.8098828460468068 F300
-G3 X32.08726127999495 Y16.742709310008852 R0.8191005178374994 F300
-G3 X32.72873571726016 Y16.51760649735293 R0.9805058622101271 F300
-G3 X33.06651575326926 Y16.57270205806782 R1.0457828654010417 F300
-G3 X33.3606519811879 Y16.738048836582642 R0.8972958000430791 F300
-G3 X33.47801039492394 Y16.86209460268855 R0.8003568383494309 F300
-G3 X33.56844593001013 Y17.013736977452666 R0.7919849565380889 F300
-G3 X33.62662127757072 Y17.192987228944393 R0.8545883820324734 F300
-G1 X33.641943472005245 Y17.292968829734797 F300
-G1 X33.64719912872997 Y17.399856625233134 F300
-G1 X33.6470625 Y17.417520833333327 F300
-G3 X33.36660416666667 Y22.629812499999993 R48.61696690023443 F300
-G1 X33.300634033203124 Y23.25014156087239 F300
-G3 X33.22281966145833 Y23.87670279947916 R21.934849459613115 F300
-G3 X32.978328125000004 Y25.111934895833325 R9.859130419092821 F300
-G3 X32.784985921223964 Y25.702312296549472 R6.068424786490671 F300
-G3 X32.526469401041666 Y26.26233496093749 R4.771759117624296 F300
-G3 X32.18944604492187 Y26.78285616048176 R4.04089431454793 F300
-G3 X31.987327891031896 Y27.025445358276354 R3.775779439360083 F300
-G3 X31.76058333333333 Y27.254729166666653 R3.7361220629303387 F300
-G3 X31.61071094986026 Y27.367644981192136 R0.9323938113234793 F300
-G3 X31.437309111943584 Y27.452510937844195 R1.0595670447647374 F300
-G3 X31.054301274393318 Y27.524485912634052 R1.0730171936552704 F300
-G3 X30.71791540627307 Y27.460274409423093 R0.8743590015544063 F300
-G3 X30.567715405199955 Y27.376378886718392 R0.7391788164865684 F300
-G3 X30.437666666666665 Y27.254729166666653 R0.7201440315687186 F300
-G3 X28.294541666666667 Y23.349479166666658 R13.387916666666666 F300
-G1 X28.101359659830727 Y22.815237874348952 F300
-G1 X27.915898111979168 Y22.27733789062499 F300
-G1 X27.551393229166663 Y21.197755208333323 F300
-G1 X27.363978312174478 Y20.659669189453115 F300
-G1 X27.16754069010417 Y20.12511783854166 F300
-G2 X26.730854166666667 Y19.073812499999992 R17.243128535470852 F300
-G3 X27.085395833333333 Y17.790583333333334 R0.9604374999999999 F300
-G0 X31.099124999999997 Y24.958145833333337
-G2 X31.21581555175781 Y24.624848347981775 R3.9937880859319104 F300
-G2 X31.3068642578125 Y24.278621419270838 R5.0361635878344915 F300
-G2 X31.429523437500002 Y23.564122395833337 R7.52194030248945 F300
-G1 X31.469877563476565 Y23.204221883138025 F300
-G1 X31.5020771484375 Y22.848135091145835 F300
-G1 X31.559499999999996 Y22.164145833333333 F300
-G2 X31.630937499999998 Y21.32277083333333 R30.078108500369154 F300
-G2 X31.252583333333327 Y21.23016666666667 R3.58775 F300
-G1 X31.027687499999995 Y21.193125000000002 F300
-G1 X30.911270833333333 Y21.179895833333337 F300
-G2 X30.458833333333335 Y21.150791666666667 R6.49629298878928 F300
-G1 X29.516916666666667 Y21.150791666666667 F300
-G1 X29.742473958333335 Y21.805635416666668 F300
-G1 X29.971999999999998 Y22.460479166666662 F300
-G2 X31.099124999999997 Y24.958145833333337 R14.491229166666667 F300;svg#Drawing > g#Layer_3 > path
-G0 X40.33572916666666 Y25.362958333333335
-G3 X39.858647514638804 Y25.591483243105095 R2.6954209242694622 F300
-G3 X39.35448415342306 Y25.72970901936165 R3.2922767562458515 F300
-G3 X38.30391417521909 Y25.81605276289664 R5.5092418525499625 F300
-G1 X38.03914583333332 Y25.81275 F300
-G3 X36.66860416666666 Y25.283583333333336 R0.9233958333333333 F300
-G1 X36.389758138020824 Y24.314629557291674 F300
-G1 X36.12075130208333 Y23.343195312500004 F300
-G1 X35.86158365885416 Y22.36928059895834 F300
-G1 X35.61225520833332 Y21.39288541666667 F300
-G1 X35.37276595052082 Y20.414009765625 F300
-G1 X35.14311588541665 Y19.432653645833334 F300
-G1 X34.92330501302082 Y18.448817057291663 F300
-G1 X34.713333333333324 Y17.4625 F300
-G3 X35.36420833333334 Y16.316854166666662 R0.9551458333333334 F300
-G3 X36.512499999999996 Y16.967729166666665 R0.9419166666666667 F300
-G1 X36.691920572916665 Y17.816090820312503 F300
-G1 X36.88027083333333 Y18.662716145833333 F300
-G1 X37.07655859374999 Y19.507853190104168 F300
-G1 X37.27979166666667 Y20.35175 F300
-G3 X38.23671516927083 Y20.406278971354165 R7.865154264338178 F300
-G3 X39.170570312500004 Y20.601450520833332 R5.319066225454295 F300
-G3 X40.027530924479166 Y20.984641601562494 R3.758497936253418 F300
-G3 X40.410355102539064 Y21.26154976399739 R3.1430924560576674 F300
-G3 X40.587409540812175 Y21.423922927856438 R3.0160521457191543 F300
-G3 X40.753770833333334 Y21.603229166666658 R3.006395946566854 F300
-G3 X41.43904166666667 Y23.55320833333333 R2.6855208333333334 F300
-G3 X40.33572916666666 Y25.362958333333335 R2.2965833333333334 F300
-G0 X40.19814583333332 Y23.246291666666668
-G1 X40.19814583333332 Y23.203958333333336 F300
-G1 X40.19549999999998 Y23.15997135416667 F300
-G2 X40.176979166666655 Y23.098125000000003 R0.15293223968388278 F300
-G2 X40.11612499999999 Y22.88645833333333 R1.93675 F300
-G1 X40.11612499999999 Y22.857354166666664 F300
-G1 X40.07643749999998 Y22.772687499999996 F300
-G2 X39.946791666666655 Y22.558374999999998 R3.5732151106862635 F300
-G1 X39.91504166666666 Y22.516041666666663 F300
-G1 X39.84889583333332 Y22.4393125 F300
-G2 X39.674270833333324 Y22.26204166666667 R3.6794148517601157 F300
-G1 X39.57902083333332 Y22.180020833333337 F300
-G1 X39.55520833333331 Y22.164145833333333 F300
-G1 X39.39910416666665 Y22.0583125 F300
-G2 X39.179499999999976 Y21.933958333333337 R2.569045525357479 F300
-G1 X39.14874218749998 Y21.92271354166667 F300
-G1 X39.09615624999998 Y21.897578125000003 F300
-G1 X39.02074999999998 Y21.85722916666667 F300
-G2 X37.605229166666646 Y21.592645833333332 R3.96875 F300
-G1 X38.00508072916665 Y23.0891953125 F300
-G1 X38.21058756510415 Y23.835113606770832 F300
-G1 X38.42279166666665 Y24.579791666666665 F300
-G2 X39.023395833333325 Y24.537458333333333 R3.6486041666666664 F300
-G2 X39.85154166666665 Y24.20672916666667 R1.6933333333333331 F300
-G1 X39.98118749999999 Y24.07708333333333 F300
-G1 X40.01822916666666 Y24.03475 F300
-G1 X40.07379166666665 Y23.9474375 F300
-G1 X40.12670833333332 Y23.849541666666664 F300
-G2 X40.192854166666656 Y23.637874999999994 R1.2250208333333332 F300
-G1 X40.20013020833332 Y23.594218749999992 F300
-G1 X40.21137499999998 Y23.55056249999999 F300
-G3 X40.21137499999998 Y23.494999999999983 R0.30956249999999996 F300
-G3 X40.20079166666666 Y23.366677083333332 R1.0331915746677411 F300
-G1 X40.198145833333335 Y23.230416666666667 F300
-G1 X40.19814583333332 Y23.246291666666668 F300;svg#Drawing > g#Layer_3 > path
-G0 X43.254083333333334 Y17.3116875
-G1 X43.368887695312495 Y18.328927734375 F300
-G2 X43.49386197916666 Y19.349640625000003 R70.5361762754659 F300
-G2 X43.81499999999999 Y21.383624999999995 R34.19133412992033 F300
-G3 X46.83125 Y22.436666666666667 R4.6460833333333325 F300
-G3 X47.79697916666666 Y24.22789583333334 R2.360083333333333 F300
-G3 X46.706895833333334 Y25.87625 R1.9288125 F300
-G3 X44.664312499999994 Y26.505958333333336 R5.535083333333334 F300
-G1 X44.664312499999994 Y26.505958333333336 F300
-G3 X44.439912312502884 Y26.59636374958123 R0.857435879113105 F300
-G3 X44.19182694200177 Y26.626551328813484 R1.0159621963416576 F300
-G3 X43.72176208150484 Y26.513343328878353 R0.9989975645035847 F300
-G3 X43.528925040760406 Y26.371513830157667 R0.803249157197167 F300
-G3 X43.38902083333333 Y26.172583333333336 R0.7136986269940052 F300
-G3 X43.159829396565755 Y25.64472408040365 R15.85376977726082 F300
-G3 X42.95062898763021 Y25.112058919270833 R16.61233278713995 F300
-G3 X42.76014061482748 Y24.57486690266927 R17.56343561699001 F300
-G3 X42.58708528645833 Y24.033427083333333 R18.736613086790314 F300
-G3 X42.04361458333334 Y21.830770833333336 R22.979556241608872 F300
-G3 X41.676753255208325 Y19.58247395833334 R37.114105368678956 F300
-G3 X41.404645833333326 Y17.30639583333334 R94.28158067676613 F300
-G1 X41.40057636870129 Y17.233000239595683 F300
-G3 X41.484829966893685 Y16.901454418540506 R0.7194980691911324 F300
-G3 X41.70320286371146 Y16.62848199269264 R0.9413418547758924 F300
-G3 X42.00411321251549 Y16.44328425539738 R1.0674677185542032 F300
-G3 X42.16940060433763 Y16.392726299127375 R0.9630159882634933 F300
-G3 X42.33597916666667 Y16.375062500000002 R0.8186834986666 F300
-G3 X43.254083333333334 Y17.3116875 R0.9524999999999999 F300
-G0 X46.177729166666666 Y24.7411875
-G1 X46.275625 Y24.677687499999994 F300
-G1 X46.2994375 Y24.661812499999993 F300
-G3 X46.331187500000006 Y24.632708333333323 R0.18785416666666666 F300
-G1 X46.41320833333333 Y24.553333333333324 F300
-G1 X46.46083333333333 Y24.503062499999988 F300
-G3 X46.48993750000001 Y24.455437499999984 R0.2645833333333333 F300
-G3 X46.50316666666668 Y24.423687499999982 R0.05798107137532701 F300
-G1 X46.52433333333334 Y24.39193749999998 F300
-G1 X46.530286458333336 Y24.370770833333317 F300
-G1 X46.54020833333334 Y24.33372916666665 F300
-G1 X46.5472638888889 Y24.306290895061714 F300
-G1 X46.54020833333334 Y24.291395833333315 F300
-G2 X46.54020833333334 Y24.193499999999982 R0.5979583333333333 F300
-G2 X46.54020833333334 Y24.15116666666665 R0.29104166666666664 F300
-G1 X46.54020833333334 Y24.114124999999987 F300
-G1 X46.49221135676493 Y23.948432580174913 F300
-G1 X46.492583333333336 Y23.94743749999999 F300
-G2 X46.38145833333334 Y23.748999999999988 R2.1007916666666664 F300
-G1 X46.34706250000001 Y23.701374999999988 F300
-G2 X46.28885416666668 Y23.632583333333326 R0.7276041666666666 F300
-G2 X46.11422916666668 Y23.455312499999994 R2.2119166666666668 F300
-G2 X45.97135416666668 Y23.333604166666657 R2.162591537614669 F300
-G1 X45.92486098921586 Y23.295331149968725 F300
-G1 X45.95018750000001 Y23.315083333333327 F300
-G1 X45.84964583333334 Y23.24364583333332 F300
-G2 X45.41837500000001 Y22.97906249999999 R4.455583333333333 F300
-G1 X45.249041666666685 Y22.897041666666656 F300
-G1 X45.16437500000002 Y22.857354166666656 F300
-G1 X45.10881250000001 Y22.836187499999983 F300
-G2 X44.62991666666668 Y22.68802083333332 R5.011208333333333 F300
-G1 X44.38650000000002 Y22.637749999999983 F300
-G1 X44.341520833333355 Y22.637749999999983 F300
-G1 X44.30447916666669 Y22.637749999999983 F300
-G1 X44.084875000000025 Y22.621874999999985 F300
-G2 X44.96329166666669 Y25.204208333333316 R15.388166666666667 F300
-G2 X45.373395833333355 Y25.101020833333322 R5.102374444360094 F300
-G2 X46.177729166666666 Y24.741187500000006 R3.175 F300
-G1 X46.177729166666666 Y24.7411875 F300;svg#Drawing > g#Layer_3 > path
-G0 X55.705375 Y28.051125000000003
-G3 X54.430083333333336 Y27.717750000000006 R0.9551458333333334 F300
-G1 X54.076203125 Y27.236869791666678 F300
-G1 X53.718354166666664 Y26.759958333333337 F300
-G1 X53.609875 Y26.61972916666667 F300
-G1 X53.46964583333333 Y26.4398125 F300
-G1 X53.12568749999999 Y26.011187500000002 F300
-G1 X52.74865624999998 Y25.549489583333333 F300
-G1 X52.3636875 Y25.0904375 F300
-G1 X52.00385416666666 Y24.675041666666672 F300
-G2 X51.94564583333332 Y24.60889583333334 R0.6932083333333333 F300
-G2 X51.760437499999995 Y24.929041666666674 R3.896693627845075 F300
-G1 X51.678416666666664 Y25.10102083333334 F300
-G1 X51.6016875 Y25.28358333333334 F300
-G2 X51.355625 Y26.230791666666676 R6.2309375000000005 F300
-G1 X51.34504166666667 Y26.31578906250001 F300
-G1 X51.33445833333333 Y26.386895833333345 F300
-G1 X51.32718229166666 Y26.511580729166678 F300
-G1 X51.31593749999999 Y26.638250000000006 F300
-G1 X51.31593749999999 Y27.225625000000008 F300
-G3 X50.38460416666666 Y28.156958333333346 R0.9366249999999999 F300
-G3 X49.45327083333332 Y27.225625000000008 R0.9524999999999999 F300
-G3 X49.871312499999995 Y24.593020833333345 R6.122458333333333 F300
-G1 X49.976794433593746 Y24.347464762369803 F300
-G1 X50.095546875 Y24.09700976562501 F300
-G3 X50.377328125 Y23.60546354166668 R4.706414581229917 F300
-G3 X50.54258935546875 Y23.376402587890638 R3.4100664701735104 F300
-G3 X50.7255859375 Y23.166503255208347 R2.734184938650406 F300
-G3 X51.149249999999995 Y22.828250000000015 R2.0044865826013516 F300
-G3 X50.847625 Y17.62389583333335 R37.181895833333336 F300
-G3 X50.86170682779948 Y17.48436946614585 R0.6738944019302708 F300
-G3 X50.901492513020834 Y17.36344661458335 R0.5717211034163222 F300
-G3 X51.043416666666666 Y17.177411458333353 R0.5390351348181216 F300
-G3 X51.47336458333333 Y17.02858333333335 R0.66195756223835 F300
-G3 X51.89839320434055 Y17.17492065502673 R0.6552509994004715 F300
-G3 X52.038226645069784 Y17.357842307143457 R0.529306856092112 F300
-G3 X52.07738204093677 Y17.47674138101933 R0.5613400809647157 F300
-G3 X52.09123327492761 Y17.61393262010688 R0.6622578294374278 F300
-G1 X52.09116666666666 Y17.62389583333335 F300
-G2 X52.387499999999996 Y22.682729166666668 R35.12608333333333 F300
-G3 X53.36116666666667 Y23.391812500000007 R2.1828125 F300
-G1 X53.809635416666666 Y23.914033854166675 F300
-G1 X54.25016666666667 Y24.45014583333334 F300
-G1 X54.7120712890625 Y25.023506184895837 F300
-G1 X55.165294270833336 Y25.604059895833338 F300
-G1 X55.610083658854165 Y26.191062825520838 F300
-G1 X56.0466875 Y26.78377083333334 F300
-G3 X55.705375 Y28.051125000000003 R0.9339791666666665 F300;svg#Drawing > g#Layer_3 > path
-G0 X15.893520833333334 Y8.96408333333334
-G1 X16.1077919921875 Y9.22267220052084 F300
-G1 X16.318838541666665 Y9.490934895833341 F300
-G3 X16.700499999999998 Y10.059458333333339 R5.399977177716655 F300
-G3 X16.97698958333333 Y10.675606770833339 R3.1601961701402383 F300
-G3 X17.0565712890625 Y11.00340071614584 R2.4019455073487226 F300
-G3 X17.086791666666667 Y11.34533333333334 R2.178026084177381 F300
-G3 X16.592020833333333 Y12.668250000000006 R1.9684999999999997 F300
-G3 X15.385520833333333 Y13.271500000000007 R2.2145624999999995 F300
-G3 X14.81402083333333 Y13.36675000000001 R8.599172480068017 F300
-G1 X14.321895833333333 Y13.440833333333336 F300
-G1 X14.271625 Y13.440833333333336 F300
-G1 X14.242520833333334 Y13.440833333333336 F300
-G2 X13.678958333333332 Y13.544020833333331 R6.779285434919048 F300
-G1 X13.369395833333334 Y13.633979166666666 F300
-G1 X13.30589583333333 Y13.657791666666666 F300
-G1 X13.229166666666666 Y13.705416666666668 F300
-G3 X12.916958333333332 Y13.803312500000002 R1.0715624999999998 F300
-G3 X12.496270833333332 Y13.9144375 R0.9233958333333333 F300
-G3 X11.5649375 Y12.983104166666665 R0.9498541666666666 F300
-G2 X11.478162434895832 Y12.454392252604165 R3.796150257230753 F300
-G2 X11.335080729166666 Y11.930393229166665 R8.074055309909856 F300
-G1 X11.174635742187498 Y11.408874674479167 F300
-G3 X11.03577083333333 Y10.887604166666668 R7.280232986690616 F300
-G3 X10.72620833333333 Y8.448145833333335 R18.465270833333335 F300
-G3 X10.63889583333333 Y6.119812499999998 R37.988735816280226 F300
-G1 X10.638493130448715 Y5.925488906406588 F300
-G3 X10.64577791966492 Y5.378993338358422 R17.046441263083786 F300
-G3 X10.68000140471009 Y4.828519975207371 R8.368835745825814 F300
-G3 X10.759717261679059 Y4.2870755177209485 R5.3026434631742 F300
-G3 X10.903479166666664 Y3.7676666666666687 R3.714535571399287 F300
-G3 X12.390437499999997 Y2.0928541666666733 R2.7252083333333332 F300
-G3 X14.544145833333332 Y2.217208333333347 R2.6458333333333335 F300
-G3 X14.965775053769407 Y2.4711225494092273 R2.6619177813623356 F300
-G3 X15.328841618424107 Y2.7907571742135273 R2.801780553152586 F300
-G3 X15.882178401965964 Y3.5839998825893025 R3.260246723702794 F300
-G3 X16.209939427112083 Y4.510560923624498 R4.027234869202733 F300
-G3 X16.291036293772105 Y4.9968434642530735 R4.42730419534592 F300
-G3 X16.317907937015637 Y5.48406476248294 R4.445985198534027 F300
-G3 X16.300979166666664 Y5.865812500000011 R4.2730889401981464 F300
-G3 X14.978062499999997 Y8.01687500000001 R2.6246666666666667 F300
-G3 X15.893520833333334 Y8.96408333333334 R9.1995625 F300
-G0 X15.224124999999999 Y11.411479166666672
-G1 X15.224124999999999 Y11.411479166666672 F300
-G1 X15.224124999999999 Y11.411479166666672 F300
-G1 X15.224124999999999 Y11.411479166666672 F300
-G0 X15.224124999999999 Y11.411479166666672
-G1 X15.224124999999999 Y11.411479166666672 F300
-G1 X15.224124999999999 Y11.411479166666672 F300
-G1 X15.224124999999999 Y11.382375000000001 F300
-G1 X15.224124999999999 Y11.419416666666665 F300
-G1 X15.224124999999999 Y11.419416666666665 F300
-G1 X15.224124999999999 Y11.411479166666672 F300
-G0 X15.224124999999999 Y11.453812500000005
-G1 X15.224124999999999 Y11.432645833333334 F300
-G1 X15.224124999999999 Y11.432645833333334 F300
-G1 X15.216187499999997 Y11.445875000000004 F300
-G1 X15.224124999999999 Y11.453812500000005 F300
-G0 X14.316604166666664 Y4.63550000000001
-G2 X13.684249999999999 Y3.873500000000011 R1.3784791666666665 F300
-G2 X13.178895833333334 Y3.7994166666666853 R0.8466666666666666 F300
-G1 X13.202691776884691 Y3.8007912060559126 F300
-G1 X13.209767789612721 Y3.804317636928882 F300
-G1 X13.201597843084386 Y3.810134408152946 F300
-G1 X13.181537846341044 Y3.8162111037761655 F300
-G1 X13.132446759259262 Y3.8229351851852025 F300
-G1 X13.119160657010369 Y3.8214696312636796 F300
-G1 X13.114024417886828 Y3.8165793380754516 F300
-G1 X13.118051269731904 Y3.8095718948979718 F300
-G1 X13.131270833333334 Y3.7994166666666853 F300
-G2 X12.903729166666668 Y3.9661041666666836 R0.6323541666666667 F300
-G2 X12.729806966145834 Y4.233126627604187 R1.1312539113200049 F300
-G2 X12.623601562500001 Y4.538596354166685 R1.6233440784075972 F300
-G2 X12.535958333333335 Y5.1964166666666785 R3.778782873033914 F300
-G2 X12.520083333333332 Y7.080250000000013 R18.624020833333333 F300
-G2 X13.607520833333334 Y6.64897916666668 R8.154458333333332 F300
-G1 X13.903771484375 Y6.503375651041678 F300
-G1 X14.036610921223957 Y6.428992594401052 F300
-G2 X14.155869791666666 Y6.347354166666678 R1.2819165764344251 F300
-G2 X14.34347591145833 Y6.143707682291678 R0.7837304753315258 F300
-G2 X14.406738199869789 Y6.012397867838555 R0.7019175250377666 F300
-G1 X14.429621815999347 Y5.937336558024102 F300
-G1 X14.44625 Y5.85522916666668 F300
-G2 X14.308666666666666 Y4.6275625000000025 R2.7040416666666665 F300
-G1 X14.316604166666664 Y4.63550000000001 F300
-G0 X15.181791666666667 Y11.16541666666668
-G1 X15.113000000000001 Y11.027833333333342 F300
-G1 X14.986 Y10.821458333333343 F300
-G1 X14.845770833333336 Y10.623020833333342 F300
-G1 X14.763750000000002 Y10.511895833333345 F300
-G2 X14.475354166666667 Y10.162645833333347 R9.15197995834926 F300
-G2 X13.784791666666665 Y9.445625000000012 R9.292166666666665 F300
-G1 X13.584369791666663 Y9.265046875000014 F300
-G1 X13.379979166666665 Y9.088437500000014 F300
-G1 X13.189479166666667 Y8.934979166666677 F300
-G1 X13.096874999999999 Y8.866187500000013 F300
-G3 X12.644437499999999 Y8.998479166666678 R14.63093552530296 F300
-G2 X12.94077083333333 Y10.850562500000013 R12.027958333333332 F300
-G2 X13.063140624999999 Y11.320859375000012 R16.890679593147564 F300
-G1 X13.189479166666663 Y11.78718750000001 F300
-G3 X13.390562499999998 Y11.739562500000009 R2.489815861625981 F300
-G3 X13.87739583333333 Y11.660187500000006 R7.484340060932246 F300
-G1 X14.2875 Y11.588750000000001 F300
-G1 X14.459479166666666 Y11.564937500000001 F300
-G1 X14.6129375 Y11.541125000000001 F300
-G2 X14.896041666666665 Y11.490854166666669 R4.842184419169353 F300
-G1 X15.136812499999998 Y11.432645833333334 F300
-G1 X15.20825 Y11.411479166666664 F300
-G1 X15.229416666666664 Y11.411479166666664 F300
-G1 X15.229416666666664 Y11.353270833333333 F300
-G3 X15.229416666666664 Y11.334750000000001 R0.044979166666666674 F300
-G1 X15.229416666666664 Y11.334750000000001 F300
-G1 X15.181791666666667 Y11.16541666666668 F300;svg#Drawing > g#Layer_3 > path
-G0 X15.232062500000001 Y11.395604166666672
-G1 X15.232062500000001 Y11.395604166666672 F300
-G1 X15.232062500000001 Y11.395604166666672 F300
-G1 X15.232062500000001 Y11.395604166666672 F300;svg#Drawing > g#Layer_3 > path
-G0 X19.27489583333333 Y12.419541666666666
-G1 X19.276310906625472 Y12.279347721260764 F300
-G2 X19.207713328622855 Y11.252981917709693 R8.145856505392313 F300
-G2 X19.03395534528859 Y10.24302013515602 R12.801845882513959 F300
-G2 X18.803089082633065 Y9.241827923672862 R31.641885607680376 F300
-G1 X18.681256635938077 Y8.742134641480572 F300
-G1 X18.563166666666664 Y8.241770833333334 F300
-G3 X18.034 Y3.6036250000000014 R21.214291666666668 F300
-G1 X18.039319184563556 Y3.4947055910505926 F300
-G1 X18.054825438822306 Y3.3928026900730455 F300
-G3 X18.113691359833847 Y3.210052235252014 R0.8765918110406297 F300
-G3 X18.205182169851554 Y3.05538528197386 R0.8101465061976371 F300
-G3 X18.323882275692362 Y2.9288134766755363 R0.8149533161092777 F300
-G3 X18.621248002111003 Y2.7600018957661905 R0.9063318899583719 F300
-G3 X18.962463793625226 Y2.7037106640196003 R1.0464626782430133 F300
-G3 X19.60256654596489 Y2.928565429574384 R0.9792282732496542 F300
-G3 X19.722437009645482 Y3.0550946017771996 R0.8266185108816152 F300
-G3 X19.815449963299734 Y3.2097731822632216 R0.8215352976391652 F300
-G3 X19.87620769679534 Y3.3926127790112295 R0.8866116757825967 F300
-G1 X19.892804071567305 Y3.494596585976846 F300
-G1 X19.899312499999994 Y3.6036250000000014 F300
-G2 X20.428479166666662 Y8.024812500000005 R19.285479166666665 F300
-G1 X20.6793687598976 Y9.0725479548928 F300
-G3 X20.909999860364678 Y10.125929745117018 R27.88284134315828 F300
-G3 X21.078837498400798 Y11.18875407757772 R13.906449923597508 F300
-G3 X21.144346704338858 Y12.264817159179964 R9.33182287143456 F300
-G1 X21.142854166666666 Y12.419541666666666 F300
-G1 X21.13485291259923 Y12.528286253765279 F300
-G1 X21.116767816806146 Y12.630036197365472 F300
-G3 X21.05307977185068 Y12.81254048833031 R0.8904508647150134 F300
-G3 X20.957257375415544 Y12.967031208080604 R0.8350767715018743 F300
-G3 X20.83476797111601 Y13.093485025135776 R0.8475192813663275 F300
-G3 X20.18748714757938 Y13.318464636793692 R1.0018638162007383 F300
-G3 X19.85084960295015 Y13.263792487910427 R1.0444189232376273 F300
-G3 X19.55862516349676 Y13.099657084037844 R0.8888949362672266 F300
-G3 X19.442234565651905 Y12.976484900282372 R0.7921550650398845 F300
-G3 X19.35262729303009 Y12.82587998934175 R0.7853567110130344 F300
-G3 X19.29503002860767 Y12.647820046736706 R0.8498689203199763 F300
-G1 X19.279868487901343 Y12.548487468410253 F300
-G1 X19.274669455361003 Y12.442282767987965 F300
-G1 X19.27489583333333 Y12.419541666666666 F300;svg#Drawing > g#Layer_3 > path
-G0 X27.376437499999998 Y6.98764583333334
-G3 X28.3289375 Y9.233958333333343 R3.5321875 F300
-G1 X28.331018700929484 Y9.339477752034963 F300
-G3 X28.31105441035222 Y9.659184754401508 R2.535726134671182 F300
-G3 X28.253007769197005 Y9.960547557540849 R2.388057104059863 F300
-G3 X28.159648123328708 Y10.243489438150661 R2.340003794508517 F300
-G3 X28.0337448186122 Y10.507933672928623 R2.379897650676241 F300
-G3 X27.69538461609404 Y10.981022311779684 R2.5886588435049953 F300
-G3 X27.488466410022127 Y11.189513269248135 R2.932780096039327 F300
-G3 X27.260081928561487 Y11.379199687675435 R3.22667281880191 F300
-G3 X26.749991522933506 Y11.701852014197275 R3.7131225380924224 F300
-G3 X26.18726816612907 Y11.948365504926606 R4.351600835499139 F300
-G3 X25.594066625067136 Y12.118126373444827 R4.789533243156808 F300
-G3 X24.992541666666664 Y12.210520833333343 R4.845245148138522 F300
-G3 X24.6310338904033 Y12.489209205361902 R0.9921044359766469 F300
-G3 X24.186053544161634 Y12.588017682591456 R1.0638568205143675 F300
-G3 X23.674561952176056 Y12.42999034212319 R0.8670872304394756 F300
-G3 X23.569239764520646 Y12.338059192857344 R0.7795553584092678 F300
-G3 X23.478198393538367 Y12.223698748210811 R0.8119326417972981 F300
-G3 X23.404068105497586 Y12.086179223323008 R0.8994579668031555 F300
-G3 X23.349479166666665 Y11.924770833333339 R1.0564664666323922 F300
-G1 X23.111672498330982 Y10.997421546708427 F300
-G1 X22.876006576526724 Y10.065337338136565 F300
-G1 X22.76182422608779 Y9.5975530633613 F300
-G1 X22.651594458898412 Y9.128625168388227 F300
-G3 X22.447549203090563 Y8.187391998233888 R36.65196827289677 F300
-G3 X22.13701150751435 Y6.291790499789114 R23.788064148277968 F300
-G3 X22.017297950954244 Y4.379388528966046 R16.139095553212893 F300
-G3 X22.055666666666664 Y3.365500000000007 R13.159655615811658 F300
-G3 X22.987 Y2.4341666666666786 R0.9551458333333334 F300
-G3 X23.920979166666665 Y3.365500000000007 R0.9392708333333333 F300
-G2 X23.884502355021333 Y4.323767863621587 R12.380814199255548 F300
-G2 X23.984108801296884 Y6.00508636178569 R14.91125005311833 F300
-G2 X24.245322473837692 Y7.669513419096843 R21.161713247068032 F300
-G2 X24.418904363886078 Y8.496279255518997 R31.062897291107813 F300
-G1 X24.513354232542255 Y8.908455405336847 F300
-G1 X24.61173487321499 Y9.319886149431639 F300
-G1 X24.81676293939583 Y10.140688740069338 F300
-G1 X25.0269375 Y10.959041666666671 F300
-G2 X26.426583333333333 Y10.451041666666674 R3.2702500000000003 F300
-G2 X27.048354166666666 Y8.937625000000008 R1.3387916666666666 F300
-G2 X26.148770833333334 Y7.553854166666676 R2.2939375 F300
-G2 X25.736062174479166 Y7.312297851562511 R3.150245497517917 F300
-G2 X25.305742187499998 Y7.113653645833345 R11.79587244565249 F300
-G3 X24.87889485677083 Y6.9075680338541785 R6.345488757026852 F300
-G3 X24.47660416666666 Y6.643687500000011 R2.631077923841332 F300
-G3 X24.47660416666666 Y5.7652708333333385 R0.6297083333333333 F300
-G2 X26.91077083333333 Y2.9792083333333457 R30.427083333333332 F300
-G3 X27.760083333333327 Y2.7569583333333525 R0.6376458333333332 F300
-G3 X27.98497916666666 Y3.606270833333347 R0.6270625 F300
-G1 X27.73837923177082 Y3.9241842447916797 F300
-G1 X27.48805859374999 Y4.23895572916668 F300
-G1 X27.234017252604158 Y4.550585286458346 F300
-G1 X26.976255208333328 Y4.859072916666679 F300
-G1 X26.714772460937493 Y5.164418619791679 F300
-G1 X26.44956901041666 Y5.466622395833346 F300
-G1 X26.180644856770826 Y5.76568424479168 F300
-G1 X25.907999999999994 Y6.0616041666666804 F300
-G1 X26.146124999999998 Y6.155200520833349 F300
-G3 X26.376312499999994 Y6.254750000000011 R3.6103603754371085 F300
-G3 X27.376437499999998 Y6.98764583333334 R3.2887708333333334 F300;svg#Drawing > g#Layer_3 > path
-G0 X35.41977083333334 Y11.575520833333334
-G1 X35.46832667649031 Y11.57387912542595 F300
-G3 X35.59647276793788 Y11.586739764975599 R0.6216402946637601 F300
-G3 X35.70753271385912 Y11.623181624525404 R0.5237739893155421 F300
-G3 X35.87839416912258 Y11.753968649030668 R0.4911300133192576 F300
-G3 X35.980911042280646 Y11.940559489752083 R0.5673598081932176 F300
-G3 X36.01508333333334 Y12.157273437500002 R0.6875940032193125 F300
-G3 X35.866255208333335 Y12.593877278645834 R0.692094895436643 F300
-G3 X35.68022005208334 Y12.746493286132813 R0.5887172125041454 F300
-G3 X35.55929720052083 Y12.793615086873372 R0.6103134453550064 F300
-G3 X35.41977083333334 Y12.816416666666665 R0.6963132325420017 F300
-G3 X32.50935416666667 Y12.938125 R67.05053563534034 F300
-G3 X31.956374999999994 Y12.951354166666672 R1.031875 F300
-G1 X30.960088418528866 Y12.958744239398978 F300
-G3 X28.752270833333334 Y12.92225 R66.86356427327978 F300
-G3 X28.612744466145834 Y12.903633565266926 R0.6871678110820622 F300
-G3 X28.491821614583333 Y12.860134928385417 R0.5925156178479478 F300
-G3 X28.305786458333337 Y12.713063802083333 R0.5640203162146764 F300
-G3 X28.156958333333336 Y12.280635416666666 R0.6748643987372381 F300
-G3 X28.192615415092888 Y12.058125327345603 R0.6981417590309281 F300
-G3 X28.29958666037156 Y11.865335794286446 R0.587819229383014 F300
-G3 X28.477872069169337 Y11.729610234967133 R0.5140303672112586 F300
-G3 X28.593757584887893 Y11.691691263421772 R0.5486575412854411 F300
-G3 X28.727471641486225 Y11.678292066865598 R0.6498827520422377 F300
-G1 X28.752270833333334 Y11.678708333333336 F300
-G1 X29.271901836322897 Y11.69357127201582 F300
-G1 X29.784751678949704 Y11.703559019178664 F300
-G2 X30.80297077575775 Y11.710939921994282 R67.34860361159919 F300
-G1 X31.080604166666664 Y11.710458333333339 F300
-G3 X30.286854166666664 Y8.080375000000004 R22.3599375 F300
-G3 X29.7576875 Y3.3866666666666694 R33.73172916666666 F300
-G1 X29.757217610114367 Y3.3540483747168395 F300
-G1 X29.76237124471938 Y3.248972761806913 F300
-G1 X29.77740045758326 Y3.150686170305552 F300
-G3 X29.83449547238064 Y2.9744745600535296 R0.8402700771524851 F300
-G3 X29.92332236309256 Y2.825402561010778 R0.7774102587450545 F300
-G3 X30.038700838305076 Y2.703459190227301 R0.785012405872289 F300
-G3 X30.328391376576093 Y2.5409144016381986 R0.8817491928621534 F300
-G3 X30.662124755882108 Y2.486752330686268 R1.036172790979544 F300
-G3 X31.306322919927176 Y2.7118551433422047 R0.9926066250098823 F300
-G3 X31.42786332451633 Y2.8384269486405285 R0.8397638379399749 F300
-G3 X31.522613602344464 Y2.9930939019186824 R0.8291888192649636 F300
-G3 X31.58508051731549 Y3.175844356739714 R0.8872162705395755 F300
-G1 X31.602491077449564 Y3.2777472577172606 F300
-G1 X31.60977083333333 Y3.3866666666666694 F300
-G2 X32.06220833333333 Y7.58295833333334 R30.188958333333332 F300
-G1 X32.1426065266927 Y8.08444193522136 F300
-G1 X32.22120638020833 Y8.593956054687506 F300
-G2 X32.39756770833333 Y9.620580729166672 R26.141993054281105 F300
-G2 X32.640405598958324 Y10.629842122395837 R11.162480239555805 F300
-G2 X32.99883333333333 Y11.588750000000001 R6.783952645852847 F300
-G2 X33.0120625 Y11.635052083333335 R0.10884538049598087 F300
-G1 X33.033229166666665 Y11.681354166666669 F300
-G1 X33.63569368489583 Y11.661841145833336 F300
-G1 X34.233445312499995 Y11.638359375 F300
-G2 X35.41977083333334 Y11.575520833333334 R58.23010223089023 F300;svg#Drawing > g#Layer_3 > path
-G0 X40.67175 Y11.903604166666668
-G3 X39.526104166666656 Y11.252729166666668 R0.9392708333333333 F300
-G2 X39.27371647135416 Y10.112953776041667 R21.30353651994145 F300
-G1 X39.12822147623697 Y9.54706583658854 F300
-G1 X38.97544010416666 Y8.982604166666665 F300
-G1 X38.664761393229156 Y7.854238932291665 F300
-G1 X38.51523563639322 Y7.288475016276041 F300
-G1 X38.375166666666665 Y6.720416666666668 F300
-G3 X38.21277864583333 Y5.926666666666671 R17.326322958725815 F300
-G3 X38.08412499999999 Y5.1329166666666755 R22.77593549095572 F300
-G1 X36.26379166666666 Y5.1329166666666755 F300
-G2 X37.107812499999994 Y9.334500000000007 R24.526875 F300
-G3 X36.458319729479086 Y10.48145233722323 R0.9320555837607597 F300
-G3 X35.311308090336375 Y9.831975976458159 R0.9320555837607597 F300
-G3 X34.2820625 Y3.6195000000000017 R26.609145833333333 F300
-G3 X35.21604166666667 Y2.6881666666666733 R0.9366249999999999 F300
-G3 X36.14737500000001 Y3.6195000000000017 R0.9472083333333333 F300
-G1 X36.163250000000005 Y3.8840833333333347 F300
-G1 X37.930666666666674 Y3.8840833333333347 F300
-G3 X37.85592187500001 Y2.9649869791666674 R34.31490310955328 F300
-G3 X37.80102083333334 Y2.032000000000002 R52.21615678557297 F300
-G3 X39.65310416666667 Y2.032000000000002 R0.9339791666666665 F300
-G1 X39.69742187500001 Y3.1227447916666686 F300
-G2 X39.78539583333334 Y4.20952083333334 R13.694451194664225 F300
-G2 X39.9623359375 Y5.285382812500011 R16.333426235109783 F300
-G2 X40.19285416666667 Y6.347354166666676 R31.63689838467003 F300
-G1 X40.32656176757813 Y6.896902018229177 F300
-G1 X40.470749348958336 Y7.444713541666678 F300
-G1 X40.772953125 Y8.538104166666677 F300
-G1 X40.92216365559896 Y9.085171549479178 F300
-G1 X41.064242838541674 Y9.633479166666678 F300
-G3 X41.30939583333334 Y10.736791666666676 R19.777634255711337 F300
-G3 X40.67175 Y11.903604166666668 R0.9551458333333334 F300;svg#Drawing > g#Layer_3 > path
-G0 X47.524458333333335 Y9.252479166666665
-G3 X47.266275528492734 Y9.538467474968531 R2.9538380152762067 F300
-G3 X46.98145249282259 Y9.781535346269 R2.9119318522633155 F300
-G3 X46.34605492232531 Y10.144813608420579 R3.0321531839940907 F300
-G3 X45.646604008504724 Y10.35412161423109 R3.423575169709596 F300
-G3 X44.91143813802411 Y10.42126702481022 R3.9635868368665825 F300
-G3 X44.34945833333333 Y10.384895833333333 R4.413676676537378 F300
-G3 X44.16425 Y10.453687499999996 R0.8598958333333333 F300
-G3 X43.01595833333333 Y9.802812499999996 R0.9524999999999999 F300
-G1 X42.817017680877484 Y9.037494542560422 F300
-G1 X42.62485213459753 Y8.267702227561267 F300
-G3 X42.28519606294397 Y6.715135243479305 R32.50483701061117 F300
-G3 X42.04568952312986 Y5.145992984944288 R20.080926976098144 F300
-G3 X41.955031919912436 Y3.5611568891463934 R14.520172434898475 F300
-G1 X41.95762500000001 Y3.3046458333333355 F300
-G3 X42.8545625 Y2.375958333333338 R0.9445624999999999 F300
-G3 X43.10007945219281 Y2.2385660907953113 R1.8065310697759076 F300
-G3 X43.35419391235783 Y2.144498402351653 R1.7647281442194818 F300
-G3 X43.880068612101304 Y2.0731886599391984 R1.9164303418997937 F300
-G3 X44.169167490244476 Y2.0920081783937388 R2.2852225533337185 F300
-G3 X44.45860241441187 Y2.1457076905338797 R2.6728344438620155 F300
-G3 X44.74580905854532 Y2.2301486315244015 R3.144429070434999 F300
-G3 X45.02822309658663 Y2.3411924365300827 R3.6741585831330257 F300
-G3 X45.56841605016005 Y2.626534379246046 R4.467839943524707 F300
-G3 X45.8210663135758 Y2.792555387285887 R5.163507949604121 F300
-G3 X46.05866666666667 Y2.9686250000000074 R5.415710431633495 F300
-G3 X47.926624999999994 Y5.722937500000014 R5.601229166666667 F300
-G3 X48.022636911268904 Y6.086267831721979 R5.73231263664956 F300
-G3 X48.095230370040156 Y6.461272430712614 R5.528155458053556 F300
-G3 X48.15719516621851 Y7.228571504773353 R4.928404874197247 F300
-G3 X48.122280741284754 Y7.771128417661687 R4.115259137216505 F300
-G3 X48.011361963654736 Y8.297744082988718 R3.473117326759663 F300
-G3 X47.815175579085306 Y8.795750374681395 R2.9786314991268816 F300
-G3 X47.524458333333335 Y9.252479166666665 R2.6953488696363075 F300
-G0 X46.71747916666666 Y6.053666666666666
-G2 X45.48716666666666 Y4.114270833333336 R4.400020833333333 F300
-G1 X45.34822358710108 Y3.9917473106176926 F300
-G1 X45.18261181546056 Y3.8572868580266446 F300
-G1 X44.99571525075409 Y3.7196038795375 F300
-G2 X44.79291779199062 Y3.5874127791275683 R3.7809558793774687 F300
-G2 X44.579603338179126 Y3.4694279607741576 R2.9593489973429117 F300
-G2 X44.36115578832859 Y3.3743638284545776 R2.1787488305525553 F300
-G2 X44.14295904144796 Y3.3109347861461367 R1.527613166954281 F300
-G2 X43.93039699654622 Y3.287855237826143 R1.0507372671069533 F300
-G2 X43.809708333333326 Y3.296708333333343 R0.802610111862606 F300
-G1 X43.809708333333326 Y3.296708333333343 F300
-G1 X43.80774117778899 Y3.5112001831448345 F300
-G2 X43.889691433808636 Y4.9477577468849985 R13.139184942115522 F300
-G2 X44.106916554025645 Y6.36417584984396 R17.903652277533606 F300
-G2 X44.252838915782235 Y7.067891691544688 R25.349135091278335 F300
-G1 X44.41647806667707 Y7.770243573667151 F300
-G1 X44.59246669773978 Y8.472455131417032 F300
-G1 X44.775437499999995 Y9.175750000000008 F300
-G2 X46.60635416666666 Y8.408458333333341 R2.0425833333333334 F300
-G2 X46.84978636776281 Y7.874556712598174 R1.618660609460686 F300
-G2 X46.904077710103024 Y7.579572996970377 R2.1431979389494136 F300
-G2 X46.92092402270522 Y7.274517310468517 R2.6694777240766085 F300
-G2 X46.86193231507495 Y6.643701806730935 R3.616177767231362 F300
-G2 X46.79733948586032 Y6.34008606204187 R4.634133974462856 F300
-G2 X46.71747916666666 Y6.053666666666674 R5.093914305007922 F300
-G1 X46.71747916666666 Y6.053666666666666 F300;svg#Drawing > g#Layer_3 > path
-G0 X54.787270833333324 Y11.199812500000004
-G3 X54.64018420410156 Y11.405934285481774 R3.719746595390075 F300
-G3 X54.473780924479165 Y11.607560221354172 R3.5787000220453176 F300
-G3 X54.089101562500005 Y11.973388020833339 R3.035459280801124 F300
-G3 X53.645387044270834 Y12.249422851562507 R2.1940214658965633 F300
-G3 X53.15479166666667 Y12.387791666666672 R1.5737380993396737 F300
-G3 X52.995746580076464 Y12.396063121193661 R1.5383809590068895 F300
-G3 X52.74978785175784 Y12.376180334270622 R1.5379992448099438 F300
-G3 X52.51400740366829 Y12.3194333670302 R1.6293135136588657 F300
-G3 X52.07545412675203 Y12.112755252769404 R1.944981798075254 F300
-G3 X51.3476875 Y11.448520833333337 R3.003781541767221 F300
-G3 X50.779081380208325 Y10.607848632812502 R6.321299987796872 F300
-G3 X50.32705729166666 Y9.695986979166667 R9.45043376572696 F300
-G3 X49.94944726562499 Y8.746918294270834 R18.28054150708582 F300
-G1 X49.604083333333335 Y7.794625000000001 F300
-G1 X49.40308268229167 Y7.233253580729167 F300
-G1 X49.20886197916667 Y6.669649739583335 F300
-G1 X49.021421223958335 Y6.103813476562502 F300
-G1 X48.84076041666667 Y5.5357447916666676 F300
-G1 X48.66687955729167 Y4.965443684895835 F300
-G1 X48.49977864583334 Y4.392910156250001 F300
-G1 X48.339457682291666 Y3.818144205729167 F300
-G1 X48.18591666666666 Y3.241145833333333 F300
-G3 X48.15981904266658 Y3.1092409864058967 R1.0799940084411679 F300
-G3 X48.151398805495624 Y2.9836824385137257 R0.9567861107448297 F300
-G3 X48.1739191491141 Y2.7865435005019803 R0.8600273296072275 F300
-G3 X48.23741878006892 Y2.6102582411178012 R0.8197817287954621 F300
-G3 X48.462987504584014 Y2.3269451442548843 R0.8826890176493805 F300
-G3 X49.13782400821074 Y2.0842233403099795 R1.0496229917302138 F300
-G3 X49.65289558893129 Y2.241966332282937 R0.8798144849272354 F300
-G3 X49.75932275832254 Y2.3337416570448926 R0.7884231627806738 F300
-G3 X49.85149500932285 Y2.447914360567375 R0.8173570811515629 F300
-G3 X49.92675302789456 Y2.585215216876904 R0.90068715124038 F300
-G3 X49.98243749999999 Y2.7463749999999987 R1.0526403012988481 F300
-G1 X50.144660156249984 Y3.3435478515624992 F300
-G1 X50.313828124999986 Y3.9419609374999998 F300
-G1 X50.48994140624999 Y4.5378935546875 F300
-G1 X50.672999999999995 Y5.127624999999998 F300
-G2 X52.130854166666666 Y5.656791666666665 R7.810499999999999 F300
-G3 X53.27649999999999 Y5.976937500000001 R20.284340356103773 F300
-G3 X53.2017990215737 Y4.612234576911867 R99.8601845233711 F300
-G3 X53.16732910624265 Y3.24633545517512 R30.222409835207852 F300
-G1 X53.173312499999994 Y2.7437291666666677 F300
-G1 X53.18108021005489 Y2.634744283653221 F300
-G1 X53.19895841408943 Y2.5327695472544276 F300
-G3 X53.26229515372275 Y2.3498621799561006 R0.8917862194581261 F300
-G3 X53.35782041815043 Y2.195030396082284 R0.8353800284355178 F300
-G3 X53.480031906623005 Y2.068297526943576 R0.8467551806155065 F300
-G3 X54.12569408597251 Y1.8428218239518093 R0.9969703369267718 F300
-G3 X54.45805421635356 Y1.8966908262929927 R1.032396923507443 F300
-G3 X54.746243093876316 Y2.0584137957489728 R0.8759873268414203 F300
-G3 X54.86095402829345 Y2.1797748755352124 R0.7799709713603407 F300
-G3 X54.949239008069156 Y2.3281646759683934 R0.7733666200008888 F300
-G3 X55.00597031939448 Y2.5036049400045943 R0.8372786739038974 F300
-G1 X55.02090093882295 Y2.601475790547603 F300
-G1 X55.02602024846046 Y2.706117410599897 F300
-G1 X55.025395833333334 Y2.7437291666666677 F300
-G1 X55.02022020982192 Y3.2192748947445313 F300
-G2 X55.08014825793663 Y5.109725354245992 R35.002850241730435 F300
-G1 X55.189437500000004 Y7.000875000000001 F300
-G3 X55.237071381546706 Y8.633465883806721 R32.34665739984757 F300
-G3 X55.229124999999996 Y9.204854166666673 R19.863828148730757 F300
-G3 X55.20994270833333 Y9.722693359375004 R9.957648738438193 F300
-G3 X55.149088541666664 Y10.240036458333336 R4.771397963370063 F300
-G3 X55.0177890625 Y10.73852799479167 R2.9851378280137 F300
-G3 X54.787270833333324 Y11.199812500000004 R2.188082750806181 F300
-G0 X52.601812499999994 Y7.069666666666673
-G1 X51.67047916666667 Y6.828895833333341 F300
-G3 X51.18364583333333 Y6.680729166666675 R8.51639651680108 F300
-G1 X51.265997395833324 Y6.921169270833343 F300
-G1 X51.350333333333325 Y7.159625000000008 F300
-G1 X51.52363541666666 Y7.638024739583342 F300
-G1 X51.70090624999999 Y8.115432291666677 F300
-G2 X52.08322916666666 Y9.059333333333344 R20.10409516267031 F300
-G2 X52.55683333333332 Y9.961562500000008 R6.810375 F300
-G1 X52.6138427734375 Y10.040524088541677 F300
-G1 X52.71922135416666 Y10.178520833333344 F300
-G1 X52.84593196614583 Y10.334376953125009 F300
-G1 X52.90883768717448 Y10.406134847005216 F300
-G1 X52.9669375 Y10.466916666666675 F300
-G1 X53.07574739583333 Y10.36240625000001 F300
-G1 X53.13891666666667 Y10.297583333333344 F300
-G2 X53.353229166666665 Y9.699625000000013 R1.0583333333333331 F300
-G2 X53.353229166666665 Y7.463895833333351 R15.015104166666665 F300
-G1 X53.353229166666665 Y7.326312500000014 F300
-G2 X52.601812499999994 Y7.069666666666666 R5.471583333333333 F300
-G1 X52.601812499999994 Y7.069666666666673 F300;svg#Drawing > g#Layer_3 > path
-G0 X63.28039583333332 Y13.56254166666667
-G3 X62.005104166666655 Y13.226520833333337 R0.9604374999999999 F300
-G1 X61.846354166666664 Y13.017500000000005 F300
-G1 X61.72993749999999 Y12.866687500000006 F300
-G1 X61.356874999999995 Y12.406312500000002 F300
-G1 X61.164390624999996 Y12.179763020833335 F300
-G1 X60.9679375 Y11.95519791666667 F300
-G1 X60.767515625 Y11.732617187500004 F300
-G1 X60.56312499999999 Y11.512020833333338 F300
-G1 X60.163604166666666 Y11.080088541666672 F300
-G1 X59.95888281249999 Y10.868339192708337 F300
-G1 X59.748208333333324 Y10.660062500000004 F300
-G1 X59.55241666666666 Y10.474854166666676 F300
-G3 X59.507437499999995 Y10.424583333333343 R0.36777083333333327 F300
-G1 X59.457166666666666 Y10.39812500000001 F300
-G3 X59.30899999999999 Y10.318750000000007 R1.5054791666666667 F300
-G2 X59.25079166666666 Y10.302875000000007 R0.23812499999999998 F300
-G1 X59.23227083333333 Y10.302875000000007 F300
-G1 X59.17935416666667 Y10.35579166666667 F300
-G1 X59.13569791666667 Y10.43020572916667 F300
-G1 X59.092041666666674 Y10.498666666666669 F300
-G1 X59.07389290364584 Y10.536576497395833 F300
-G1 X59.054007812500004 Y10.5935859375 F300
-G1 X59.02589583333333 Y10.686520833333336 F300
-G1 X58.95445833333333 Y10.95110416666667 F300
-G2 X58.827458333333325 Y11.554354166666672 R9.178817362857165 F300
-G1 X58.78367805989582 Y11.830554361979171 F300
-G1 X58.75105989583333 Y12.08914322916667 F300
-G1 X58.71695345052083 Y12.362118815104168 F300
-G1 X58.66870833333334 Y12.681479166666668 F300
-G3 X58.09985416666667 Y13.824479166666666 R2.1007916666666664 F300
-G3 X56.776937488736124 Y13.824479177930534 R0.9354433459447031 F300
-G3 X56.77707191001344 Y12.50142811729804 R0.9354433459447031 F300
-G1 X56.790166666666664 Y12.4856875 F300
-G1 X56.790166666666664 Y12.4856875 F300
-G2 X56.83779166666667 Y12.30841666666667 R1.953614864031566 F300
-G1 X56.86160416666667 Y12.18141666666667 F300
-G1 X56.86160416666667 Y12.160250000000001 F300
-G3 X56.873510416666676 Y12.017375000000001 R1.0396942763304742 F300
-G1 X56.893354166666676 Y11.8745 F300
-G3 X57.176458333333336 Y10.326687499999998 R11.377083333333333 F300
-G3 X57.97020833333334 Y8.93497916666667 R3.0003749999999996 F300
-G3 X57.414583333333326 Y3.405187500000001 R27.78125 F300
-G3 X59.266666666666666 Y3.405187500000001 R0.9313333333333332 F300
-G2 X59.8090625 Y8.52222916666667 R24.58508333333333 F300
-G3 X60.370020507812505 Y8.777510742187502 R2.4028891345596106 F300
-G3 X60.8716953125 Y9.151606770833336 R3.7058330715170613 F300
-G3 X61.328225585937496 Y9.59267545572917 R7.18037438453229 F300
-G1 X61.54397985839843 Y9.822123982747401 F300
-G1 X61.75375 Y10.048875000000004 F300
-G3 X63.60583333333334 Y12.287250000000007 R28.889854166666666 F300
-G3 X63.747940577405785 Y12.729506314853797 R0.7161657836625646 F300
-G3 X63.618167290036155 Y13.216220756007386 R1.005085718948005 F300
-G3 X63.47044866984457 Y13.41743952696523 R0.9588678310905848 F300
-G3 X63.28039583333332 Y13.56254166666667 R0.7585155151482174 F300
-G0 X59.25079166666666 Y10.297583333333337
-G1 X59.19522916666667 Y10.284354166666667 F300
-G2 X59.25079166666666 Y10.297583333333337 R0.14287500000000003 F300
-G0 X59.287833333333325 Y10.297583333333337
-G1 X59.287833333333325 Y10.297583333333337 F300
-G1 X59.33349550728319 Y10.273162745967657 F300
-G1 X59.333289069356994 Y10.273064213254433 F300
-G1 X59.28783333333334 Y10.292291666666669 F300
-G1 X59.287833333333325 Y10.297583333333337 F300
@@ -1,17 +1,14 @@
 use std::{
-    io::Cursor,
+    io::{Cursor, Write},
     path::{Path, PathBuf},
 };
 
 use base64::Engine;
-use g_code::{
-    emit::{format_gcode_fmt, format_gcode_io, FormatOptions},
-    parse::snippet_parser,
-};
+use g_code::parse::snippet_parser;
 use js_sys::Date;
-use log::{info, Level};
+use log::{error, info, Level};
 use roxmltree::{Document, ParsingOptions};
-use svg2gcode::{svg2program, ConversionOptions, Machine};
+use svg2gcode::{tokens_to_gcode, try_svg2program, ConversionOptions, Machine};
 use yew::prelude::*;
 
 mod forms;
@@ -65,6 +62,7 @@ fn app() -> Html {
             for svg in app_store.svgs.iter() {
                 let options = ConversionOptions {
                     dimensions: svg.dimensions,
+                    ..Default::default()
                 };
 
                 let machine = Machine::new(
@@ -109,6 +107,25 @@ fn app() -> Html {
                         .map(snippet_parser)
                         .transpose()
                         .unwrap(),
+                )
+                .with_config(&app_store.settings.machine)
+                .with_accessory_sequences(
+                    app_store
+                        .settings
+                        .machine
+                        .accessory_on_sequence
+                        .as_deref()
+                        .map(snippet_parser)
+                        .transpose()
+                        .unwrap(),
+                    app_store
+                        .settings
+                        .machine
+                        .accessory_off_sequence
+                        .as_deref()
+                        .map(snippet_parser)
+                        .transpose()
+                        .unwrap(),
                 );
                 let document = Document::parse_with_options(
                     svg.content.as_str(),
@@ -119,8 +136,31 @@ fn app() -> Html {
                 )
                 .unwrap();
 
-                let program =
-                    svg2program(&document, &app_store.settings.conversion, options, machine);
+                let mut program = match try_svg2program(
+                    &document,
+                    &app_store.settings.conversion,
+                    options,
+                    machine,
+                ) {
+                    Ok(program) => program,
+                    Err(err) => {
+                        error!("could not convert {}: {err}", svg.filename);
+                        generating_setter.set(false);
+                        return;
+                    }
+                };
+                app_store
+                    .settings
+                    .postprocess
+                    .apply(&mut program, app_store.settings.conversion.feedrate_units);
+                let gcode = match tokens_to_gcode(&program, &app_store.settings.postprocess) {
+                    Ok(gcode) => gcode,
+                    Err(err) => {
+                        error!("could not convert {}: {err}", svg.filename);
+                        generating_setter.set(false);
+                        return;
+                    }
+                };
 
                 let filepath = if app_store.svgs.len() > 1 {
                     PathBuf::from("svg2gcode_output")
@@ -132,43 +172,11 @@ fn app() -> Html {
                 match app_store.svgs.len() {
                     0 => unreachable!(),
                     1 => {
-                        let gcode = {
-                            let mut acc = String::new();
-                            format_gcode_fmt(
-                                &program,
-                                FormatOptions {
-                                    checksums: app_store.settings.postprocess.checksums,
-                                    line_numbers: app_store.settings.postprocess.line_numbers,
-                                    newline_before_comment: app_store
-                                        .settings
-                                        .postprocess
-                                        .newline_before_comment,
-                                    ..Default::default()
-                                },
-                                &mut acc,
-                            )
-                            .unwrap();
-                            acc
-                        };
                         prompt_download(filepath, gcode.as_bytes());
                     }
                     _multiple => {
                         zip.start_file(filepath.to_string_lossy(), opts).unwrap();
-
-                        format_gcode_io(
-                            &program,
-                            FormatOptions {
-                                checksums: app_store.settings.postprocess.checksums,
-                                line_numbers: app_store.settings.postprocess.line_numbers,
-                                newline_before_comment: app_store
-                                    .settings
-                                    .postprocess
-                                    .newline_before_comment,
-                                ..Default::default()
-                            },
-                            &mut zip,
-                        )
-                        .unwrap();
+                        zip.write_all(gcode.as_bytes()).unwrap();
                     }
                 }
             }
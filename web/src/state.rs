@@ -55,8 +55,9 @@ impl<'a> TryInto<Settings> for &'a FormState {
                     self.origin[0].clone().transpose()?,
                     self.origin[1].clone().transpose()?,
                 ],
-        min_arc_radius: self.min_arc_radius.clone().transpose()?,
-		extra_attribute_name: None,
+                min_arc_radius: self.min_arc_radius.clone().transpose()?,
+                extra_attribute_name: None,
+                ..Default::default()
             },
             machine: MachineConfig {
                 supported_functionality: SupportedFunctionality {
@@ -87,11 +88,13 @@ impl<'a> TryInto<Settings> for &'a FormState {
                     .clone()
                     .transpose()
                     .map_err(FormStateConversionError::GCode)?,
+                ..Default::default()
             },
             postprocess: PostprocessConfig {
                 checksums: self.checksums,
                 line_numbers: self.line_numbers,
                 newline_before_comment: self.newline_before_comment,
+                ..Default::default()
             },
             version: Version::latest(),
         })